@@ -74,6 +74,12 @@ pub enum CustomIconName {
     Square,
     SquareCheck,
     ListX,
+    Scissors,
+    Download,
+    Replace,
+    WrapText,
+    ListTree,
+    Import,
 }
 
 impl CustomIconName {
@@ -112,6 +118,12 @@ impl CustomIconName {
             CustomIconName::Square => "icons/square.svg",
             CustomIconName::SquareCheck => "icons/square-check.svg",
             CustomIconName::ListX => "icons/list-x.svg",
+            CustomIconName::Scissors => "icons/scissors.svg",
+            CustomIconName::Download => "icons/download.svg",
+            CustomIconName::Replace => "icons/replace.svg",
+            CustomIconName::WrapText => "icons/wrap-text.svg",
+            CustomIconName::ListTree => "icons/list-tree.svg",
+            CustomIconName::Import => "icons/import.svg",
         }
         .into()
     }
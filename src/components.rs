@@ -14,10 +14,12 @@
 
 mod card;
 mod dialog;
+mod export;
 mod kv_delegate;
 mod skeleton_loading;
 
 pub use card::Card;
 pub use dialog::*;
+pub use export::{export_to_file, sanitize_file_name};
 pub use kv_delegate::{INDEX_COLUMN_NAME, ZedisKvDelegate, ZedisKvFetcher};
 pub use skeleton_loading::SkeletonLoading;
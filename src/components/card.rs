@@ -12,8 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use gpui::{AnyElement, App, ClickEvent, ElementId, Fill, SharedString, Window, div, prelude::*, px};
-use gpui_component::{ActiveTheme, Icon, button::Button, h_flex, label::Label, list::ListItem};
+use gpui::{AnyElement, App, ClickEvent, ElementId, Fill, Hsla, SharedString, Window, div, prelude::*, px};
+use gpui_component::{ActiveTheme, Icon, button::Button, h_flex, label::Label, list::ListItem, tooltip::Tooltip};
+
+/// Number of description lines shown before the rest moves into the tooltip.
+const DESCRIPTION_MAX_LINES: usize = 3;
+/// Width of the left border drawn in the accent color.
+const ACCENT_WIDTH: f32 = 4.0;
 
 /// Type alias for the click handler closure.
 type OnClick = Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>;
@@ -41,6 +46,8 @@ pub struct Card {
     footer: Option<AnyElement>,
     /// Custom background fill.
     bg: Option<Fill>,
+    /// Color of the border, with a wider left side as a stripe.
+    accent: Option<Hsla>,
 }
 impl Card {
     /// Creates a new `Card` with the given element ID.
@@ -54,6 +61,7 @@ impl Card {
             on_click: None,
             footer: None,
             bg: None,
+            accent: None,
         }
     }
 
@@ -99,6 +107,12 @@ impl Card {
         self.bg = Some(bg.into());
         self
     }
+
+    /// Draws the border in the given color, with a stripe on the left.
+    pub fn accent(mut self, accent: Option<Hsla>) -> Self {
+        self.accent = accent;
+        self
+    }
 }
 
 impl RenderOnce for Card {
@@ -124,6 +138,9 @@ impl RenderOnce for Card {
             .m_2()
             .border(px(1.))
             .border_color(cx.theme().border)
+            .when_some(self.accent, |this, accent| {
+                this.border_l(px(ACCENT_WIDTH)).border_color(accent)
+            })
             .p_4()
             .rounded(cx.theme().radius)
             // Apply custom background if provided
@@ -132,9 +149,19 @@ impl RenderOnce for Card {
             .when_some(self.on_click, |this, handler| this.on_click(handler))
             // Add Header
             .child(header)
-            // Add Description
+            // Add Description, clamped on the card with the full text in a tooltip
             .when_some(self.description, |this, description| {
-                this.child(Label::new(description).text_sm().whitespace_normal())
+                this.child(
+                    div()
+                        .id("card-description")
+                        .child(
+                            Label::new(description.clone())
+                                .text_sm()
+                                .whitespace_normal()
+                                .line_clamp(DESCRIPTION_MAX_LINES),
+                        )
+                        .tooltip(move |window, cx| Tooltip::new(description.clone()).build(window, cx)),
+                )
             })
             // Add Footer
             .when_some(self.footer, |this, footer| this.child(footer))
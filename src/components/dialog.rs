@@ -13,14 +13,16 @@
 // limitations under the License.
 
 use crate::helpers::is_windows;
-use crate::states::i18n_common;
-use gpui::{App, Entity, SharedString, Window, prelude::*};
+use crate::states::{DeleteCommand, i18n_common};
+use gpui::{App, Entity, SharedString, Window, prelude::*, px};
 use gpui_component::{
     WindowExt,
     button::{Button, ButtonVariants},
     form::{field, v_form},
     input::{Input, InputState},
     radio::RadioGroup,
+    scroll::ScrollableElement,
+    v_flex,
 };
 use std::{cell::Cell, rc::Rc};
 
@@ -32,6 +34,160 @@ type SubmitHandler = Rc<dyn Fn(Vec<SharedString>, &mut Window, &mut App) -> bool
 /// Returns `true` if valid, `false` otherwise.
 type ValidateHandler = Rc<dyn Fn(&str) -> bool>;
 
+/// Handler closure run when a confirm dialog is accepted.
+type ConfirmHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
+/// Rows a multi-line form field starts with and grows up to.
+const TEXTAREA_MIN_ROWS: usize = 4;
+const TEXTAREA_MAX_ROWS: usize = 12;
+
+/// Orders dialog footer buttons for the current platform.
+///
+/// Buttons are given as `[cancel, ..., ok]`, which is the macOS/Linux order,
+/// Windows puts the primary action first.
+pub fn platform_button_order(mut buttons: Vec<Button>) -> Vec<Button> {
+    if is_windows() {
+        buttons.reverse();
+    }
+    buttons
+}
+
+/// Configuration for a confirm dialog.
+pub struct ConfirmDialog {
+    /// Optional title of the dialog.
+    pub title: Option<SharedString>,
+    /// Message asking the user to confirm.
+    pub message: SharedString,
+    /// Handler run when the user confirms.
+    pub handle_confirm: ConfirmHandler,
+}
+
+impl ConfirmDialog {
+    /// Creates a confirm dialog with a message and the handler run on confirm.
+    pub fn new<F>(message: impl Into<SharedString>, handle_confirm: F) -> Self
+    where
+        F: Fn(&mut Window, &mut App) + 'static,
+    {
+        Self {
+            title: None,
+            message: message.into(),
+            handle_confirm: Rc::new(handle_confirm),
+        }
+    }
+    /// Sets the title of the dialog.
+    pub fn with_title(mut self, title: SharedString) -> Self {
+        self.title = Some(title);
+        self
+    }
+}
+
+/// Opens a confirm dialog with Cancel/Confirm buttons in the platform order.
+pub fn open_confirm_dialog(params: ConfirmDialog, window: &mut Window, cx: &mut App) {
+    let ConfirmDialog {
+        title,
+        message,
+        handle_confirm,
+    } = params;
+    window.open_dialog(cx, move |dialog, _, _cx| {
+        let mut dialog = dialog
+            .confirm()
+            .child(
+                v_flex()
+                    .w_full()
+                    .max_h(px(200.0))
+                    .overflow_y_scrollbar()
+                    .child(message.clone()),
+            )
+            .on_ok({
+                let handle = handle_confirm.clone();
+                move |_, window, cx| {
+                    handle(window, cx);
+                    true
+                }
+            })
+            .footer({
+                let handle = handle_confirm.clone();
+                move |_, _, _, cx| {
+                    let confirm_label = i18n_common(cx, "confirm");
+                    let cancel_label = i18n_common(cx, "cancel");
+                    platform_button_order(vec![
+                        Button::new("cancel").label(cancel_label).on_click(|_, window, cx| {
+                            window.close_dialog(cx);
+                        }),
+                        Button::new("ok").primary().label(confirm_label).on_click({
+                            let handle = handle.clone();
+                            move |_, window, cx| {
+                                handle(window, cx);
+                                window.close_dialog(cx);
+                            }
+                        }),
+                    ])
+                }
+            });
+        if let Some(title) = title.clone() {
+            dialog = dialog.title(title);
+        }
+        dialog
+    });
+}
+
+/// Opens a dialog for destructive actions that only confirms once the user types `keyword`.
+///
+/// `fields` are shown below the keyword input, their values are passed to `handle_confirm`.
+pub fn open_typed_confirm_dialog<F>(
+    title: SharedString,
+    message: SharedString,
+    keyword: SharedString,
+    fields: Vec<FormField>,
+    handle_confirm: F,
+    window: &mut Window,
+    cx: &mut App,
+) where
+    F: Fn(&[SharedString], &mut Window, &mut App) + 'static,
+{
+    let placeholder = keyword.clone();
+    let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+        if values.first().map(|value| value.trim()) != Some(keyword.as_str()) {
+            return false;
+        }
+        handle_confirm(&values[1..], window, cx);
+        true
+    });
+    let mut form_fields = vec![FormField::new(message).with_placeholder(placeholder).with_focus()];
+    form_fields.extend(fields);
+    open_add_form_dialog(
+        FormDialog {
+            title,
+            fields: form_fields,
+            handle_submit,
+        },
+        window,
+        cx,
+    );
+}
+
+/// Radio field choosing the command of a delete, the first command is selected.
+///
+/// The submitted value is the index into `commands`.
+pub fn delete_command_field(label: SharedString, commands: &[DeleteCommand], cx: &App) -> FormField {
+    FormField::new(label).with_options(
+        commands
+            .iter()
+            .map(|command| i18n_common(cx, command.label_key()))
+            .collect(),
+    )
+}
+
+/// The command picked in a `delete_command_field`, the first command when nothing was picked.
+pub fn selected_delete_command(commands: &[DeleteCommand], value: Option<&SharedString>) -> DeleteCommand {
+    value
+        .and_then(|value| value.parse::<usize>().ok())
+        .and_then(|index| commands.get(index))
+        .or(commands.first())
+        .copied()
+        .unwrap_or_default()
+}
+
 /// Configuration for a dynamic form dialog.
 pub struct FormDialog {
     /// Title of the dialog.
@@ -50,6 +206,8 @@ pub enum FormFieldType {
     Input,
     /// Radio group field.
     RadioGroup,
+    /// Multi-line input field.
+    Textarea,
 }
 
 #[derive(Clone, Default)]
@@ -92,6 +250,11 @@ impl FormField {
         self.options = Some(options);
         self
     }
+    /// Configures the field as a multi-line input growing with its content.
+    pub fn with_multi_line(mut self) -> Self {
+        self.field_type = FormFieldType::Textarea;
+        self
+    }
     /// Configures the field to be validated with the provided function.
     pub fn with_validate<F>(mut self, validate: F) -> Self
     where
@@ -142,6 +305,17 @@ pub fn open_add_form_dialog(params: FormDialog, window: &mut Window, cx: &mut Ap
                 }
                 states.push(FieldState::Input(state));
             }
+            FormFieldType::Textarea => {
+                let state = cx.new(|cx| {
+                    InputState::new(window, cx)
+                        .auto_grow(TEXTAREA_MIN_ROWS, TEXTAREA_MAX_ROWS)
+                        .placeholder(field.placeholder.clone())
+                });
+                if field.focus && focus_target.is_none() {
+                    focus_target = Some(state.clone());
+                }
+                states.push(FieldState::Input(state));
+            }
             FormFieldType::RadioGroup => {
                 states.push(FieldState::Radio(Rc::new(Cell::new(0))));
             }
@@ -228,7 +402,7 @@ pub fn open_add_form_dialog(params: FormDialog, window: &mut Window, cx: &mut Ap
                 move |_, _, _, cx| {
                     let confirm_label = i18n_common(cx, "confirm");
                     let cancel_label = i18n_common(cx, "cancel");
-                    let mut buttons = vec![
+                    platform_button_order(vec![
                         // Cancel button - closes dialog without saving
                         Button::new("cancel").label(cancel_label).on_click(|_, window, cx| {
                             window.close_dialog(cx);
@@ -240,11 +414,7 @@ pub fn open_add_form_dialog(params: FormDialog, window: &mut Window, cx: &mut Ap
                                 do_submit.clone()(window, cx);
                             }
                         }),
-                    ];
-                    if is_windows() {
-                        buttons.reverse();
-                    }
-                    buttons
+                    ])
                 }
             })
     });
@@ -0,0 +1,74 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::get_home_dir;
+use crate::states::ZedisGlobalStore;
+use gpui::{App, Window};
+use gpui_component::{WindowExt, notification::Notification};
+use rust_i18n::t;
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Replaces characters that are not safe in file names with `_`.
+///
+/// Redis keys often contain separators like `:` or `/`, which are
+/// invalid (or misleading) in file names on most platforms.
+pub fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() { "untitled".to_string() } else { name }
+}
+
+/// Prompts the user for a destination path and writes `content` to it.
+///
+/// The write runs on the background executor, and the result is reported
+/// with a success or error notification in the given window.
+pub fn export_to_file(file_name: String, content: Vec<u8>, window: &mut Window, cx: &mut App) {
+    let directory = get_home_dir().unwrap_or_else(|| PathBuf::from("."));
+    let path_rx = cx.prompt_for_new_path(&directory, Some(&file_name));
+    window
+        .spawn(cx, async move |cx| {
+            let Ok(Ok(Some(path))) = path_rx.await else {
+                return;
+            };
+            let result = cx
+                .background_spawn(async move { smol::fs::write(&path, content).await.map(|_| path) })
+                .await;
+            let _ = cx.update(|window, cx| {
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let notification = match result {
+                    Ok(path) => {
+                        info!(path = %path.display(), "export file success");
+                        let message = t!("common.export_success", path = path.display(), locale = locale);
+                        Notification::success(message.to_string())
+                    }
+                    Err(e) => {
+                        error!(error = %e, "export file fail");
+                        let message = t!("common.export_failed", error = e, locale = locale);
+                        Notification::error(message.to_string())
+                    }
+                };
+                window.push_notification(notification, cx);
+            });
+        })
+        .detach();
+}
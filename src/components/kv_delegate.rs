@@ -13,11 +13,12 @@
 // limitations under the License.
 
 use crate::assets::CustomIconName;
-use crate::states::{RedisValue, ZedisGlobalStore, ZedisServerState, i18n_common};
+use crate::components::{ConfirmDialog, open_confirm_dialog};
+use crate::states::{RedisValue, ViewMode, ZedisGlobalStore, ZedisServerState, i18n_common};
 use crate::views::{KvTableColumn, KvTableColumnType};
 use gpui::{App, Edges, Entity, SharedString, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, Sizable, StyledExt, WindowExt,
+    ActiveTheme, Disableable, Icon, IconName, Sizable, StyledExt,
     button::{Button, ButtonVariants},
     h_flex,
     input::{Input, InputState},
@@ -38,15 +39,30 @@ pub trait ZedisKvFetcher: 'static {
     /// Retrieves a value for a specific cell in the table.
     fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString>;
 
+    /// Retrieves the value an edit input of a cell is filled with, the displayed value by default.
+    fn get_edit(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
+        self.get(row_ix, col_ix)
+    }
+
+    /// Retrieves the field/value pairs of a cell rendered as a nested list, e.g. the fields of a stream entry.
+    fn get_pairs(&self, _row_ix: usize, _col_ix: usize) -> Option<Vec<(SharedString, SharedString)>> {
+        None
+    }
+
     /// Returns the total count of items available.
     fn count(&self) -> usize;
 
     /// Returns the number of rows currently loaded.
     fn rows_count(&self) -> usize;
 
-    /// Returns true if all data has been loaded.
-    fn is_eof(&self) -> bool {
-        !self.is_done()
+    /// Returns true while more data remains to be loaded and the load wasn't canceled.
+    fn has_more(&self) -> bool {
+        !self.is_done() && !self.is_canceled()
+    }
+
+    /// Returns true if the user stopped loading before every row was fetched.
+    fn is_canceled(&self) -> bool {
+        false
     }
 
     /// Returns whether the data supports in-place updates.
@@ -79,6 +95,14 @@ pub trait ZedisKvFetcher: 'static {
     /// Opens a dialog to add a new value.
     fn handle_add_value(&self, _window: &mut Window, _cx: &mut App);
 
+    /// Returns whether many values can be imported at once.
+    fn can_import(&self) -> bool {
+        false
+    }
+
+    /// Opens a dialog to import many values at once.
+    fn handle_import(&self, _window: &mut Window, _cx: &mut App) {}
+
     /// Updates values for a specific row.
     fn handle_update_value(&self, _row_ix: usize, _values: Vec<SharedString>, _window: &mut Window, _cx: &mut App) {}
 
@@ -86,6 +110,43 @@ pub trait ZedisKvFetcher: 'static {
     fn new(server_state: Entity<ZedisServerState>, value: RedisValue) -> Self;
 }
 
+/// Whether the table should fetch the next page, marking the delegate as processing when it does.
+///
+/// Nothing is fetched once the data is exhausted or while a page is still loading.
+fn should_load_more(has_more: bool, processing: &Cell<bool>) -> bool {
+    has_more && !processing.replace(true)
+}
+
+/// Fetches the next page with `load` when `should_load_more` allows it.
+///
+/// The processing flag stays set until the loaded value replaces the fetcher.
+fn load_next_page<T: ZedisKvFetcher>(fetcher: &T, processing: &Cell<bool>, load: impl FnOnce(&T)) -> bool {
+    if !should_load_more(fetcher.has_more(), processing) {
+        return false;
+    }
+    load(fetcher);
+    true
+}
+
+/// Formats a value cell in the viewer mode of the key, `Auto` and `Plain` show it as loaded.
+fn format_cell(view_mode: &ViewMode, value: SharedString) -> SharedString {
+    match view_mode {
+        ViewMode::Hex => value
+            .as_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into(),
+        ViewMode::Json => serde_json::from_str::<serde_json::Value>(&value)
+            .ok()
+            .and_then(|json| serde_json::to_string_pretty(&json).ok())
+            .map(Into::into)
+            .unwrap_or(value),
+        _ => value,
+    }
+}
+
 pub type OnEditHandler = Box<dyn Fn(usize, Vec<SharedString>, &mut Window, &mut App) + 'static>;
 
 /// A Table Delegate that manages the display and editing of Key-Value pairs.
@@ -97,6 +158,8 @@ pub struct ZedisKvDelegate<T: ZedisKvFetcher> {
     processing: Rc<Cell<bool>>,
     /// Whether the delegate is readonly
     readonly: bool,
+    /// Why the delegate is readonly, shown on disabled actions
+    readonly_reason: SharedString,
     /// The data source provider.
     fetcher: Arc<T>,
     /// Column definitions for the UI component.
@@ -109,6 +172,8 @@ pub struct ZedisKvDelegate<T: ZedisKvFetcher> {
     edit_focus_done: bool,
     /// Callback function to be called when editing a row.
     on_edit: Option<OnEditHandler>,
+    /// Viewer mode the value cells are shown in
+    view_mode: ViewMode,
 }
 
 impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
@@ -159,12 +224,19 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
             editing_row: Cell::new(None),
             edit_focus_done: false,
             readonly: false,
+            readonly_reason: SharedString::default(),
             on_edit: None,
+            view_mode: ViewMode::default(),
         }
     }
 
-    pub fn enable_readonly(&mut self) {
+    pub fn set_view_mode(&mut self, view_mode: ViewMode) {
+        self.view_mode = view_mode;
+    }
+
+    pub fn enable_readonly(&mut self, reason: SharedString) {
         self.readonly = true;
+        self.readonly_reason = reason;
     }
 
     pub fn set_on_edit(&mut self, on_edit: Option<OnEditHandler>) {
@@ -196,7 +268,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
         // keep the order of the columns
         columns.sort_unstable();
         for col_ix in columns {
-            if let Some(value) = fetcher.get(row_ix, col_ix) {
+            if let Some(value) = fetcher.get_edit(row_ix, col_ix) {
                 values.push((col_ix, value));
             }
         }
@@ -276,7 +348,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                 .mr_2()
                 .icon(icon)
                 .tooltip(if self.readonly {
-                    i18n_common(cx, "disable_in_readonly")
+                    self.readonly_reason.clone()
                 } else {
                     i18n_common(cx, "update_tooltip")
                 })
@@ -320,7 +392,7 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
                 .ghost()
                 .icon(Icon::new(CustomIconName::FileXCorner))
                 .tooltip(if self.readonly {
-                    i18n_common(cx, "disable_in_readonly")
+                    self.readonly_reason.clone()
                 } else {
                     i18n_common(cx, "remove_tooltip")
                 })
@@ -332,25 +404,19 @@ impl<T: ZedisKvFetcher> ZedisKvDelegate<T> {
 
                     cx.stop_propagation();
 
-                    window.open_dialog(cx, move |dialog, _, cx| {
-                        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
-                        let message = t!(
-                            "common.remove_item_prompt",
-                            row = row_ix + 1,
-                            value = value,
-                            locale = locale
-                        );
-
-                        let processing = processing.clone();
-                        let fetcher = fetcher.clone();
-
-                        dialog.confirm().child(message.to_string()).on_ok(move |_, window, cx| {
-                            processing.replace(true);
-                            fetcher.remove(row_ix, cx);
-                            window.close_dialog(cx);
-                            true
-                        })
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "common.remove_item_prompt",
+                        row = row_ix + 1,
+                        value = value,
+                        locale = locale
+                    )
+                    .to_string();
+                    let dialog = ConfirmDialog::new(message, move |_window, cx| {
+                        processing.replace(true);
+                        fetcher.remove(row_ix, cx);
                     });
+                    open_confirm_dialog(dialog, window, cx);
                 }));
             base = base.child(remove_btn);
         }
@@ -434,13 +500,39 @@ impl<T: ZedisKvFetcher + 'static> TableDelegate for ZedisKvDelegate<T> {
             return base.child(Input::new(value_state).small().cleanable(true));
         }
 
+        if let Some(pairs) = self.fetcher.get_pairs(row_ix, col_ix) {
+            let muted = cx.theme().muted_foreground;
+            return base
+                .gap_3()
+                .overflow_hidden()
+                .children(pairs.into_iter().map(|(field, value)| {
+                    h_flex()
+                        .gap_1()
+                        .flex_shrink_0()
+                        .child(Label::new(field).text_color(muted))
+                        .child(Label::new(value))
+                }));
+        }
+
         // Default: Render value as label
-        let value = self.fetcher.get(row_ix, col_ix).unwrap_or_else(|| "--".into());
+        let value = match self.fetcher.get(row_ix, col_ix) {
+            // Field names, scores and TTLs stay as they are
+            Some(value)
+                if self
+                    .table_columns
+                    .get(col_ix)
+                    .is_some_and(|column| column.is_code_editor()) =>
+            {
+                format_cell(&self.view_mode, value)
+            }
+            value => value.unwrap_or_else(|| "--".into()),
+        };
         base.child(Label::new(value).text_align(column.align))
     }
-    /// Returns whether all data has been loaded (end of file).
+    /// Despite its name, the table only calls `load_more` while this returns true,
+    /// so it reports whether more data remains rather than whether the end was reached.
     fn is_eof(&self, _: &App) -> bool {
-        self.fetcher.is_eof()
+        self.fetcher.has_more()
     }
 
     /// Defines how many rows from the bottom should trigger load_more.
@@ -452,11 +544,146 @@ impl<T: ZedisKvFetcher + 'static> TableDelegate for ZedisKvDelegate<T> {
     /// Loads more data when user scrolls near the bottom of the table.
     /// Prevents concurrent load operations using the processing flag.
     fn load_more(&mut self, window: &mut Window, cx: &mut Context<TableState<ZedisKvDelegate<T>>>) {
-        // Don't load if already done or currently processing
-        if self.fetcher.is_done() || self.processing.replace(true) {
-            return;
+        // Don't load if nothing remains or currently processing
+        load_next_page(self.fetcher.as_ref(), &self.processing, |fetcher| {
+            fetcher.load_more(window, cx);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    /// Pages through cursors like HSCAN, done once the cursor is back to 0
+    struct PagedFetcher {
+        cursors: RefCell<VecDeque<u64>>,
+        cursor: Cell<u64>,
+        done: Cell<bool>,
+        canceled: Cell<bool>,
+        loads: Cell<usize>,
+    }
+
+    impl PagedFetcher {
+        fn with_cursors(cursors: &[u64]) -> Self {
+            Self {
+                cursors: RefCell::new(cursors.iter().copied().collect()),
+                cursor: Cell::new(0),
+                done: Cell::new(false),
+                canceled: Cell::new(false),
+                loads: Cell::new(0),
+            }
+        }
+        fn next_page(&self) {
+            self.loads.set(self.loads.get() + 1);
+            let cursor = self.cursors.borrow_mut().pop_front().unwrap_or_default();
+            self.cursor.set(cursor);
+            self.done.set(cursor == 0);
+        }
+    }
+
+    /// Asks for the next page like the table does when scrolled to the bottom
+    fn scroll_to_bottom(fetcher: &PagedFetcher, processing: &Cell<bool>) -> bool {
+        load_next_page(fetcher, processing, PagedFetcher::next_page)
+    }
+
+    impl ZedisKvFetcher for PagedFetcher {
+        fn get(&self, _row_ix: usize, _col_ix: usize) -> Option<SharedString> {
+            None
+        }
+        fn count(&self) -> usize {
+            0
+        }
+        fn rows_count(&self) -> usize {
+            0
         }
+        fn is_canceled(&self) -> bool {
+            self.canceled.get()
+        }
+        fn is_done(&self) -> bool {
+            self.done.get()
+        }
+        fn load_more(&self, _window: &mut Window, _cx: &mut App) {
+            self.next_page();
+        }
+        fn remove(&self, _index: usize, _cx: &mut App) {}
+        fn filter(&self, _keyword: SharedString, _cx: &mut App) {}
+        fn handle_add_value(&self, _window: &mut Window, _cx: &mut App) {}
+        fn new(_server_state: Entity<ZedisServerState>, _value: RedisValue) -> Self {
+            unreachable!("the test fetcher is built with `with_cursors`")
+        }
+    }
+
+    #[test]
+    fn test_load_next_page_until_cursor_exhausted() {
+        let fetcher = PagedFetcher::with_cursors(&[17, 42, 0]);
+        let processing = Cell::new(false);
+        assert!(fetcher.has_more());
+
+        assert!(scroll_to_bottom(&fetcher, &processing));
+        assert_eq!(17, fetcher.cursor.get());
+        assert!(!fetcher.is_done());
+        // The page is still loading
+        assert!(!scroll_to_bottom(&fetcher, &processing));
+        assert_eq!(1, fetcher.loads.get());
+
+        // The loaded value replaces the fetcher, which clears the flag
+        processing.set(false);
+        assert!(scroll_to_bottom(&fetcher, &processing));
+        assert_eq!(42, fetcher.cursor.get());
+
+        processing.set(false);
+        assert!(scroll_to_bottom(&fetcher, &processing));
+        assert_eq!(0, fetcher.cursor.get());
+        assert!(fetcher.is_done());
+        assert!(!fetcher.has_more());
+
+        processing.set(false);
+        assert!(!scroll_to_bottom(&fetcher, &processing));
+        assert_eq!(3, fetcher.loads.get());
+        assert!(!processing.get());
+    }
+
+    #[test]
+    fn test_load_next_page_stops_when_canceled() {
+        let fetcher = PagedFetcher::with_cursors(&[17, 42, 0]);
+        let processing = Cell::new(false);
+        assert!(scroll_to_bottom(&fetcher, &processing));
+        processing.set(false);
+
+        fetcher.canceled.set(true);
+        assert!(!fetcher.has_more());
+        assert!(!scroll_to_bottom(&fetcher, &processing));
+        assert_eq!(1, fetcher.loads.get());
+        assert_eq!(17, fetcher.cursor.get());
+        assert!(!fetcher.is_done());
+    }
+
+    #[test]
+    fn test_format_cell() {
+        let value = SharedString::from(r#"{"id":1}"#);
+        assert_eq!(value, format_cell(&ViewMode::Auto, value.clone()));
+        assert_eq!(value, format_cell(&ViewMode::Plain, value.clone()));
+        assert_eq!("{\n  \"id\": 1\n}", format_cell(&ViewMode::Json, value).to_string());
+        // Not JSON, shown as loaded
+        assert_eq!("zedis", format_cell(&ViewMode::Json, "zedis".into()).to_string());
+        assert_eq!("7a 65 0a", format_cell(&ViewMode::Hex, "ze\n".into()).to_string());
+    }
+
+    #[test]
+    fn test_should_load_more() {
+        let processing = Cell::new(false);
+        assert!(!should_load_more(false, &processing));
+        assert!(!processing.get());
+
+        assert!(should_load_more(true, &processing));
+        assert!(processing.get());
+        // A page is still loading
+        assert!(!should_load_more(true, &processing));
 
-        self.fetcher.load_more(window, cx);
+        processing.set(false);
+        assert!(should_load_more(true, &processing));
     }
 }
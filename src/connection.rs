@@ -16,6 +16,7 @@ use tracing::info;
 
 mod async_connection;
 mod command;
+mod command_log;
 mod config;
 mod manager;
 mod ssh_cluster_connection;
@@ -23,8 +24,14 @@ mod ssh_stream;
 mod ssh_tunnel;
 
 pub use async_connection::{RedisAsyncConn, set_redis_connection_timeout, set_redis_response_timeout};
-pub use config::{QueryMode, RedisServer, get_servers, save_servers};
-pub use manager::{AccessMode, RedisClientDescription, get_connection_manager};
+pub use command_log::{CommandLog, clear_command_logs, command_logs, set_command_logging};
+pub use config::{
+    QueryMode, RedisServer, SERVER_COLORS, add_transient_server, get_servers, save_servers, server_color,
+};
+pub use manager::{
+    AccessMode, ClusterNodeInfo, ConnectionStep, NodeRole, RedisClientDescription, SlowLogEntry, get_connection_manager,
+};
+pub use ssh_tunnel::trust_host_key;
 pub fn clear_expired_cache() {
     let (removed_count, total_count) = async_connection::clear_expired_connection_pool();
     if removed_count > 0 {
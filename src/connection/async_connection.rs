@@ -12,16 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::command_log::LoggedConnection;
 use super::config::RedisServer;
 use super::ssh_cluster_connection::SshMultiplexedConnection;
 use super::ssh_tunnel::open_single_ssh_tunnel_connection;
 use crate::error::Error;
 use crate::helpers::{TtlCache, now_secs};
 use arc_swap::ArcSwap;
+use futures::StreamExt;
 use futures::future::try_join_all;
+use futures::stream;
 use redis::{
     AsyncConnectionConfig, Client, Cmd, FromRedisValue, Pipeline, RedisFuture, Value,
-    aio::{ConnectionLike, MultiplexedConnection},
+    aio::{ConnectionLike, MultiplexedConnection, PubSub},
     cluster_async::ClusterConnection,
     cmd,
 };
@@ -33,6 +36,9 @@ use std::{sync::LazyLock, time::Duration};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Connection to a single node, its commands are logged under the node's `host:port`.
+pub(crate) type NodeConnection = LoggedConnection<MultiplexedConnection>;
+
 static DELAY: LazyLock<Option<Duration>> = LazyLock::new(|| {
     let value = std::env::var("REDIS_DELAY").unwrap_or_default();
     humantime::parse_duration(&value).ok()
@@ -64,6 +70,11 @@ impl MultiplexedConnectionCache {
 static CONNECTION_POOL: LazyLock<TtlCache<(u64, usize), Arc<MultiplexedConnectionCache>>> =
     LazyLock::new(|| TtlCache::new(Duration::from_secs(5 * 60)));
 
+/// Drops the pooled connection of a node, the next `open_single_connection` connects again.
+pub(crate) fn remove_pooled_connection(config: &RedisServer, db: usize) {
+    CONNECTION_POOL.remove(&(config.get_hash(), db));
+}
+
 /// Clears expired connections from the connection pool.
 pub fn clear_expired_connection_pool() -> (usize, usize) {
     CONNECTION_POOL.clear_expired()
@@ -120,15 +131,16 @@ pub fn get_redis_response_timeout() -> Duration {
 /// # Returns
 ///
 /// A multiplexed Redis connection connected to the specified database
-pub async fn open_single_connection(config: &RedisServer, db: usize) -> Result<MultiplexedConnection> {
+pub async fn open_single_connection(config: &RedisServer, db: usize) -> Result<NodeConnection> {
     // Generate a unique key for this connection based on config hash and database number
     let hash = config.get_hash();
     let key = (hash, db);
+    let node = config.address();
     // Try to reuse an existing connection from the pool
     if let Some(conn) = CONNECTION_POOL.get(&key)
         && let Some(conn) = conn.get_connection().await
     {
-        return Ok(conn);
+        return Ok(LoggedConnection::new(conn, node));
     }
     // Create a new connection: SSH tunnel or direct connection
     let mut conn = if config.is_ssh_tunnel() {
@@ -137,8 +149,8 @@ pub async fn open_single_connection(config: &RedisServer, db: usize) -> Result<M
         let client = open_single_client(config)?;
         // Configure connection with timeouts
         let cfg = AsyncConnectionConfig::default()
-            .set_connection_timeout(Some(get_redis_connection_timeout()))
-            .set_response_timeout(Some(get_redis_response_timeout()));
+            .set_connection_timeout(Some(config.connection_timeout()))
+            .set_response_timeout(Some(config.response_timeout()));
         client.get_multiplexed_async_connection_with_config(&cfg).await?
     };
     // Select the specified database if not the default (db 0)
@@ -153,7 +165,20 @@ pub async fn open_single_connection(config: &RedisServer, db: usize) -> Result<M
             check_time: AtomicU64::new(now_secs()),
         }),
     );
-    Ok(conn)
+    Ok(LoggedConnection::new(conn, node))
+}
+
+/// Opens a pub/sub connection to a single node, it is never pooled as subscribing blocks it.
+///
+/// SSH tunnels only carry multiplexed connections, the view disables pub/sub for them.
+pub(crate) async fn open_pubsub_connection(config: &RedisServer) -> Result<PubSub> {
+    if config.is_ssh_tunnel() {
+        return Err(Error::Invalid {
+            message: "Pub/sub is not supported over an ssh tunnel".to_string(),
+        });
+    }
+    let client = open_single_client(config)?;
+    Ok(client.get_async_pubsub().await?)
 }
 
 /// Creates a Redis client from the server configuration.
@@ -186,9 +211,9 @@ fn open_single_client(config: &RedisServer) -> Result<Client> {
 /// allowing generic usage across the application.
 #[derive(Clone)]
 pub enum RedisAsyncConn {
-    Single(MultiplexedConnection),
-    Cluster(ClusterConnection),
-    SshCluster(ClusterConnection<SshMultiplexedConnection>),
+    Single(NodeConnection),
+    Cluster(ClusterConnection<NodeConnection>),
+    SshCluster(ClusterConnection<LoggedConnection<SshMultiplexedConnection>>),
 }
 
 impl ConnectionLike for RedisAsyncConn {
@@ -279,3 +304,59 @@ pub(crate) async fn query_async_masters<T: FromRedisValue>(
 
     Ok(values)
 }
+
+/// Executes a pipeline on each node, for commands that must share the node's connection.
+///
+/// Nodes are queried concurrently and any failure fails the whole call.
+/// Results keep the order of `addrs`.
+pub(crate) async fn pipeline_async_masters<T: FromRedisValue>(
+    addrs: Vec<RedisServer>,
+    db: usize,
+    pipelines: Vec<Pipeline>,
+) -> Result<Vec<T>> {
+    let tasks = addrs.into_iter().zip(pipelines).map(|(addr, pipeline)| async move {
+        let mut conn = open_single_connection(&addr, db).await?;
+        let value: T = pipeline.query_async(&mut conn).await?;
+        Ok::<T, Error>(value)
+    });
+    try_join_all(tasks).await
+}
+
+/// Executes commands on the master nodes with at most `concurrency` nodes queried at once.
+///
+/// Every node has its own timeout, a slow or failing node only fails its own
+/// result. Each result is paired with the address of its node.
+pub(crate) async fn query_async_masters_settled<T: FromRedisValue>(
+    addrs: Vec<RedisServer>,
+    db: usize,
+    cmds: Vec<Cmd>,
+    concurrency: usize,
+    timeout: Duration,
+) -> Vec<(String, Result<T>)> {
+    let Some(first_cmd) = cmds.first() else {
+        return vec![];
+    };
+    let tasks = addrs.into_iter().enumerate().map(|(index, addr)| {
+        let current_cmd = cmds.get(index).unwrap_or(first_cmd).clone();
+        async move {
+            let name = addr.address();
+            // Each node is queried on its own, so its error doesn't fail the others
+            let query = async {
+                let mut values: Vec<T> = query_async_masters(vec![addr], db, vec![current_cmd]).await?;
+                values.pop().ok_or_else(|| Error::Invalid {
+                    message: format!("{name} returned no value"),
+                })
+            };
+            let expired = async {
+                smol::Timer::after(timeout).await;
+                Err(Error::Invalid {
+                    message: format!("{name} timed out"),
+                })
+            };
+            let result = smol::future::or(query, expired).await;
+            (name, result)
+        }
+    });
+
+    stream::iter(tasks).buffered(concurrency.max(1)).collect().await
+}
@@ -0,0 +1,321 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Local};
+use parking_lot::Mutex;
+use redis::{
+    Arg, AsyncConnectionConfig, Cmd, IntoConnectionInfo, Pipeline, RedisFuture, Value, aio::ConnectionLike,
+    cluster_async::Connect,
+};
+use std::collections::VecDeque;
+use std::sync::{
+    Arc, LazyLock,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Entries kept for the log panel, the oldest are dropped first
+const MAX_COMMAND_LOGS: usize = 1000;
+/// Chars of an argument shown before it is cut
+const MAX_ARG_CHARS: usize = 64;
+/// Arguments of a command shown before the rest are counted
+const MAX_ARGS: usize = 16;
+/// Shown instead of passwords and other secrets
+const REDACTED: &str = "<redacted>";
+
+static COMMAND_LOGGING: AtomicBool = AtomicBool::new(false);
+
+static COMMAND_LOGS: LazyLock<Mutex<VecDeque<CommandLog>>> = LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+/// A command sent through the connection layer while command logging is on.
+#[derive(Debug, Clone)]
+pub struct CommandLog {
+    pub time: DateTime<Local>,
+    /// `host:port` of the node that served the command
+    pub node: Arc<str>,
+    /// The command with its arguments, secrets redacted
+    pub command: String,
+    pub latency: Duration,
+    pub error: Option<String>,
+}
+
+/// Turns command logging on or off, takes effect for the next command.
+pub fn set_command_logging(enabled: bool) {
+    COMMAND_LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+fn is_command_logging() -> bool {
+    COMMAND_LOGGING.load(Ordering::Relaxed)
+}
+
+/// The logged commands, oldest first.
+pub fn command_logs() -> Vec<CommandLog> {
+    COMMAND_LOGS.lock().iter().cloned().collect()
+}
+
+pub fn clear_command_logs() {
+    COMMAND_LOGS.lock().clear();
+}
+
+fn record(node: Arc<str>, command: String, latency: Duration, error: Option<String>) {
+    info!(
+        node = node.as_ref(),
+        command = command.as_str(),
+        latency = ?latency,
+        error = error.as_deref(),
+        "redis command"
+    );
+    let mut logs = COMMAND_LOGS.lock();
+    if logs.len() >= MAX_COMMAND_LOGS {
+        logs.pop_front();
+    }
+    logs.push_back(CommandLog {
+        time: Local::now(),
+        node,
+        command,
+        latency,
+        error,
+    });
+}
+
+/// Hides the secrets of commands that carry credentials.
+///
+/// - `AUTH [username] password`
+/// - `HELLO ... AUTH username password ...`
+/// - `CONFIG SET requirepass|masterauth value`
+/// - `MIGRATE ... AUTH password` / `AUTH2 username password`
+/// - `ACL SETUSER` password rules (`>pass`, `<pass`, `#hash`, `!hash`)
+fn redact_args(args: &mut [String]) {
+    let Some(name) = args.first().map(|name| name.to_ascii_uppercase()) else {
+        return;
+    };
+    let sub = args.get(1).map(|sub| sub.to_ascii_uppercase()).unwrap_or_default();
+    fn redact_from(index: usize, count: usize, args: &mut [String]) {
+        for arg in args.iter_mut().skip(index).take(count) {
+            *arg = REDACTED.to_string();
+        }
+    }
+    match (name.as_str(), sub.as_str()) {
+        ("AUTH", _) => redact_from(1, usize::MAX, args),
+        ("HELLO", _) | ("MIGRATE", _) => {
+            let mut index = 1;
+            while index < args.len() {
+                let count = match args[index].to_ascii_uppercase().as_str() {
+                    "AUTH" if name == "MIGRATE" => 1,
+                    "AUTH" | "AUTH2" => 2,
+                    _ => 0,
+                };
+                redact_from(index + 1, count, args);
+                index += count + 1;
+            }
+        }
+        ("CONFIG", "SET") => {
+            let mut index = 2;
+            while index + 1 < args.len() {
+                if matches!(args[index].to_ascii_lowercase().as_str(), "requirepass" | "masterauth") {
+                    redact_from(index + 1, 1, args);
+                }
+                index += 2;
+            }
+        }
+        ("ACL", "SETUSER") => {
+            for arg in args.iter_mut().skip(3) {
+                if arg.starts_with(['>', '<', '#', '!']) {
+                    *arg = REDACTED.to_string();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Renders a command as `NAME arg1 arg2 ...`, long arguments and argument lists are cut.
+fn describe_cmd(cmd: &Cmd) -> String {
+    let mut args: Vec<String> = cmd
+        .args_iter()
+        .map(|arg| match arg {
+            Arg::Simple(value) => String::from_utf8_lossy(value).to_string(),
+            Arg::Cursor => "<cursor>".to_string(),
+        })
+        .collect();
+    redact_args(&mut args);
+    let total = args.len();
+    let mut parts: Vec<String> = args
+        .into_iter()
+        .take(MAX_ARGS)
+        .map(|arg| {
+            if arg.chars().count() > MAX_ARG_CHARS {
+                let cut: String = arg.chars().take(MAX_ARG_CHARS).collect();
+                format!("{cut}…")
+            } else {
+                arg
+            }
+        })
+        .collect();
+    if total > MAX_ARGS {
+        parts.push(format!("…(+{})", total - MAX_ARGS));
+    }
+    parts.join(" ")
+}
+
+/// A connection that records the commands it sends while command logging is on.
+///
+/// Logging is checked per command, so it can be toggled without reconnecting.
+#[derive(Clone)]
+pub struct LoggedConnection<C> {
+    inner: C,
+    node: Arc<str>,
+}
+
+impl<C> LoggedConnection<C> {
+    pub fn new(inner: C, node: impl Into<Arc<str>>) -> Self {
+        Self {
+            inner,
+            node: node.into(),
+        }
+    }
+}
+
+impl<C: ConnectionLike + Send> ConnectionLike for LoggedConnection<C> {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        if !is_command_logging() {
+            return self.inner.req_packed_command(cmd);
+        }
+        let node = self.node.clone();
+        let command = describe_cmd(cmd);
+        let future = self.inner.req_packed_command(cmd);
+        Box::pin(async move {
+            let started = Instant::now();
+            let result = future.await;
+            record(
+                node,
+                command,
+                started.elapsed(),
+                result.as_ref().err().map(|e| e.to_string()),
+            );
+            result
+        })
+    }
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        pipeline: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        if !is_command_logging() {
+            return self.inner.req_packed_commands(pipeline, offset, count);
+        }
+        let node = self.node.clone();
+        let command = pipeline.cmd_iter().map(describe_cmd).collect::<Vec<_>>().join("; ");
+        let future = self.inner.req_packed_commands(pipeline, offset, count);
+        Box::pin(async move {
+            let started = Instant::now();
+            let result = future.await;
+            record(
+                node,
+                command,
+                started.elapsed(),
+                result.as_ref().err().map(|e| e.to_string()),
+            );
+            result
+        })
+    }
+    fn get_db(&self) -> i64 {
+        self.inner.get_db()
+    }
+}
+
+/// The cluster client opens a logged connection to each node it routes to,
+/// so cluster commands are logged under the node that served them.
+impl<C: Connect + Send + 'static> Connect for LoggedConnection<C> {
+    fn connect_with_config<'a, T>(info: T, config: AsyncConnectionConfig) -> RedisFuture<'a, Self>
+    where
+        T: IntoConnectionInfo + Send + 'a,
+    {
+        Box::pin(async move {
+            let info = info.into_connection_info()?;
+            let node = info.addr().to_string();
+            let inner = C::connect_with_config(info, config).await?;
+            Ok(LoggedConnection::new(inner, node))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redacted(args: &[&str]) -> Vec<String> {
+        let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        redact_args(&mut args);
+        args
+    }
+
+    #[test]
+    fn test_redact_args() {
+        let r = REDACTED;
+        let cases: Vec<(Vec<&str>, Vec<&str>)> = vec![
+            (vec!["AUTH", "secret"], vec!["AUTH", r]),
+            (vec!["auth", "user", "secret"], vec!["auth", r, r]),
+            (vec!["Auth", "secret"], vec!["Auth", r]),
+            (
+                vec!["HELLO", "3", "AUTH", "user", "secret", "SETNAME", "app"],
+                vec!["HELLO", "3", "AUTH", r, r, "SETNAME", "app"],
+            ),
+            (
+                vec!["hello", "3", "auth", "user", "secret"],
+                vec!["hello", "3", "auth", r, r],
+            ),
+            (vec!["HELLO", "3"], vec!["HELLO", "3"]),
+            (
+                vec!["MIGRATE", "host", "6379", "key", "0", "1000", "AUTH", "secret"],
+                vec!["MIGRATE", "host", "6379", "key", "0", "1000", "AUTH", r],
+            ),
+            (
+                vec![
+                    "migrate", "host", "6379", "", "0", "1000", "Auth2", "user", "secret", "KEYS", "a",
+                ],
+                vec!["migrate", "host", "6379", "", "0", "1000", "Auth2", r, r, "KEYS", "a"],
+            ),
+            (
+                vec!["CONFIG", "SET", "requirepass", "secret"],
+                vec!["CONFIG", "SET", "requirepass", r],
+            ),
+            (
+                vec!["config", "set", "maxmemory", "1gb", "MasterAuth", "secret"],
+                vec!["config", "set", "maxmemory", "1gb", "MasterAuth", r],
+            ),
+            (
+                vec!["CONFIG", "GET", "requirepass"],
+                vec!["CONFIG", "GET", "requirepass"],
+            ),
+            (
+                vec![
+                    "ACL", "SETUSER", "alice", "on", ">secret", "<old", "#abc", "!def", "~*", "+@all",
+                ],
+                vec!["ACL", "SETUSER", "alice", "on", r, r, r, r, "~*", "+@all"],
+            ),
+            (
+                vec!["acl", "setuser", "bob", ">secret"],
+                vec!["acl", "setuser", "bob", r],
+            ),
+            (vec!["SET", "AUTH", "secret"], vec!["SET", "AUTH", "secret"]),
+            (vec!["GET", "key"], vec!["GET", "key"]),
+        ];
+        for (args, expected) in cases {
+            assert_eq!(redacted(&args), expected, "{args:?}");
+        }
+    }
+}
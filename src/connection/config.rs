@@ -12,13 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::async_connection::{get_redis_connection_timeout, get_redis_response_timeout};
 use crate::{
     error::Error,
     helpers::{decrypt, encrypt, get_or_create_config_dir, is_development},
 };
 use arc_swap::ArcSwap;
-use gpui::Action;
-use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use gpui::{Action, Hsla};
+use percent_encoding::{AsciiSet, CONTROLS, NON_ALPHANUMERIC, utf8_percent_encode};
 use redis::{ClientTlsConfig, TlsCertificates};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -27,17 +28,25 @@ use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{fmt, fs::read_to_string, path::PathBuf, str::FromStr, sync::LazyLock};
 use tracing::info;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// `server_type` of servers configured as cluster (0 auto, 1 standalone, 2 sentinel).
+const CLUSTER_SERVER_TYPE: usize = 3;
+/// Chars of a socket path that would end the path part of the url
+const PATH_ENCODE_SET: &AsciiSet = &CONTROLS.add(b' ').add(b'#').add(b'?').add(b'%');
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, Action)]
 pub enum QueryMode {
     #[default]
     All,
     Prefix,
     Exact,
+    /// The keyword is the `MATCH` pattern as typed
+    Pattern,
 }
 
 impl fmt::Display for QueryMode {
@@ -45,12 +54,52 @@ impl fmt::Display for QueryMode {
         let s = match self {
             QueryMode::Prefix => "^",
             QueryMode::Exact => "=",
+            QueryMode::Pattern => "#",
             _ => "*",
         };
         write!(f, "{}", s)
     }
 }
 
+impl QueryMode {
+    /// The glob pattern (or exact key) a keyword is searched with in this mode
+    ///
+    /// - `All` matches keys containing the keyword: `*{keyword}*`
+    /// - `Prefix` matches keys starting with the keyword: `{keyword}*`
+    /// - `Exact` isn't scanned, the keyword is the key name as is, so nothing in it is a glob
+    /// - `Pattern` uses the keyword as the glob pattern, e.g. `user:*:session`
+    ///
+    /// With `escape`, glob metacharacters in the keyword are matched literally,
+    /// otherwise they keep their glob meaning. `Pattern` is never escaped.
+    pub fn scan_pattern(&self, keyword: &str, escape: bool) -> String {
+        let escaped;
+        let keyword = if escape && matches!(self, QueryMode::All | QueryMode::Prefix) {
+            escaped = escape_glob(keyword);
+            escaped.as_str()
+        } else {
+            keyword
+        };
+        match self {
+            QueryMode::All if keyword.is_empty() => "*".to_string(),
+            QueryMode::All => format!("*{keyword}*"),
+            QueryMode::Prefix => format!("{keyword}*"),
+            QueryMode::Exact | QueryMode::Pattern => keyword.to_string(),
+        }
+    }
+}
+
+/// Backslash-escapes the glob metacharacters of `MATCH` patterns, so `foo[bar]` matches literally.
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 impl FromStr for QueryMode {
     type Err = std::convert::Infallible;
 
@@ -58,6 +107,7 @@ impl FromStr for QueryMode {
         match s {
             "^" => Ok(QueryMode::Prefix),
             "=" => Ok(QueryMode::Exact),
+            "#" => Ok(QueryMode::Pattern),
             _ => Ok(QueryMode::All),
         }
     }
@@ -88,21 +138,155 @@ pub struct RedisServer {
     pub ssh_username: Option<String>,
     pub ssh_password: Option<String>,
     pub ssh_key: Option<String>,
+    pub ssh_key_passphrase: Option<String>,
+    /// Authenticate the tunnel with the identities of the SSH agent
+    pub ssh_agent: Option<bool>,
+    /// Reject SSH hosts missing from known_hosts instead of asking before trusting them
+    pub ssh_strict_host_key: Option<bool>,
+    pub default_db: Option<usize>,
+    /// Name of a color of `SERVER_COLORS`, tells environments apart at a glance
+    pub color: Option<String>,
+    /// Path of a Unix domain socket, replaces host and port when set
+    pub socket_path: Option<String>,
+    /// Route the reads of a cluster (SCAN included) to replicas, writes stay on the masters
+    pub prefer_replica: Option<bool>,
+    /// Overrides the global connection timeout for slow or remote servers
+    pub connect_timeout_ms: Option<u64>,
+    /// Overrides the global response timeout for slow or remote servers
+    pub response_timeout_ms: Option<u64>,
 }
+
+/// Colors a server can be tagged with, saved by name and rendered from the hue
+pub const SERVER_COLORS: [(&str, f32); 6] = [
+    ("red", 0.0),
+    ("orange", 0.08),
+    ("yellow", 0.14),
+    ("green", 0.36),
+    ("blue", 0.6),
+    ("purple", 0.78),
+];
+
+/// Renders a hue of `SERVER_COLORS`
+pub fn server_color(hue: f32) -> Hsla {
+    gpui::hsla(hue, 0.7, 0.5, 1.0)
+}
+
 impl RedisServer {
+    /// The color the server is tagged with, if any
+    pub fn tag_color(&self) -> Option<Hsla> {
+        let name = self.color.as_deref()?;
+        SERVER_COLORS
+            .iter()
+            .find(|(color, _)| *color == name)
+            .map(|(_, hue)| server_color(*hue))
+    }
     pub fn get_hash(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.hash(&mut hasher);
         hasher.finish()
     }
+    /// Hash of the connection settings, the id, name, timestamps and display preferences are left out
+    pub fn connection_hash(&self) -> u64 {
+        RedisServer {
+            id: String::new(),
+            name: String::new(),
+            description: None,
+            updated_at: None,
+            query_mode: None,
+            soft_wrap: None,
+            color: None,
+            ..self.clone()
+        }
+        .get_hash()
+    }
+    /// The socket path when connecting over a Unix domain socket
+    pub fn unix_socket(&self) -> Option<&str> {
+        self.socket_path.as_deref().filter(|path| !path.is_empty())
+    }
+    /// `host:port`, or the socket path for a Unix domain socket
+    pub fn address(&self) -> String {
+        match self.unix_socket() {
+            Some(path) => path.to_string(),
+            None => format!("{}:{}", self.host, self.port),
+        }
+    }
+    /// SSH tunneling is ignored for a Unix domain socket
     pub fn is_ssh_tunnel(&self) -> bool {
-        self.ssh_tunnel.unwrap_or(false) && self.ssh_addr.as_ref().map(|addr| !addr.is_empty()).unwrap_or(false)
+        self.unix_socket().is_none()
+            && self.ssh_tunnel.unwrap_or(false)
+            && self.ssh_addr.as_ref().map(|addr| !addr.is_empty()).unwrap_or(false)
     }
     pub fn readonly(&self) -> bool {
         self.readonly.unwrap_or(false)
     }
+    pub fn prefer_replica(&self) -> bool {
+        self.prefer_replica.unwrap_or(false)
+    }
+    /// The connection timeout of the server, the global one when unset
+    pub fn connection_timeout(&self) -> Duration {
+        self.connect_timeout_ms
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+            .unwrap_or_else(get_redis_connection_timeout)
+    }
+    /// The response timeout of the server, the global one when unset
+    pub fn response_timeout(&self) -> Duration {
+        self.response_timeout_ms
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+            .unwrap_or_else(get_redis_response_timeout)
+    }
+    /// The db selected when connecting, always 0 for cluster which only has db 0.
+    pub fn default_db(&self) -> usize {
+        if self.server_type == Some(CLUSTER_SERVER_TYPE) {
+            return 0;
+        }
+        self.default_db.unwrap_or(0)
+    }
+    /// Whether connecting with `other` differs from this config, display-only fields
+    /// (name, description, query mode...) are ignored.
+    pub fn connection_changed(&self, other: &RedisServer) -> bool {
+        self.host != other.host
+            || self.port != other.port
+            || self.username != other.username
+            || self.password != other.password
+            || self.server_type != other.server_type
+            || self.master_name != other.master_name
+            || self.tls != other.tls
+            || self.insecure != other.insecure
+            || self.client_cert != other.client_cert
+            || self.client_key != other.client_key
+            || self.root_cert != other.root_cert
+            || self.ssh_tunnel != other.ssh_tunnel
+            || self.ssh_addr != other.ssh_addr
+            || self.ssh_username != other.ssh_username
+            || self.ssh_password != other.ssh_password
+            || self.ssh_key != other.ssh_key
+            || self.ssh_key_passphrase != other.ssh_key_passphrase
+            || self.ssh_agent != other.ssh_agent
+            || self.ssh_strict_host_key != other.ssh_strict_host_key
+            || self.socket_path != other.socket_path
+            || self.prefer_replica != other.prefer_replica
+            || self.connect_timeout_ms != other.connect_timeout_ms
+            || self.response_timeout_ms != other.response_timeout_ms
+    }
     /// Generates the connection URL based on host, port, and optional password.
     pub fn get_connection_url(&self) -> String {
+        // Unix socket credentials are passed as query parameters, TLS doesn't apply
+        if let Some(path) = self.unix_socket() {
+            let mut params = vec![];
+            if let Some(username) = &self.username {
+                params.push(format!("user={}", utf8_percent_encode(username, NON_ALPHANUMERIC)));
+            }
+            if let Some(pwd) = &self.password {
+                params.push(format!("pass={}", utf8_percent_encode(pwd, NON_ALPHANUMERIC)));
+            }
+            let path = utf8_percent_encode(path.trim_start_matches('/'), PATH_ENCODE_SET);
+            if params.is_empty() {
+                return format!("redis+unix:///{path}");
+            }
+            return format!("redis+unix:///{path}?{}", params.join("&"));
+        }
         let tls = self.tls.unwrap_or(false);
         let scheme = if tls { "rediss" } else { "redis" };
 
@@ -125,7 +309,7 @@ impl RedisServer {
         url
     }
     pub fn tls_certificates(&self) -> Option<TlsCertificates> {
-        if !self.tls.unwrap_or(false) {
+        if !self.tls.unwrap_or(false) || self.unix_socket().is_some() {
             return None;
         }
         let mut client_tls = None;
@@ -168,6 +352,19 @@ fn get_or_create_server_config() -> Result<PathBuf> {
 static SERVER_CONFIG_MAP: LazyLock<ArcSwap<HashMap<String, RedisServer>>> =
     LazyLock::new(|| ArcSwap::from_pointee(HashMap::new()));
 
+/// Servers connected from a URL without being saved, kept until the app quits.
+static TRANSIENT_SERVER_CONFIG_MAP: LazyLock<ArcSwap<HashMap<String, RedisServer>>> =
+    LazyLock::new(|| ArcSwap::from_pointee(HashMap::new()));
+
+/// Registers a server that is connected without being saved to the config file.
+pub fn add_transient_server(server: RedisServer) {
+    TRANSIENT_SERVER_CONFIG_MAP.rcu(|configs| {
+        let mut configs = HashMap::clone(configs);
+        configs.insert(server.id.clone(), server.clone());
+        configs
+    });
+}
+
 pub fn get_servers() -> Result<Vec<RedisServer>> {
     let path = get_or_create_server_config()?;
     let value = read_to_string(path)?;
@@ -187,6 +384,9 @@ pub fn get_servers() -> Result<Vec<RedisServer>> {
         if let Some(ssh_key) = &server.ssh_key {
             server.ssh_key = Some(decrypt(ssh_key).unwrap_or(ssh_key.clone()));
         }
+        if let Some(ssh_key_passphrase) = &server.ssh_key_passphrase {
+            server.ssh_key_passphrase = Some(decrypt(ssh_key_passphrase).unwrap_or(ssh_key_passphrase.clone()));
+        }
         configs.insert(server.id.clone(), server.clone());
     }
     SERVER_CONFIG_MAP.store(Arc::new(configs));
@@ -207,6 +407,9 @@ pub async fn save_servers(mut servers: Vec<RedisServer>) -> Result<()> {
         if let Some(ssh_key) = &server.ssh_key {
             server.ssh_key = Some(encrypt(ssh_key)?);
         }
+        if let Some(ssh_key_passphrase) = &server.ssh_key_passphrase {
+            server.ssh_key_passphrase = Some(encrypt(ssh_key_passphrase)?);
+        }
     }
     SERVER_CONFIG_MAP.store(Arc::new(configs));
     let path = get_or_create_server_config()?;
@@ -220,6 +423,9 @@ pub fn get_config(id: &str) -> Result<RedisServer> {
     if let Some(server) = SERVER_CONFIG_MAP.load().get(id) {
         return Ok(server.clone());
     }
+    if let Some(server) = TRANSIENT_SERVER_CONFIG_MAP.load().get(id) {
+        return Ok(server.clone());
+    }
     let servers = get_servers()?;
     let config = servers.iter().find(|config| config.id == id).ok_or(Error::Invalid {
         message: format!("Redis config not found: {id}"),
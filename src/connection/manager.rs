@@ -14,26 +14,64 @@
 
 use super::{
     async_connection::{
-        RedisAsyncConn, get_redis_connection_timeout, get_redis_response_timeout, open_single_connection,
-        query_async_masters,
+        NodeConnection, RedisAsyncConn, open_pubsub_connection, open_single_connection, pipeline_async_masters,
+        query_async_masters, query_async_masters_settled, remove_pooled_connection,
     },
+    command_log::LoggedConnection,
     config::{RedisServer, get_config},
     ssh_cluster_connection::SshMultiplexedConnection,
 };
 use crate::error::Error;
-use crate::helpers::TtlCache;
+use crate::helpers::{TtlCache, redis_value_to_string};
+use crate::states::{DeleteCommand, KeyType};
+use dashmap::DashMap;
 use gpui::SharedString;
-use redis::{Cmd, FromRedisValue, InfoDict, Role, aio::MultiplexedConnection, cluster, cmd};
+use redis::{
+    Cmd, ErrorKind, FromRedisValue, InfoDict, Role, Value, aio::PubSub, cluster, cluster_async::ClusterConnection, cmd,
+    pipe,
+};
 use semver::Version;
 use std::{
     collections::{HashMap, HashSet},
-    sync::LazyLock,
+    future::Future,
+    sync::{Arc, LazyLock},
     time::Duration,
 };
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Number of hash slots of a cluster
+const CLUSTER_SLOTS: u16 = 16384;
+/// Delays before each retry of an operation that failed during a failover
+const FAILOVER_RETRY_DELAYS: [Duration; 2] = [Duration::from_millis(100), Duration::from_millis(300)];
+/// Keys dumped or restored in one pipeline, a DUMP payload can be as big as the value
+const DUMP_BATCH_SIZE: usize = 100;
+
+/// Hash slot of a key in a cluster, only the hash tag (`{...}`) is hashed when it has one.
+fn key_slot(key: &[u8]) -> u16 {
+    let tag = key.iter().position(|b| *b == b'{').and_then(|open| {
+        let rest = &key[open + 1..];
+        rest.iter()
+            .position(|b| *b == b'}')
+            .filter(|close| *close > 0)
+            .map(|close| &rest[..close])
+    });
+    // CRC16/XMODEM
+    let mut crc: u16 = 0;
+    for byte in tag.unwrap_or(key) {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc % CLUSTER_SLOTS
+}
+
 // Global singleton for ConnectionManager
 static CONNECTION_MANAGER: LazyLock<ConnectionManager> = LazyLock::new(ConnectionManager::new);
 
@@ -47,6 +85,36 @@ pub enum AccessMode {
     StrictReadOnly,
 }
 
+/// Phases of establishing a new client, reported while connecting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionStep {
+    /// Opening the SSH session the connection is tunneled through
+    SshTunnel,
+    /// Connecting to the configured address
+    Connecting,
+    /// Detecting the server type and discovering sentinel/cluster nodes
+    DiscoverTopology,
+    /// Connecting to the discovered nodes
+    ConnectNodes,
+    /// Checking whether the ACL user may write
+    CheckAccess,
+    /// Loading the server version
+    ServerInfo,
+}
+
+impl ConnectionStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectionStep::SshTunnel => "ssh_tunnel",
+            ConnectionStep::Connecting => "connecting",
+            ConnectionStep::DiscoverTopology => "discover_topology",
+            ConnectionStep::ConnectNodes => "connect_nodes",
+            ConnectionStep::CheckAccess => "check_access",
+            ConnectionStep::ServerInfo => "server_info",
+        }
+    }
+}
+
 // Enum representing the type of Redis server
 #[derive(Debug, Clone, PartialEq)]
 enum ServerType {
@@ -73,6 +141,46 @@ enum RClient {
     SshCluster(cluster::ClusterClient),
 }
 
+/// An entry of `SLOWLOG GET`
+#[derive(Debug, Clone, Default)]
+pub struct SlowLogEntry {
+    pub id: i64,
+    /// Unix time in seconds the command was logged at
+    pub timestamp: i64,
+    /// Execution time in microseconds
+    pub duration: i64,
+    pub args: Vec<String>,
+    /// Address of the client, empty before Redis 4.0
+    pub client: String,
+    /// `host:port` of the master the entry comes from
+    pub node: String,
+}
+
+impl SlowLogEntry {
+    /// Parses `[id, timestamp, duration, [args...], client, client name]`
+    fn parse(value: &Value, node: String) -> Option<Self> {
+        let Value::Array(fields) = value else {
+            return None;
+        };
+        let int = |index: usize| match fields.get(index) {
+            Some(Value::Int(value)) => Some(*value),
+            _ => None,
+        };
+        let args = match fields.get(3) {
+            Some(Value::Array(args)) => args.iter().map(redis_value_to_string).collect(),
+            _ => vec![],
+        };
+        Some(Self {
+            id: int(0)?,
+            timestamp: int(1)?,
+            duration: int(2)?,
+            args,
+            client: fields.get(4).map(redis_value_to_string).unwrap_or_default(),
+            node,
+        })
+    }
+}
+
 // Node roles in a Redis setup
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum NodeRole {
@@ -90,24 +198,38 @@ struct RedisNode {
     // connection_url: String,
     role: NodeRole,
     master_name: Option<String>,
+    /// Cluster node id, and the id of the master a replica follows
+    node_id: Option<String>,
+    master_id: Option<String>,
 }
 
 impl RedisNode {
     pub fn host_port(&self) -> String {
-        format!("{}:{}", self.server.host, self.server.port)
+        self.server.address()
     }
 }
 
 // Information parsed from `CLUSTER NODES` command
 #[derive(Debug, Clone)]
 pub struct ClusterNodeInfo {
+    pub id: String,
     pub ip: String,
     pub port: u16,
     pub role: NodeRole,
+    /// The master followed by a replica, `-` for masters
+    pub master_id: Option<String>,
+    /// Inclusive slot ranges served by a master
+    pub slots: Vec<(u16, u16)>,
+    /// Slots moving to another node, with the id of that node
+    pub migrating: Vec<(u16, String)>,
+    /// Slots moving in from another node, with the id of that node
+    pub importing: Vec<(u16, String)>,
 }
 
 /// Parses a Redis address string like "ip:port@cport" or just "ip:port".
 fn parse_address(address_str: &str) -> Result<(String, u16, Option<u16>)> {
+    // Redis 7 appends the announced hostname, e.g. "ip:port@cport,hostname"
+    let address_str = address_str.split_once(',').map_or(address_str, |(address, _)| address);
     // Split into address part and optional cluster bus port part
     let (addr_part, cport_part) = address_str
         .split_once('@')
@@ -135,6 +257,33 @@ fn parse_address(address_str: &str) -> Result<(String, u16, Option<u16>)> {
     Ok((ip.to_string(), port, cport))
 }
 
+/// Parses the output of `CLIENT LIST`, returning the IDs of clients idle for at least `min_idle` seconds.
+///
+/// Replication links (flags `M`/`S`) are skipped, killing them would break replication,
+/// and so is `own_id`, the connection of the app that listed the clients.
+fn parse_idle_client_ids(raw_data: &str, min_idle: u64, own_id: u64) -> Vec<u64> {
+    raw_data
+        .lines()
+        .filter_map(|line| {
+            let mut id = None;
+            let mut idle = None;
+            let mut replication = false;
+            for (name, value) in line.split_whitespace().filter_map(|field| field.split_once('=')) {
+                match name {
+                    "id" => id = value.parse::<u64>().ok(),
+                    "idle" => idle = value.parse::<u64>().ok(),
+                    "flags" => replication = value.contains(['M', 'S']),
+                    _ => {}
+                }
+            }
+            if replication || idle? < min_idle {
+                return None;
+            }
+            id.filter(|id| *id != own_id)
+        })
+        .collect()
+}
+
 /// Parses the output of the `CLUSTER NODES` command.
 fn parse_cluster_nodes(raw_data: &str) -> Result<Vec<ClusterNodeInfo>> {
     let mut nodes = Vec::new();
@@ -161,13 +310,54 @@ fn parse_cluster_nodes(raw_data: &str) -> Result<Vec<ClusterNodeInfo>> {
             NodeRole::Unknown
         };
 
-        nodes.push(ClusterNodeInfo { ip, port, role });
+        let master_id = Some(parts[3]).filter(|id| *id != "-").map(String::from);
+
+        // Trailing fields are slots `5461`, ranges `0-5460` and migrations
+        // `[slot->-node]` (migrating) or `[slot-<-node]` (importing)
+        let mut slots = Vec::new();
+        let mut migrating = Vec::new();
+        let mut importing = Vec::new();
+        for field in &parts[8..] {
+            if let Some(migration) = field.strip_prefix('[').and_then(|field| field.strip_suffix(']')) {
+                if let Some((slot, node)) = migration.split_once("->-")
+                    && let Ok(slot) = slot.parse::<u16>()
+                {
+                    migrating.push((slot, node.to_string()));
+                } else if let Some((slot, node)) = migration.split_once("-<-")
+                    && let Ok(slot) = slot.parse::<u16>()
+                {
+                    importing.push((slot, node.to_string()));
+                }
+                continue;
+            }
+            let range = match field.split_once('-') {
+                Some((start, end)) => start.parse::<u16>().ok().zip(end.parse::<u16>().ok()),
+                None => field.parse::<u16>().ok().map(|slot| (slot, slot)),
+            };
+            if let Some(range) = range {
+                slots.push(range);
+            }
+        }
+
+        nodes.push(ClusterNodeInfo {
+            id: parts[0].to_string(),
+            ip,
+            port,
+            role,
+            master_id,
+            slots,
+            migrating,
+            importing,
+        });
     }
 
     Ok(nodes)
 }
 
 /// Establishes an asynchronous connection based on the client type.
+///
+/// Cluster clients get the timeouts from their builder, and a logged connection
+/// per node so each command is logged under the node that served it.
 async fn get_async_connection(client: &RClient, db: usize) -> Result<RedisAsyncConn> {
     match client {
         RClient::Single(config) => {
@@ -175,14 +365,11 @@ async fn get_async_connection(client: &RClient, db: usize) -> Result<RedisAsyncC
             Ok(RedisAsyncConn::Single(conn))
         }
         RClient::Cluster(client) => {
-            let cfg = cluster::ClusterConfig::default()
-                .set_connection_timeout(get_redis_connection_timeout())
-                .set_response_timeout(get_redis_response_timeout());
-            let conn = client.get_async_connection_with_config(cfg).await?;
+            let conn: ClusterConnection<NodeConnection> = client.get_async_generic_connection().await?;
             Ok(RedisAsyncConn::Cluster(conn))
         }
         RClient::SshCluster(client) => {
-            let conn: redis::cluster_async::ClusterConnection<SshMultiplexedConnection> =
+            let conn: ClusterConnection<LoggedConnection<SshMultiplexedConnection>> =
                 client.get_async_generic_connection().await?;
             Ok(RedisAsyncConn::SshCluster(conn))
         }
@@ -197,8 +384,15 @@ pub struct RedisClient {
     server_type: ServerType,
     nodes: Vec<RedisNode>,
     master_nodes: Vec<RedisNode>,
+    /// A replica of each master (the master itself when it has none), in master order,
+    /// only set for clusters configured to read from replicas
+    replica_nodes: Vec<RedisNode>,
     version: Version,
     connection: RedisAsyncConn,
+    /// User of the connection from ACL WHOAMI, None when the server has no ACL
+    acl_user: Option<String>,
+    /// ACL DRYRUN result of each command, dropped with the client on reconnect
+    command_permissions: Arc<DashMap<String, bool>>,
 }
 #[derive(Debug, Clone, Default)]
 pub struct RedisClientDescription {
@@ -207,6 +401,14 @@ pub struct RedisClientDescription {
     pub slave_nodes: SharedString,
 }
 impl RedisClient {
+    /// Drops the pooled connections of every node, including the db 0 ones of discovery,
+    /// as a pooled connection is only pinged once a minute and may already be dead.
+    fn remove_pooled_connections(&self) {
+        for node in self.master_nodes.iter().chain(self.nodes.iter()) {
+            remove_pooled_connection(&node.server, self.db);
+            remove_pooled_connection(&node.server, 0);
+        }
+    }
     pub fn nodes(&self) -> (usize, usize) {
         (self.master_nodes.len(), self.nodes.len())
     }
@@ -216,9 +418,41 @@ impl RedisClient {
     pub fn supports_db_selection(&self) -> bool {
         self.server_type != ServerType::Cluster
     }
+    /// Rejects commands moving a value between keys of different hash slots,
+    /// which a cluster refuses with CROSSSLOT.
+    pub fn ensure_same_slot(&self, from: &str, to: &str) -> Result<()> {
+        if self.server_type != ServerType::Cluster {
+            return Ok(());
+        }
+        let from_slot = key_slot(from.as_bytes());
+        let to_slot = key_slot(to.as_bytes());
+        if from_slot != to_slot {
+            return Err(Error::Invalid {
+                message: format!(
+                    "{from} (slot {from_slot}) and {to} (slot {to_slot}) are in different hash slots, share a hash tag like {{tag}} to keep them together"
+                ),
+            });
+        }
+        Ok(())
+    }
     pub fn access_mode(&self) -> AccessMode {
         self.access_mode
     }
+    /// Whether the ACL of the connected user allows the command, checked once by ACL DRYRUN.
+    ///
+    /// Servers without ACL allow every command.
+    pub async fn can_run(&self, command: &str) -> bool {
+        let Some(user) = &self.acl_user else {
+            return true;
+        };
+        let command = command.to_ascii_uppercase();
+        if let Some(allowed) = self.command_permissions.get(&command) {
+            return *allowed;
+        }
+        let allowed = !acl_dryrun_denied(self.connection(), user, &command).await;
+        self.command_permissions.insert(command, allowed);
+        allowed
+    }
 
     pub fn nodes_description(&self) -> RedisClientDescription {
         let master_nodes: Vec<String> = self.master_nodes.iter().map(|node| node.host_port()).collect();
@@ -249,6 +483,15 @@ impl RedisClient {
         self.version >= Version::parse(version).unwrap_or(Version::new(0, 0, 0))
     }
 
+    /// The nodes SCAN cursors walk, in master order: the replicas when reading from
+    /// replicas, so the scans don't load the masters, otherwise the masters.
+    fn scan_nodes(&self) -> &[RedisNode] {
+        if self.replica_nodes.is_empty() {
+            &self.master_nodes
+        } else {
+            &self.replica_nodes
+        }
+    }
     /// Executes commands on all master nodes concurrently.
     /// # Arguments
     /// * `cmds` - A vector of commands to execute.
@@ -259,12 +502,263 @@ impl RedisClient {
         let values = query_async_masters(addrs, self.db, cmds).await?;
         Ok(values)
     }
-    /// Calculates the total DB size across all masters.
+    /// Executes commands on all masters with bounded concurrency and a per-node timeout.
+    /// # Returns
+    /// * `Vec<(String, Result<T>)>` - The address and result of each master.
+    pub async fn query_async_masters_settled<T: FromRedisValue>(
+        &self,
+        cmds: Vec<Cmd>,
+        concurrency: usize,
+        timeout: Duration,
+    ) -> Vec<(String, Result<T>)> {
+        let addrs: Vec<_> = self.master_nodes.iter().map(|item| item.server.clone()).collect();
+        query_async_masters_settled(addrs, self.db, cmds, concurrency, timeout).await
+    }
+    /// Returns the address of every node, masters first.
+    pub fn node_addrs(&self) -> Vec<String> {
+        let mut addrs: Vec<String> = self.master_nodes.iter().map(|node| node.host_port()).collect();
+        for node in self.nodes.iter() {
+            let addr = node.host_port();
+            if !addrs.contains(&addr) {
+                addrs.push(addr);
+            }
+        }
+        addrs
+    }
+    /// Gets the unparsed `INFO ALL` output of a single node.
+    /// # Arguments
+    /// * `addr` - The `host:port` of the node, as listed by `node_addrs`.
+    /// # Returns
+    /// * `String` - The raw INFO text of the node.
+    pub async fn node_raw_info(&self, addr: &str) -> Result<String> {
+        let node = self
+            .master_nodes
+            .iter()
+            .chain(self.nodes.iter())
+            .find(|node| node.host_port() == addr)
+            .ok_or_else(|| Error::Invalid {
+                message: format!("Node not found: {addr}"),
+            })?;
+        let mut conn = open_single_connection(&node.server, self.db).await?;
+        let info: String = cmd("INFO").arg("ALL").query_async(&mut conn).await?;
+        Ok(info)
+    }
+    /// Gets the topology of a cluster from `CLUSTER NODES`, with the slots of each master.
+    pub async fn cluster_nodes(&self) -> Result<Vec<ClusterNodeInfo>> {
+        if self.server_type != ServerType::Cluster {
+            return Err(Error::Invalid {
+                message: "Cluster topology is only available for clusters".to_string(),
+            });
+        }
+        let mut last_error = None;
+        // Any node knows the whole topology, the next one is asked when a node is down
+        for node in self.master_nodes.iter() {
+            let result = async {
+                let mut conn = open_single_connection(&node.server, self.db).await?;
+                let raw: String = cmd("CLUSTER").arg("NODES").query_async(&mut conn).await?;
+                parse_cluster_nodes(&raw)
+            }
+            .await;
+            match result {
+                Ok(nodes) => return Ok(nodes),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::Invalid {
+            message: "No master nodes available".to_string(),
+        }))
+    }
+    /// Gets the newest slow log entries of every master, newest first.
+    /// # Arguments
+    /// * `count` - The number of entries fetched from each master.
     /// # Returns
-    /// * `u64` - The total DB size.
-    pub async fn dbsize(&self) -> Result<u64> {
-        let list = self.query_async_masters(vec![cmd("DBSIZE")]).await?;
-        Ok(list.iter().sum())
+    /// * `Vec<SlowLogEntry>` - The entries tagged with the master they come from.
+    pub async fn slowlog(&self, count: usize) -> Result<Vec<SlowLogEntry>> {
+        self.ensure_masters()?;
+        let list: Vec<Vec<Value>> = self
+            .query_async_masters(vec![cmd("SLOWLOG").arg("GET").arg(count).clone()])
+            .await?;
+        let mut entries: Vec<SlowLogEntry> = self
+            .master_nodes
+            .iter()
+            .zip(list)
+            .flat_map(|(node, items)| {
+                let node = node.host_port();
+                items
+                    .into_iter()
+                    .filter_map(move |item| SlowLogEntry::parse(&item, node.clone()))
+            })
+            .collect();
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(b.id.cmp(&a.id)));
+        Ok(entries)
+    }
+    /// Serializes keys with `DUMP`, along with their `PTTL`, for a later `RESTORE`.
+    /// # Arguments
+    /// * `keys` - The keys to dump, pipelined in batches.
+    /// # Returns
+    /// * `Vec<(String, Vec<u8>, i64)>` - The key, its binary payload and its PTTL (-1 without expiry).
+    ///   Keys that no longer exist (nil DUMP) are skipped.
+    pub async fn dump_keys(&self, keys: Vec<String>) -> Result<Vec<(String, Vec<u8>, i64)>> {
+        let mut conn = self.connection();
+        let mut dumps = Vec::with_capacity(keys.len());
+        for batch in keys.chunks(DUMP_BATCH_SIZE) {
+            let mut pipeline = pipe();
+            for key in batch {
+                pipeline.cmd("DUMP").arg(key).cmd("PTTL").arg(key);
+            }
+            let values: Vec<Value> = pipeline.query_async(&mut conn).await?;
+            for (key, reply) in batch.iter().zip(values.chunks(2)) {
+                let [payload, ttl] = reply else {
+                    continue;
+                };
+                let Value::BulkString(payload) = payload else {
+                    continue;
+                };
+                let ttl = match ttl {
+                    Value::Int(ttl) => *ttl,
+                    _ => -1,
+                };
+                // Expired between DUMP and PTTL
+                if ttl == -2 {
+                    continue;
+                }
+                dumps.push((key.clone(), payload.clone(), ttl));
+            }
+        }
+        Ok(dumps)
+    }
+    /// Restores keys serialized by `DUMP`, pipelined in batches. Refused on read only connections.
+    /// # Arguments
+    /// * `entries` - The key, its payload and its TTL in milliseconds (0 or less without expiry).
+    /// * `replace` - Whether existing keys are overwritten (`REPLACE`).
+    /// # Returns
+    /// * `(usize, Vec<String>)` - The number of keys restored and the keys skipped as they already exist.
+    pub async fn restore_keys(
+        &self,
+        entries: Vec<(String, Vec<u8>, i64)>,
+        replace: bool,
+    ) -> Result<(usize, Vec<String>)> {
+        if self.access_mode != AccessMode::ReadWrite {
+            return Err(Error::Invalid {
+                message: "Restoring keys is not allowed on a read only connection".to_string(),
+            });
+        }
+        let mut conn = self.connection();
+        let mut restored = 0;
+        let mut skipped = Vec::new();
+        for batch in entries.chunks(DUMP_BATCH_SIZE) {
+            let mut pipeline = pipe();
+            pipeline.ignore_errors();
+            for (key, payload, ttl) in batch {
+                pipeline
+                    .cmd("RESTORE")
+                    .arg(key)
+                    .arg((*ttl).max(0))
+                    .arg(payload.as_slice());
+                if replace {
+                    pipeline.arg("REPLACE");
+                }
+            }
+            let values: Vec<Value> = pipeline.query_async(&mut conn).await?;
+            for ((key, _, _), value) in batch.iter().zip(values) {
+                match value {
+                    Value::ServerError(e) if e.code() == "BUSYKEY" => skipped.push(key.clone()),
+                    Value::ServerError(e) => {
+                        return Err(Error::Invalid {
+                            message: format!("Restore {key} fail: {e}"),
+                        });
+                    }
+                    _ => restored += 1,
+                }
+            }
+        }
+        Ok((restored, skipped))
+    }
+    /// Removes every key of the db with FLUSHDB, or of all dbs with FLUSHALL ASYNC,
+    /// on each master. Refused on read only connections.
+    pub async fn flush(&self, everything: bool) -> Result<()> {
+        if self.access_mode != AccessMode::ReadWrite {
+            return Err(Error::Invalid {
+                message: "Flushing is not allowed on a read only connection".to_string(),
+            });
+        }
+        self.ensure_masters()?;
+        let command = if everything {
+            let mut command = cmd("FLUSHALL");
+            // ASYNC was added in Redis 4.0
+            if self.is_at_least_version("4.0.0") {
+                command.arg("ASYNC");
+            }
+            command
+        } else {
+            cmd("FLUSHDB")
+        };
+        let _: Vec<()> = self.query_async_masters(vec![command]).await?;
+        Ok(())
+    }
+    /// Clears the slow log of every master. Refused on read only connections.
+    pub async fn slowlog_reset(&self) -> Result<()> {
+        if self.access_mode != AccessMode::ReadWrite {
+            return Err(Error::Invalid {
+                message: "Resetting the slow log is not allowed on a read only connection".to_string(),
+            });
+        }
+        self.ensure_masters()?;
+        let _: Vec<()> = self
+            .query_async_masters(vec![cmd("SLOWLOG").arg("RESET").clone()])
+            .await?;
+        Ok(())
+    }
+    /// Gets the DB size of each master node, the total DB size is their sum.
+    /// # Returns
+    /// * `Vec<(String, u64)>` - The master address and its DB size.
+    pub async fn dbsize_per_master(&self) -> Result<Vec<(String, u64)>> {
+        self.ensure_masters()?;
+        let list: Vec<u64> = self.query_async_masters(vec![cmd("DBSIZE")]).await?;
+        Ok(self
+            .master_nodes
+            .iter()
+            .map(|node| node.host_port())
+            .zip(list)
+            .collect())
+    }
+    /// Lists the clients idle for at least `min_idle` seconds on every master.
+    /// # Returns
+    /// * `Vec<Vec<u64>>` - The idle client IDs of each master, in master order.
+    pub async fn idle_client_ids(&self, min_idle: u64) -> Result<Vec<Vec<u64>>> {
+        // CLIENT ID shares the connection of CLIENT LIST, so the app's own connection is known
+        let mut pipeline = pipe();
+        pipeline.cmd("CLIENT").arg("ID").cmd("CLIENT").arg("LIST");
+        let addrs: Vec<_> = self.master_nodes.iter().map(|item| item.server.clone()).collect();
+        let pipelines = vec![pipeline; addrs.len()];
+        let list: Vec<(u64, String)> = pipeline_async_masters(addrs, self.db, pipelines).await?;
+        Ok(list
+            .iter()
+            .map(|(own_id, raw)| parse_idle_client_ids(raw, min_idle, *own_id))
+            .collect())
+    }
+    /// Kills clients with `CLIENT KILL ID` on the master they are connected to.
+    /// # Arguments
+    /// * `ids` - The client IDs of each master, in master order.
+    /// # Returns
+    /// * `usize` - The number of clients killed.
+    pub async fn kill_clients(&self, ids: Vec<Vec<u64>>) -> Result<usize> {
+        let (addrs, pipelines): (Vec<_>, Vec<_>) = self
+            .master_nodes
+            .iter()
+            .zip(ids)
+            .filter(|(_, ids)| !ids.is_empty())
+            .map(|(node, ids)| {
+                let mut pipeline = pipe();
+                for id in ids {
+                    pipeline.cmd("CLIENT").arg("KILL").arg("ID").arg(id);
+                }
+                (node.server.clone(), pipeline)
+            })
+            .unzip();
+        // Clients that already disconnected count as 0
+        let killed: Vec<Vec<usize>> = pipeline_async_masters(addrs, self.db, pipelines).await?;
+        Ok(killed.into_iter().flatten().sum())
     }
     /// Pings the server to check connectivity.
     pub async fn ping(&self) -> Result<()> {
@@ -272,59 +766,207 @@ impl RedisClient {
         let _: () = cmd("PING").query_async(&mut conn).await?;
         Ok(())
     }
-    /// Returns the number of master nodes.
-    /// # Returns
-    /// * `usize` - The number of master nodes.
-    pub fn count_masters(&self) -> Result<usize> {
-        Ok(self.master_nodes.len())
+    /// Fails with a clear error when no master was discovered (e.g. a misconfigured sentinel or cluster),
+    /// instead of scanning nothing.
+    fn ensure_masters(&self) -> Result<()> {
+        ensure_masters(&self.master_nodes)
     }
     /// Initiates a SCAN operation across all masters.
     /// # Arguments
     /// * `pattern` - The pattern to match keys.
     /// * `count` - The count of keys to return.
+    /// * `type_filter` - Only return keys of this type.
     /// # Returns
     /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
-    pub async fn first_scan(&self, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
-        let master_count = self.count_masters()?;
-        let cursors = vec![0; master_count];
+    pub async fn first_scan(
+        &self,
+        pattern: &str,
+        count: u64,
+        type_filter: Option<KeyType>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
+        self.ensure_masters()?;
+        let cursors = vec![0; self.scan_nodes().len()];
 
-        let (cursors, keys) = self.scan(cursors, pattern, count).await?;
+        let (cursors, keys) = self.scan(cursors, pattern, count, type_filter).await?;
         Ok((cursors, keys))
     }
     /// Continues a SCAN operation.
+    ///
+    /// Masters whose cursor came back to 0 are done and aren't scanned again,
+    /// unless all cursors are 0, which starts a new scan cycle.
+    ///
+    /// The type filter is sent as `SCAN ... TYPE` on Redis 6.0+, older servers
+    /// get the type of each returned key with a pipelined `TYPE` instead.
     /// # Arguments
     /// * `cursors` - A vector of cursors for each master.
     /// * `pattern` - The pattern to match keys.
     /// * `count` - The count of keys to return.
+    /// * `type_filter` - Only return keys of this type.
     /// # Returns
     /// * `(Vec<u64>, Vec<SharedString>)` - A tuple containing the new cursors and the keys.
-    pub async fn scan(&self, cursors: Vec<u64>, pattern: &str, count: u64) -> Result<(Vec<u64>, Vec<SharedString>)> {
-        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}");
-        let cmds: Vec<Cmd> = cursors
+    pub async fn scan(
+        &self,
+        cursors: Vec<u64>,
+        pattern: &str,
+        count: u64,
+        type_filter: Option<KeyType>,
+    ) -> Result<(Vec<u64>, Vec<SharedString>)> {
+        debug!("scan, cursors: {cursors:?}, pattern: {pattern}, count: {count}, type: {type_filter:?}");
+        let scan_type = self.is_at_least_version("6.0.0");
+        self.ensure_masters()?;
+        // Cursors of another topology (e.g. from before a failover) can't be mapped to the nodes
+        if cursors.len() != self.scan_nodes().len() {
+            return Box::pin(self.first_scan(pattern, count, type_filter)).await;
+        }
+        let restart = cursors.iter().all(|cursor| *cursor == 0);
+        let pending: Vec<usize> = (0..cursors.len())
+            .filter(|index| restart || cursors[*index] != 0)
+            .collect();
+        let scan_nodes = self.scan_nodes();
+        let addrs: Vec<_> = pending
             .iter()
-            .map(|cursor| {
-                cmd("SCAN")
-                    .cursor_arg(*cursor)
+            .filter_map(|index| scan_nodes.get(*index))
+            .map(|item| item.server.clone())
+            .collect();
+        let cmds: Vec<Cmd> = pending
+            .iter()
+            .map(|index| {
+                let mut scan = cmd("SCAN");
+                scan.cursor_arg(cursors[*index])
                     .arg("MATCH")
                     .arg(pattern)
                     .arg("COUNT")
-                    .arg(count)
-                    .clone()
+                    .arg(count);
+                if let Some(key_type) = type_filter.filter(|_| scan_type) {
+                    scan.arg("TYPE").arg(key_type.redis_name());
+                }
+                scan
             })
             .collect();
-        let values: Vec<(u64, Vec<Vec<u8>>)> = self.query_async_masters(cmds).await?;
-        let mut cursors = Vec::with_capacity(values.len());
-        let mut keys = Vec::with_capacity(values[0].1.len() * values.len());
-        for (cursor, keys_in_node) in values {
-            cursors.push(cursor);
+        let values: Vec<(u64, Vec<Vec<u8>>)> = query_async_masters(addrs, self.db, cmds).await?;
+        let mut next_cursors = vec![0; cursors.len()];
+        let mut keys = Vec::with_capacity(values.first().map_or(0, |(_, keys)| keys.len()) * values.len());
+        for (index, (cursor, keys_in_node)) in pending.into_iter().zip(values) {
+            next_cursors[index] = cursor;
             keys.extend(
                 keys_in_node
                     .iter()
                     .map(|k| String::from_utf8_lossy(k).to_string().into()),
             );
         }
+        if let Some(key_type) = type_filter
+            && !scan_type
+            && !keys.is_empty()
+        {
+            keys = self.filter_keys_by_type(keys, key_type).await?;
+        }
         keys.sort_unstable();
-        Ok((cursors, keys))
+        Ok((next_cursors, keys))
+    }
+    /// Keeps the keys of the given type, for servers without `SCAN ... TYPE`
+    async fn filter_keys_by_type(&self, keys: Vec<SharedString>, key_type: KeyType) -> Result<Vec<SharedString>> {
+        let mut pipe = pipe();
+        for key in keys.iter() {
+            pipe.cmd("TYPE").arg(key.as_str());
+        }
+        let types: Vec<String> = pipe.query_async(&mut self.connection()).await?;
+        Ok(keys
+            .into_iter()
+            .zip(types)
+            .filter(|(_, name)| KeyType::from(name.as_str()) == key_type)
+            .map(|(key, _)| key)
+            .collect())
+    }
+    /// Walks a SCAN over all masters for at most `max_rounds` rounds.
+    /// # Arguments
+    /// * `pattern` - The pattern to match keys.
+    /// * `count` - The count of keys to return per round.
+    /// * `max_rounds` - The maximum number of SCAN rounds.
+    /// # Returns
+    /// * `(Vec<SharedString>, bool)` - The matched keys, and whether the scan cycle finished.
+    pub async fn scan_rounds(&self, pattern: &str, count: u64, max_rounds: usize) -> Result<(Vec<SharedString>, bool)> {
+        let mut matched = Vec::new();
+        let mut cursors: Option<Vec<u64>> = None;
+        for _ in 0..max_rounds {
+            let (new_cursors, keys) = if let Some(cursors) = cursors {
+                self.scan(cursors, pattern, count, None).await?
+            } else {
+                self.first_scan(pattern, count, None).await?
+            };
+            matched.extend(keys);
+            if new_cursors.iter().all(|cursor| *cursor == 0) {
+                matched.sort_unstable();
+                matched.dedup();
+                return Ok((matched, true));
+            }
+            cursors = Some(new_cursors);
+        }
+        matched.sort_unstable();
+        matched.dedup();
+        Ok((matched, false))
+    }
+    /// Counts the keys matching the pattern, without loading their values.
+    ///
+    /// SCAN runs on each master until the cycle finishes, keys returned twice
+    /// by SCAN are only counted once.
+    /// # Arguments
+    /// * `pattern` - The pattern to match keys.
+    /// * `count` - The count of keys to return per round.
+    pub async fn count_matching(&self, pattern: &str, count: u64) -> Result<usize> {
+        let mut matched = HashSet::new();
+        let mut cursors: Option<Vec<u64>> = None;
+        loop {
+            let (new_cursors, keys) = if let Some(cursors) = cursors {
+                self.scan(cursors, pattern, count, None).await?
+            } else {
+                self.first_scan(pattern, count, None).await?
+            };
+            matched.extend(keys);
+            if new_cursors.iter().all(|cursor| *cursor == 0) {
+                return Ok(matched.len());
+            }
+            cursors = Some(new_cursors);
+        }
+    }
+    /// Deletes every key matching the pattern.
+    ///
+    /// SCAN runs on each master, the keys of each batch are removed with the given
+    /// command in a pipeline. Refused on read only connections.
+    /// # Arguments
+    /// * `pattern` - The pattern to match keys.
+    /// * `count` - The count of keys to return per round.
+    /// * `command` - UNLINK or DEL, the caller picks one the server supports and the ACL permits.
+    /// # Returns
+    /// * `usize` - The number of keys removed.
+    pub async fn delete_matching(&self, pattern: &str, count: u64, command: DeleteCommand) -> Result<usize> {
+        if self.access_mode != AccessMode::ReadWrite {
+            return Err(Error::Invalid {
+                message: "Deleting keys is not allowed on a read only connection".to_string(),
+            });
+        }
+        let command = command.as_str();
+        let mut conn = self.connection();
+        let mut deleted = 0;
+        let mut cursors: Option<Vec<u64>> = None;
+        loop {
+            let (new_cursors, keys) = if let Some(cursors) = cursors {
+                self.scan(cursors, pattern, count, None).await?
+            } else {
+                self.first_scan(pattern, count, None).await?
+            };
+            if !keys.is_empty() {
+                let mut pipe = pipe();
+                for key in keys.iter() {
+                    pipe.cmd(command).arg(key.as_str());
+                }
+                let removed: Vec<usize> = pipe.query_async(&mut conn).await?;
+                deleted += removed.iter().sum::<usize>();
+            }
+            if new_cursors.iter().all(|cursor| *cursor == 0) {
+                return Ok(deleted);
+            }
+            cursors = Some(new_cursors);
+        }
     }
 }
 
@@ -338,7 +980,7 @@ pub struct ConnectionManager {
 /// * `client` - The Redis client to check the server type.
 /// # Returns
 /// * `ServerType` - The type of the Redis server.
-async fn detect_server_type(mut conn: MultiplexedConnection) -> Result<ServerType> {
+async fn detect_server_type(mut conn: NodeConnection) -> Result<ServerType> {
     // Check if it's a Sentinel
     // Note: `ROLE` command might not exist on old Redis versions, consider fallback if needed.
     // Assuming modern Redis here.
@@ -359,25 +1001,40 @@ async fn detect_server_type(mut conn: MultiplexedConnection) -> Result<ServerTyp
     }
 }
 
-async fn safe_check_user_readonly(mut conn: RedisAsyncConn) -> bool {
+/// User of the connection, None when ACL WHOAMI fails or is empty (no ACL before Redis 6)
+async fn acl_whoami(mut conn: RedisAsyncConn) -> Option<String> {
     let user: String = cmd("ACL")
         .arg("WHOAMI")
         .query_async(&mut conn)
         .await
         .unwrap_or_default();
-    if user.is_empty() {
-        return false;
+    (!user.is_empty()).then_some(user)
+}
+
+/// Sample arguments of a command for ACL DRYRUN, which checks the arity too
+fn dryrun_args(command: &str) -> &'static [&'static str] {
+    match command {
+        "SET" => &["zedis", "treexie"],
+        "FLUSHDB" | "FLUSHALL" => &[],
+        _ => &["zedis"],
     }
+}
+
+/// Whether ACL DRYRUN reports the command as not permitted for the user.
+///
+/// The sample key of `dryrun_args` may be outside the key patterns of the user while the
+/// keys being edited aren't, so a denied key leaves the command allowed, the server still
+/// refuses it when it actually runs on a key the user can't access.
+async fn acl_dryrun_denied(mut conn: RedisAsyncConn, user: &str, command: &str) -> bool {
     let result: redis::RedisResult<String> = cmd("ACL")
         .arg("DRYRUN")
         .arg(user)
-        .arg("SET")
-        .arg("zedis")
-        .arg("treexie")
+        .arg(command)
+        .arg(dryrun_args(command))
         .query_async(&mut conn)
         .await;
     match result {
-        Ok(res) => res != "OK",
+        Ok(res) => res != "OK" && !is_acl_key_denial(&res),
 
         Err(e) => {
             if let Some(code) = e.code()
@@ -390,6 +1047,142 @@ async fn safe_check_user_readonly(mut conn: RedisAsyncConn) -> bool {
     }
 }
 
+/// Whether an ACL DRYRUN reply denies the sample key or channel rather than the command,
+/// e.g. "User alice has no permissions to access the 'zedis' key"
+fn is_acl_key_denial(reply: &str) -> bool {
+    reply.contains("permissions to access")
+}
+
+/// Converts the reply of `SENTINEL MASTERS` to master nodes, keeping only the
+/// configured `master_name` when set.
+///
+/// Fails when no master is left, or when several are and `master_name` isn't set.
+fn sentinel_master_nodes(
+    config: &RedisServer,
+    masters_response: Vec<HashMap<String, String>>,
+) -> Result<Vec<RedisNode>> {
+    let mut nodes = vec![];
+
+    for item in masters_response {
+        let ip = item.get("ip").ok_or_else(|| Error::Invalid {
+            message: "ip is not found".to_string(),
+        })?;
+        let port: u16 = item
+            .get("port")
+            .ok_or_else(|| Error::Invalid {
+                message: "port is not found".to_string(),
+            })?
+            .parse()
+            .map_err(|e| Error::Invalid {
+                message: format!("Invalid port {e:?}"),
+            })?;
+        let name = item.get("name").ok_or_else(|| Error::Invalid {
+            message: "master_name is not found".to_string(),
+        })?;
+        // Filter by master name if configured
+        if let Some(master_name) = &config.master_name
+            && name != master_name
+        {
+            continue;
+        }
+        let mut tmp_config = config.clone();
+        tmp_config.host = ip.clone();
+        tmp_config.port = port;
+
+        nodes.push(RedisNode {
+            server: tmp_config,
+            role: NodeRole::Master,
+            master_name: Some(name.clone()),
+            ..Default::default()
+        });
+    }
+    // A master_name that matches nothing would otherwise leave the topology empty
+    if nodes.is_empty() {
+        return Err(Error::Invalid {
+            message: format!(
+                "no reachable master for master_name={}",
+                config.master_name.as_deref().unwrap_or_default()
+            ),
+        });
+    }
+    // Check for ambiguous master configuration
+    let unique_masters: HashSet<_> = nodes.iter().filter_map(|n| n.master_name.as_ref()).collect();
+    if unique_masters.len() > 1 {
+        return Err(Error::Invalid {
+            message: format!(
+                "Multiple masters found in Sentinel, please specify master_name, master_names: {unique_masters:?}"
+            ),
+        });
+    }
+    Ok(nodes)
+}
+
+/// Fails with "No master nodes available" when `master_nodes` is empty.
+fn ensure_masters(master_nodes: &[RedisNode]) -> Result<()> {
+    if master_nodes.is_empty() {
+        return Err(Error::Invalid {
+            message: "No master nodes available".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Error codes of a server refusing the credentials of the connection
+const AUTH_REJECTED_CODES: [&str; 3] = ["NOAUTH", "WRONGPASS", "NOPERM"];
+
+/// Whether the error code is one of `AUTH_REJECTED_CODES`
+fn is_auth_rejected_code(code: &str) -> bool {
+    AUTH_REJECTED_CODES.contains(&code)
+}
+
+/// Whether the server rejected the AUTH sent during the connection handshake.
+///
+/// Only the error kind and code are checked, a generic `ERR` reply isn't an
+/// auth failure whatever its message says.
+fn is_auth_rejected(err: &Error) -> bool {
+    let Error::Redis { source } = err else {
+        return false;
+    };
+    source.kind() == ErrorKind::AuthenticationFailed || source.code().is_some_and(is_auth_rejected_code)
+}
+
+/// Opens the connection used to detect the server type.
+///
+/// Sentinels often run without a password while the data nodes need one, so when
+/// the server rejects the AUTH the connection is retried once without it.
+/// `None` means the server couldn't be reached for another reason and is
+/// handled as standalone.
+async fn open_detection_connection<C, F, Fut>(config: &RedisServer, open: F) -> Result<Option<C>>
+where
+    F: Fn(RedisServer) -> Fut,
+    Fut: Future<Output = Result<C>>,
+{
+    match open(config.clone()).await {
+        Ok(conn) => Ok(Some(conn)),
+        Err(e) if is_auth_rejected(&e) => {
+            info!(name = %config.name, error = %e, "auth rejected, retry server type detection without password");
+            let mut tmp_config = config.clone();
+            tmp_config.password = None;
+            open(tmp_config).await.map(Some)
+        }
+        Err(e) => {
+            error!("detect server type failed: {e:?}, use standalone mode");
+            Ok(None)
+        }
+    }
+}
+
+/// Whether the error suggests the cached client points at a stale topology,
+/// e.g. its connection was dropped or a cluster failed over.
+fn is_failover_error(err: &Error) -> bool {
+    let Error::Redis { source } = err else {
+        return false;
+    };
+    source.is_connection_dropped()
+        || source.is_connection_refusal()
+        || matches!(source.code(), Some("MOVED") | Some("CLUSTERDOWN"))
+}
+
 impl ConnectionManager {
     pub fn new() -> Self {
         Self {
@@ -397,30 +1190,34 @@ impl ConnectionManager {
         }
     }
     /// Discovers Redis nodes and server type based on initial configuration.
-    async fn get_redis_nodes(&self, name: &str) -> Result<(Vec<RedisNode>, ServerType)> {
+    async fn get_redis_nodes<F>(&self, name: &str, progress: &F) -> Result<(Vec<RedisNode>, ServerType)>
+    where
+        F: Fn(ConnectionStep),
+    {
         let config = get_config(name)?;
+        progress(if config.is_ssh_tunnel() {
+            ConnectionStep::SshTunnel
+        } else {
+            ConnectionStep::Connecting
+        });
         let (mut conn, server_type) = {
-            let conn = match open_single_connection(&config, 0).await {
-                Ok(conn) => conn,
-                Err(e) => {
-                    if !e.to_string().contains("AuthenticationFailed") {
-                        error!("detect server type failed: {e:?}, use standalone mode");
-                        return Ok((
-                            vec![RedisNode {
-                                server: config.clone(),
-                                role: NodeRole::Master,
-                                ..Default::default()
-                            }],
-                            ServerType::Standalone,
-                        ));
-                    }
-                    // sentinel without password
-                    // detect server type again
-                    let mut tmp_config = config.clone();
-                    tmp_config.password = None;
-                    open_single_connection(&tmp_config, 0).await?
-                }
+            let Some(conn) =
+                open_detection_connection(
+                    &config,
+                    |config| async move { open_single_connection(&config, 0).await },
+                )
+                .await?
+            else {
+                return Ok((
+                    vec![RedisNode {
+                        server: config.clone(),
+                        role: NodeRole::Master,
+                        ..Default::default()
+                    }],
+                    ServerType::Standalone,
+                ));
             };
+            progress(ConnectionStep::DiscoverTopology);
             if let Some(server_type) = config.server_type
                 && server_type > 0
             {
@@ -453,6 +1250,8 @@ impl ConnectionManager {
                         RedisNode {
                             server: tmp_config,
                             role: item.role.clone(),
+                            node_id: Some(item.id.clone()),
+                            master_id: item.master_id.clone(),
                             ..Default::default()
                         }
                     })
@@ -464,50 +1263,7 @@ impl ConnectionManager {
                 // Fetch masters from Sentinel
                 let masters_response: Vec<HashMap<String, String>> =
                     cmd("SENTINEL").arg("MASTERS").query_async(&mut conn).await?;
-                let mut nodes = vec![];
-
-                for item in masters_response {
-                    let ip = item.get("ip").ok_or_else(|| Error::Invalid {
-                        message: "ip is not found".to_string(),
-                    })?;
-                    let port: u16 = item
-                        .get("port")
-                        .ok_or_else(|| Error::Invalid {
-                            message: "port is not found".to_string(),
-                        })?
-                        .parse()
-                        .map_err(|e| Error::Invalid {
-                            message: format!("Invalid port {e:?}"),
-                        })?;
-                    let name = item.get("name").ok_or_else(|| Error::Invalid {
-                        message: "master_name is not found".to_string(),
-                    })?;
-                    // Filter by master name if configured
-                    if let Some(master_name) = &config.master_name
-                        && name != master_name
-                    {
-                        continue;
-                    }
-                    let mut tmp_config = config.clone();
-                    tmp_config.host = ip.clone();
-                    tmp_config.port = port;
-
-                    nodes.push(RedisNode {
-                        server: tmp_config,
-                        role: NodeRole::Master,
-                        master_name: Some(name.clone()),
-                    });
-                }
-                // Check for ambiguous master configuration
-                let unique_masters: HashSet<_> = nodes.iter().filter_map(|n| n.master_name.as_ref()).collect();
-                if unique_masters.len() > 1 {
-                    return Err(Error::Invalid {
-                        message: format!(
-                            "Multiple masters found in Sentinel, please specify master_name, master_names: {unique_masters:?}"
-                        ),
-                    });
-                }
-
+                let nodes = sentinel_master_nodes(&config, masters_response)?;
                 Ok((nodes, server_type))
             }
             _ => Ok((
@@ -520,20 +1276,68 @@ impl ConnectionManager {
             )),
         }
     }
-    pub fn remove_client(&self, name: &str) {
-        self.clients.remove(&name.to_string());
+    /// Drops the cached client so the next `get_client` connects again.
+    pub fn remove_client(&self, server_id: &str, db: usize) {
+        let Ok(config) = get_config(server_id) else {
+            return;
+        };
+        self.clients.remove(&client_cache_key(&config, db));
+    }
+    /// Drops the cached clients of every db of the server, e.g. after its address was edited.
+    pub fn remove_server_clients(&self, server_id: &str) {
+        let Ok(config) = get_config(server_id) else {
+            return;
+        };
+        remove_config_clients(&self.clients, &config);
+    }
+    /// Runs `op` with the client of the server. When it fails with a failover error,
+    /// the cached client and its pooled node connections are dropped and `op` runs
+    /// again with a new one, after each delay of `FAILOVER_RETRY_DELAYS`. `op` is told
+    /// whether it's a retry, state tied to the old client (e.g. scan cursors of its nodes)
+    /// must not be reused then.
+    pub async fn with_client_retry<T, F, Fut>(&self, server_id: &str, db: usize, op: F) -> Result<T>
+    where
+        F: Fn(RedisClient, bool) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut delays = FAILOVER_RETRY_DELAYS.iter();
+        let mut retried = false;
+        loop {
+            let client = self.get_client(server_id, db).await?;
+            match op(client.clone(), retried).await {
+                Err(e) if is_failover_error(&e) => {
+                    let Some(delay) = delays.next() else {
+                        return Err(e);
+                    };
+                    warn!(server_id, db, error = %e, "operation failed, retry with a new client");
+                    self.remove_client(server_id, db);
+                    // The new client would otherwise get the same dead node connections from the pool
+                    client.remove_pooled_connections();
+                    retried = true;
+                    smol::Timer::after(*delay).await;
+                }
+                result => return result,
+            }
+        }
     }
     /// Retrieves or creates a RedisClient for the given configuration name.
     pub async fn get_client(&self, server_id: &str, db: usize) -> Result<RedisClient> {
+        self.get_client_with_progress(server_id, db, |_| {}).await
+    }
+    /// Same as `get_client`, reporting each step while a new client is established.
+    pub async fn get_client_with_progress<F>(&self, server_id: &str, db: usize, progress: F) -> Result<RedisClient>
+    where
+        F: Fn(ConnectionStep),
+    {
         let config = get_config(server_id)?;
-        let key = format!("{:x}:{}", config.get_hash(), db);
+        let key = client_cache_key(&config, db);
         if let Some(client) = self.clients.get(&key) {
             return Ok(client.clone());
         }
-        let (nodes, server_type) = self.get_redis_nodes(server_id).await?;
+        let (nodes, server_type) = self.get_redis_nodes(server_id, &progress).await?;
         let Some(first_node) = nodes.first() else {
             return Err(Error::Invalid {
-                message: "no nodes found".to_string(),
+                message: format!("no reachable nodes for server_id={server_id}"),
             });
         };
         let client = match server_type {
@@ -546,9 +1350,16 @@ impl ConnectionManager {
                 if first_node.server.insecure.unwrap_or(false) {
                     builder = builder.danger_accept_invalid_hostnames(true);
                 }
+                // Read only commands are routed to replicas, writes always go to the masters
+                if config.prefer_replica() {
+                    builder = builder.read_from_replicas();
+                }
+                // Generic cluster connections take no ClusterConfig, the timeouts are set here
+                builder = builder
+                    .connection_timeout(config.connection_timeout())
+                    .response_timeout(config.response_timeout());
                 if first_node.server.is_ssh_tunnel() {
                     builder = builder.username(server_id);
-
                     RClient::SshCluster(builder.build()?)
                 } else {
                     RClient::Cluster(builder.build()?)
@@ -563,8 +1374,36 @@ impl ConnectionManager {
             .collect();
         let master_nodes_description: Vec<String> = master_nodes.iter().map(|node| node.host_port()).collect();
         info!(master_nodes = ?master_nodes_description, "server master nodes");
+        let replica_nodes: Vec<RedisNode> = if server_type == ServerType::Cluster && config.prefer_replica() {
+            master_nodes
+                .iter()
+                .map(|master| {
+                    nodes
+                        .iter()
+                        .find(|node| {
+                            node.role == NodeRole::Slave && node.master_id.is_some() && node.master_id == master.node_id
+                        })
+                        .unwrap_or(master)
+                        .clone()
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        progress(ConnectionStep::ConnectNodes);
         let connection = get_async_connection(&client, db).await?;
-        let access_mode = if safe_check_user_readonly(connection.clone()).await {
+        progress(ConnectionStep::CheckAccess);
+        let acl_user = acl_whoami(connection.clone()).await;
+        let readonly_user = match &acl_user {
+            Some(user) => acl_dryrun_denied(connection.clone(), user, "SET").await,
+            None => false,
+        };
+        // Seeded with the check above, so `can_run("SET")` doesn't dry-run it again
+        let command_permissions = Arc::new(DashMap::new());
+        if acl_user.is_some() {
+            command_permissions.insert("SET".to_string(), !readonly_user);
+        }
+        let access_mode = if readonly_user {
             AccessMode::StrictReadOnly
         } else if config.readonly.unwrap_or(false) {
             AccessMode::SafeMode
@@ -577,10 +1416,14 @@ impl ConnectionManager {
             server_type: server_type.clone(),
             nodes,
             master_nodes,
+            replica_nodes,
             version: Version::new(0, 0, 0),
             connection,
+            acl_user,
+            command_permissions,
         };
         let mut conn = client.connection.clone();
+        progress(ConnectionStep::ServerInfo);
         client.version = match server_type {
             ServerType::Cluster => {
                 let info: redis::Value = cmd("INFO").arg("server").query_async(&mut conn).await?;
@@ -613,6 +1456,47 @@ impl ConnectionManager {
         let client = self.get_client(server_id, db).await?;
         Ok(client.connection.clone())
     }
+    /// Opens a fresh connection for SUBSCRIBE/PSUBSCRIBE instead of the cached multiplexed one.
+    ///
+    /// A cluster is subscribed on its first master, PUBLISH is broadcast to every node
+    /// but messages of SPUBLISH only reach the shard of their channel.
+    pub async fn get_pubsub_connection(&self, server_id: &str) -> Result<PubSubConnection> {
+        let (nodes, server_type) = self.get_redis_nodes(server_id, &|_| {}).await?;
+        let node = nodes
+            .iter()
+            .find(|node| node.role == NodeRole::Master)
+            .or(nodes.first())
+            .ok_or_else(|| Error::Invalid {
+                message: format!("no reachable nodes for server_id={server_id}"),
+            })?;
+        let pubsub = open_pubsub_connection(&node.server).await?;
+        Ok(PubSubConnection {
+            pubsub,
+            node: node.host_port(),
+            cluster: server_type == ServerType::Cluster,
+        })
+    }
+}
+
+/// Dedicated pub/sub connection of a server
+pub struct PubSubConnection {
+    pub pubsub: PubSub,
+    /// `host:port` of the node subscribed on
+    pub node: String,
+    /// Only one node of a cluster is subscribed on
+    pub cluster: bool,
+}
+
+/// Cache key of a client, changes whenever the server config is edited.
+fn client_cache_key(config: &RedisServer, db: usize) -> String {
+    format!("{:x}:{}", config.get_hash(), db)
+}
+
+/// Removes the clients cached for any db of the config.
+fn remove_config_clients<V: Clone>(clients: &TtlCache<String, V>, config: &RedisServer) {
+    // Same layout as `client_cache_key` without the db
+    let prefix = format!("{:x}:", config.get_hash());
+    clients.remove_matching(|key| key.starts_with(&prefix));
 }
 
 /// Global accessor for the connection manager.
@@ -624,3 +1508,385 @@ pub fn get_connection_manager() -> &'static ConnectionManager {
 pub fn clear_expired_clients() -> (usize, usize) {
     CONNECTION_MANAGER.clients.clear_expired()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> RedisServer {
+        RedisServer {
+            id: "local".to_string(),
+            name: "local".to_string(),
+            host: "127.0.0.1".to_string(),
+            port: 6379,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_editing_address_invalidates_cached_client() {
+        let clients: TtlCache<String, usize> = TtlCache::new(Duration::from_secs(60));
+        let server = server();
+        let other = RedisServer {
+            id: "other".to_string(),
+            port: 6380,
+            ..server.clone()
+        };
+        for db in [0, 3, 10] {
+            clients.insert(client_cache_key(&server, db), db);
+        }
+        clients.insert(client_cache_key(&other, 0), 100);
+
+        remove_config_clients(&clients, &server);
+        for db in [0, 3, 10] {
+            assert_eq!(None, clients.get(&client_cache_key(&server, db)));
+        }
+        assert_eq!(Some(100), clients.get(&client_cache_key(&other, 0)));
+
+        let moved = RedisServer {
+            host: "10.0.0.1".to_string(),
+            ..server.clone()
+        };
+        assert!(server.connection_changed(&moved));
+
+        let port_changed = RedisServer {
+            port: 6380,
+            ..server.clone()
+        };
+        assert!(server.connection_changed(&port_changed));
+
+        // Renaming doesn't need a reconnect
+        let renamed = RedisServer {
+            name: "renamed".to_string(),
+            ..server.clone()
+        };
+        assert!(!server.connection_changed(&renamed));
+    }
+
+    /// Starts a server answering `+OK` to every command, enough to open a connection
+    /// that is never queried.
+    fn fake_server() -> u16 {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind fake server");
+        let port = listener.local_addr().expect("fake server address").port();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                std::thread::spawn(move || {
+                    let mut buf = [0; 4096];
+                    while let Ok(n) = stream.read(&mut buf)
+                        && n > 0
+                    {
+                        // Each command of the handshake is an array of bulk strings
+                        let count = buf[..n]
+                            .split(|b| *b == b'\n')
+                            .filter(|line| line.starts_with(b"*"))
+                            .count();
+                        if stream.write_all(&b"+OK\r\n".repeat(count)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_empty_masters() {
+        let err = ensure_masters(&[]).expect_err("no master");
+        assert_eq!("Invalid: No master nodes available", err.to_string());
+
+        let master = RedisNode {
+            server: server(),
+            role: NodeRole::Master,
+            ..Default::default()
+        };
+        assert!(ensure_masters(&[master]).is_ok());
+
+        // A misconfigured sentinel or cluster leaves a connected client without masters
+        let config = RedisServer {
+            port: fake_server(),
+            ..server()
+        };
+        smol::block_on(async {
+            let connection = open_single_connection(&config, 0).await.expect("connect fake server");
+            let client = RedisClient {
+                access_mode: AccessMode::default(),
+                db: 0,
+                server_type: ServerType::Sentinel,
+                nodes: vec![],
+                master_nodes: vec![],
+                replica_nodes: vec![],
+                version: Version::new(7, 0, 0),
+                connection: RedisAsyncConn::Single(connection),
+                acl_user: None,
+                command_permissions: Arc::default(),
+            };
+            let err = client
+                .first_scan("*", 100, None)
+                .await
+                .expect_err("scan without master");
+            assert_eq!("Invalid: No master nodes available", err.to_string());
+            let err = client
+                .scan(vec![], "*", 100, None)
+                .await
+                .expect_err("scan without master");
+            assert_eq!("Invalid: No master nodes available", err.to_string());
+            let err = client.dbsize_per_master().await.expect_err("dbsize without master");
+            assert_eq!("Invalid: No master nodes available", err.to_string());
+        });
+    }
+
+    #[test]
+    fn test_settled_results_are_paired_with_node_address() {
+        let reachable = RedisServer {
+            port: fake_server(),
+            ..server()
+        };
+        let socket = RedisServer {
+            socket_path: Some("/nonexistent/zedis.sock".to_string()),
+            ..server()
+        };
+        let results: Vec<(String, Result<String>)> = smol::block_on(query_async_masters_settled(
+            vec![socket, reachable.clone()],
+            0,
+            vec![cmd("PING")],
+            1,
+            Duration::from_secs(5),
+        ));
+        assert_eq!(2, results.len());
+        assert_eq!("/nonexistent/zedis.sock", results[0].0);
+        assert!(results[0].1.is_err());
+        assert_eq!(reachable.address(), results[1].0);
+        assert_eq!("OK", results[1].1.as_ref().expect("fake server reply"));
+    }
+
+    #[test]
+    fn test_is_auth_rejected_code() {
+        assert!(is_auth_rejected_code("NOAUTH"));
+        assert!(is_auth_rejected_code("WRONGPASS"));
+        assert!(is_auth_rejected_code("NOPERM"));
+        // e.g. "ERR unknown command 'AUTH'" or a key named AUTH in the message
+        assert!(!is_auth_rejected_code("ERR"));
+        assert!(!is_auth_rejected_code("WRONGTYPE"));
+        assert!(!is_auth_rejected_code("noauth"));
+    }
+
+    #[test]
+    fn test_is_auth_rejected() {
+        let failed = Error::Redis {
+            source: redis::RedisError::from((ErrorKind::AuthenticationFailed, "Password authentication failed")),
+        };
+        assert!(is_auth_rejected(&failed));
+
+        let io = Error::Redis {
+            source: redis::RedisError::from(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)),
+        };
+        assert!(!is_auth_rejected(&io));
+
+        let invalid = Error::Invalid {
+            message: "AUTH failed".to_string(),
+        };
+        assert!(!is_auth_rejected(&invalid));
+    }
+
+    /// Opens a fake connection recording the passwords it was tried with,
+    /// the node rejects any AUTH like a sentinel without password.
+    fn open_passwordless(
+        attempts: &std::sync::Mutex<Vec<Option<String>>>,
+        config: RedisServer,
+    ) -> impl Future<Output = Result<&'static str>> {
+        attempts.lock().expect("attempts lock").push(config.password.clone());
+        async move {
+            if config.password.is_some() {
+                return Err(Error::Redis {
+                    source: redis::RedisError::from((ErrorKind::AuthenticationFailed, "WRONGPASS")),
+                });
+            }
+            Ok("sentinel")
+        }
+    }
+
+    #[test]
+    fn test_detection_retries_without_password() {
+        let attempts = std::sync::Mutex::new(vec![]);
+        let config = RedisServer {
+            password: Some("secret".to_string()),
+            ..server()
+        };
+        let conn = smol::block_on(open_detection_connection(&config, |config| {
+            open_passwordless(&attempts, config)
+        }))
+        .expect("retry without password");
+        assert_eq!(Some("sentinel"), conn);
+        assert_eq!(
+            vec![Some("secret".to_string()), None],
+            *attempts.lock().expect("attempts lock")
+        );
+    }
+
+    #[test]
+    fn test_detection_rejected_twice() {
+        let attempts = std::sync::Mutex::new(0);
+        let result = smol::block_on(open_detection_connection(&server(), |_| {
+            *attempts.lock().expect("attempts lock") += 1;
+            async {
+                Err::<(), _>(Error::Redis {
+                    source: redis::RedisError::from((ErrorKind::AuthenticationFailed, "NOAUTH")),
+                })
+            }
+        }));
+        assert!(result.is_err());
+        assert_eq!(2, *attempts.lock().expect("attempts lock"));
+    }
+
+    #[test]
+    fn test_detection_unreachable_is_standalone() {
+        let attempts = std::sync::Mutex::new(0);
+        let conn = smol::block_on(open_detection_connection(&server(), |_| {
+            *attempts.lock().expect("attempts lock") += 1;
+            async {
+                Err::<(), _>(Error::Redis {
+                    source: redis::RedisError::from(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)),
+                })
+            }
+        }))
+        .expect("fallback to standalone");
+        assert_eq!(None, conn);
+        assert_eq!(1, *attempts.lock().expect("attempts lock"));
+    }
+
+    fn sentinel_master(name: &str, port: u16) -> HashMap<String, String> {
+        HashMap::from([
+            ("name".to_string(), name.to_string()),
+            ("ip".to_string(), "10.0.0.1".to_string()),
+            ("port".to_string(), port.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_sentinel_master_nodes() {
+        let config = RedisServer {
+            master_name: Some("mymaster".to_string()),
+            ..server()
+        };
+        let nodes = sentinel_master_nodes(
+            &config,
+            vec![sentinel_master("other", 6380), sentinel_master("mymaster", 6379)],
+        )
+        .expect("master of the name");
+        assert_eq!(1, nodes.len());
+        assert_eq!(6379, nodes[0].server.port);
+        assert_eq!("10.0.0.1", nodes[0].server.host);
+        assert_eq!(Some("mymaster".to_string()), nodes[0].master_name);
+    }
+
+    #[test]
+    fn test_sentinel_wrong_master_name() {
+        let config = RedisServer {
+            master_name: Some("unknown".to_string()),
+            ..server()
+        };
+        let err =
+            sentinel_master_nodes(&config, vec![sentinel_master("mymaster", 6379)]).expect_err("unknown master name");
+        assert_eq!("Invalid: no reachable master for master_name=unknown", err.to_string());
+
+        // A sentinel monitoring nothing
+        let err = sentinel_master_nodes(&server(), vec![]).expect_err("no master");
+        assert_eq!("Invalid: no reachable master for master_name=", err.to_string());
+    }
+
+    #[test]
+    fn test_sentinel_ambiguous_masters() {
+        let err = sentinel_master_nodes(&server(), vec![sentinel_master("a", 6379), sentinel_master("b", 6380)])
+            .expect_err("ambiguous masters");
+        assert!(err.to_string().contains("please specify master_name"));
+    }
+
+    #[test]
+    fn test_is_acl_key_denial() {
+        assert!(is_acl_key_denial(
+            "User alice has no permissions to access the 'zedis' key"
+        ));
+        assert!(!is_acl_key_denial(
+            "User alice has no permissions to run the 'set' command"
+        ));
+        assert!(!is_acl_key_denial("OK"));
+    }
+
+    #[test]
+    fn test_key_slot() {
+        assert_eq!(12739, key_slot(b"123456789"));
+        assert_eq!(12182, key_slot(b"foo"));
+        let following = key_slot(b"{user1000}.following");
+        assert_eq!(following, key_slot(b"{user1000}.followers"));
+        assert_eq!(following, key_slot(b"user1000"));
+        // an empty tag is ignored and the whole key is hashed
+        assert_eq!(9500, key_slot(b"{}foo"));
+        assert_eq!(8363, key_slot(b"foo{}{bar}"));
+        assert_ne!(key_slot(b"bar"), key_slot(b"foo{}{bar}"));
+    }
+
+    #[test]
+    fn test_parse_cluster_nodes() {
+        let raw = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,hostname4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002,hostname2 master - 0 1426238316232 2 connected 5461-10922 [5460-<-e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca]
+292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f 127.0.0.1:30003@31003 master - 0 1426238318243 3 connected 10923-16382 16383
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001,hostname1 myself,master - 0 0 1 connected 0-5460 [5460->-67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1]
+";
+        let nodes = parse_cluster_nodes(raw).expect("parse cluster nodes");
+        assert_eq!(4, nodes.len());
+
+        let replica = &nodes[0];
+        assert_eq!(("127.0.0.1", 30004), (replica.ip.as_str(), replica.port));
+        assert_eq!(NodeRole::Slave, replica.role);
+        assert_eq!(
+            Some("e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca"),
+            replica.master_id.as_deref()
+        );
+        assert!(replica.slots.is_empty());
+
+        let importing = &nodes[1];
+        assert_eq!(NodeRole::Master, importing.role);
+        assert_eq!(None, importing.master_id);
+        assert_eq!(vec![(5461, 10922)], importing.slots);
+        assert!(importing.migrating.is_empty());
+        assert_eq!(
+            vec![(5460, "e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca".to_string())],
+            importing.importing
+        );
+
+        // A single slot after a range
+        assert_eq!(30003, nodes[2].port);
+        assert_eq!(vec![(10923, 16382), (16383, 16383)], nodes[2].slots);
+
+        let migrating = &nodes[3];
+        assert_eq!(NodeRole::Master, migrating.role);
+        assert_eq!(vec![(0, 5460)], migrating.slots);
+        assert_eq!(
+            vec![(5460, "67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1".to_string())],
+            migrating.migrating
+        );
+        assert!(migrating.importing.is_empty());
+    }
+
+    #[test]
+    fn test_parse_idle_client_ids() {
+        let raw = "\
+id=3 addr=127.0.0.1:52555 laddr=127.0.0.1:6379 fd=8 name= age=855 idle=900 flags=N db=0 cmd=client|list
+id=4 addr=10.0.0.2:52787 laddr=127.0.0.1:6379 fd=9 name=worker age=1000 idle=600 flags=N db=0 cmd=get
+id=5 addr=10.0.0.3:6379 laddr=127.0.0.1:6379 fd=10 name= age=1200 idle=900 flags=S db=0 cmd=replconf
+id=6 addr=10.0.0.4:6379 laddr=127.0.0.1:6379 fd=11 name= age=1200 idle=700 flags=M db=0 cmd=ping
+id=7 addr=10.0.0.5:40001 laddr=127.0.0.1:6379 fd=12 name= age=40 idle=30 flags=N db=0 cmd=set
+id=8 addr=10.0.0.6:40002 laddr=127.0.0.1:6379 fd=13 name= age=300 idle=300 flags=P db=0 cmd=subscribe
+";
+        // The app's own connection (id 3) and the replication links are never listed
+        assert_eq!(vec![4, 8], parse_idle_client_ids(raw, 300, 3));
+        assert_eq!(vec![3, 4], parse_idle_client_ids(raw, 600, 0));
+        assert_eq!(vec![4, 7, 8], parse_idle_client_ids(raw, 0, 3));
+        assert!(parse_idle_client_ids("", 0, 3).is_empty());
+        // Lines without an id or idle field are skipped
+        assert!(parse_idle_client_ids("addr=10.0.0.2:52787 idle=600\nid=9 flags=N", 0, 3).is_empty());
+    }
+}
@@ -12,21 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::async_connection::{get_redis_connection_timeout, get_redis_response_timeout};
 use super::config::RedisServer;
 use super::ssh_stream::SshRedisStream;
 use crate::error::Error;
 use crate::helpers::{TtlCache, get_home_dir};
 use redis::{RedisConnectionInfo, aio::MultiplexedConnection, cmd};
 use russh::client::{Handle, Handler};
-use russh::keys::ssh_key::PublicKey;
-use russh::keys::{PrivateKeyWithHashAlg, decode_secret_key, load_secret_key};
-use std::path::Path;
+#[cfg(unix)]
+use russh::keys::agent::client::AgentClient;
+use russh::keys::ssh_key::{HashAlg, PublicKey};
+use russh::keys::{
+    PrivateKeyWithHashAlg, check_known_hosts_path, decode_secret_key, learn_known_hosts_path, load_secret_key,
+};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::{LazyLock, OnceLock};
 use std::time::Duration;
 use tokio::runtime::Runtime;
-use tracing::info;
+use tracing::{error, info};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -92,43 +95,93 @@ pub struct ClientHandler {
     host: String,
     /// The remote SSH server port
     port: u16,
+    /// Reject hosts missing from known_hosts instead of asking the user
+    strict_host_key: bool,
 }
 
 impl Handler for ClientHandler {
-    type Error = russh::Error;
+    type Error = Error;
 
     /// Verifies the SSH server's public key during connection establishment.
     ///
+    /// The key is looked up in `~/.ssh/known_hosts` for `host:port`, hashed
+    /// entries and every key type included.
+    ///
     /// # Arguments
     ///
-    /// * `_server_public_key` - The server's public key to validate
+    /// * `server_public_key` - The server's public key to validate
     ///
     /// # Returns
     ///
-    /// `Ok(true)` to accept the connection, `Ok(false)` to reject it
-    ///
-    /// # Note
-    ///
-    /// Currently accepts all server keys without validation.
-    /// TODO: Implement proper validation against ~/.ssh/known_hosts
+    /// `Ok(true)` to accept the connection, `Ok(false)` to reject it.
+    /// A host missing from known_hosts fails with `Error::UnknownHostKey` so the
+    /// user decides whether to trust it, strict checking rejects it outright.
     async fn check_server_key(&mut self, server_public_key: &PublicKey) -> Result<bool, Self::Error> {
         info!(host = self.host, port = self.port, "check server key");
         let Ok(public_key) = server_public_key.to_openssh() else {
             return Ok(false);
         };
-        let Some(home) = get_home_dir() else {
-            return Ok(true);
+        let known = match get_known_hosts_path() {
+            Some(known_hosts) => check_known_hosts_path(&self.host, self.port, server_public_key, &known_hosts),
+            None => Ok(false),
         };
-        let known_hosts = home.join(".ssh/known_hosts");
-        if known_hosts.exists() {
-            let known_hosts = std::fs::read_to_string(known_hosts)?;
-            // simply check if the public key is in the known_hosts file
-            return Ok(known_hosts.contains(public_key.as_str()));
+        match known {
+            Ok(true) => Ok(true),
+            Ok(false) if self.strict_host_key => {
+                error!(
+                    host = self.host,
+                    port = self.port,
+                    "reject ssh host missing from known_hosts"
+                );
+                Err(Error::Invalid {
+                    message: format!(
+                        "Host key of {}:{} is not in ~/.ssh/known_hosts and strict host key checking is enabled",
+                        self.host, self.port
+                    ),
+                })
+            }
+            Ok(false) => Err(Error::UnknownHostKey {
+                host: self.host.clone(),
+                port: self.port,
+                fingerprint: server_public_key.fingerprint(HashAlg::Sha256).to_string(),
+                key: public_key.to_string(),
+            }),
+            Err(russh::keys::Error::KeyChanged { line }) => {
+                error!(
+                    host = self.host,
+                    port = self.port,
+                    line,
+                    "ssh host key does not match known_hosts"
+                );
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
         }
-        Ok(true)
     }
 }
 
+/// The known_hosts file of the user, `None` when the home directory is unavailable.
+fn get_known_hosts_path() -> Option<PathBuf> {
+    get_home_dir().map(|home| home.join(".ssh/known_hosts"))
+}
+
+/// Appends the host key of a server to the known_hosts file of the user.
+///
+/// # Arguments
+///
+/// * `host` - The SSH server hostname or IP address
+/// * `port` - The SSH server port
+/// * `key` - The host key in OpenSSH format, as reported by `Error::UnknownHostKey`
+pub fn trust_host_key(host: &str, port: u16, key: &str) -> Result<()> {
+    let known_hosts = get_known_hosts_path().ok_or_else(|| Error::Invalid {
+        message: "Home directory is unavailable".to_string(),
+    })?;
+    let key = PublicKey::from_openssh(key).map_err(|e| Error::Invalid { message: e.to_string() })?;
+    learn_known_hosts_path(host, port, &key, known_hosts)?;
+    info!(host, port, "ssh host key added to known_hosts");
+    Ok(())
+}
+
 type SshHandle = Handle<ClientHandler>;
 
 /// Global cache of SSH sessions keyed by "user@host:port" identifier.
@@ -171,12 +224,23 @@ async fn is_alive(session: Arc<SshHandle>) -> bool {
 /// * `addr` - SSH server address in "host:port" or "host" format (defaults to port 22)
 /// * `user` - SSH username for authentication
 /// * `key` - Optional SSH private key (file path or key content)
-/// * `password` - Optional password for key decryption or password authentication
+/// * `passphrase` - Optional passphrase of an encrypted key, falls back to `password`
+/// * `password` - Optional password for password authentication
+/// * `use_agent` - Try the identities of the SSH agent first
+/// * `strict_host_key` - Reject hosts missing from known_hosts
 ///
 /// # Returns
 ///
 /// An Arc-wrapped SSH session handle ready for use
-pub async fn get_or_init_ssh_session(addr: &str, user: &str, key: &str, password: &str) -> Result<Arc<SshHandle>> {
+pub async fn get_or_init_ssh_session(
+    addr: &str,
+    user: &str,
+    key: &str,
+    passphrase: &str,
+    password: &str,
+    use_agent: bool,
+    strict_host_key: bool,
+) -> Result<Arc<SshHandle>> {
     // Generate unique identifier for this SSH connection
     let id = format!("{user}@{addr}");
     // Check cache for existing session
@@ -190,7 +254,7 @@ pub async fn get_or_init_ssh_session(addr: &str, user: &str, key: &str, password
     }
     info!(id, "start to create new ssh session");
     // Create new session if none exists or cached session is dead
-    let session = new_ssh_session(addr, user, key, password).await?;
+    let session = new_ssh_session(addr, user, key, passphrase, password, use_agent, strict_host_key).await?;
     info!(id, "new ssh session established");
     let session = Arc::new(session);
     // Cache the new session for future reuse
@@ -209,7 +273,10 @@ pub async fn get_or_init_ssh_session(addr: &str, user: &str, key: &str, password
 /// * `addr` - SSH server address in "host:port" or "host" format (defaults to port 22)
 /// * `user` - SSH username for authentication
 /// * `key` - Optional SSH private key (file path or PEM/OpenSSH format content)
-/// * `password` - Optional password for key decryption or password authentication
+/// * `passphrase` - Optional passphrase of an encrypted key, falls back to `password`
+/// * `password` - Optional password for password authentication
+/// * `use_agent` - Try the identities of the SSH agent first
+/// * `strict_host_key` - Reject hosts missing from known_hosts
 ///
 /// # Returns
 ///
@@ -217,12 +284,21 @@ pub async fn get_or_init_ssh_session(addr: &str, user: &str, key: &str, password
 ///
 /// # Authentication Methods
 ///
-/// 1. Public Key: If `key` is provided, attempts public key authentication
+/// 1. Agent: If `use_agent` is set or no credentials are provided, tries each identity of the agent
+/// 2. Public Key: If `key` is provided, attempts public key authentication
 ///    - If key is a valid file path, loads the key from disk
 ///    - Otherwise, decodes the key from the string content
-/// 2. Password: If only `password` is provided, uses password authentication
-/// 3. Error: If neither key nor password is provided, returns an error
-async fn new_ssh_session(addr: &str, user: &str, key: &str, password: &str) -> Result<SshHandle> {
+/// 3. Password: If only `password` is provided, uses password authentication
+/// 4. Error: If the agent accepted no identity and neither key nor password is provided, returns an error
+async fn new_ssh_session(
+    addr: &str,
+    user: &str,
+    key: &str,
+    passphrase: &str,
+    password: &str,
+    use_agent: bool,
+    strict_host_key: bool,
+) -> Result<SshHandle> {
     // Configure SSH client with keepalive to maintain connection
     let config = russh::client::Config {
         keepalive_interval: Some(Duration::from_secs(5 * 60)),
@@ -242,11 +318,21 @@ async fn new_ssh_session(addr: &str, user: &str, key: &str, password: &str) -> R
     let handler = ClientHandler {
         host: host.clone(),
         port,
+        strict_host_key,
     };
 
     // Establish SSH connection
     let mut session = russh::client::connect(config, (host, port), handler).await?;
 
+    // Keys held by the agent never touch the disk, e.g. hardware keys
+    if use_agent || (key.is_empty() && password.is_empty()) {
+        match authenticate_with_agent(&mut session, user).await {
+            Ok(true) => return Ok(session),
+            Ok(false) => info!(user, "no ssh agent identity was accepted"),
+            Err(e) => info!(error = %e, "ssh agent is unavailable"),
+        }
+    }
+
     // Authenticate using provided credentials
     let auth_res = if !key.is_empty() {
         let key = if key.starts_with("~")
@@ -256,13 +342,23 @@ async fn new_ssh_session(addr: &str, user: &str, key: &str, password: &str) -> R
         } else {
             key.to_string()
         };
-        // Public key authentication
-        let key_pair = if Path::new(&key).exists() {
-            // Load key from file path
-            load_secret_key(key, None)?
-        } else {
-            // Decode key from string content
-            decode_secret_key(&key, None)?
+        // Older configs kept the key passphrase in the ssh password
+        let passphrase = if passphrase.is_empty() { password } else { passphrase };
+        let passphrase = (!passphrase.is_empty()).then_some(passphrase);
+        let load_key_pair = |passphrase: Option<&str>| {
+            if Path::new(&key).exists() {
+                // Load key from file path
+                load_secret_key(&key, passphrase)
+            } else {
+                // Decode key from string content
+                decode_secret_key(&key, passphrase)
+            }
+        };
+        // Public key authentication, the fallback password may not belong to an unencrypted key
+        let key_pair = match load_key_pair(passphrase) {
+            Ok(key_pair) => key_pair,
+            Err(_) if passphrase.is_some() => load_key_pair(None)?,
+            Err(e) => return Err(e.into()),
         };
         let key = Arc::new(key_pair);
         let key_with_alg = PrivateKeyWithHashAlg::new(key, None);
@@ -271,7 +367,6 @@ async fn new_ssh_session(addr: &str, user: &str, key: &str, password: &str) -> R
         // Password authentication
         session.authenticate_password(user, password).await?
     } else {
-        // TODO: Add SSH agent support
         return Err(Error::Invalid {
             message: "Ssh authentication failed".to_string(),
         });
@@ -287,6 +382,35 @@ async fn new_ssh_session(addr: &str, user: &str, key: &str, password: &str) -> R
     Ok(session)
 }
 
+/// Tries the identities of the agent at `$SSH_AUTH_SOCK` until one is accepted.
+///
+/// Returns `false` when the agent has no identity the server accepts.
+#[cfg(unix)]
+async fn authenticate_with_agent(session: &mut SshHandle, user: &str) -> Result<bool> {
+    let mut agent = AgentClient::connect_env().await?;
+    let identities = agent.request_identities().await?;
+    let rsa_hash = session.best_supported_rsa_hash().await?.flatten();
+    for identity in identities {
+        let hash_alg = if identity.algorithm().is_rsa() { rsa_hash } else { None };
+        match session
+            .authenticate_publickey_with(user, identity, hash_alg, &mut agent)
+            .await
+        {
+            Ok(auth_res) if auth_res.success() => return Ok(true),
+            Ok(_) => {}
+            Err(e) => info!(error = %e, "ssh agent identity failed"),
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(not(unix))]
+async fn authenticate_with_agent(_session: &mut SshHandle, _user: &str) -> Result<bool> {
+    Err(Error::Invalid {
+        message: "Ssh agent is only supported on unix".to_string(),
+    })
+}
+
 /// Opens a Redis connection through an SSH tunnel.
 ///
 /// This function establishes an SSH session using the provided configuration,
@@ -305,15 +429,29 @@ pub async fn open_single_ssh_tunnel_connection(config: &RedisServer) -> Result<M
     let ssh_addr = config.ssh_addr.clone().unwrap_or_default();
     let ssh_user = config.ssh_username.clone().unwrap_or_default();
     let ssh_key = config.ssh_key.clone().unwrap_or_default();
+    let ssh_key_passphrase = config.ssh_key_passphrase.clone().unwrap_or_default();
     let ssh_password = config.ssh_password.clone().unwrap_or_default();
+    let ssh_agent = config.ssh_agent.unwrap_or(false);
+    let ssh_strict_host_key = config.ssh_strict_host_key.unwrap_or(false);
     // Extract Redis server details
     let host = config.host.to_string();
     let port = config.port;
     let username = config.username.clone();
     let password = config.password.clone();
+    let connection_timeout = config.connection_timeout();
+    let response_timeout = config.response_timeout();
     run_in_tokio(async move {
         // Get or initialize an SSH session
-        let session = get_or_init_ssh_session(&ssh_addr, &ssh_user, &ssh_key, &ssh_password).await?;
+        let session = get_or_init_ssh_session(
+            &ssh_addr,
+            &ssh_user,
+            &ssh_key,
+            &ssh_key_passphrase,
+            &ssh_password,
+            ssh_agent,
+            ssh_strict_host_key,
+        )
+        .await?;
         // Open a direct TCP channel through the SSH tunnel to the Redis server
         let channel = session
             .channel_open_direct_tcpip(&host, port as u32, "127.0.0.1", 0)
@@ -323,8 +461,8 @@ pub async fn open_single_ssh_tunnel_connection(config: &RedisServer) -> Result<M
         let compat_stream = SshRedisStream::new(channel.into_stream());
         let info = RedisConnectionInfo::default();
         let conn_config = redis::AsyncConnectionConfig::new()
-            .set_connection_timeout(Some(get_redis_connection_timeout()))
-            .set_response_timeout(Some(get_redis_response_timeout()));
+            .set_connection_timeout(Some(connection_timeout))
+            .set_response_timeout(Some(response_timeout));
         // Create a multiplexed connection with the stream
         let (mut connection, driver) =
             MultiplexedConnection::new_with_config(&info, compat_stream, conn_config).await?;
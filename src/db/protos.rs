@@ -14,20 +14,25 @@
 
 use super::{PROTO_TABLE, get_database};
 use crate::error::Error;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use dashmap::DashMap;
-use prost_reflect::{DescriptorPool, DynamicMessage};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MapKey, SerializeOptions, Value};
 use redb::{ReadableDatabase, ReadableTable};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
 use tempfile::TempDir;
-use tracing::info;
+use tracing::{info, warn};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
-static PROTO_META_CACHE: LazyLock<DashMap<String, ProtoConfig>> = LazyLock::new(DashMap::new);
+/// Server id of proto configs that apply to every server
+pub const ALL_SERVERS_ID: &str = "*";
+
+static PROTO_META_CACHE: LazyLock<DashMap<String, CachedProto>> = LazyLock::new(DashMap::new);
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MatchMode {
@@ -58,6 +63,20 @@ impl From<MatchMode> for usize {
         }
     }
 }
+impl MatchMode {
+    /// Checks whether the key matches the pattern under this mode.
+    /// Returns an error if the pattern is not a valid regex in regex mode.
+    pub fn is_match(&self, pattern: &str, key: &str) -> Result<bool, regex::Error> {
+        let matched = match self {
+            MatchMode::Exact => key == pattern,
+            MatchMode::Prefix => key.starts_with(pattern),
+            MatchMode::Suffix => key.ends_with(pattern),
+            MatchMode::Regex => Regex::new(pattern)?.is_match(key),
+        };
+        Ok(matched)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProtoConfig {
     pub server_id: String,
@@ -68,18 +87,169 @@ pub struct ProtoConfig {
     pub target_message: Option<String>,
 }
 
-fn proto_to_json(pool: &DescriptorPool, message_name: &str, bytes: &[u8]) -> Result<String> {
+/// Converts a map key to the string form used for JSON object keys.
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(v) => v.to_string(),
+        MapKey::I32(v) => v.to_string(),
+        MapKey::I64(v) => v.to_string(),
+        MapKey::U32(v) => v.to_string(),
+        MapKey::U64(v) => v.to_string(),
+        MapKey::String(v) => v.clone(),
+    }
+}
+
+fn value_to_raw_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Bool(v) => serde_json::Value::from(*v),
+        Value::I32(v) => serde_json::Value::from(*v),
+        Value::I64(v) => serde_json::Value::from(*v),
+        Value::U32(v) => serde_json::Value::from(*v),
+        Value::U64(v) => serde_json::Value::from(*v),
+        Value::F32(v) => serde_json::Value::from(*v),
+        Value::F64(v) => serde_json::Value::from(*v),
+        Value::String(v) => serde_json::Value::from(v.as_str()),
+        Value::Bytes(v) => serde_json::Value::from(BASE64.encode(v)),
+        Value::EnumNumber(v) => serde_json::Value::from(*v),
+        Value::Message(v) => message_to_raw_json(v),
+        Value::List(items) => serde_json::Value::Array(items.iter().map(value_to_raw_json).collect()),
+        Value::Map(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| (map_key_to_string(key), value_to_raw_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Renders a message field by field, without the protojson special forms
+/// for well-known types (e.g. a Timestamp stays `{ "seconds", "nanos" }`).
+fn message_to_raw_json(message: &DynamicMessage) -> serde_json::Value {
+    serde_json::Value::Object(
+        message
+            .fields()
+            .map(|(field, value)| (field.json_name().to_string(), value_to_raw_json(value)))
+            .collect(),
+    )
+}
+
+impl ProtoConfig {
+    /// Whether the config applies to the server, either directly or through the wildcard.
+    pub fn applies_to(&self, server_id: &str) -> bool {
+        self.server_id == server_id || self.server_id == ALL_SERVERS_ID
+    }
+}
+
+fn proto_to_json(pool: &DescriptorPool, message_name: &str, bytes: &[u8], raw: bool) -> Result<String> {
     let message_descriptor = pool.get_message_by_name(message_name).ok_or(Error::Invalid {
         message: "message not found".to_string(),
     })?;
 
     let dynamic_msg = DynamicMessage::decode(message_descriptor, bytes)?;
 
-    let json_output = serde_json::to_string_pretty(&dynamic_msg)?;
+    if raw {
+        return Ok(serde_json::to_string_pretty(&message_to_raw_json(&dynamic_msg))?);
+    }
+
+    // Canonical protojson: Timestamp as RFC 3339, Duration as "1.5s",
+    // Struct/Value/wrappers as plain JSON values
+    let mut serializer = serde_json::Serializer::pretty(Vec::new());
+    dynamic_msg.serialize_with_options(&mut serializer, &SerializeOptions::new())?;
+    let json_output =
+        String::from_utf8(serializer.into_inner()).map_err(|e| Error::Invalid { message: e.to_string() })?;
 
     Ok(json_output)
 }
 
+fn json_to_proto(pool: &DescriptorPool, message_name: &str, json: &str) -> Result<Vec<u8>> {
+    let message_descriptor = pool.get_message_by_name(message_name).ok_or(Error::Invalid {
+        message: "message not found".to_string(),
+    })?;
+
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let dynamic_msg =
+        DynamicMessage::deserialize(message_descriptor, &mut deserializer).map_err(|e| Error::Invalid {
+            message: format!("{message_name}: {e}"),
+        })?;
+    deserializer.end()?;
+
+    Ok(dynamic_msg.encode_to_vec())
+}
+
+/// Compiles proto source (or the path of a `.proto` file) into a descriptor pool.
+fn compile_descriptor(name: &str, content: &str) -> Result<DescriptorPool> {
+    if content.trim().is_empty() {
+        return Err(Error::Invalid {
+            message: "proto content is empty".to_string(),
+        });
+    };
+    let temp_dir = TempDir::new()?;
+    let temp_path = temp_dir.path();
+    let mut files = Vec::new();
+    if content.ends_with(".proto") {
+        files.push(Path::new(content).to_path_buf());
+    } else {
+        let file_name = if name.is_empty() { "zedis.proto" } else { name };
+        let file_path = temp_path.join(file_name);
+        fs::write(&file_path, content)?;
+        files.push(file_path);
+    }
+    let result = protox::compile(files, [temp_path]);
+    // Dropping also removes the dir, closing surfaces a failed removal
+    if let Err(e) = temp_dir.close() {
+        warn!(error = %e, "failed to remove proto temp dir");
+    }
+    let pool = DescriptorPool::from_file_descriptor_set(result?)?;
+    Ok(pool)
+}
+
+/// A loaded config without its content, with the pattern ready for matching.
+///
+/// Replaced as a whole on upsert, so the compiled regex can't outlive its pattern.
+struct CachedProto {
+    config: ProtoConfig,
+    /// Compiled pattern of a regex mode config
+    regex: Option<Regex>,
+    /// Why the pattern of a regex mode config doesn't compile
+    pattern_error: Option<String>,
+    /// Compiled descriptor pool and resolved target message, set by the first decode.
+    ///
+    /// The only descriptor cache, dropped with the config on upsert or delete. A config
+    /// pointing at a `.proto` file is compiled again once it is saved.
+    descriptor: OnceLock<(DescriptorPool, String)>,
+}
+
+impl CachedProto {
+    /// Compiles the pattern of a regex mode config once, an invalid pattern is
+    /// logged and flagged instead of silently never matching.
+    fn new(id: &str, mut config: ProtoConfig) -> Self {
+        config.content = None;
+        let mut regex = None;
+        let mut pattern_error = None;
+        if config.mode == MatchMode::Regex {
+            match Regex::new(&config.match_pattern) {
+                Ok(compiled) => regex = Some(compiled),
+                Err(e) => {
+                    warn!(id, match_pattern = config.match_pattern, error = %e, "invalid proto match pattern");
+                    pattern_error = Some(e.to_string());
+                }
+            }
+        }
+        Self {
+            config,
+            regex,
+            pattern_error,
+            descriptor: OnceLock::new(),
+        }
+    }
+    /// Checks the key against the pattern, the regex is compiled only once.
+    fn matches_key(&self, key: &str) -> bool {
+        match &self.config.mode {
+            MatchMode::Regex => self.regex.as_ref().is_some_and(|regex| regex.is_match(key)),
+            mode => mode.is_match(&self.config.match_pattern, key).unwrap_or(false),
+        }
+    }
+}
+
 pub struct ProtoManager;
 
 impl ProtoManager {
@@ -91,7 +261,7 @@ impl ProtoManager {
         for item in table.iter()? {
             let (key, value) = item?;
             let id = key.value();
-            let mut config: ProtoConfig = serde_json::from_slice(value.value())?;
+            let config: ProtoConfig = serde_json::from_slice(value.value())?;
             info!(
                 id,
                 name = config.name,
@@ -99,8 +269,7 @@ impl ProtoManager {
                 match_pattern = config.match_pattern,
                 "load proto"
             );
-            config.content = None;
-            PROTO_META_CACHE.insert(id.to_string(), config);
+            PROTO_META_CACHE.insert(id.to_string(), CachedProto::new(id, config));
         }
         info!(count = PROTO_META_CACHE.len(), "load protos success");
 
@@ -110,7 +279,7 @@ impl ProtoManager {
         let cache = &PROTO_META_CACHE;
         cache
             .iter()
-            .map(|item| (item.key().clone(), item.value().clone()))
+            .map(|item| (item.key().clone(), item.config.clone()))
             .collect::<Vec<_>>()
     }
     pub fn get_proto(id: &str) -> Result<ProtoConfig> {
@@ -136,28 +305,33 @@ impl ProtoManager {
         PROTO_META_CACHE.remove(id);
         Ok(())
     }
+    /// Returns the name of the proto config, if it is loaded.
+    pub fn proto_name(id: &str) -> Option<String> {
+        PROTO_META_CACHE.get(id).map(|item| item.config.name.clone())
+    }
+    /// Returns why the regex pattern of the config is invalid, if it is.
+    pub fn pattern_error(id: &str) -> Option<String> {
+        PROTO_META_CACHE.get(id).and_then(|item| item.pattern_error.clone())
+    }
+    /// Finds the proto config matching the key, configs bound to the server
+    /// take precedence over the ones applying to all servers.
     pub fn match_key_to_name(server_id: &str, key: &str) -> Option<String> {
         let cache = &PROTO_META_CACHE;
-        let item = cache.iter().find(|item| {
-            if item.server_id != server_id {
-                return false;
+        let mut wildcard = None;
+        for item in cache.iter() {
+            if !item.config.applies_to(server_id) || !item.matches_key(key) {
+                continue;
             }
-            match item.mode {
-                MatchMode::Exact => key == item.match_pattern,
-                MatchMode::Prefix => key.starts_with(&item.match_pattern),
-                MatchMode::Suffix => key.ends_with(&item.match_pattern),
-                MatchMode::Regex => {
-                    if let Ok(re) = Regex::new(&item.match_pattern) {
-                        re.is_match(key)
-                    } else {
-                        false
-                    }
-                }
+            if item.config.server_id == server_id {
+                return Some(item.key().to_string());
             }
-        })?;
-        Some(item.key().to_string())
+            if wildcard.is_none() {
+                wildcard = Some(item.key().to_string());
+            }
+        }
+        wildcard
     }
-    pub fn upsert_proto(id: &str, mut proto: ProtoConfig) -> Result<()> {
+    pub fn upsert_proto(id: &str, proto: ProtoConfig) -> Result<()> {
         if proto.name.is_empty() {
             return Err(Error::Invalid {
                 message: "proto name is empty".to_string(),
@@ -171,11 +345,36 @@ impl ProtoManager {
             table.insert(id, json_val.as_bytes())?;
         }
         write_txn.commit()?;
-        proto.content = None;
-        PROTO_META_CACHE.insert(id.to_string(), proto);
+        PROTO_META_CACHE.insert(id.to_string(), CachedProto::new(id, proto));
         Ok(())
     }
-    pub fn decode_data(id: &str, data: &[u8]) -> Result<String> {
+    /// Lists the fully qualified names of all messages defined by the proto content.
+    pub fn list_message_names(name: &str, content: &str) -> Result<Vec<String>> {
+        let pool = compile_descriptor(name, content)?;
+        Ok(pool
+            .all_messages()
+            .map(|message| message.full_name().to_string())
+            .collect())
+    }
+    /// Checks that the target message exists in the proto content.
+    pub fn validate_target_message(name: &str, content: &str, target_message: &str) -> Result<()> {
+        let pool = compile_descriptor(name, content)?;
+        if pool.get_message_by_name(target_message).is_none() {
+            return Err(Error::Invalid {
+                message: format!("message not found: {target_message}"),
+            });
+        }
+        Ok(())
+    }
+    /// Compiles the proto config and resolves the target message name.
+    ///
+    /// Falls back to the first message in the descriptor pool when no
+    /// target message is configured. The result is kept with the cached
+    /// config, so repeated decodes of matched keys don't compile again.
+    fn load_descriptor(id: &str) -> Result<(DescriptorPool, String)> {
+        if let Some(descriptor) = PROTO_META_CACHE.get(id).and_then(|item| item.descriptor.get().cloned()) {
+            return Ok(descriptor);
+        }
         let proto = {
             let db = get_database()?;
             let read_txn = db.begin_read()?;
@@ -188,24 +387,7 @@ impl ProtoManager {
             let proto: ProtoConfig = serde_json::from_slice(v.value())?;
             proto
         };
-        let content = proto.content.unwrap_or_default();
-        if content.trim().is_empty() {
-            return Err(Error::Invalid {
-                message: "proto content is empty".to_string(),
-            });
-        };
-        let temp_dir = TempDir::new()?;
-        let temp_path = temp_dir.path();
-        let mut files = Vec::new();
-        if content.ends_with(".proto") {
-            files.push(Path::new(&content).to_path_buf());
-        } else {
-            let file_path = temp_path.join(proto.name);
-            fs::write(&file_path, content)?;
-            files.push(file_path);
-        }
-        let file_descriptor_set = protox::compile(files, [temp_path])?;
-        let pool = prost_reflect::DescriptorPool::from_file_descriptor_set(file_descriptor_set)?;
+        let pool = compile_descriptor(&proto.name, &proto.content.unwrap_or_default())?;
         let mut target_message = proto.target_message.unwrap_or_default();
         if target_message.is_empty()
             && let Some(message) = pool.all_messages().next()
@@ -222,6 +404,146 @@ impl ProtoManager {
                 message: "target message is empty".to_string(),
             });
         }
-        proto_to_json(&pool, &target_message, data)
+        if let Some(item) = PROTO_META_CACHE.get(id) {
+            let _ = item.descriptor.set((pool.clone(), target_message.clone()));
+        }
+        Ok((pool, target_message))
+    }
+    /// Decodes protobuf bytes into JSON.
+    ///
+    /// Well-known types use their canonical protojson forms unless `raw` is set,
+    /// in which case every message is rendered as its plain field structure.
+    pub fn decode_data(id: &str, data: &[u8], raw: bool) -> Result<String> {
+        let (pool, target_message) = Self::load_descriptor(id)?;
+        proto_to_json(&pool, &target_message, data, raw)
+    }
+    /// Encodes JSON into the protobuf bytes of the configured target message.
+    ///
+    /// The JSON is validated against the message schema, unknown fields and
+    /// mistyped values are rejected with the offending field in the error.
+    pub fn encode_data(id: &str, json: &str) -> Result<Vec<u8>> {
+        let (pool, target_message) = Self::load_descriptor(id)?;
+        json_to_proto(&pool, &target_message, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn config(pattern: &str, mode: MatchMode) -> ProtoConfig {
+        ProtoConfig {
+            server_id: "server".to_string(),
+            name: pattern.to_string(),
+            match_pattern: pattern.to_string(),
+            mode,
+            content: None,
+            target_message: None,
+        }
+    }
+
+    fn regex_protos() -> Vec<CachedProto> {
+        [
+            r"^user:\d+:profile$",
+            r"^order:[0-9a-f]{8}$",
+            r"^session:.+:token$",
+            r"^cart:\d+$",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, pattern)| CachedProto::new(&i.to_string(), config(pattern, MatchMode::Regex)))
+        .collect()
+    }
+
+    fn sample_keys(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|i| match i % 5 {
+                0 => format!("user:{i}:profile"),
+                1 => format!("order:{i:08x}"),
+                2 => format!("session:{i}:token"),
+                3 => format!("cart:{i}"),
+                _ => format!("misc:{i}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_key() {
+        let proto = CachedProto::new("1", config(r"^user:\d+$", MatchMode::Regex));
+        assert!(proto.pattern_error.is_none());
+        assert!(proto.matches_key("user:42"));
+        assert!(!proto.matches_key("user:abc"));
+
+        let invalid = CachedProto::new("2", config("user:(", MatchMode::Regex));
+        assert!(invalid.pattern_error.is_some());
+        assert!(!invalid.matches_key("user:("));
+
+        let prefix = CachedProto::new("3", config("user:", MatchMode::Prefix));
+        assert!(prefix.matches_key("user:42"));
+        assert!(!prefix.matches_key("order:42"));
+    }
+
+    #[test]
+    fn test_matches_key_agrees_with_match_mode() {
+        let protos = regex_protos();
+        for key in sample_keys(500) {
+            for proto in &protos {
+                let expected = proto
+                    .config
+                    .mode
+                    .is_match(&proto.config.match_pattern, &key)
+                    .expect("valid pattern");
+                assert_eq!(proto.matches_key(&key), expected, "{key}");
+            }
+        }
+    }
+
+    /// Compares matching 50,000 keys against regex protos with a pattern compiled
+    /// per key versus the pattern compiled once in `CachedProto`.
+    ///
+    /// Run with `cargo test --release match_key_speed -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_match_key_speed() {
+        let protos = regex_protos();
+        let keys = sample_keys(50_000);
+
+        let start = Instant::now();
+        let mut uncached = 0;
+        for key in &keys {
+            for proto in &protos {
+                if proto
+                    .config
+                    .mode
+                    .is_match(&proto.config.match_pattern, key)
+                    .unwrap_or(false)
+                {
+                    uncached += 1;
+                    break;
+                }
+            }
+        }
+        let uncached_elapsed = start.elapsed();
+
+        let start = Instant::now();
+        let mut cached = 0;
+        for key in &keys {
+            if protos.iter().any(|proto| proto.matches_key(key)) {
+                cached += 1;
+            }
+        }
+        let cached_elapsed = start.elapsed();
+
+        assert_eq!(uncached, cached);
+        println!(
+            "{} keys, {} patterns: compiled per key {:?}, compiled once {:?} ({:.1}x)",
+            keys.len(),
+            protos.len(),
+            uncached_elapsed,
+            cached_elapsed,
+            uncached_elapsed.as_secs_f64() / cached_elapsed.as_secs_f64().max(f64::EPSILON)
+        );
+        assert!(cached_elapsed < uncached_elapsed);
     }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use miette::Diagnostic;
 use snafu::Snafu;
 
 #[derive(Debug, Snafu)]
@@ -32,6 +33,15 @@ pub enum Error {
     Ssh { source: russh::Error },
     #[snafu(display("Key error: {source}"))]
     Key { source: russh::keys::Error },
+    /// The SSH server is missing from known_hosts, the user is asked whether to trust it
+    #[snafu(display("Unknown ssh host key of {host}:{port}: {fingerprint}"))]
+    UnknownHostKey {
+        host: String,
+        port: u16,
+        fingerprint: String,
+        /// The host key in OpenSSH format
+        key: String,
+    },
     #[snafu(display("Redb error: {source}"))]
     Redb { source: redb::Error },
     #[snafu(display("Redb database error: {source}"))]
@@ -47,8 +57,9 @@ pub enum Error {
     #[snafu(display("Redb storage error: {source}"))]
     RedbStorage { source: redb::StorageError },
 
-    #[snafu(display("Protox error: {source}"))]
-    Protox { source: protox::Error },
+    /// `message` points at the file, line and column of the failing definition
+    #[snafu(display("Proto compile error: {message}"))]
+    Protox { message: String, source: protox::Error },
 
     #[snafu(display("Prost reflect descriptor error: {source}"))]
     ProstReflectDescriptor { source: prost_reflect::DescriptorError },
@@ -57,6 +68,21 @@ pub enum Error {
     ProstReflectDecode { source: prost_reflect::prost::DecodeError },
 }
 
+impl Error {
+    /// The message followed by the chain of underlying causes, one per line.
+    ///
+    /// The direct source is skipped, every variant's message already contains it.
+    pub fn detail(&self) -> String {
+        let mut detail = self.to_string();
+        let mut source = std::error::Error::source(self).and_then(std::error::Error::source);
+        while let Some(cause) = source {
+            detail.push_str(&format!("\nCaused by: {cause}"));
+            source = std::error::Error::source(cause);
+        }
+        detail
+    }
+}
+
 impl From<redis::RedisError> for Error {
     fn from(source: redis::RedisError) -> Self {
         Error::Redis { source }
@@ -135,9 +161,27 @@ impl From<redb::StorageError> for Error {
     }
 }
 
+/// Prefixes the compile error with `file:line:column` of its first label,
+/// so a broken `.proto` can be fixed from the message alone.
+fn describe_protox_error(source: &protox::Error) -> String {
+    let location = source.labels().and_then(|mut labels| labels.next()).and_then(|label| {
+        let contents = source.source_code()?.read_span(label.inner(), 0, 0).ok()?;
+        Some(format!("{}:{}", contents.line() + 1, contents.column() + 1))
+    });
+    match (source.file(), location) {
+        (Some(file), Some(location)) => format!("{file}:{location}: {source}"),
+        (Some(file), None) => format!("{file}: {source}"),
+        (None, Some(location)) => format!("{location}: {source}"),
+        (None, None) => source.to_string(),
+    }
+}
+
 impl From<protox::Error> for Error {
     fn from(source: protox::Error) -> Self {
-        Error::Protox { source }
+        Error::Protox {
+            message: describe_protox_error(&source),
+            source,
+        }
     }
 }
 
@@ -152,3 +196,44 @@ impl From<prost_reflect::prost::DecodeError> for Error {
         Error::ProstReflectDecode { source }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Wrapped(std::io::Error);
+
+    impl std::fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "read config failed")
+        }
+    }
+
+    impl std::error::Error for Wrapped {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_detail() {
+        let err = Error::Invalid {
+            message: "Server name is required".to_string(),
+        };
+        assert_eq!("Invalid: Server name is required", err.detail());
+
+        let err = Error::Io {
+            source: std::io::Error::other("permission denied"),
+        };
+        assert_eq!("IO error: permission denied", err.detail());
+
+        let err = Error::Io {
+            source: std::io::Error::other(Wrapped(std::io::Error::other("permission denied"))),
+        };
+        assert_eq!(
+            "IO error: read config failed\nCaused by: permission denied",
+            err.detail()
+        );
+    }
+}
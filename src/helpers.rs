@@ -18,6 +18,7 @@ mod action;
 mod common;
 mod font;
 mod fs;
+mod resp;
 mod string;
 mod time;
 mod ttl_cache;
@@ -27,8 +28,9 @@ pub use action::*;
 pub use common::*;
 pub use font::get_font_family;
 pub use fs::{get_home_dir, get_or_create_config_dir, is_app_store_build};
+pub use resp::parse_restore_commands;
 pub use string::*;
-pub use time::{parse_duration, unix_ts};
+pub use time::{format_timestamp, parse_duration, unix_ts};
 pub use ttl_cache::*;
 pub use validate::*;
 pub fn is_development() -> bool {
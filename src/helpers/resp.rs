@@ -0,0 +1,184 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::Error;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Arguments preallocated for a command, the count comes from the file and can't be trusted
+const MAX_PREALLOCATED_ARGS: usize = 1024;
+
+fn invalid(message: &str) -> Error {
+    Error::Invalid {
+        message: format!("Invalid RESTORE dump: {message}"),
+    }
+}
+
+/// Reads a `<prefix><number>\r\n` line, e.g. the `*4` of an array or the `$3` of a bulk string.
+fn read_number(content: &[u8], pos: &mut usize, prefix: u8) -> Result<usize> {
+    if content.get(*pos) != Some(&prefix) {
+        return Err(invalid(&format!("expected '{}' at byte {pos}", prefix as char)));
+    }
+    let start = *pos + 1;
+    let end = content[start..]
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .map(|offset| start + offset)
+        .ok_or_else(|| invalid("unterminated line"))?;
+    let number = std::str::from_utf8(&content[start..end])
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .ok_or_else(|| invalid(&format!("bad length at byte {start}")))?;
+    *pos = end + 2;
+    Ok(number)
+}
+
+/// Reads a bulk string, its bytes are kept as is so payloads stay binary safe.
+fn read_bulk_string<'a>(content: &'a [u8], pos: &mut usize) -> Result<&'a [u8]> {
+    let len = read_number(content, pos, b'$')?;
+    let (end, next) = pos
+        .checked_add(len)
+        .and_then(|end| Some((end, end.checked_add(2)?)))
+        .ok_or_else(|| invalid("bulk string length overflows"))?;
+    if content.get(end..next) != Some(b"\r\n".as_slice()) {
+        return Err(invalid("truncated bulk string"));
+    }
+    let value = &content[*pos..end];
+    *pos = next;
+    Ok(value)
+}
+
+/// Parses the `RESTORE key ttl payload [REPLACE]` commands of a RESP file, as written by the key export.
+/// # Returns
+/// * `Vec<(String, Vec<u8>, i64)>` - The key, its `DUMP` payload and its TTL in milliseconds (0 without expiry).
+pub fn parse_restore_commands(content: &[u8]) -> Result<Vec<(String, Vec<u8>, i64)>> {
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while pos < content.len() {
+        if content[pos].is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        let count = read_number(content, &mut pos, b'*')?;
+        let mut args = Vec::with_capacity(count.min(MAX_PREALLOCATED_ARGS));
+        for _ in 0..count {
+            args.push(read_bulk_string(content, &mut pos)?);
+        }
+        let [command, key, ttl, payload, ..] = args.as_slice() else {
+            return Err(invalid("RESTORE needs a key, a ttl and a payload"));
+        };
+        if !command.eq_ignore_ascii_case(b"RESTORE") {
+            return Err(invalid(&format!(
+                "unexpected command {}",
+                String::from_utf8_lossy(command)
+            )));
+        }
+        let key = String::from_utf8(key.to_vec()).map_err(|_| invalid("key is not valid UTF-8"))?;
+        let ttl = std::str::from_utf8(ttl)
+            .ok()
+            .and_then(|value| value.parse::<i64>().ok())
+            .ok_or_else(|| invalid(&format!("bad ttl of {key}")))?;
+        entries.push((key, payload.to_vec(), ttl));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restore(key: &str, ttl: i64, payload: &[u8]) -> Vec<u8> {
+        redis::cmd("RESTORE")
+            .arg(key)
+            .arg(ttl)
+            .arg(payload)
+            .arg("REPLACE")
+            .get_packed_command()
+    }
+
+    fn parse_error(content: &[u8]) -> String {
+        parse_restore_commands(content)
+            .expect_err("invalid dump parsed")
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_restore_commands() {
+        let payload = b"\x00\x05hello\r\n\x0c\x00\xff".to_vec();
+        let mut content = restore("user:1", 0, &payload);
+        content.extend(b"\r\n");
+        content.extend(restore("user:2", 60_000, b""));
+        let entries = parse_restore_commands(&content).expect("parse export");
+        assert_eq!(
+            vec![
+                ("user:1".to_string(), payload, 0),
+                ("user:2".to_string(), vec![], 60_000)
+            ],
+            entries
+        );
+
+        assert!(parse_restore_commands(b"").expect("empty file").is_empty());
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        let content = restore("user:1", 0, b"payload");
+        for len in [1, 4, content.len() - 3, content.len() - 1] {
+            assert!(parse_restore_commands(&content[..len]).is_err(), "parsed {len} bytes");
+        }
+        // The bulk string is longer than its declared length
+        assert_eq!(
+            "Invalid: Invalid RESTORE dump: truncated bulk string",
+            parse_error(b"*1\r\n$2\r\nabc\r\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_oversized_lengths() {
+        assert_eq!(
+            "Invalid: Invalid RESTORE dump: bulk string length overflows",
+            parse_error(format!("*1\r\n${}\r\nab\r\n", usize::MAX).as_bytes())
+        );
+
+        assert_eq!(
+            "Invalid: Invalid RESTORE dump: bad length at byte 5",
+            parse_error(b"*1\r\n$99999999999999999999999\r\nab\r\n")
+        );
+
+        // The arg count isn't preallocated as is, the missing args fail the parse
+        assert_eq!(
+            "Invalid: Invalid RESTORE dump: expected '$' at byte 30",
+            parse_error(format!("*{}\r\n$1\r\na\r\n", usize::MAX).as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_parse_unexpected_command() {
+        let content = redis::cmd("SET")
+            .arg("user:1")
+            .arg("0")
+            .arg("zedis")
+            .get_packed_command();
+        assert_eq!(
+            "Invalid: Invalid RESTORE dump: unexpected command SET",
+            parse_error(&content)
+        );
+
+        let content = redis::cmd("RESTORE").arg("user:1").get_packed_command();
+        assert_eq!(
+            "Invalid: Invalid RESTORE dump: RESTORE needs a key, a ttl and a payload",
+            parse_error(&content)
+        );
+    }
+}
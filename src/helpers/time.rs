@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::error::Error;
-use chrono::Local;
+use chrono::{Local, TimeZone};
 use std::time::Duration;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
@@ -23,6 +23,12 @@ pub fn unix_ts() -> i64 {
     Local::now().timestamp()
 }
 
+/// Formats a Unix timestamp in seconds as a local date time, e.g. `2026-01-02 15:04:05`.
+pub fn format_timestamp(ts: i64) -> Option<String> {
+    let time = Local.timestamp_opt(ts, 0).single()?;
+    Some(time.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
 /// Parse a duration string into a Duration.
 pub fn parse_duration(s: &str) -> Result<Duration> {
     if let Ok(secs) = s.parse::<u64>() {
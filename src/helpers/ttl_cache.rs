@@ -67,6 +67,10 @@ impl<K: Eq + Hash + Debug, V: Clone> TtlCache<K, V> {
     pub fn remove(&self, key: &K) {
         self.cache.remove(key);
     }
+    /// Removes every item whose key matches `f`
+    pub fn remove_matching(&self, f: impl Fn(&K) -> bool) {
+        self.cache.retain(|key, _| !f(key));
+    }
     pub fn clear_expired(&self) -> (usize, usize) {
         let now = now_secs();
         let mut count = 0;
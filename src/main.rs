@@ -4,8 +4,9 @@ use crate::constants::SIDEBAR_WIDTH;
 use crate::db::{ProtoManager, init_database};
 use crate::helpers::{MemuAction, get_or_create_config_dir, is_app_store_build, is_development, new_hot_keys};
 use crate::states::{
-    FontSize, FontSizeAction, LocaleAction, NotificationCategory, Route, ServerEvent, SettingsAction, ThemeAction,
-    ZedisAppState, ZedisGlobalStore, ZedisServerState, save_app_state, update_app_state_and_save,
+    FontSize, FontSizeAction, LocaleAction, NotificationCategory, PendingNavigation, Route, ServerEvent,
+    SettingsAction, ThemeAction, ZedisAppState, ZedisGlobalStore, ZedisServerState, save_app_state,
+    update_app_state_and_save,
 };
 use crate::views::{ZedisContent, ZedisSidebar, ZedisTitleBar, open_about_window};
 use gpui::{
@@ -39,6 +40,7 @@ pub struct Zedis {
     pending_notification: Option<Notification>,
     last_bounds: Bounds<Pixels>,
     save_task: Option<Task<()>>,
+    server_state: Entity<ZedisServerState>,
     // views
     sidebar: Entity<ZedisSidebar>,
     content: Entity<ZedisContent>,
@@ -89,13 +91,18 @@ impl Zedis {
         .detach();
         let clear_expired_cache = Some(cx.spawn(async move |_this, cx| {
             loop {
-                cx.background_executor().timer(Duration::from_secs(30)).await;
+                // Read every round so a changed interval applies without restarting
+                let interval = cx
+                    .update(|cx| cx.global::<ZedisGlobalStore>().read(cx).cache_cleanup_interval())
+                    .unwrap_or(Duration::from_secs(30));
+                cx.background_executor().timer(interval).await;
                 clear_expired_cache();
             }
         }));
         let title_bar = Some(cx.new(|cx| ZedisTitleBar::new(window, cx)));
 
         Self {
+            server_state,
             sidebar,
             save_task: None,
             content,
@@ -233,7 +240,7 @@ impl Render for Zedis {
                     state.set_font_size(font_size);
                 });
             }))
-            .on_action(cx.listener(move |_this, e: &SettingsAction, _window, cx| {
+            .on_action(cx.listener(move |this, e: &SettingsAction, _window, cx| {
                 let action = *e;
                 let mut route = None;
                 if action == SettingsAction::Editor {
@@ -241,11 +248,10 @@ impl Render for Zedis {
                 } else if action == SettingsAction::Protos {
                     route = Some(Route::Protos);
                 }
+                // Leaving the editor drops it, so unsaved value edits are confirmed first
                 if let Some(route) = route {
-                    cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
-                        store.update(cx, |state, cx| {
-                            state.go_to(route, cx);
-                        });
+                    this.server_state.update(cx, |state, cx| {
+                        state.request_navigation(PendingNavigation::GoTo(route), cx);
                     });
                 }
             }))
@@ -334,9 +340,14 @@ fn main() {
         }
         cx.set_global(app_store);
         cx.bind_keys(new_hot_keys());
-        cx.on_action(|e: &MemuAction, cx: &mut App| match e {
+        let server_state = cx.new(|_| server_state.clone());
+        let quit_server_state = server_state.clone();
+        cx.on_action(move |e: &MemuAction, cx: &mut App| match e {
             MemuAction::Quit => {
-                cx.quit();
+                // Quits right away unless the value has unsaved edits to confirm
+                quit_server_state.update(cx, |state, cx| {
+                    state.request_navigation(PendingNavigation::Quit, cx);
+                });
             }
             MemuAction::About => {
                 open_about_window(cx);
@@ -350,7 +361,6 @@ fn main() {
             ],
         }]);
 
-        let server_state = cx.new(|_| server_state.clone());
         cx.spawn(async move |cx| {
             cx.open_window(
                 WindowOptions {
@@ -371,6 +381,13 @@ fn main() {
                         cx.hide();
                         false
                     });
+                    #[cfg(not(target_os = "macos"))]
+                    {
+                        let server_state = server_state.clone();
+                        window.on_window_should_close(cx, move |_window, cx| {
+                            server_state.update(cx, |state, cx| state.request_navigation(PendingNavigation::Quit, cx))
+                        });
+                    }
                     let zedis_view = cx.new(|cx| Zedis::new(window, cx, server_state));
                     cx.new(|cx| Root::new(zedis_view, window, cx))
                 },
@@ -29,9 +29,15 @@ pub use i18n::i18n_set_editor;
 pub use i18n::i18n_settings;
 pub use i18n::i18n_sidebar;
 pub use i18n::i18n_status_bar;
+pub use i18n::i18n_stream_editor;
 pub use i18n::i18n_zset_editor;
 pub use server::ErrorMessage;
+pub use server::HostKeyPrompt;
+pub use server::KeyTypePrefetchCost;
+pub use server::PendingNavigation;
+pub use server::ServerFeatures;
 pub use server::ZedisServerState;
 pub use server::event::ServerEvent;
 pub use server::event::ServerTask;
+pub use server::stat::RedisInfo;
 pub use server::value::*;
@@ -12,16 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::connection::{set_redis_connection_timeout, set_redis_response_timeout};
+use crate::connection::{set_command_logging, set_redis_connection_timeout, set_redis_response_timeout};
 use crate::constants::SIDEBAR_WIDTH;
 use crate::error::Error;
 use crate::helpers::{get_key_tree_widths, get_or_create_config_dir};
+use crate::states::{DeleteCommand, KeyType, ViewMode};
 use gpui::{Action, App, AppContext, Bounds, Context, Entity, Global, Pixels};
 use gpui_component::{PixelsExt, ThemeMode};
 use locale_config::Locale;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 use tracing::{error, info};
@@ -54,6 +56,65 @@ impl FontSize {
     }
 }
 
+/// Layout of the server cards on the home page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ServerGridLayout {
+    /// Pick the number of columns from the window width
+    #[default]
+    Auto,
+    One,
+    Two,
+    Three,
+    Four,
+    /// Single column of compact cards
+    List,
+}
+impl ServerGridLayout {
+    pub const ALL: [ServerGridLayout; 6] = [
+        ServerGridLayout::Auto,
+        ServerGridLayout::One,
+        ServerGridLayout::Two,
+        ServerGridLayout::Three,
+        ServerGridLayout::Four,
+        ServerGridLayout::List,
+    ];
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ServerGridLayout::Auto => "auto",
+            ServerGridLayout::One => "1",
+            ServerGridLayout::Two => "2",
+            ServerGridLayout::Three => "3",
+            ServerGridLayout::Four => "4",
+            ServerGridLayout::List => "list",
+        }
+    }
+    /// Fixed number of columns, `None` follows the viewport breakpoints
+    pub fn columns(self) -> Option<u16> {
+        match self {
+            ServerGridLayout::Auto => None,
+            ServerGridLayout::One | ServerGridLayout::List => Some(1),
+            ServerGridLayout::Two => Some(2),
+            ServerGridLayout::Three => Some(3),
+            ServerGridLayout::Four => Some(4),
+        }
+    }
+}
+
+/// Last failed connect of a server, shown on its card until it connects again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerError {
+    pub message: String,
+    /// Unix timestamp of the failure
+    pub created_at: i64,
+}
+
+/// Key selected last on a server, opened again on the next connect to the same db
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastKey {
+    pub key: String,
+    pub db: usize,
+}
+
 /// Theme selection actions for the settings menu
 #[derive(Clone, Copy, PartialEq, Debug, Deserialize, JsonSchema, Action)]
 pub enum ThemeAction {
@@ -88,6 +149,9 @@ pub enum SettingsAction {
 
 const LIGHT_THEME_MODE: &str = "light";
 const DARK_THEME_MODE: &str = "dark";
+/// Bounds of the scan settings, so a typo can't make each round useless or unbounded
+const SCAN_LIMIT_MIN: usize = 100;
+const SCAN_LIMIT_MAX: usize = 1_000_000;
 
 fn get_or_create_server_config() -> Result<PathBuf> {
     let config_dir = get_or_create_config_dir()?;
@@ -107,11 +171,44 @@ pub struct ZedisAppState {
     key_tree_width: Pixels,
     theme: Option<String>,
     font_size: Option<FontSize>,
+    server_grid_layout: Option<ServerGridLayout>,
     max_key_tree_depth: Option<usize>,
     key_separator: Option<String>,
     max_truncate_length: Option<usize>,
+    max_loaded_keys: Option<usize>,
+    prefetch_key_types: Option<bool>,
+    escape_glob_keyword: Option<bool>,
+    prefetch_batch_size: Option<usize>,
+    /// COUNT hint of each SCAN round of the key tree
+    scan_batch_count: Option<usize>,
+    /// Keys the key tree scans before waiting for an explicit scan more
+    max_initial_keys: Option<usize>,
     redis_connection_timeout: Option<Duration>,
     redis_response_timeout: Option<Duration>,
+    cache_cleanup_interval: Option<Duration>,
+    /// Maximum number of nodes queried for INFO at the same time
+    info_concurrency: Option<usize>,
+    /// Timeout of the INFO query on a single node
+    info_node_timeout: Option<Duration>,
+    proto_raw_json: Option<bool>,
+    confirm_unsaved_changes: Option<bool>,
+    ttl_absolute: Option<bool>,
+    command_logging: Option<bool>,
+    pin_last_error: Option<bool>,
+    /// Default command of key deletes, `DeleteCommand::as_str`
+    delete_command: Option<String>,
+    /// Preferred viewer mode of each key type, keyed by `KeyType::as_str`
+    default_view_modes: Option<HashMap<String, String>>,
+    /// Expanded key tree folders of each server, keyed by server id
+    expanded_folders: Option<HashMap<String, Vec<String>>>,
+    /// Last connection error of each server, keyed by server id
+    server_errors: Option<HashMap<String, ServerError>>,
+    /// Last selected key of each server, keyed by server id
+    last_keys: Option<HashMap<String, LastKey>>,
+    /// Db picked last on each server, keyed by server id
+    last_dbs: Option<HashMap<String, usize>>,
+    /// Key types shown in the key tree of each server, `KeyType::redis_name` keyed by server id
+    visible_key_types: Option<HashMap<String, Vec<String>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -168,6 +265,7 @@ impl ZedisAppState {
         if let Some(redis_response_timeout) = state.redis_response_timeout {
             set_redis_response_timeout(redis_response_timeout);
         }
+        set_command_logging(state.command_logging());
 
         Ok(state)
     }
@@ -217,9 +315,34 @@ impl ZedisAppState {
     pub fn set_redis_response_timeout(&mut self, redis_response_timeout: Option<Duration>) {
         self.redis_response_timeout = redis_response_timeout;
     }
+    /// How often idle clients, connections and SSH sessions are dropped from the caches
+    pub fn cache_cleanup_interval(&self) -> Duration {
+        self.cache_cleanup_interval.unwrap_or(Duration::from_secs(30))
+    }
+    pub fn set_cache_cleanup_interval(&mut self, cache_cleanup_interval: Option<Duration>) {
+        self.cache_cleanup_interval = cache_cleanup_interval.filter(|interval| !interval.is_zero());
+    }
+    pub fn info_concurrency(&self) -> usize {
+        self.info_concurrency.unwrap_or(8)
+    }
+    pub fn set_info_concurrency(&mut self, info_concurrency: usize) {
+        self.info_concurrency = Some(info_concurrency.max(1));
+    }
+    pub fn info_node_timeout(&self) -> Duration {
+        self.info_node_timeout.unwrap_or(Duration::from_secs(5))
+    }
+    pub fn set_info_node_timeout(&mut self, info_node_timeout: Option<Duration>) {
+        self.info_node_timeout = info_node_timeout.filter(|timeout| !timeout.is_zero());
+    }
     pub fn set_font_size(&mut self, font_size: Option<FontSize>) {
         self.font_size = font_size;
     }
+    pub fn server_grid_layout(&self) -> ServerGridLayout {
+        self.server_grid_layout.unwrap_or_default()
+    }
+    pub fn set_server_grid_layout(&mut self, server_grid_layout: ServerGridLayout) {
+        self.server_grid_layout = Some(server_grid_layout);
+    }
     pub fn theme(&self) -> Option<ThemeMode> {
         match self.theme.as_deref() {
             Some(LIGHT_THEME_MODE) => Some(ThemeMode::Light),
@@ -266,6 +389,170 @@ impl ZedisAppState {
     pub fn set_max_truncate_length(&mut self, max_truncate_length: usize) {
         self.max_truncate_length = Some(max_truncate_length);
     }
+    /// Maximum number of keys the key tree loads before it waits for an explicit load more
+    pub fn max_loaded_keys(&self) -> usize {
+        self.max_loaded_keys.unwrap_or(10_000)
+    }
+    pub fn set_max_loaded_keys(&mut self, max_loaded_keys: usize) {
+        self.max_loaded_keys = Some(max_loaded_keys);
+    }
+    /// Whether the key tree fetches the types of scanned keys, otherwise they load on selection
+    pub fn prefetch_key_types(&self) -> bool {
+        self.prefetch_key_types.unwrap_or(true)
+    }
+    pub fn set_prefetch_key_types(&mut self, prefetch_key_types: bool) {
+        self.prefetch_key_types = Some(prefetch_key_types);
+    }
+    /// Whether glob metacharacters (`*`, `?`, `[`) in the search keyword are matched literally
+    pub fn escape_glob_keyword(&self) -> bool {
+        self.escape_glob_keyword.unwrap_or(false)
+    }
+    pub fn set_escape_glob_keyword(&mut self, escape_glob_keyword: bool) {
+        self.escape_glob_keyword = Some(escape_glob_keyword);
+    }
+    /// Number of TYPE commands sent in one pipeline while prefetching key types
+    pub fn prefetch_batch_size(&self) -> usize {
+        self.prefetch_batch_size.unwrap_or(100)
+    }
+    pub fn set_prefetch_batch_size(&mut self, prefetch_batch_size: usize) {
+        self.prefetch_batch_size = Some(prefetch_batch_size);
+    }
+    /// COUNT hint of each SCAN round, a keyword scan uses five times as much since few keys match
+    pub fn scan_batch_count(&self) -> usize {
+        self.scan_batch_count.unwrap_or(2_000)
+    }
+    pub fn set_scan_batch_count(&mut self, scan_batch_count: usize) {
+        self.scan_batch_count = Some(clamp_scan_limit(scan_batch_count));
+    }
+    /// Keys scanned before the key tree stops, each scan more fetches another `scan_batch_count`
+    pub fn max_initial_keys(&self) -> usize {
+        self.max_initial_keys.unwrap_or(1_000)
+    }
+    pub fn set_max_initial_keys(&mut self, max_initial_keys: usize) {
+        self.max_initial_keys = Some(clamp_scan_limit(max_initial_keys));
+    }
+    /// Whether protobuf values skip the canonical JSON forms of well-known types
+    pub fn proto_raw_json(&self) -> bool {
+        self.proto_raw_json.unwrap_or(false)
+    }
+    pub fn set_proto_raw_json(&mut self, proto_raw_json: bool) {
+        self.proto_raw_json = Some(proto_raw_json);
+    }
+    /// Whether leaving a key with unsaved value edits asks for confirmation
+    pub fn confirm_unsaved_changes(&self) -> bool {
+        self.confirm_unsaved_changes.unwrap_or(true)
+    }
+    pub fn set_confirm_unsaved_changes(&mut self, confirm_unsaved_changes: bool) {
+        self.confirm_unsaved_changes = Some(confirm_unsaved_changes);
+    }
+    /// Viewer mode applied when a key of this type is loaded
+    pub fn default_view_mode(&self, key_type: KeyType) -> ViewMode {
+        self.default_view_modes
+            .as_ref()
+            .and_then(|modes| modes.get(key_type.as_str()))
+            .map(|mode| ViewMode::from_str(mode))
+            .unwrap_or_default()
+    }
+    pub fn set_default_view_mode(&mut self, key_type: KeyType, view_mode: ViewMode) {
+        let modes = self.default_view_modes.get_or_insert_default();
+        if view_mode == ViewMode::Auto {
+            modes.remove(key_type.as_str());
+        } else {
+            modes.insert(key_type.as_str().to_string(), view_mode.as_str().to_string());
+        }
+    }
+    /// Whether TTL is shown as the local expiry time instead of the remaining duration
+    pub fn ttl_absolute(&self) -> bool {
+        self.ttl_absolute.unwrap_or(false)
+    }
+    pub fn set_ttl_absolute(&mut self, ttl_absolute: bool) {
+        self.ttl_absolute = Some(ttl_absolute);
+    }
+    /// Whether the last error stays in the status bar until dismissed,
+    /// instead of being cleared when the next task starts
+    pub fn pin_last_error(&self) -> bool {
+        self.pin_last_error.unwrap_or(false)
+    }
+    pub fn set_pin_last_error(&mut self, pin_last_error: bool) {
+        self.pin_last_error = Some(pin_last_error);
+    }
+    /// Whether every command sent to Redis is logged, for debugging
+    pub fn command_logging(&self) -> bool {
+        self.command_logging.unwrap_or(false)
+    }
+    pub fn set_command_logging(&mut self, command_logging: bool) {
+        self.command_logging = Some(command_logging);
+        set_command_logging(command_logging);
+    }
+    pub fn delete_command(&self) -> DeleteCommand {
+        self.delete_command
+            .as_deref()
+            .map(DeleteCommand::from_str)
+            .unwrap_or_default()
+    }
+    pub fn set_delete_command(&mut self, delete_command: DeleteCommand) {
+        self.delete_command = Some(delete_command.as_str().to_string());
+    }
+    pub fn expanded_folders(&self, server_id: &str) -> Vec<String> {
+        self.expanded_folders
+            .as_ref()
+            .and_then(|folders| folders.get(server_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+    pub fn set_expanded_folders(&mut self, server_id: &str, folders: Vec<String>) {
+        let expanded_folders = self.expanded_folders.get_or_insert_default();
+        if folders.is_empty() {
+            expanded_folders.remove(server_id);
+        } else {
+            expanded_folders.insert(server_id.to_string(), folders);
+        }
+    }
+    pub fn visible_key_types(&self, server_id: &str) -> Vec<String> {
+        self.visible_key_types
+            .as_ref()
+            .and_then(|key_types| key_types.get(server_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+    pub fn set_visible_key_types(&mut self, server_id: &str, key_types: Vec<String>) {
+        let visible_key_types = self.visible_key_types.get_or_insert_default();
+        if key_types.is_empty() {
+            visible_key_types.remove(server_id);
+        } else {
+            visible_key_types.insert(server_id.to_string(), key_types);
+        }
+    }
+    pub fn server_error(&self, server_id: &str) -> Option<&ServerError> {
+        self.server_errors.as_ref().and_then(|errors| errors.get(server_id))
+    }
+    pub fn set_server_error(&mut self, server_id: &str, error: Option<ServerError>) {
+        let server_errors = self.server_errors.get_or_insert_default();
+        match error {
+            Some(error) => server_errors.insert(server_id.to_string(), error),
+            None => server_errors.remove(server_id),
+        };
+    }
+    pub fn last_key(&self, server_id: &str) -> Option<&LastKey> {
+        self.last_keys.as_ref().and_then(|keys| keys.get(server_id))
+    }
+    pub fn set_last_key(&mut self, server_id: &str, last_key: Option<LastKey>) {
+        let last_keys = self.last_keys.get_or_insert_default();
+        match last_key {
+            Some(last_key) => last_keys.insert(server_id.to_string(), last_key),
+            None => last_keys.remove(server_id),
+        };
+    }
+    pub fn last_db(&self, server_id: &str) -> Option<usize> {
+        self.last_dbs.as_ref().and_then(|dbs| dbs.get(server_id)).copied()
+    }
+    pub fn set_last_db(&mut self, server_id: &str, db: Option<usize>) {
+        let last_dbs = self.last_dbs.get_or_insert_default();
+        match db {
+            Some(db) => last_dbs.insert(server_id.to_string(), db),
+            None => last_dbs.remove(server_id),
+        };
+    }
     pub fn redis_connection_timeout(&self) -> String {
         self.redis_connection_timeout
             .map(|timeout| timeout.as_secs().to_string())
@@ -278,6 +565,11 @@ impl ZedisAppState {
     }
 }
 
+/// Keeps a scan setting (SCAN COUNT or initial keys) within the bounds it accepts
+pub fn clamp_scan_limit(value: usize) -> usize {
+    value.clamp(SCAN_LIMIT_MIN, SCAN_LIMIT_MAX)
+}
+
 /// Update app state in background, persist to disk, and refresh UI
 ///
 /// This helper function abstracts the common pattern for updating global state:
@@ -72,6 +72,11 @@ pub fn i18n_hash_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
     t!(format!("hash_editor.{key}"), locale = locale).into()
 }
 
+pub fn i18n_stream_editor<'a>(cx: &'a App, key: &'a str) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    t!(format!("stream_editor.{key}"), locale = locale).into()
+}
+
 pub fn i18n_settings<'a>(cx: &'a App, key: &'a str) -> SharedString {
     let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
     t!(format!("settings.{key}"), locale = locale).into()
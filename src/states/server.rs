@@ -13,21 +13,31 @@
 // limitations under the License.
 
 use crate::connection::{
-    AccessMode, QueryMode, RedisClientDescription, RedisServer, get_connection_manager, save_servers,
+    AccessMode, ConnectionStep, QueryMode, RedisClientDescription, RedisServer, add_transient_server,
+    get_connection_manager, save_servers, trust_host_key,
 };
 use crate::db::HistoryManager;
 use crate::error::Error;
 use crate::helpers::unix_ts;
 use crate::states::server::event::{ServerEvent, ServerTask};
+use crate::states::server::hash::HASH_FIELD_TTL_VERSION;
+use crate::states::server::key::{COPY_VERSION, UNLINK_VERSION};
 use crate::states::server::stat::RedisInfo;
+use crate::states::{
+    LastKey, NotificationAction, Route, ServerError, ZedisGlobalStore, i18n_common, save_app_state,
+    update_app_state_and_save,
+};
 use ahash::AHashMap;
 use ahash::AHashSet;
 use chrono::Local;
-use gpui::SharedString;
 use gpui::prelude::*;
+use gpui::{App, SharedString};
 use parking_lot::RwLock;
+use redis::cmd;
+use rust_i18n::t;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::debug;
 use tracing::error;
 use uuid::Uuid;
@@ -39,6 +49,7 @@ pub mod key;
 pub mod list;
 pub mod set;
 pub mod stat;
+pub mod stream;
 pub mod string;
 pub mod value;
 pub mod zset;
@@ -47,6 +58,7 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 
 // Constants for state management
 const MAX_ERROR_MESSAGES: usize = 10; // Maximum error messages to keep in memory
+const GATED_COMMANDS: [&str; 5] = ["SET", "DEL", "UNLINK", "FLUSHDB", "FLUSHALL"]; // Commands checked by ACL DRYRUN when connecting
 /// Error message with categorization and timestamp
 #[derive(Debug, Clone)]
 pub struct ErrorMessage {
@@ -56,6 +68,9 @@ pub struct ErrorMessage {
     /// Human-readable error message
     pub message: SharedString,
 
+    /// The message with its underlying causes, shown in the error detail
+    pub detail: SharedString,
+
     /// Unix timestamp when error occurred
     pub created_at: i64,
 }
@@ -71,6 +86,52 @@ pub enum RedisServerStatus {
     Loading,
 }
 
+/// Host key of an SSH server missing from known_hosts
+#[derive(Clone, PartialEq, Debug)]
+pub struct HostKeyPrompt {
+    pub host: SharedString,
+    pub port: u16,
+    /// SHA256 fingerprint shown to the user
+    pub fingerprint: SharedString,
+    /// The host key in OpenSSH format
+    pub key: SharedString,
+}
+
+/// Navigation that would drop unsaved edits in the value editor
+#[derive(Clone, PartialEq, Debug)]
+pub enum PendingNavigation {
+    /// Select another key
+    SelectKey(SharedString),
+    /// Switch server or database, optionally moving to another route
+    SelectServer(SharedString, usize, Option<Route>),
+    /// Move to another route
+    GoTo(Route),
+    /// Quit the application
+    Quit,
+}
+
+/// Version dependent commands of the connected server, checked once with the client
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ServerFeatures {
+    /// UNLINK, added in Redis 4.0
+    pub unlink: bool,
+    /// COPY, added in Redis 6.2
+    pub copy: bool,
+    /// Per-field HASH TTL (HTTL, HEXPIRE...), added in Redis 7.4
+    pub hash_field_ttl: bool,
+}
+
+/// Extra commands spent prefetching key types and TTLs for the key tree
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyTypePrefetchCost {
+    /// Number of TYPE and TTL commands sent
+    pub commands: usize,
+    /// Number of pipelines the commands were sent in
+    pub round_trips: usize,
+    /// Total time spent waiting for the pipelines
+    pub elapsed: Duration,
+}
+
 /// Main state management for Redis server operations
 ///
 /// This struct manages:
@@ -82,6 +143,8 @@ pub enum RedisServerStatus {
 #[derive(Debug, Clone, Default)]
 pub struct ZedisServerState {
     redis_info: Option<RedisInfo>,
+    /// INFO of each master by address from the last refresh, used to fill in nodes that don't respond
+    node_redis_infos: Vec<(String, Option<RedisInfo>)>,
 
     /// Whether the terminal is open
     terminal: bool,
@@ -100,18 +163,34 @@ pub struct ZedisServerState {
 
     /// Access mode
     access_mode: AccessMode,
+    /// Commands of `GATED_COMMANDS` the ACL of the user doesn't permit
+    denied_commands: AHashSet<&'static str>,
 
     /// Query mode (All/Prefix/Exact) for key filtering
     query_mode: QueryMode,
 
+    /// Only keys of this type are scanned, reset when switching server or db
+    key_type_filter: Option<KeyType>,
+
     /// Whether to soft wrap the editor
     soft_wrap: bool,
 
+    /// Soft wrap toggled for the current key only, never saved to the server config
+    soft_wrap_override: Option<bool>,
+
     /// Current server status
     server_status: RedisServerStatus,
 
+    /// Whether the user asked to re-establish the current connection
+    reconnecting: bool,
+
+    /// Step the connection to the current server is at, while it is established
+    connection_step: Option<ConnectionStep>,
+
     /// Total number of keys in the database (from DBSIZE command)
     dbsize: Option<u64>,
+    /// Number of keys on each master node (address, DBSIZE)
+    dbsize_per_master: Arc<Vec<(SharedString, u64)>>,
 
     /// Number of Redis nodes (master, replica) for cluster info
     nodes: (usize, usize),
@@ -121,15 +200,36 @@ pub struct ZedisServerState {
     /// Redis server version string
     version: SharedString,
 
+    /// Commands the server version supports
+    features: ServerFeatures,
+
     /// List of all configured servers
     servers: Option<Vec<RedisServer>>,
 
+    /// Server connected from a URL, not saved unless the user asks to
+    transient_server: Option<RedisServer>,
+
+    /// Key selected when the server was used last, opened once the first scan is done
+    restore_key: Option<SharedString>,
+
+    /// Selected key not saved to the app state yet, written on server switch or quit
+    last_key: Option<LastKey>,
+
+    /// Proto config to open for editing when the proto editor is shown next
+    proto_to_edit: Option<SharedString>,
+
     /// Currently selected key name
     key: Option<SharedString>,
 
     /// Value data for the currently selected key
     value: Option<RedisValue>,
 
+    /// Whether the value editor holds edits that haven't been saved
+    unsaved_changes: bool,
+
+    /// Navigation held back until the user saves or discards the edits
+    pending_navigation: Option<PendingNavigation>,
+
     // ===== Key scanning state =====
     /// Search keyword for filtering keys
     keyword: SharedString,
@@ -143,9 +243,12 @@ pub struct ZedisServerState {
     /// Whether the current scan has completed
     scan_completed: bool,
 
-    /// Number of scan iterations performed
+    /// Number of scan more requests since the scan started
     scan_times: usize,
 
+    /// Loaded key count at which auto-scanning stops until the user loads more
+    loaded_keys_limit: usize,
+
     /// Unique ID for current key tree (changes when keys are reloaded)
     key_tree_id: SharedString,
 
@@ -155,6 +258,12 @@ pub struct ZedisServerState {
     /// Map of all loaded keys and their types
     keys: AHashMap<SharedString, KeyType>,
 
+    /// Expiration timestamps prefetched along with the key types, keys without TTL are left out
+    key_expire_at: Arc<AHashMap<SharedString, i64>>,
+
+    /// Cost of the key type prefetch for the current scan
+    key_type_prefetch_cost: KeyTypePrefetchCost,
+
     // ===== Error tracking =====
     /// Recent error messages (limited to MAX_ERROR_MESSAGES)
     error_messages: Arc<RwLock<Vec<ErrorMessage>>>,
@@ -173,40 +282,53 @@ impl ZedisServerState {
         self.keyword = SharedString::default();
         self.cursors = None;
         self.keys.clear();
+        self.key_expire_at = Arc::default();
         self.key_tree_id = Uuid::now_v7().to_string().into();
         self.scanning = false;
         self.scan_completed = false;
         self.scan_times = 0;
+        self.loaded_keys_limit = 0;
+        self.key_type_prefetch_cost = KeyTypePrefetchCost::default();
         self.loaded_prefixes.clear();
     }
 
     /// Reset all state when switching to a different server
     fn reset(&mut self) {
         self.server_id = SharedString::default();
+        self.key_type_filter = None;
         self.version = SharedString::default();
+        self.features = ServerFeatures::default();
         self.nodes = (0, 0);
         self.keys.clear();
         self.key_tree_id = SharedString::default();
         self.nodes_description = Arc::new(RedisClientDescription::default());
         self.dbsize = None;
+        self.dbsize_per_master = Arc::new(Vec::new());
         self.key = None;
         self.redis_info = None;
+        self.node_redis_infos.clear();
         self.value = None;
+        self.unsaved_changes = false;
+        self.soft_wrap_override = None;
+        self.reconnecting = false;
+        self.connection_step = None;
+        self.denied_commands.clear();
         self.reset_scan();
         self.terminal = false;
     }
 
     /// Add new keys to the key map (deduplicating automatically)
     ///
+    /// New keys get `key_type`, Unknown until their type is fetched.
     /// If any new keys were added, generates a new tree ID to trigger UI refresh
-    fn extend_keys(&mut self, keys: Vec<SharedString>) {
+    fn extend_keys(&mut self, keys: Vec<SharedString>, key_type: KeyType) {
         self.keys.reserve(keys.len());
         let mut insert_count = 0;
 
         for key in keys {
             self.keys.entry(key).or_insert_with(|| {
                 insert_count += 1;
-                KeyType::Unknown
+                key_type
             });
         }
 
@@ -219,7 +341,7 @@ impl ZedisServerState {
     /// Add an error message to the history and emit error event
     ///
     /// Maintains a rolling window of MAX_ERROR_MESSAGES most recent errors
-    fn add_error_message(&mut self, category: String, message: String, cx: &mut Context<Self>) {
+    fn add_error_message(&mut self, category: String, message: String, detail: String, cx: &mut Context<Self>) {
         let mut guard = self.error_messages.write();
 
         // Remove oldest error if at capacity
@@ -230,6 +352,7 @@ impl ZedisServerState {
         let info = ErrorMessage {
             category: category.into(),
             message: message.into(),
+            detail: detail.into(),
             created_at: unix_ts(),
         };
         guard.push(info.clone());
@@ -278,7 +401,7 @@ impl ZedisServerState {
                     error!(error = %e, message);
                     // only add error message if the server id is the same as the current server id
                     if this.server_id == server_id {
-                        this.add_error_message(name.as_str().to_string(), e.to_string(), cx);
+                        this.add_error_message(name.as_str().to_string(), e.to_string(), e.detail(), cx);
                     }
                 }
                 callback(this, result, cx);
@@ -291,11 +414,15 @@ impl ZedisServerState {
     where
         F: FnOnce(&mut RedisServer),
     {
-        let mut servers = self.servers.clone().unwrap_or_default();
-
-        if let Some(s) = servers.iter_mut().find(|s| s.id == self.server_id) {
+        // Apply the change in memory first, so a save started before this one finishes can't revert it
+        if let Some(s) = self
+            .servers
+            .as_mut()
+            .and_then(|servers| servers.iter_mut().find(|s| s.id == self.server_id))
+        {
             modifier(s);
         }
+        let servers = self.servers.clone().unwrap_or_default();
 
         self.spawn(
             task_name,
@@ -357,17 +484,35 @@ impl ZedisServerState {
         self.search_history.clear();
     }
 
+    /// Get the access mode of the current server
+    pub fn access_mode(&self) -> AccessMode {
+        self.access_mode
+    }
     /// Get whether the server is readonly
     pub fn readonly(&self) -> bool {
         matches!(self.access_mode, AccessMode::StrictReadOnly | AccessMode::SafeMode)
     }
+    /// Explains why write operations are disabled, used as tooltip of disabled controls
+    pub fn readonly_reason(&self, cx: &App) -> SharedString {
+        match self.access_mode {
+            AccessMode::StrictReadOnly => i18n_common(cx, "readonly_reason_acl"),
+            AccessMode::SafeMode => i18n_common(cx, "readonly_reason_config"),
+            AccessMode::ReadWrite => i18n_common(cx, "disable_in_readonly"),
+        }
+    }
+    /// Whether the ACL of the user permits the command, only `GATED_COMMANDS` are checked
+    pub fn can_run(&self, command: &str) -> bool {
+        !self.denied_commands.contains(command)
+    }
+    /// Explains why a command is disabled, used as tooltip of the controls sending it
+    pub fn acl_denied_reason(&self, command: &str, cx: &App) -> SharedString {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        t!("common.acl_denied_reason", command = command, locale = locale).into()
+    }
     pub fn toggle_readonly(&mut self, cx: &mut Context<Self>) {
         if matches!(self.access_mode, AccessMode::StrictReadOnly) {
-            self.add_error_message(
-                "toggle_readonly".to_string(),
-                "Strict read-only mode, cannot be toggled".to_string(),
-                cx,
-            );
+            let message = "Strict read-only mode, cannot be toggled".to_string();
+            self.add_error_message("toggle_readonly".to_string(), message.clone(), message, cx);
             return;
         }
         if self.access_mode == AccessMode::ReadWrite {
@@ -389,6 +534,7 @@ impl ZedisServerState {
     /// Set whether to soft wrap the editor
     pub fn set_soft_wrap(&mut self, soft_wrap: bool, cx: &mut Context<Self>) {
         self.soft_wrap = soft_wrap;
+        self.soft_wrap_override = None;
         cx.emit(ServerEvent::SoftWrapToggled(self.soft_wrap));
 
         self.update_and_save_server_config(ServerTask::UpdateServerSoftWrap, cx, move |server| {
@@ -399,6 +545,14 @@ impl ZedisServerState {
     pub fn query_mode(&self) -> QueryMode {
         self.query_mode
     }
+    /// Get the key type scans are restricted to
+    pub fn key_type_filter(&self) -> Option<KeyType> {
+        self.key_type_filter
+    }
+    /// Restrict the scans to keys of one type, the caller runs the search again
+    pub fn set_key_type_filter(&mut self, key_type: Option<KeyType>) {
+        self.key_type_filter = key_type;
+    }
 
     /// Check if the current scan has completed
     pub fn scan_completed(&self) -> bool {
@@ -410,11 +564,34 @@ impl ZedisServerState {
         self.scanning
     }
 
+    /// Check if the scan paused because the loaded key limit was reached
+    pub fn loaded_keys_limit_reached(&self) -> bool {
+        self.cursors.is_some() && self.keys.len() >= self.loaded_keys_limit
+    }
+
+    /// Get the masters done with the unfinished scan and the total masters,
+    /// a master is done once its cursor came back to 0
+    pub fn scan_masters_progress(&self) -> Option<(usize, usize)> {
+        let cursors = self.cursors.as_ref()?;
+        let done = cursors.iter().filter(|cursor| **cursor == 0).count();
+        Some((done, cursors.len()))
+    }
+
+    /// Get the cost of the key type prefetch for the current scan
+    pub fn key_type_prefetch_cost(&self) -> KeyTypePrefetchCost {
+        self.key_type_prefetch_cost
+    }
+
     /// Get the total database size (number of keys)
     pub fn dbsize(&self) -> Option<u64> {
         self.dbsize
     }
 
+    /// Get the number of keys on each master node
+    pub fn dbsize_per_master(&self) -> Arc<Vec<(SharedString, u64)>> {
+        self.dbsize_per_master.clone()
+    }
+
     /// Get the count of scanned/loaded keys
     pub fn scan_count(&self) -> usize {
         self.keys.len()
@@ -433,12 +610,21 @@ impl ZedisServerState {
     pub fn nodes_description(&self) -> Arc<RedisClientDescription> {
         self.nodes_description.clone()
     }
+    /// Whether the connected server is a cluster, false until connected
+    pub fn is_cluster(&self) -> bool {
+        self.nodes_description.server_type == "Cluster"
+    }
 
     /// Get the Redis server version string
     pub fn version(&self) -> &str {
         &self.version
     }
 
+    /// Get the commands the server version supports
+    pub fn features(&self) -> ServerFeatures {
+        self.features
+    }
+
     /// Get the currently selected server id
     pub fn server_id(&self) -> &str {
         &self.server_id
@@ -457,17 +643,99 @@ impl ZedisServerState {
     pub fn soft_wrap(&self) -> bool {
         self.soft_wrap
     }
+    /// Soft wrap used by the value editor, the per-key toggle wins over the server default
+    pub fn editor_soft_wrap(&self) -> bool {
+        self.soft_wrap_override.unwrap_or(self.soft_wrap)
+    }
+    /// Toggle soft wrap for the current key without changing the saved server default
+    pub fn toggle_editor_soft_wrap(&mut self, cx: &mut Context<Self>) {
+        let soft_wrap = !self.editor_soft_wrap();
+        self.soft_wrap_override = Some(soft_wrap);
+        cx.emit(ServerEvent::SoftWrapToggled(soft_wrap));
+        cx.notify();
+    }
+    /// Drop the per-key soft wrap toggle, going back to the server default
+    fn reset_soft_wrap_override(&mut self, cx: &mut Context<Self>) {
+        if let Some(soft_wrap) = self.soft_wrap_override.take()
+            && soft_wrap != self.soft_wrap
+        {
+            cx.emit(ServerEvent::SoftWrapToggled(self.soft_wrap));
+        }
+    }
 
     /// Set the list of configured servers
     pub fn set_servers(&mut self, servers: Vec<RedisServer>) {
         self.servers = Some(servers);
     }
 
-    /// Get a server by id
+    /// Get a server by id, the server connected from a URL included
     pub fn server(&self, server_id: &str) -> Option<&RedisServer> {
         self.servers
             .as_deref()
             .and_then(|servers| servers.iter().find(|s| s.id == server_id))
+            .or_else(|| self.transient_server.as_ref().filter(|s| s.id == server_id))
+    }
+
+    /// Check if the current server was connected from a URL and isn't saved yet
+    pub fn is_transient_server(&self) -> bool {
+        self.transient_server.as_ref().is_some_and(|s| s.id == self.server_id)
+            && !self
+                .servers
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .any(|s| s.id == self.server_id)
+    }
+
+    /// Connect to a server built from a URL without saving it
+    pub fn quick_connect(&mut self, server: RedisServer, cx: &mut Context<Self>) {
+        add_transient_server(server.clone());
+        let server_id = server.id.clone();
+        let db = server.default_db();
+        self.transient_server = Some(server);
+        self.select(server_id.into(), db, cx);
+    }
+
+    /// Open the proto config for editing the next time the proto editor is shown
+    pub fn edit_proto(&mut self, id: SharedString, cx: &mut Context<Self>) {
+        self.proto_to_edit = Some(id);
+        self.request_navigation(PendingNavigation::GoTo(Route::Protos), cx);
+    }
+    /// Takes the proto config waiting to be edited, if any
+    pub fn take_proto_to_edit(&mut self) -> Option<SharedString> {
+        self.proto_to_edit.take()
+    }
+
+    /// Save the server connected from a URL to the server list
+    pub fn save_transient_server(&mut self, cx: &mut Context<Self>) {
+        if !self.is_transient_server() {
+            return;
+        }
+        if let Some(server) = self.transient_server.clone() {
+            self.update_or_insrt_server(server, cx);
+        }
+    }
+
+    /// Get the db a server is connected to when selected, the db picked last wins over the configured one
+    pub fn default_db(&self, server_id: &str, cx: &App) -> usize {
+        let Some(server) = self.server(server_id) else {
+            return 0;
+        };
+        cx.global::<ZedisGlobalStore>()
+            .read(cx)
+            .last_db(server_id)
+            .unwrap_or_else(|| server.default_db())
+    }
+
+    /// Switch the current server to another db, keys are scanned again on the new connection
+    ///
+    /// Returns `false` when the switch is held back by unsaved value edits.
+    pub fn select_db(&mut self, db: usize, cx: &mut Context<Self>) -> bool {
+        if self.server_id.is_empty() || self.db == db {
+            return true;
+        }
+        let server_id = self.server_id.clone();
+        self.request_navigation(PendingNavigation::SelectServer(server_id, db, None), cx)
     }
 
     /// Get the list of all configured servers
@@ -479,10 +747,80 @@ impl ZedisServerState {
     pub fn key(&self) -> Option<SharedString> {
         self.key.clone()
     }
+    /// Whether the value editor has edits that haven't been saved
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes
+    }
+    pub fn set_unsaved_changes(&mut self, unsaved_changes: bool) {
+        self.unsaved_changes = unsaved_changes;
+    }
+    /// Run a navigation, or hold it back while the value has unsaved edits.
+    ///
+    /// When held back, `ServerEvent::UnsavedChangesPrompted` asks the user to
+    /// save or discard first. Returns true if the navigation ran right away.
+    pub fn request_navigation(&mut self, navigation: PendingNavigation, cx: &mut Context<Self>) -> bool {
+        let confirm = cx.global::<ZedisGlobalStore>().read(cx).confirm_unsaved_changes();
+        if self.unsaved_changes && confirm {
+            self.pending_navigation = Some(navigation.clone());
+            cx.emit(ServerEvent::UnsavedChangesPrompted(navigation));
+            return false;
+        }
+        self.navigate(navigation, cx);
+        true
+    }
+    /// Run the held back navigation once the edits are saved or discarded
+    pub fn resume_navigation(&mut self, cx: &mut Context<Self>) {
+        self.unsaved_changes = false;
+        if let Some(navigation) = self.pending_navigation.take() {
+            self.navigate(navigation, cx);
+        }
+    }
+    /// Drop the held back navigation and stay on the edited value
+    pub fn cancel_navigation(&mut self) {
+        self.pending_navigation = None;
+        self.proto_to_edit = None;
+    }
+    fn navigate(&mut self, navigation: PendingNavigation, cx: &mut Context<Self>) {
+        let go_to = |route: Route, cx: &mut Context<Self>| {
+            cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+                store.update(cx, |state, cx| {
+                    state.go_to(route, cx);
+                });
+            });
+        };
+        match navigation {
+            PendingNavigation::SelectKey(key) => self.select_key(key, cx),
+            PendingNavigation::SelectServer(server_id, db, route) => {
+                if let Some(route) = route {
+                    go_to(route, cx);
+                }
+                self.select(server_id, db, cx);
+            }
+            PendingNavigation::GoTo(route) => go_to(route, cx),
+            PendingNavigation::Quit => {
+                // Saved right away, the app exits before a spawned save would run
+                if let Some((server_id, last_key)) = self.unsaved_last_key(cx) {
+                    cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+                        store.update(cx, |state, _cx| {
+                            state.set_last_key(&server_id, Some(last_key));
+                            if let Err(e) = save_app_state(state) {
+                                error!(error = %e, "save last key fail");
+                            }
+                        });
+                    });
+                }
+                cx.quit();
+            }
+        }
+    }
     /// Get the map of all loaded keys and their types
     pub fn keys(&self) -> &AHashMap<SharedString, KeyType> {
         &self.keys
     }
+    /// Prefetched expiration timestamps of the loaded keys
+    pub fn key_expire_at(&self) -> Arc<AHashMap<SharedString, i64>> {
+        self.key_expire_at.clone()
+    }
 
     /// Get the value data for the currently selected key
     pub fn value(&self) -> Option<&RedisValue> {
@@ -501,6 +839,7 @@ impl ZedisServerState {
     pub fn remove_server(&mut self, id: &str, cx: &mut Context<Self>) {
         let mut servers = self.servers.clone().unwrap_or_default();
         servers.retain(|s| s.id != id);
+        let server_id = id.to_string();
 
         self.spawn(
             ServerTask::RemoveServer,
@@ -512,6 +851,9 @@ impl ZedisServerState {
                 if let Ok(servers) = result {
                     cx.emit(ServerEvent::ServerListUpdated);
                     this.servers = Some(servers);
+                    update_app_state_and_save(cx, "remove_server_error", move |state, _cx| {
+                        state.set_server_error(&server_id, None);
+                    });
                 }
                 cx.notify();
             },
@@ -519,6 +861,14 @@ impl ZedisServerState {
         );
     }
 
+    /// Another server connecting to the same place with the same settings, whatever its name
+    pub fn find_duplicate_server(&self, server: &RedisServer) -> Option<&RedisServer> {
+        let hash = server.connection_hash();
+        self.servers
+            .as_ref()?
+            .iter()
+            .find(|item| item.id != server.id && item.connection_hash() == hash)
+    }
     /// Add new server or update existing server configuration
     ///
     /// # Arguments
@@ -530,6 +880,16 @@ impl ZedisServerState {
             server.id = Uuid::now_v7().to_string();
         }
         server.updated_at = Some(Local::now().to_rfc3339());
+        // The cached client of the connected server would keep using the old address or credentials
+        let should_reconnect = server.id == self.server_id
+            && servers
+                .iter()
+                .find(|s| s.id == server.id)
+                .is_some_and(|existing| existing.connection_changed(&server));
+        if should_reconnect {
+            // Removed before saving, the cache key is derived from the config being replaced
+            get_connection_manager().remove_server_clients(&server.id);
+        }
 
         self.spawn(
             ServerTask::UpdateOrInsertServer,
@@ -552,6 +912,9 @@ impl ZedisServerState {
                 if let Ok(servers) = result {
                     cx.emit(ServerEvent::ServerListUpdated);
                     this.servers = Some(servers);
+                    if should_reconnect {
+                        this.reconnect(cx);
+                    }
                 }
                 cx.notify();
             },
@@ -559,6 +922,34 @@ impl ZedisServerState {
         );
     }
 
+    /// Show a summary of what the user just connected to
+    fn notify_connected(&self, cx: &mut Context<Self>) {
+        let access_mode = match self.access_mode {
+            AccessMode::ReadWrite => i18n_common(cx, "access_mode_read_write"),
+            AccessMode::SafeMode => i18n_common(cx, "access_mode_safe_mode"),
+            AccessMode::StrictReadOnly => i18n_common(cx, "access_mode_strict_readonly"),
+        };
+        let name = self
+            .server(self.server_id.as_str())
+            .map(|server| server.name.clone())
+            .unwrap_or_else(|| self.server_id.to_string());
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let title = t!("common.connected_title", name = name, locale = locale).to_string();
+        let message = t!(
+            "common.connected_message",
+            server_type = self.nodes_description.server_type,
+            version = self.version,
+            masters = self.nodes.0,
+            replicas = self.nodes.1.saturating_sub(self.nodes.0),
+            access_mode = access_mode,
+            locale = locale
+        )
+        .to_string();
+        cx.emit(ServerEvent::Notification(
+            NotificationAction::new_success(message.into()).with_title(title.into()),
+        ));
+    }
+
     /// Select and connect to a Redis server
     ///
     /// This initiates a connection and loads server metadata:
@@ -576,9 +967,15 @@ impl ZedisServerState {
     pub fn select(&mut self, server_id: SharedString, db: usize, cx: &mut Context<Self>) {
         // Only proceed if selecting a different server
         if self.server_id != server_id || self.db != db {
+            // Switching db on the same server doesn't need the connection summary again
+            let server_changed = self.server_id != server_id;
+            self.save_last_key(cx);
             self.reset();
             self.server_id = server_id.clone();
             self.db = db;
+            if !server_changed {
+                self.remember_last_db(cx);
+            }
             let (query_mode, soft_wrap) = self
                 .server(server_id.as_str())
                 .map(|server_config| {
@@ -596,6 +993,14 @@ impl ZedisServerState {
                 .unwrap_or((QueryMode::All, true));
             self.query_mode = query_mode;
             self.soft_wrap = soft_wrap;
+            self.restore_key = cx
+                .global::<ZedisGlobalStore>()
+                .read(cx)
+                .last_key(&server_id)
+                .filter(|last_key| last_key.db == db)
+                .map(|last_key| last_key.key.clone().into());
+            // Editors kept open across the switch pick up the saved soft wrap of the new server
+            cx.emit(ServerEvent::SoftWrapToggled(soft_wrap));
 
             debug!(server_id = self.server_id.as_str(), "Selecting server");
             if let Ok(history) = HistoryManager::records(server_id.as_str()) {
@@ -607,69 +1012,321 @@ impl ZedisServerState {
             if self.server_id.is_empty() {
                 return;
             }
+            self.connect(server_changed, cx);
+        }
+    }
 
-            // Set loading state
-            self.server_status = RedisServerStatus::Loading;
-            self.scanning = true;
-            cx.notify();
+    /// Drop the cached connection of the current server and connect again
+    ///
+    /// Keys are re-scanned with the current keyword and the selected key is reloaded.
+    pub fn reconnect(&mut self, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() || self.is_busy() {
+            return;
+        }
+        get_connection_manager().remove_client(&self.server_id, self.db);
+        self.reconnecting = true;
+        self.connect(false, cx);
+    }
+
+    /// Add the host key to known_hosts and connect to the current server again
+    pub fn trust_host_key(&mut self, prompt: HostKeyPrompt, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        self.spawn(
+            ServerTask::TrustHostKey,
+            move || async move { trust_host_key(&prompt.host, prompt.port, &prompt.key) },
+            move |this, result, cx| {
+                if result.is_ok() && this.server_id == server_id && !this.is_busy() {
+                    this.connect(true, cx);
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Check if a user triggered reconnect is in progress
+    pub fn reconnecting(&self) -> bool {
+        self.reconnecting
+    }
+
+    /// Get the step the connection to the current server is at
+    pub fn connection_step(&self) -> Option<ConnectionStep> {
+        self.connection_step
+    }
+
+    /// Connect to the current server and load its metadata
+    fn connect(&mut self, server_changed: bool, cx: &mut Context<Self>) {
+        // Set loading state
+        self.server_status = RedisServerStatus::Loading;
+        self.scanning = true;
+        cx.notify();
+
+        let server_id_clone = self.server_id.clone();
+        let counting_server_id = server_id_clone.clone();
+        let db = self.db;
+        let task = if self.reconnecting {
+            ServerTask::Reconnect
+        } else {
+            ServerTask::SelectServer
+        };
 
-            let server_id_clone = self.server_id.clone();
-            let counting_server_id = server_id_clone.clone();
-            let db = self.db;
-
-            self.spawn(
-                ServerTask::SelectServer,
-                move || async move {
-                    let client = get_connection_manager().get_client(&server_id_clone, db).await?;
-
-                    // Gather server metadata
-                    let dbsize = client.dbsize().await?;
-                    let version = client.version().to_string();
-                    let nodes = client.nodes();
-                    let nodes_description = client.nodes_description();
-                    let supports_db_selection = client.supports_db_selection();
-                    let access_mode = client.access_mode();
-                    Ok((
-                        dbsize,
-                        nodes,
-                        nodes_description,
-                        version,
-                        supports_db_selection,
-                        access_mode,
-                    ))
-                },
-                move |this, result, cx| {
-                    // Ignore if user switched to a different server while loading
-                    if this.server_id != counting_server_id {
+        // Forward the connection steps reported by the background task to the UI
+        let (progress_tx, progress_rx) = smol::channel::unbounded::<ConnectionStep>();
+        let progress_server_id = self.server_id.clone();
+        cx.spawn(async move |handle, cx| {
+            while let Ok(step) = progress_rx.recv().await {
+                let _ = handle.update(cx, |this, cx| {
+                    if this.server_id != progress_server_id || !this.is_busy() {
                         return;
                     }
-
-                    // Update metadata if successful
-                    if let Ok((dbsize, nodes, nodes_description, version, supports_db_selection, access_mode)) = result
-                    {
-                        this.dbsize = Some(dbsize);
-                        this.nodes = nodes;
-                        this.nodes_description = Arc::new(nodes_description);
-                        this.version = version.into();
-                        this.supports_db_selection = supports_db_selection;
-                        this.access_mode = access_mode;
-                    };
-
-                    let server_id = this.server_id.clone();
-                    this.server_status = RedisServerStatus::Idle;
-                    cx.emit(ServerEvent::ServerInfoUpdated(server_id.clone()));
+                    this.connection_step = Some(step);
+                    cx.emit(ServerEvent::ConnectionProgress(step));
                     cx.notify();
+                });
+            }
+        })
+        .detach();
 
-                    // Auto-scan keys if in All mode
-                    if this.query_mode == QueryMode::All {
-                        this.scan_keys(server_id, SharedString::default(), cx);
-                    } else {
-                        this.scanning = false;
-                        cx.notify();
+        self.spawn(
+            task,
+            move || async move {
+                // Connect with progress, the retry below starts with the cached client
+                get_connection_manager()
+                    .get_client_with_progress(&server_id_clone, db, move |step| {
+                        let _ = progress_tx.try_send(step);
+                    })
+                    .await?;
+
+                // Gather server metadata
+                let (client, dbsize_per_master) = get_connection_manager()
+                    .with_client_retry(&server_id_clone, db, |client, _| async move {
+                        let dbsize_per_master = client.dbsize_per_master().await?;
+                        Ok((client, dbsize_per_master))
+                    })
+                    .await?;
+                let version = client.version().to_string();
+                let features = ServerFeatures {
+                    unlink: client.is_at_least_version(UNLINK_VERSION),
+                    copy: client.is_at_least_version(COPY_VERSION),
+                    hash_field_ttl: client.is_at_least_version(HASH_FIELD_TTL_VERSION),
+                };
+                let nodes = client.nodes();
+                let nodes_description = client.nodes_description();
+                let supports_db_selection = client.supports_db_selection();
+                let access_mode = client.access_mode();
+                let mut denied_commands = AHashSet::new();
+                for command in GATED_COMMANDS {
+                    if !client.can_run(command).await {
+                        denied_commands.insert(command);
                     }
-                },
-                cx,
-            );
+                }
+                Ok((
+                    dbsize_per_master,
+                    nodes,
+                    nodes_description,
+                    version,
+                    features,
+                    supports_db_selection,
+                    access_mode,
+                    denied_commands,
+                ))
+            },
+            move |this, result, cx| {
+                // Ignore if user switched to a different server while loading
+                if this.server_id != counting_server_id {
+                    return;
+                }
+
+                let reconnecting = std::mem::take(&mut this.reconnecting);
+                this.connection_step = None;
+                if let Err(Error::UnknownHostKey {
+                    host,
+                    port,
+                    fingerprint,
+                    key,
+                }) = &result
+                {
+                    cx.emit(ServerEvent::HostKeyPrompted(HostKeyPrompt {
+                        host: host.clone().into(),
+                        port: *port,
+                        fingerprint: fingerprint.clone().into(),
+                        key: key.clone().into(),
+                    }));
+                }
+                this.record_server_error(
+                    result.as_ref().err().map(|e| ServerError {
+                        message: e.to_string(),
+                        created_at: unix_ts(),
+                    }),
+                    cx,
+                );
+                // Update metadata if successful
+                if let Ok((
+                    dbsize_per_master,
+                    nodes,
+                    nodes_description,
+                    version,
+                    features,
+                    supports_db_selection,
+                    access_mode,
+                    denied_commands,
+                )) = result
+                {
+                    this.dbsize = Some(dbsize_per_master.iter().map(|(_, size)| size).sum());
+                    this.dbsize_per_master = Arc::new(
+                        dbsize_per_master
+                            .into_iter()
+                            .map(|(addr, size)| (addr.into(), size))
+                            .collect(),
+                    );
+                    this.nodes = nodes;
+                    this.nodes_description = Arc::new(nodes_description);
+                    this.version = version.into();
+                    this.features = features;
+                    this.supports_db_selection = supports_db_selection;
+                    this.access_mode = access_mode;
+                    this.denied_commands = denied_commands;
+                    if server_changed || reconnecting {
+                        this.notify_connected(cx);
+                    }
+                } else if reconnecting {
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_error(i18n_common(
+                        cx,
+                        "reconnect_failed",
+                    ))));
+                }
+
+                let server_id = this.server_id.clone();
+                this.server_status = RedisServerStatus::Idle;
+                cx.emit(ServerEvent::ServerInfoUpdated(server_id.clone()));
+                cx.notify();
+
+                // Restore the previous view on the fresh connection
+                if reconnecting {
+                    this.reload_after_reconnect(cx);
+                    return;
+                }
+
+                // Auto-scan keys if in All mode
+                if this.query_mode == QueryMode::All {
+                    this.scan_keys(server_id, SharedString::default(), cx);
+                } else {
+                    this.scanning = false;
+                    cx.notify();
+                    this.restore_last_key(cx);
+                }
+            },
+            cx,
+        );
+    }
+
+    /// Keep the last connection error of the current server for its card, `None` clears it
+    fn record_server_error(&self, error: Option<ServerError>, cx: &mut Context<Self>) {
+        // Servers connected from a URL have no card
+        if self.is_transient_server() {
+            return;
+        }
+        let server_id = self.server_id.to_string();
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        if error.is_none() && store.server_error(&server_id).is_none() {
+            return;
+        }
+        update_app_state_and_save(cx, "save_server_error", move |state, _cx| {
+            state.set_server_error(&server_id, error.clone());
+        });
+    }
+    /// Remember the selected key of the server, to open it again on the next connect.
+    ///
+    /// Only kept in memory, the app state is saved by `save_last_key` on server switch or quit.
+    fn remember_last_key(&mut self, key: &str) {
+        if self.is_transient_server() {
+            return;
+        }
+        self.last_key = Some(LastKey {
+            key: key.to_string(),
+            db: self.db,
+        });
+    }
+    /// Take the remembered key, `None` when the app state already has it
+    fn unsaved_last_key(&mut self, cx: &App) -> Option<(String, LastKey)> {
+        let last_key = self.last_key.take()?;
+        let server_id = self.server_id.to_string();
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        (store.last_key(&server_id) != Some(&last_key)).then_some((server_id, last_key))
+    }
+    /// Save the remembered key of the server to the app state
+    fn save_last_key(&mut self, cx: &mut Context<Self>) {
+        let Some((server_id, last_key)) = self.unsaved_last_key(cx) else {
+            return;
+        };
+        update_app_state_and_save(cx, "save_last_key", move |state, _cx| {
+            state.set_last_key(&server_id, Some(last_key.clone()));
+        });
+    }
+    /// Remember the db picked on the server, to connect to it again next time
+    fn remember_last_db(&self, cx: &mut Context<Self>) {
+        if self.is_transient_server() {
+            return;
+        }
+        let server_id = self.server_id.to_string();
+        // Picking the configured db again forgets the choice
+        let configured_db = self.server(&server_id).map(|server| server.default_db()).unwrap_or(0);
+        let last_db = (self.db != configured_db).then_some(self.db);
+        if cx.global::<ZedisGlobalStore>().read(cx).last_db(&server_id) == last_db {
+            return;
+        }
+        update_app_state_and_save(cx, "save_last_db", move |state, _cx| {
+            state.set_last_db(&server_id, last_db);
+        });
+    }
+    /// Open the key remembered for the server if it still exists, otherwise forget it
+    fn restore_last_key(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.restore_key.take() else {
+            return;
+        };
+        // The user already opened another key
+        if self.key.is_some() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let exists_key = key.clone();
+        self.spawn(
+            ServerTask::RestoreLastKey,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let exists: bool = cmd("EXISTS").arg(exists_key.as_str()).query_async(&mut conn).await?;
+                Ok(exists)
+            },
+            move |this, result, cx| match result {
+                Ok(true) if this.key.is_none() => this.select_key(key, cx),
+                Ok(false) => {
+                    let server_id = this.server_id.to_string();
+                    update_app_state_and_save(cx, "remove_last_key", move |state, _cx| {
+                        state.set_last_key(&server_id, None);
+                    });
+                }
+                _ => {}
+            },
+            cx,
+        );
+    }
+    /// Re-scan keys with the current keyword and reload the selected key
+    fn reload_after_reconnect(&mut self, cx: &mut Context<Self>) {
+        let keyword = self.keyword.clone();
+        let key = self.key.clone();
+        self.reset_scan();
+        self.scanning = false;
+        match self.query_mode {
+            QueryMode::All => self.scan(keyword, cx),
+            QueryMode::Pattern if !keyword.is_empty() => self.scan(keyword, cx),
+            QueryMode::Prefix if !keyword.is_empty() => self.scan_keyword_prefix(keyword, cx),
+            QueryMode::Exact if !keyword.is_empty() => self.select_key(keyword, cx),
+            _ => {}
+        }
+        if self.query_mode != QueryMode::Exact
+            && let Some(key) = key
+        {
+            self.select_key(key, cx);
         }
     }
 }
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::connection::ConnectionStep;
 use crate::helpers::EditorAction;
-use crate::states::{ErrorMessage, NotificationAction, ZedisServerState};
+use crate::states::{ErrorMessage, HostKeyPrompt, NotificationAction, PendingNavigation, ZedisServerState};
 use gpui::prelude::*;
 use gpui::{EventEmitter, SharedString};
 
@@ -28,6 +29,12 @@ pub enum ServerTask {
     /// Connect to and load metadata from a server
     SelectServer,
 
+    /// Drop the cached connection and connect to the current server again
+    Reconnect,
+
+    /// Add the host key of the SSH server to known_hosts
+    TrustHostKey,
+
     /// Remove a server from configuration
     RemoveServer,
 
@@ -55,6 +62,12 @@ pub enum ServerTask {
     /// Delete multiple keys from Redis
     DeleteKeys,
 
+    /// Delete the keys matching a pattern
+    DeleteMatchingKeys,
+
+    /// Remove every key with FLUSHDB or FLUSHALL
+    FlushDb,
+
     /// Scan for keys matching pattern
     ScanKeys,
 
@@ -63,6 +76,10 @@ pub enum ServerTask {
 
     /// Add a new key
     AddKey,
+    /// Rename a key (RENAME)
+    RenameKey,
+    /// Copy a key to a new name (COPY)
+    CopyKey,
     /// Update TTL (time-to-live) for a key
     UpdateKeyTtl,
 
@@ -75,9 +92,19 @@ pub enum ServerTask {
     /// Push a value to a list
     PushListValue,
 
+    /// Trim a list to a range
+    TrimList,
+
     /// Load more items
     LoadMoreValue,
 
+    /// Append an entry to a stream
+    AddStreamEntry,
+    /// Delete an entry from a stream
+    RemoveStreamEntry,
+    /// Trim a stream to a maximum length
+    TrimStream,
+
     /// Add a value to a set
     AddSetValue,
     /// Remove a value from a set
@@ -90,9 +117,17 @@ pub enum ServerTask {
 
     /// Remove a value from a hash
     RemoveHashValue,
+    /// Write many fields to a hash at once
+    ImportHashValues,
 
     /// Save edited value back to Redis
     SaveValue,
+
+    /// Kill clients idle beyond a threshold
+    KillIdleClients,
+
+    /// Check the key selected last on the server still exists
+    RestoreLastKey,
 }
 
 impl ServerTask {
@@ -101,6 +136,8 @@ impl ServerTask {
         match self {
             ServerTask::RefreshRedisInfo => "refresh_redis_info",
             ServerTask::SelectServer => "select_server",
+            ServerTask::Reconnect => "reconnect",
+            ServerTask::TrustHostKey => "trust_host_key",
             ServerTask::RemoveServer => "remove_server",
             ServerTask::UpdateOrInsertServer => "update_or_insert_server",
             ServerTask::FillKeyTypes => "fill_key_types",
@@ -108,9 +145,13 @@ impl ServerTask {
             ServerTask::DeleteKey => "delete_key",
             ServerTask::DeleteKeys => "delete_keys",
             ServerTask::DeleteFolder => "delete_folder",
+            ServerTask::DeleteMatchingKeys => "delete_matching_keys",
+            ServerTask::FlushDb => "flush_db",
             ServerTask::ScanKeys => "scan_keys",
             ServerTask::ScanPrefix => "scan_prefix",
             ServerTask::AddKey => "add_key",
+            ServerTask::RenameKey => "rename_key",
+            ServerTask::CopyKey => "copy_key",
             ServerTask::UpdateKeyTtl => "update_key_ttl",
             ServerTask::RemoveListValue => "remove_list_value",
             ServerTask::UpdateListValue => "update_list_value",
@@ -119,11 +160,18 @@ impl ServerTask {
             ServerTask::UpdateServerQueryMode => "update_server_query_mode",
             ServerTask::UpdateServerSoftWrap => "update_server_soft_wrap",
             ServerTask::PushListValue => "push_list_value",
+            ServerTask::TrimList => "trim_list",
+            ServerTask::AddStreamEntry => "add_stream_entry",
+            ServerTask::RemoveStreamEntry => "remove_stream_entry",
+            ServerTask::TrimStream => "trim_stream",
             ServerTask::AddSetValue => "add_set_value",
             ServerTask::RemoveSetValue => "remove_set_value",
             ServerTask::AddZsetValue => "add_zset_value",
             ServerTask::RemoveZsetValue => "remove_zset_value",
             ServerTask::RemoveHashValue => "remove_hash_value",
+            ServerTask::ImportHashValues => "import_hash_values",
+            ServerTask::KillIdleClients => "kill_idle_clients",
+            ServerTask::RestoreLastKey => "restore_last_key",
         }
     }
 }
@@ -149,11 +197,17 @@ pub enum ServerEvent {
     KeyScanFinished(SharedString),
     /// Key collapse all
     KeyCollapseAll,
+    /// Types of a batch of loaded keys have been prefetched
+    KeyTypesPrefetched(SharedString),
+    /// The keys matching a pattern have been deleted
+    MatchingKeysDeleted(SharedString),
 
     /// A key's value has been fetched (initial load).
     ValueLoaded(SharedString),
     /// A key's value has been updated
     ValueUpdated(SharedString),
+    /// A save of a string value finished, true when it was written
+    ValueSaveFinished(SharedString, bool),
     /// A key's value view mode has been updated
     ValueModeViewUpdated(SharedString),
     /// Load more value
@@ -165,6 +219,10 @@ pub enum ServerEvent {
 
     /// User selected a different server
     ServerSelected(SharedString, usize),
+    /// Connecting to the server moved on to another step
+    ConnectionProgress(ConnectionStep),
+    /// The SSH server is missing from known_hosts, waiting for the user to trust it
+    HostKeyPrompted(HostKeyPrompt),
     /// Server list config has been modified (add/remove/edit).
     ServerListUpdated,
     /// Server metadata (info/dbsize) has been refreshed.
@@ -181,6 +239,9 @@ pub enum ServerEvent {
 
     /// Trigger Action
     EditionActionTriggered(EditorAction),
+
+    /// A navigation is waiting for the user to save or discard value edits
+    UnsavedChangesPrompted(PendingNavigation),
 }
 
 impl EventEmitter<ServerEvent> for ZedisServerState {}
@@ -18,8 +18,10 @@
 //! - Loading HASH field-value pairs with pagination support via HSCAN
 //! - Adding/updating fields in a HASH (HSET)
 //! - Removing fields from a HASH (HDEL)
+//! - Importing many fields with a single HSET
 //! - Filtering HASH fields with pattern matching
 //! - Efficient incremental loading for large HASHes
+//! - Per-field TTL (HTTL/HEXPIRE/HPERSIST/HEXPIREAT) on Redis 7.4+
 
 use super::{
     KeyType, RedisValueData, ServerTask, ZedisServerState,
@@ -28,14 +30,21 @@ use super::{
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
-    states::{NotificationAction, ServerEvent, i18n_hash_editor},
+    helpers::unix_ts,
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore, i18n_hash_editor},
 };
+use ahash::AHashMap;
 use gpui::{SharedString, prelude::*};
 use redis::cmd;
+use rust_i18n::t;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Minimum Redis version supporting per-field TTL commands (HTTL, HEXPIRE, HPERSIST).
+pub const HASH_FIELD_TTL_VERSION: &str = "7.4.0";
+
 /// Type alias for HSCAN result: (cursor, vec of (field, value) pairs as bytes)
 type HashScanValue = (u64, Vec<(Vec<u8>, Vec<u8>)>);
 
@@ -93,6 +102,134 @@ async fn get_redis_hash_value(
     Ok((next_cursor, values))
 }
 
+/// Retrieves the absolute expiration timestamps of HASH fields using HTTL.
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The HASH key
+/// * `fields` - The fields to query
+///
+/// # Returns
+/// A map of field to expiration timestamp, fields without TTL are omitted
+async fn get_redis_hash_field_expire_at(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    fields: &[SharedString],
+) -> Result<AHashMap<SharedString, i64>> {
+    let mut expire_at = AHashMap::new();
+    if fields.is_empty() {
+        return Ok(expire_at);
+    }
+    let mut ttl_cmd = cmd("HTTL");
+    ttl_cmd.arg(key).arg("FIELDS").arg(fields.len());
+    for field in fields {
+        ttl_cmd.arg(field.as_str());
+    }
+    // -1: field has no TTL, -2: field does not exist
+    let ttls: Vec<i64> = ttl_cmd.query_async(conn).await?;
+    let now = unix_ts();
+    for (field, ttl) in fields.iter().zip(ttls) {
+        if ttl >= 0 {
+            expire_at.insert(field.clone(), now + ttl);
+        }
+    }
+    Ok(expire_at)
+}
+
+/// Parses the TTL of a HASH field given in whole seconds.
+///
+/// An empty string means the TTL should be removed and yields `None`.
+fn parse_field_ttl(ttl: &str) -> Result<Option<u64>> {
+    let ttl = ttl.trim();
+    if ttl.is_empty() {
+        return Ok(None);
+    }
+    match ttl.parse::<u64>() {
+        Ok(seconds) if seconds > 0 => Ok(Some(seconds)),
+        _ => Err(Error::Invalid {
+            message: format!("Invalid TTL: {ttl}, expected a positive number of seconds"),
+        }),
+    }
+}
+
+/// Sets or clears the TTL of a HASH field (Redis 7.4+).
+///
+/// Uses HEXPIRE when seconds are given and HPERSIST when `seconds` is `None`.
+///
+/// # Arguments
+/// * `conn` - Redis async connection
+/// * `key` - The HASH key
+/// * `field` - The field to update
+/// * `seconds` - The TTL in seconds, `None` to remove the TTL
+async fn set_redis_hash_field_ttl(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    field: &SharedString,
+    seconds: Option<u64>,
+) -> Result<()> {
+    if let Some(seconds) = seconds {
+        let _: Vec<i64> = cmd("HEXPIRE")
+            .arg(key)
+            .arg(seconds)
+            .arg("FIELDS")
+            .arg(1)
+            .arg(field.as_str())
+            .query_async(conn)
+            .await?;
+    } else {
+        let _: Vec<i64> = cmd("HPERSIST")
+            .arg(key)
+            .arg("FIELDS")
+            .arg(1)
+            .arg(field.as_str())
+            .query_async(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Gets when a HASH field expires, as a Unix timestamp in seconds (Redis 7.4+).
+///
+/// Returns `None` when the field has no TTL or doesn't exist.
+async fn get_redis_hash_field_expire_time(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    field: &SharedString,
+) -> Result<Option<i64>> {
+    // -1: field has no TTL, -2: field does not exist
+    let times: Vec<i64> = cmd("HEXPIRETIME")
+        .arg(key)
+        .arg("FIELDS")
+        .arg(1)
+        .arg(field.as_str())
+        .query_async(conn)
+        .await?;
+    Ok(times.first().copied().filter(|time| *time > 0))
+}
+
+/// Puts back a HASH field TTL read by `get_redis_hash_field_expire_time` before HSET cleared it.
+///
+/// Nothing is done when the time has passed meanwhile, HEXPIREAT would delete the field.
+async fn restore_redis_hash_field_expire_time(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    field: &SharedString,
+    expire_time: i64,
+) -> Result<()> {
+    if expire_time <= unix_ts() {
+        return Ok(());
+    }
+    let _: Vec<i64> = cmd("HEXPIREAT")
+        .arg(key)
+        .arg(expire_time)
+        .arg("FIELDS")
+        .arg(1)
+        .arg(field.as_str())
+        .query_async(conn)
+        .await?;
+    Ok(())
+}
+
 /// Performs initial load of a Redis HASH value.
 ///
 /// Fetches the total number of fields (HLEN) and loads the first batch of field-value
@@ -101,16 +238,28 @@ async fn get_redis_hash_value(
 /// # Arguments
 /// * `conn` - Redis async connection
 /// * `key` - The HASH key to load
+/// * `with_field_ttl` - Whether to load per-field TTLs (Redis 7.4+)
 ///
 /// # Returns
 /// A `RedisValue` containing HASH metadata and initial field-value pairs
-pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
+pub(crate) async fn first_load_hash_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    with_field_ttl: bool,
+) -> Result<RedisValue> {
     // Get total number of fields in the HASH
     let size: usize = cmd("HLEN").arg(key).query_async(conn).await?;
 
     // Load first batch of field-value pairs (up to 100)
     let (cursor, values) = get_redis_hash_value(conn, key, None, 0, 100).await?;
 
+    let field_expire_at = if with_field_ttl {
+        let fields: Vec<SharedString> = values.iter().map(|(field, _)| field.clone()).collect();
+        get_redis_hash_field_expire_at(conn, key, &fields).await?
+    } else {
+        AHashMap::new()
+    };
+
     // If cursor is 0, all values have been loaded in one iteration
     let done = cursor == 0;
 
@@ -121,6 +270,7 @@ pub(crate) async fn first_load_hash_value(conn: &mut RedisAsyncConn, key: &str)
             size,
             values,
             done,
+            field_expire_at,
             ..Default::default()
         }))),
         ..Default::default()
@@ -138,20 +288,35 @@ impl ZedisServerState {
     /// * `new_value` - The value to set for the field
     /// * `cx` - GPUI context for spawning async tasks and UI updates
     pub fn add_hash_value(&mut self, new_field: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
-        self.add_or_update_hash_value(new_field, new_value, cx);
+        self.add_or_update_hash_value(new_field, new_value, None, cx);
     }
     /// Updates a field-value pair in the Redis HASH.
     ///
-    /// Uses HSET command to update the value of the specified field.
+    /// Uses HSET command to update the value of the specified field. When `ttl`
+    /// is provided, the field TTL is updated afterwards with HEXPIRE/HPERSIST,
+    /// otherwise the TTL HSET clears is put back with HEXPIREAT.
     ///
     /// # Arguments
     /// * `new_field` - The field name to update
     /// * `new_value` - The value to set for the field
+    /// * `ttl` - New field TTL (empty to remove it), `None` to leave it untouched
     /// * `cx` - GPUI context for spawning async tasks and UI updates
-    pub fn update_hash_value(&mut self, new_field: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
-        self.add_or_update_hash_value(new_field, new_value, cx);
+    pub fn update_hash_value(
+        &mut self,
+        new_field: SharedString,
+        new_value: SharedString,
+        ttl: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        self.add_or_update_hash_value(new_field, new_value, ttl, cx);
     }
-    fn add_or_update_hash_value(&mut self, new_field: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+    fn add_or_update_hash_value(
+        &mut self,
+        new_field: SharedString,
+        new_value: SharedString,
+        ttl: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
         // Early return if no key/value is selected
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
@@ -166,12 +331,21 @@ impl ZedisServerState {
         let key_clone = key.clone();
         let new_field_clone = new_field.clone();
         let new_value_clone = new_value.clone();
+        let with_field_ttl = self.features.hash_field_ttl;
 
         self.spawn(
             ServerTask::AddSetValue,
             // Async operation: execute HSET on Redis
             move || async move {
+                // Validate the TTL first so a bad one doesn't leave the value half updated
+                let ttl = ttl.map(|ttl| parse_field_ttl(&ttl)).transpose()?;
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                // HSET clears the TTL of an existing field, remember it to put it back
+                let kept_expire_time = if with_field_ttl && ttl.is_none() {
+                    get_redis_hash_field_expire_time(&mut conn, &key, &new_field).await?
+                } else {
+                    None
+                };
 
                 // HSET returns 1 if new field created, 0 if existing field updated
                 let count: usize = cmd("HSET")
@@ -180,7 +354,20 @@ impl ZedisServerState {
                     .arg(new_value.as_str())
                     .query_async(&mut conn)
                     .await?;
-                Ok(count)
+                if let Some(seconds) = ttl {
+                    set_redis_hash_field_ttl(&mut conn, &key, &new_field, seconds).await?;
+                } else if let Some(expire_time) = kept_expire_time {
+                    restore_redis_hash_field_expire_time(&mut conn, &key, &new_field, expire_time).await?;
+                }
+                // Read the TTL back, it may have passed while the value was written
+                let expire_at = if with_field_ttl {
+                    let expire_at =
+                        get_redis_hash_field_expire_at(&mut conn, &key, std::slice::from_ref(&new_field)).await?;
+                    Some(expire_at.get(&new_field).copied())
+                } else {
+                    None
+                };
+                Ok((count, expire_at))
             },
             // UI callback: handle result and update local state
             move |this, result, cx| {
@@ -191,11 +378,22 @@ impl ZedisServerState {
                 if let Some(value) = this.value.as_mut() {
                     value.status = RedisValueStatus::Idle;
 
-                    if let Ok(count) = result
+                    if let Ok((count, expire_at)) = result
                         && let Some(RedisValueData::Hash(hash_data)) = value.data.as_mut()
                     {
                         let hash = Arc::make_mut(hash_data);
 
+                        // Refresh the field TTL read back after HSET
+                        match expire_at {
+                            Some(Some(expire_at)) => {
+                                hash.field_expire_at.insert(new_field_clone.clone(), expire_at);
+                            }
+                            Some(None) => {
+                                hash.field_expire_at.remove(&new_field_clone);
+                            }
+                            None => {}
+                        }
+
                         // Increment size only if new field was created
                         hash.size += count;
 
@@ -228,6 +426,70 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Writes many field-value pairs to the Redis HASH with a single HSET.
+    ///
+    /// Values are sent as the given strings, nothing is converted, so `007` or `1e3`
+    /// are stored exactly as written. Fields must be unique, HSET would silently keep
+    /// the last value of a repeated field. The first page of the HASH is reloaded
+    /// afterwards so the imported fields show up.
+    ///
+    /// # Arguments
+    /// * `pairs` - The field-value pairs to write
+    /// * `cx` - GPUI context for spawning async tasks and UI updates
+    pub fn import_hash_values(&mut self, pairs: Vec<(SharedString, SharedString)>, cx: &mut Context<Self>) {
+        if pairs.is_empty() {
+            return;
+        }
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let with_field_ttl = self.features.hash_field_ttl;
+        let key_clone = key.clone();
+        let written = pairs.len();
+
+        self.spawn(
+            ServerTask::ImportHashValues,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+
+                // HSET returns the number of fields created, the rest were updated
+                let mut hset = cmd("HSET");
+                hset.arg(key.as_str());
+                for (field, value) in pairs.iter() {
+                    hset.arg(field.as_str()).arg(value.as_str());
+                }
+                let created: usize = hset.query_async(&mut conn).await?;
+                let value = first_load_hash_value(&mut conn, &key, with_field_ttl).await?;
+                Ok((created, value))
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((created, new_value)) = result {
+                        value.data = new_value.data;
+                        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                        let msg = t!(
+                            "hash_editor.import_success",
+                            count = written,
+                            created = created,
+                            locale = locale
+                        )
+                        .to_string();
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_success(msg.into())));
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
     /// Applies a filter to HASH fields by resetting the scan state with a keyword.
     ///
     /// Creates a new HASH value state with the filter keyword and triggers a load.
@@ -251,6 +513,10 @@ impl ZedisServerState {
             ..Default::default()
         };
         value.data = Some(RedisValueData::Hash(Arc::new(new_hash)));
+        // A new filter starts a new load, the batches of the previous one are dropped
+        value.load_cancel.store(true, Ordering::Relaxed);
+        value.load_cancel = Arc::default();
+        value.status = RedisValueStatus::Idle;
 
         // Trigger load with the new filter
         self.load_more_hash_value(cx);
@@ -330,6 +596,11 @@ impl ZedisServerState {
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        // A canceled load stays stopped until the filter changes
+        if value.is_load_canceled() {
+            return;
+        }
+        let cancel = value.load_cancel.clone();
 
         // Update UI to show loading state
         value.status = RedisValueStatus::Loading;
@@ -343,6 +614,7 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
+        let with_field_ttl = self.features.hash_field_ttl;
         cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
 
         let key_clone = key.clone();
@@ -356,12 +628,23 @@ impl ZedisServerState {
                 // Use larger batch size when filtering to reduce round trips
                 let count = if keyword.is_some() { 1000 } else { 100 };
 
-                get_redis_hash_value(&mut conn, &key, keyword, cursor, count).await
+                let (new_cursor, new_values) = get_redis_hash_value(&mut conn, &key, keyword, cursor, count).await?;
+                let field_expire_at = if with_field_ttl {
+                    let fields: Vec<SharedString> = new_values.iter().map(|(field, _)| field.clone()).collect();
+                    get_redis_hash_field_expire_at(&mut conn, &key, &fields).await?
+                } else {
+                    AHashMap::new()
+                };
+                Ok((new_cursor, new_values, field_expire_at))
             },
             // UI callback: merge results into local state
             move |this, result, cx| {
+                // Drop the batch of a canceled load, the rows loaded before stay
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
                 let mut should_load_more = false;
-                if let Ok((new_cursor, new_values)) = result
+                if let Ok((new_cursor, new_values, field_expire_at)) = result
                     && let Some(RedisValueData::Hash(hash_data)) = this.value.as_mut().and_then(|v| v.data.as_mut())
                 {
                     let hash = Arc::make_mut(hash_data);
@@ -376,6 +659,7 @@ impl ZedisServerState {
                     if !new_values.is_empty() {
                         hash.values.extend(new_values);
                     }
+                    hash.field_expire_at.extend(field_expire_at);
                     if !hash.done && hash.values.len() < 50 {
                         should_load_more = true;
                     }
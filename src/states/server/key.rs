@@ -13,30 +13,44 @@
 // limitations under the License.
 
 use super::{
-    ServerEvent, ServerTask, ZedisServerState,
+    KeyTypePrefetchCost, ServerEvent, ServerTask, ZedisServerState,
     hash::first_load_hash_value,
     list::first_load_list_value,
     set::first_load_set_value,
-    string::get_redis_bytes_value,
-    value::{KeyType, RedisValue, RedisValueData, RedisValueStatus, SortOrder},
+    stream::first_load_stream_value,
+    string::{MAX_STRING_LOAD_BYTES, get_redis_bytes_value},
+    value::{DeleteCommand, KeyType, RedisValue, RedisValueData, RedisValueStatus, SortOrder},
     zset::first_load_zset_value,
 };
 use crate::db::add_normalize_history;
-use crate::states::ZedisGlobalStore;
+use crate::states::{NotificationAction, ZedisGlobalStore, i18n_key_tree};
 use crate::{
-    connection::{QueryMode, get_connection_manager},
+    connection::{AccessMode, QueryMode, RedisAsyncConn, get_connection_manager},
     error::Error,
-    helpers::{parse_duration, unix_ts},
+    helpers::unix_ts,
 };
-use futures::{StreamExt, stream};
-use gpui::{SharedString, prelude::*};
-use redis::{cmd, pipe};
+use futures::{StreamExt, future, stream};
+use gpui::{App, SharedString, prelude::*};
+use redis::{Value, cmd, pipe};
+use rust_i18n::t;
+use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Duration;
-use tracing::debug;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 use uuid::Uuid;
 
-const DEFAULT_SCAN_RESULT_MAX: usize = 1_000;
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Version UNLINK was added in
+pub(crate) const UNLINK_VERSION: &str = "4.0.0";
+/// Version COPY was added in
+pub(crate) const COPY_VERSION: &str = "6.2.0";
+/// Number of delete commands sent in one pipeline when deleting a folder
+const DELETE_FOLDER_BATCH_SIZE: usize = 1_000;
+/// SCAN count of a delete by pattern, each round's keys are removed in one pipeline per master
+const DELETE_MATCHING_SCAN_COUNT: u64 = 1_000;
+/// Keys typed at most in one go for the type filter of the key tree
+const FILTER_TYPES_MAX_KEYS: usize = 10_000;
 
 impl ZedisServerState {
     /// Fills the type of keys that are currently loaded but have an unknown type.
@@ -49,8 +63,13 @@ impl ZedisServerState {
         let prefix = binding.as_str();
         let count = self.keys.len();
         let binding = cx.global::<ZedisGlobalStore>().value(cx);
+        // Types load lazily on selection when prefetch is disabled
+        if !binding.prefetch_key_types() {
+            return;
+        }
+        let batch_size = binding.prefetch_batch_size().max(1);
         let separator = binding.key_separator();
-        let mut keys = self
+        let keys = self
             .keys
             .iter()
             .filter_map(|(key, value)| {
@@ -75,46 +94,98 @@ impl ZedisServerState {
             .take(2000)
             .collect::<Vec<SharedString>>();
         debug!(prefix, size = keys.len(), "fill key types");
+        self.fetch_key_types(keys, batch_size, cx);
+    }
+    /// Fills the types of every loaded key with an unknown type, whatever its folder.
+    ///
+    /// The type filter of the key tree can only hide a key once its type is known,
+    /// so this runs even when the prefetch is disabled.
+    pub fn fill_all_key_types(&mut self, cx: &mut Context<Self>) {
+        let batch_size = cx.global::<ZedisGlobalStore>().value(cx).prefetch_batch_size().max(1);
+        let keys = self
+            .keys
+            .iter()
+            .filter(|(_, value)| **value == KeyType::Unknown)
+            .map(|(key, _)| key.clone())
+            .take(FILTER_TYPES_MAX_KEYS)
+            .collect::<Vec<SharedString>>();
+        debug!(size = keys.len(), "fill all key types");
+        self.fetch_key_types(keys, batch_size, cx);
+    }
+    /// Fetches the types and TTLs of `keys` with pipelined TYPE and TTL commands
+    fn fetch_key_types(&mut self, mut keys: Vec<SharedString>, batch_size: usize, cx: &mut Context<Self>) {
         if keys.is_empty() {
             return;
         }
         let server_id = self.server_id.clone();
         let db = self.db;
         keys.sort_unstable();
-        // Spawn a background task to fetch types concurrently
+        let emit_server_id = server_id.clone();
+        // Spawn a background task to fetch types in pipelined batches
         self.spawn(
             ServerTask::FillKeyTypes,
             move || async move {
                 let conn = get_connection_manager().get_connection(&server_id, db).await?;
-                // Use a stream to execute commands concurrently with backpressure
-                let types: Vec<(SharedString, String)> = stream::iter(keys.iter().cloned())
-                    .map(|key| {
+                let started_at = Instant::now();
+                let batches = keys.chunks(batch_size).map(|batch| batch.to_vec()).collect::<Vec<_>>();
+                let round_trips = batches.len();
+                // Use a stream to execute pipelines concurrently with backpressure
+                let types: Vec<(SharedString, String, i64)> = stream::iter(batches)
+                    .map(|batch| {
                         let mut conn_clone = conn.clone();
-                        let key = key.clone();
                         async move {
-                            let t: String = cmd("TYPE")
-                                .arg(key.as_str())
-                                .query_async(&mut conn_clone)
-                                .await
-                                .unwrap_or_default();
-                            (key, t)
+                            let mut pipeline = pipe();
+                            for key in batch.iter() {
+                                pipeline.cmd("TYPE").arg(key.as_str()).cmd("TTL").arg(key.as_str());
+                            }
+                            // Replies alternate between a type and a TTL, integers read as strings too
+                            // A failed batch only leaves its keys without a type, the others still apply
+                            let replies: Vec<String> = match pipeline.query_async(&mut conn_clone).await {
+                                Ok(replies) => replies,
+                                Err(e) => {
+                                    warn!(error = %e, keys = batch.len(), "prefetch key types failed");
+                                    vec![]
+                                }
+                            };
+                            batch
+                                .into_iter()
+                                .zip(replies.chunks_exact(2))
+                                .map(|(key, reply)| (key, reply[0].clone(), reply[1].parse().unwrap_or(-1)))
+                                .collect::<Vec<_>>()
                         }
                     })
-                    .buffer_unordered(100) // Limit concurrency to 100
+                    .buffer_unordered(10) // Limit concurrency to 10 pipelines
+                    .flat_map(stream::iter)
                     .collect::<Vec<_>>()
                     .await;
-                Ok(types)
+                let cost = KeyTypePrefetchCost {
+                    commands: keys.len() * 2,
+                    round_trips,
+                    elapsed: started_at.elapsed(),
+                };
+                Ok((types, cost))
             },
             move |this, result, cx| {
-                if let Ok(types) = result {
-                    // Update local state with fetched types
-                    for (key, value) in types {
+                if let Ok((types, cost)) = result {
+                    // Update local state with fetched types and TTLs
+                    let key_expire_at = Arc::make_mut(&mut this.key_expire_at);
+                    let now = unix_ts();
+                    for (key, value, ttl) in types {
                         if let Some(k) = this.keys.get_mut(&key) {
                             *k = KeyType::from(value.as_str());
+                            if ttl >= 0 {
+                                key_expire_at.insert(key, now + ttl);
+                            } else {
+                                key_expire_at.remove(&key);
+                            }
                         }
                     }
+                    this.key_type_prefetch_cost.commands += cost.commands;
+                    this.key_type_prefetch_cost.round_trips += cost.round_trips;
+                    this.key_type_prefetch_cost.elapsed += cost.elapsed;
                     // Trigger UI update by changing the tree ID
                     this.key_tree_id = Uuid::now_v7().to_string().into();
+                    cx.emit(ServerEvent::KeyTypesPrefetched(emit_server_id));
                 }
                 cx.notify();
             },
@@ -131,28 +202,46 @@ impl ZedisServerState {
             return;
         }
         let cursors = self.cursors.clone();
-        // Calculate max limit based on scan times to prevent infinite scrolling from loading too much
-        let max = (self.scan_times + 1) * DEFAULT_SCAN_RESULT_MAX;
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let scan_batch_count = store.scan_batch_count();
+        // Auto-scanning stops at the initial keys, each scan more raises it by a batch
+        let max = store.max_initial_keys() + self.scan_times * scan_batch_count;
 
         let processing_server = server_id.clone();
         let processing_keyword = keyword.clone();
         let db = self.db;
+        let escape = cx.global::<ZedisGlobalStore>().read(cx).escape_glob_keyword();
+        // A raw pattern is passed to MATCH as typed, other keywords are searched as contained
+        let query_mode = if self.query_mode == QueryMode::Pattern {
+            QueryMode::Pattern
+        } else {
+            QueryMode::All
+        };
+        let type_filter = self.key_type_filter;
         self.spawn(
             ServerTask::ScanKeys,
             move || async move {
-                let client = get_connection_manager().get_client(&server_id, db).await?;
-                let pattern = if keyword.is_empty() {
-                    "*".to_string()
-                } else {
-                    format!("*{}*", keyword)
-                };
+                let pattern = query_mode.scan_pattern(&keyword, escape);
                 // Adjust count based on keyword specificity
-                let count = if keyword.is_empty() { 2_000 } else { 10_000 };
-                if let Some(cursors) = cursors {
-                    client.scan(cursors, &pattern, count).await
+                let count = if keyword.is_empty() {
+                    scan_batch_count
                 } else {
-                    client.first_scan(&pattern, count).await
-                }
+                    scan_batch_count * 5
+                } as u64;
+                get_connection_manager()
+                    .with_client_retry(&server_id, db, |client, retried| {
+                        // Restart the scan on a new client, its nodes may have changed
+                        let cursors = if retried { None } else { cursors.clone() };
+                        let pattern = pattern.clone();
+                        async move {
+                            if let Some(cursors) = cursors {
+                                client.scan(cursors, &pattern, count, type_filter).await
+                            } else {
+                                client.first_scan(&pattern, count, type_filter).await
+                            }
+                        }
+                    })
+                    .await
             },
             move |this, result, cx| {
                 match result {
@@ -166,7 +255,7 @@ impl ZedisServerState {
                         } else {
                             this.cursors = Some(cursors);
                         }
-                        this.extend_keys(keys);
+                        this.extend_keys(keys, type_filter.unwrap_or_default());
                     }
                     Err(_) => {
                         this.cursors = None;
@@ -176,14 +265,18 @@ impl ZedisServerState {
                     cx.emit(ServerEvent::KeyScanPaged(processing_keyword.clone()));
                 }
                 // Automatically load more if we haven't reached the limit and scan isn't done
-                if this.cursors.is_some() && this.keys.len() < max {
+                if this.cursors.is_some() && this.keys.len() < max && !this.loaded_keys_limit_reached() {
                     // run again
                     this.scan_keys(processing_server, processing_keyword, cx);
                     return cx.notify();
                 }
                 this.scanning = false;
                 cx.notify();
-                if this.keys.len() == 1
+                // The key open when the server was used last wins over picking the only key
+                if this.restore_key.is_some() {
+                    this.restore_last_key(cx);
+                    this.fill_key_types(None, cx);
+                } else if this.keys.len() == 1
                     && let Some(key) = this.keys.keys().next()
                 {
                     this.select_key(key.clone(), cx);
@@ -195,12 +288,20 @@ impl ZedisServerState {
         );
     }
     pub fn handle_filter(&mut self, keyword: SharedString, cx: &mut Context<Self>) {
+        if self.query_mode == QueryMode::Pattern && keyword.trim().is_empty() {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!("key_tree.pattern_empty", locale = locale).to_string();
+            cx.emit(ServerEvent::Notification(NotificationAction::new_warning(
+                message.into(),
+            )));
+            return;
+        }
         self.reset_scan();
         if !keyword.is_empty() {
             add_normalize_history(&mut self.search_history, keyword.clone());
         }
         match self.query_mode {
-            QueryMode::Prefix => self.scan_prefix(keyword, cx),
+            QueryMode::Prefix => self.scan_keyword_prefix(keyword, cx),
             QueryMode::Exact => self.select_key(keyword, cx),
             _ => self.scan(keyword, cx),
         }
@@ -214,6 +315,7 @@ impl ZedisServerState {
         self.reset_scan();
         self.scanning = true;
         self.keyword = keyword.clone();
+        self.loaded_keys_limit = cx.global::<ZedisGlobalStore>().read(cx).max_loaded_keys();
         cx.emit(ServerEvent::KeyScanStarted(keyword.clone()));
         cx.notify();
         self.scan_keys(self.server_id.clone(), keyword, cx);
@@ -224,13 +326,28 @@ impl ZedisServerState {
             return;
         }
         self.scan_times += 1;
+        // Loading more past the limit is explicit, so allow another full batch of keys
+        if self.loaded_keys_limit_reached() {
+            self.loaded_keys_limit = self.keys.len() + cx.global::<ZedisGlobalStore>().read(cx).max_loaded_keys();
+        }
         self.scan_keys(self.server_id.clone(), self.keyword.clone(), cx);
         cx.notify();
     }
+    /// Scans keys under a folder of the key tree.
+    ///
+    /// The prefix is a literal part of key names, so it is always glob-escaped.
+    pub fn scan_prefix(&mut self, prefix: SharedString, cx: &mut Context<Self>) {
+        self.scan_prefix_pattern(prefix, true, cx);
+    }
+    /// Scans keys starting with the searched keyword, escaped when the setting asks for it.
+    pub(crate) fn scan_keyword_prefix(&mut self, keyword: SharedString, cx: &mut Context<Self>) {
+        let escape = cx.global::<ZedisGlobalStore>().read(cx).escape_glob_keyword();
+        self.scan_prefix_pattern(keyword, escape, cx);
+    }
     /// Scans keys matching a specific prefix.
     ///
     /// Optimized for populating directory-like structures in the key view.
-    pub fn scan_prefix(&mut self, prefix: SharedString, cx: &mut Context<Self>) {
+    fn scan_prefix_pattern(&mut self, prefix: SharedString, escape: bool, cx: &mut Context<Self>) {
         // Avoid reloading if already loaded
         let mut key_type_full_loaded = false;
         let mut key_full_loaded = false;
@@ -260,12 +377,12 @@ impl ZedisServerState {
 
         let server_id = self.server_id.clone();
         let db = self.db;
-        let pattern = format!("{}*", prefix);
+        let pattern = QueryMode::Prefix.scan_pattern(&prefix, escape);
+        let type_filter = self.key_type_filter;
+        let count = cx.global::<ZedisGlobalStore>().read(cx).scan_batch_count() as u64 * 5;
         self.spawn(
             ServerTask::ScanPrefix,
             move || async move {
-                let client = get_connection_manager().get_client(&server_id, db).await?;
-                let count = 10_000;
                 // let mut cursors: Option<Vec<u64>>,
                 let mut cursors: Option<Vec<u64>> = None;
                 let mut result_keys = vec![];
@@ -273,11 +390,20 @@ impl ZedisServerState {
                 // Attempt to fetch keys in a loop (up to 20 iterations)
                 // to gather a sufficient amount without blocking for too long.
                 for _ in 0..20 {
-                    let (new_cursor, keys) = if let Some(cursors) = cursors.clone() {
-                        client.scan(cursors, &pattern, count).await?
-                    } else {
-                        client.first_scan(&pattern, count).await?
-                    };
+                    let (new_cursor, keys) = get_connection_manager()
+                        .with_client_retry(&server_id, db, |client, retried| {
+                            // Restart the scan on a new client, its nodes may have changed
+                            let cursors = if retried { None } else { cursors.clone() };
+                            let pattern = pattern.clone();
+                            async move {
+                                if let Some(cursors) = cursors {
+                                    client.scan(cursors, &pattern, count, type_filter).await
+                                } else {
+                                    client.first_scan(&pattern, count, type_filter).await
+                                }
+                            }
+                        })
+                        .await?;
                     result_keys.extend(keys);
                     // Break if scan cycle finishes
                     if new_cursor.iter().sum::<u64>() == 0 {
@@ -300,7 +426,7 @@ impl ZedisServerState {
                     if done {
                         this.loaded_prefixes.insert(prefix.clone());
                     }
-                    this.extend_keys(keys);
+                    this.extend_keys(keys, type_filter.unwrap_or_default());
                 }
                 cx.notify();
                 // Resolve types for the keys under this prefix
@@ -318,7 +444,25 @@ impl ZedisServerState {
     }
 
     /// Selects a key and fetches its details (Type, TTL, Value).
+    ///
+    /// String values larger than `MAX_STRING_LOAD_BYTES` are only loaded partially.
     pub fn select_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+        if !key.is_empty() {
+            self.remember_last_key(&key);
+        }
+        self.load_key_value(key, Some(MAX_STRING_LOAD_BYTES), cx);
+    }
+    /// Reloads the selected key without capping the bytes of a string value.
+    pub fn load_full_value(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        self.load_key_value(key, None, cx);
+    }
+    fn load_key_value(&mut self, key: SharedString, max_load_bytes: Option<usize>, cx: &mut Context<Self>) {
+        if self.key.as_ref() != Some(&key) {
+            self.reset_soft_wrap_override(cx);
+        }
         self.key = Some(key.clone());
         if key.is_empty() {
             return;
@@ -340,62 +484,40 @@ impl ZedisServerState {
         let server_id = self.server_id.clone();
         let db = self.db;
         let current_key = key.clone();
-        let max_truncate_length = cx.global::<ZedisGlobalStore>().read(cx).max_truncate_length();
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let options = LoadValueOptions {
+            max_truncate_length: store.max_truncate_length(),
+            proto_raw_json: store.proto_raw_json(),
+            with_field_ttl: self.features.hash_field_ttl,
+            max_load_bytes,
+        };
+        // Type already resolved by the key tree, the value is fetched along with the metadata
+        let cached_key_type = self
+            .keys
+            .get(&key)
+            .copied()
+            .filter(|key_type| *key_type != KeyType::Unknown);
 
         self.spawn(
             ServerTask::Selectkey,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let (t, ttl): (String, i64) = pipe()
-                    .cmd("TYPE")
-                    .arg(key.as_str())
-                    .cmd("TTL")
-                    .arg(key.as_str())
-                    .query_async(&mut conn)
-                    .await?;
-                // the key does not exist
-                if ttl == -2 {
-                    return Ok(RedisValue {
-                        expire_at: Some(-2),
-                        ..Default::default()
-                    });
-                }
-                // Calculate absolute expiration timestamp
-                let expire_at = match ttl {
-                    -1 => Some(-1), // Persistent
-                    t if t >= 0 => Some(unix_ts() + t),
-                    _ => None,
+                let redis_value = match cached_key_type {
+                    Some(key_type) => load_cached_type_value(&mut conn, &server_id, &key, key_type, &options).await,
+                    None => match load_redis_value(&mut conn, &server_id, &key, &options).await {
+                        // Another client replaced the key with a different type while it was loading
+                        Err(e) if is_wrong_type(&e) => load_redis_value(&mut conn, &server_id, &key, &options).await,
+                        redis_value => redis_value,
+                    },
                 };
-
-                let key_type = KeyType::from(t.as_str());
-                let mut redis_value = match key_type {
-                    KeyType::String => {
-                        let mut data = get_redis_bytes_value(&mut conn, &key).await?;
-                        data.detect_and_update(server_id.as_str(), key.as_str(), max_truncate_length);
-                        Ok(RedisValue {
-                            key_type: KeyType::String,
-                            data: Some(RedisValueData::Bytes(Arc::new(data))),
-                            ..Default::default()
-                        })
-                    }
-                    KeyType::List => first_load_list_value(&mut conn, &key).await,
-                    KeyType::Set => first_load_set_value(&mut conn, &key).await,
-                    KeyType::Zset => first_load_zset_value(&mut conn, &key, SortOrder::Asc).await,
-                    KeyType::Hash => first_load_hash_value(&mut conn, &key).await,
-                    _ => Err(Error::Invalid {
-                        message: "unsupported key type".to_string(),
-                    }),
-                }?;
-                if let Ok(memory_usage) = cmd("MEMORY")
+                let mut redis_value = redis_value?;
+                // Older servers and some cluster proxies don't know MEMORY USAGE
+                redis_value.memory_usage = cmd("MEMORY")
                     .arg("USAGE")
                     .arg(key.as_str())
                     .query_async::<u64>(&mut conn)
                     .await
-                {
-                    redis_value.size = memory_usage;
-                }
-
-                redis_value.expire_at = expire_at;
+                    .ok();
 
                 Ok(redis_value)
             },
@@ -405,14 +527,20 @@ impl ZedisServerState {
                     return;
                 }
                 match result {
-                    Ok(value) => {
+                    Ok(mut value) => {
+                        // The key is gone, its cached type must not be reused
+                        if value.is_expired() && this.keys.remove(&current_key).is_some() {
+                            this.key_tree_id = Uuid::now_v7().to_string().into();
+                        }
                         if !value.is_expired()
                             && let Some(key) = this.key.as_ref()
                         {
                             let mut should_refresh_key_tree = false;
+                            let mut previous_key_type = None;
                             if let Some(k) = this.keys.get_mut(key) {
                                 if *k != value.key_type {
                                     should_refresh_key_tree = true;
+                                    previous_key_type = Some(*k).filter(|k| *k != KeyType::Unknown);
                                     *k = value.key_type();
                                 }
                             } else {
@@ -422,10 +550,35 @@ impl ZedisServerState {
                             if should_refresh_key_tree {
                                 this.key_tree_id = Uuid::now_v7().to_string().into();
                             }
+                            // The tree listed the key with a stale type, it was changed by another client
+                            if let Some(previous_key_type) = previous_key_type {
+                                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                                let message = t!(
+                                    "editor.key_type_changed",
+                                    key = key,
+                                    from = previous_key_type.as_str(),
+                                    to = value.key_type().as_str(),
+                                    locale = locale
+                                )
+                                .to_string();
+                                cx.emit(ServerEvent::Notification(NotificationAction::new_warning(
+                                    message.into(),
+                                )));
+                            }
                         }
+                        // Every load starts from the type default, picking a mode only changes the current value
+                        let view_mode = cx
+                            .global::<ZedisGlobalStore>()
+                            .read(cx)
+                            .default_view_mode(value.key_type());
+                        value.set_view_mode(view_mode);
                         this.value = Some(value);
                     }
                     Err(_) => {
+                        // Resolve the type again next time instead of trusting the cached one
+                        if let Some(key_type) = this.keys.get_mut(&current_key) {
+                            *key_type = KeyType::Unknown;
+                        }
                         this.value = None;
                     }
                 };
@@ -435,15 +588,53 @@ impl ZedisServerState {
             cx,
         );
     }
-    pub fn delete_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+    /// Whether the server knows UNLINK
+    pub fn supports_unlink(&self) -> bool {
+        self.features.unlink
+    }
+    /// Whether the server has COPY to duplicate a key
+    pub fn supports_copy(&self) -> bool {
+        self.features.copy
+    }
+    /// Delete commands a destructive dialog offers, the preferred one first.
+    ///
+    /// Servers without UNLINK only get DEL.
+    pub fn delete_commands(&self, cx: &App) -> Vec<DeleteCommand> {
+        if !self.supports_unlink() {
+            return vec![DeleteCommand::Del];
+        }
+        let mut commands = match cx.global::<ZedisGlobalStore>().read(cx).delete_command() {
+            DeleteCommand::Unlink => vec![DeleteCommand::Unlink, DeleteCommand::Del],
+            DeleteCommand::Del => vec![DeleteCommand::Del, DeleteCommand::Unlink],
+        };
+        // Commands the ACL denies aren't offered, unless none of them is allowed
+        if self.can_delete() {
+            commands.retain(|command| self.can_run(command.as_str()));
+        }
+        commands
+    }
+    /// Whether the ACL of the user permits a delete command the server supports
+    pub fn can_delete(&self) -> bool {
+        self.can_run(DeleteCommand::Del.as_str())
+            || (self.supports_unlink() && self.can_run(DeleteCommand::Unlink.as_str()))
+    }
+    /// The command actually sent, UNLINK falls back to DEL on servers before 4.0
+    fn effective_delete_command(&self, command: DeleteCommand) -> DeleteCommand {
+        if command == DeleteCommand::Unlink && !self.supports_unlink() {
+            return DeleteCommand::Del;
+        }
+        command
+    }
+    pub fn delete_key(&mut self, key: SharedString, command: DeleteCommand, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
         let remove_key = key.clone();
+        let command = self.effective_delete_command(command);
         self.spawn(
             ServerTask::DeleteKey,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let _: () = cmd("DEL").arg(key.as_str()).query_async(&mut conn).await?;
+                let _: () = cmd(command.as_str()).arg(key.as_str()).query_async(&mut conn).await?;
                 Ok(())
             },
             move |this, result, cx| {
@@ -462,66 +653,134 @@ impl ZedisServerState {
             cx,
         );
     }
-    pub fn delete_folder(&mut self, folder: SharedString, cx: &mut Context<Self>) {
+    /// Deletes the keys of a folder listed by the delete preview, in batches.
+    ///
+    /// Only the previewed keys are removed, keys created under the folder since then are kept.
+    pub fn delete_folder(&mut self, keys: Vec<SharedString>, command: DeleteCommand, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
-        let separator = cx.global::<ZedisGlobalStore>().value(cx).key_separator().to_string();
-        let prefix = format!("{folder}{separator}");
-        let pattern = format!("{prefix}*");
+        let remove_keys: HashSet<SharedString> = keys.iter().cloned().collect();
+        let command = self.effective_delete_command(command);
         self.spawn(
-            ServerTask::DeleteKeys,
+            ServerTask::DeleteFolder,
             move || async move {
                 let client = get_connection_manager().get_client(&server_id, db).await?;
-                let count = 10_000;
-                let mut cursors: Option<Vec<u64>> = None;
-                // Attempt to fetch keys in a loop (up to 20 iterations)
-                // to gather a sufficient amount without blocking for too long.
-                for _ in 0..20 {
-                    let (new_cursor, keys) = if let Some(cursors) = cursors.clone() {
-                        client.scan(cursors, &pattern, count).await?
-                    } else {
-                        client.first_scan(&pattern, count).await?
-                    };
-                    if !keys.is_empty() {
-                        let mut pipe = redis::pipe();
-                        for key in keys {
-                            pipe.cmd("UNLINK").arg(key.as_str());
-                        }
-                        let mut conn = client.connection();
-                        let _: () = pipe.query_async(&mut conn).await?;
-                    }
-
-                    // Break if scan cycle finishes
-                    if new_cursor.iter().sum::<u64>() == 0 {
-                        break;
+                let mut conn = client.connection();
+                for chunk in keys.chunks(DELETE_FOLDER_BATCH_SIZE) {
+                    let mut pipe = redis::pipe();
+                    for key in chunk {
+                        pipe.cmd(command.as_str()).arg(key.as_str());
                     }
-                    cursors = Some(new_cursor);
+                    let _: () = pipe.query_async(&mut conn).await?;
                 }
-
-                Ok(())
+                Ok(keys.len())
             },
             move |this, result, cx| {
-                if let Ok(()) = result {
-                    this.keys.retain(|key, _| !key.starts_with(prefix.as_str()));
+                if let Ok(count) = result {
+                    this.keys.retain(|key, _| !remove_keys.contains(key));
                     // Force refresh of the key tree view
                     this.key_tree_id = Uuid::now_v7().to_string().into();
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("key_tree.folder_deleted", count = count, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
                 }
                 cx.notify();
             },
             cx,
         );
     }
-    pub fn unlink_key(&mut self, keys: Vec<SharedString>, cx: &mut Context<Self>) {
+    /// Deletes every key matching the pattern on all masters, not only the loaded ones.
+    pub fn delete_matching_keys(&mut self, pattern: SharedString, command: DeleteCommand, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let command = self.effective_delete_command(command);
+        self.spawn(
+            ServerTask::DeleteMatchingKeys,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                let count = client
+                    .delete_matching(&pattern, DELETE_MATCHING_SCAN_COUNT, command)
+                    .await?;
+                Ok((pattern, count))
+            },
+            move |_this, result, cx| {
+                if let Ok((pattern, count)) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!(
+                        "key_tree.matching_keys_deleted",
+                        count = count,
+                        pattern = pattern,
+                        locale = locale
+                    )
+                    .to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                    cx.emit(ServerEvent::MatchingKeysDeleted(pattern));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Removes every key of the db with FLUSHDB, or of all dbs with FLUSHALL when `everything` is set.
+    ///
+    /// Refused unless the connection is read write, the key tree and value are cleared afterwards.
+    pub fn flush_db(&mut self, everything: bool, cx: &mut Context<Self>) {
+        if self.access_mode != AccessMode::ReadWrite {
+            let message = "Flushing is not allowed on a read only connection".to_string();
+            self.add_error_message("flush_db".to_string(), message.clone(), message, cx);
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::FlushDb,
+            move || async move {
+                // Flushing again is harmless, so it is retried like the reads
+                get_connection_manager()
+                    .with_client_retry(
+                        &server_id,
+                        db,
+                        |client, _| async move { client.flush(everything).await },
+                    )
+                    .await
+            },
+            move |this, result, cx| {
+                if let Ok(()) = result {
+                    // The db is empty now, nothing is left to scan
+                    this.reset_scan();
+                    this.scan_completed = true;
+                    this.key = None;
+                    this.value = None;
+                    this.dbsize = Some(0);
+                    let message = if everything {
+                        i18n_key_tree(cx, "flush_all_done")
+                    } else {
+                        i18n_key_tree(cx, "flush_db_done")
+                    };
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(message)));
+                    cx.emit(ServerEvent::ServerInfoUpdated(this.server_id.clone()));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    pub fn delete_keys(&mut self, keys: Vec<SharedString>, command: DeleteCommand, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
         let remove_keys = keys.clone();
+        let command = self.effective_delete_command(command);
         self.spawn(
             ServerTask::DeleteKeys,
             move || async move {
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
                 let mut pipe = redis::pipe();
                 for key in keys {
-                    pipe.cmd("UNLINK").arg(key.as_str());
+                    pipe.cmd(command.as_str()).arg(key.as_str());
                 }
                 let _: () = pipe.query_async(&mut conn).await?;
                 Ok(())
@@ -538,19 +797,19 @@ impl ZedisServerState {
         );
     }
     /// Deletes a specified key.
-    pub fn delete_select_key(&mut self, key: SharedString, cx: &mut Context<Self>) {
+    pub fn delete_select_key(&mut self, key: SharedString, command: DeleteCommand, cx: &mut Context<Self>) {
         let Some(value) = self.value.as_mut() else {
             return;
         };
         value.status = RedisValueStatus::Updating;
         cx.notify();
-        self.delete_key(key, cx);
+        self.delete_key(key, command, cx);
     }
-    /// Updates the TTL (expiration) for a key.
-    pub fn update_key_ttl(&mut self, key: SharedString, ttl: SharedString, cx: &mut Context<Self>) {
-        if ttl.is_empty() {
-            return;
-        }
+    /// Sets the TTL of a key with EXPIRE, `None` removes it with PERSIST.
+    ///
+    /// The expiration is shown right away and rolled back if the update fails.
+    /// A key that no longer exists is reported as an error.
+    pub fn set_ttl(&mut self, key: SharedString, seconds: Option<i64>, cx: &mut Context<Self>) {
         let server_id = self.server_id.clone();
         let db = self.db;
         let Some(value) = self.value.as_mut() else {
@@ -558,40 +817,42 @@ impl ZedisServerState {
         };
         value.status = RedisValueStatus::Updating;
         let original_ttl = value.expire_at;
-
-        let mut new_ttl = Duration::ZERO;
-        let mut parse_fail_error = "".to_string();
-        match parse_duration(&ttl) {
-            Ok(ttl) => new_ttl = ttl,
-            Err(err) => {
-                parse_fail_error = err.to_string();
-            }
-        }
-
-        if !new_ttl.is_zero() {
-            value.expire_at = Some(unix_ts() + new_ttl.as_secs() as i64);
-        }
+        value.expire_at = Some(match seconds {
+            Some(seconds) => unix_ts() + seconds,
+            None => -1,
+        });
         cx.notify();
         self.spawn(
             ServerTask::UpdateKeyTtl,
             move || async move {
-                if !parse_fail_error.is_empty() {
+                if let Some(seconds) = seconds
+                    && seconds <= 0
+                {
                     return Err(Error::Invalid {
-                        message: parse_fail_error,
+                        message: format!("TTL must be positive: {seconds}"),
                     });
                 }
                 let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
-                let _: () = cmd("EXPIRE")
-                    .arg(key.as_str())
-                    .arg(new_ttl.as_secs())
-                    .query_async(&mut conn)
-                    .await?;
+                let mut pipe = pipe();
+                match seconds {
+                    Some(seconds) => pipe.cmd("EXPIRE").arg(key.as_str()).arg(seconds).ignore(),
+                    None => pipe.cmd("PERSIST").arg(key.as_str()).ignore(),
+                };
+                // EXPIRE and PERSIST both return 0 for a missing key, TTL tells it apart
+                let (ttl,): (i64,) = pipe.cmd("TTL").arg(key.as_str()).query_async(&mut conn).await?;
+                if ttl == -2 {
+                    return Err(Error::Invalid {
+                        message: format!("Key does not exist: {key}"),
+                    });
+                }
                 Ok(ttl)
             },
             move |this, result, cx| {
                 if let Some(value) = this.value.as_mut() {
-                    if result.is_err() {
-                        value.expire_at = original_ttl;
+                    match result {
+                        Ok(ttl) if ttl >= 0 => value.expire_at = Some(unix_ts() + ttl),
+                        Ok(ttl) => value.expire_at = Some(ttl),
+                        Err(_) => value.expire_at = original_ttl,
                     }
                     value.status = RedisValueStatus::Idle;
                 }
@@ -659,6 +920,15 @@ impl ZedisServerState {
                             .query_async(&mut conn)
                             .await?;
                     }
+                    KeyType::Stream => {
+                        let _: () = cmd("XADD")
+                            .arg(key.as_str())
+                            .arg("*")
+                            .arg("field1")
+                            .arg("value1")
+                            .query_async(&mut conn)
+                            .await?;
+                    }
                     _ => {
                         return Err(Error::Invalid {
                             message: "Invalid key type".to_string(),
@@ -686,4 +956,249 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Renames a key with RENAME, an existing key of the new name is overwritten.
+    ///
+    /// The renamed key is selected once done.
+    pub fn rename_key(&mut self, from: SharedString, to: SharedString, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let from_clone = from.clone();
+        let to_clone = to.clone();
+        self.spawn(
+            ServerTask::RenameKey,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure_same_slot(&from, &to)?;
+                let mut conn = client.connection();
+                let _: () = cmd("RENAME")
+                    .arg(from.as_str())
+                    .arg(to.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    let key_type = this.keys.remove(&from_clone).unwrap_or_default();
+                    this.keys.insert(to_clone.clone(), key_type);
+                    // Force refresh of the key tree view
+                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                    this.select_key(to_clone, cx);
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Copies a key to a new name with COPY, `replace` overwrites an existing key of that name.
+    pub fn copy_key(&mut self, from: SharedString, to: SharedString, replace: bool, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let supports_copy = self.supports_copy();
+        let key_type = self.keys.get(&from).copied().unwrap_or_default();
+        let to_clone = to.clone();
+        self.spawn(
+            ServerTask::CopyKey,
+            move || async move {
+                if !supports_copy {
+                    return Err(Error::Invalid {
+                        message: format!("COPY requires Redis {COPY_VERSION} or later"),
+                    });
+                }
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.ensure_same_slot(&from, &to)?;
+                let mut conn = client.connection();
+                let mut copy = cmd("COPY");
+                copy.arg(from.as_str()).arg(to.as_str());
+                if replace {
+                    copy.arg("REPLACE");
+                }
+                let copied: bool = copy.query_async(&mut conn).await?;
+                if !copied {
+                    return Err(Error::Invalid {
+                        message: format!("Key already exists: {to}"),
+                    });
+                }
+                Ok(())
+            },
+            move |this, result, cx| {
+                if result.is_ok() {
+                    this.keys.insert(to_clone.clone(), key_type);
+                    // Force refresh of the key tree view
+                    this.key_tree_id = Uuid::now_v7().to_string().into();
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("editor.key_copied", key = to_clone, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
+
+/// Settings that shape how a loaded value is decoded and displayed.
+#[derive(Clone)]
+struct LoadValueOptions {
+    max_truncate_length: usize,
+    proto_raw_json: bool,
+    with_field_ttl: bool,
+    max_load_bytes: Option<usize>,
+}
+
+/// Converts a TTL reply into an absolute expiration timestamp.
+fn ttl_to_expire_at(ttl: i64) -> Option<i64> {
+    match ttl {
+        -1 => Some(-1), // Persistent
+        t if t >= 0 => Some(unix_ts() + t),
+        _ => None,
+    }
+}
+
+/// Type, TTL and internals of a key, fetched before its value
+struct KeyMeta {
+    key_type: KeyType,
+    ttl: i64,
+    encoding: Option<SharedString>,
+    idle_time: Option<i64>,
+}
+
+impl KeyMeta {
+    fn apply(self, value: &mut RedisValue) {
+        value.expire_at = ttl_to_expire_at(self.ttl);
+        value.encoding = self.encoding;
+        value.idle_time = self.idle_time;
+    }
+}
+
+/// Loads the type, TTL, `OBJECT ENCODING` and `OBJECT IDLETIME` of a key in one round-trip.
+///
+/// Managed Redis may disable the OBJECT commands, and IDLETIME fails under an LFU
+/// eviction policy, their errors are ignored and leave the fields empty.
+async fn load_key_meta(conn: &mut RedisAsyncConn, key: &str) -> Result<KeyMeta> {
+    let (t, ttl, encoding, idle_time): (String, i64, Value, Value) = pipe()
+        .ignore_errors()
+        .cmd("TYPE")
+        .arg(key)
+        .cmd("TTL")
+        .arg(key)
+        .cmd("OBJECT")
+        .arg("ENCODING")
+        .arg(key)
+        .cmd("OBJECT")
+        .arg("IDLETIME")
+        .arg(key)
+        .query_async(conn)
+        .await?;
+    let encoding = match encoding {
+        Value::BulkString(encoding) => Some(String::from_utf8_lossy(&encoding).to_string().into()),
+        Value::SimpleString(encoding) => Some(encoding.into()),
+        _ => None,
+    };
+    let idle_time = match idle_time {
+        Value::Int(idle_time) => Some(idle_time),
+        _ => None,
+    };
+    Ok(KeyMeta {
+        key_type: KeyType::from(t.as_str()),
+        ttl,
+        encoding,
+        idle_time,
+    })
+}
+
+/// Loads the type, TTL and first page of a key's value.
+async fn load_redis_value(
+    conn: &mut RedisAsyncConn,
+    server_id: &str,
+    key: &str,
+    options: &LoadValueOptions,
+) -> Result<RedisValue> {
+    let meta = load_key_meta(conn, key).await?;
+    // the key does not exist
+    if meta.ttl == -2 {
+        return Ok(RedisValue {
+            expire_at: Some(-2),
+            ..Default::default()
+        });
+    }
+    let mut redis_value = load_typed_value(conn, server_id, key, meta.key_type, options).await?;
+    meta.apply(&mut redis_value);
+    Ok(redis_value)
+}
+
+/// Loads a key whose type is already known by the key tree.
+///
+/// The type, TTL, encoding and idle time are fetched concurrently with the value on the
+/// same multiplexed connection, so no round-trip is spent on TYPE. The metadata is sent
+/// first, reading the value would reset the idle time. Falls back to `load_redis_value`
+/// when the key changed type meanwhile.
+async fn load_cached_type_value(
+    conn: &mut RedisAsyncConn,
+    server_id: &str,
+    key: &str,
+    key_type: KeyType,
+    options: &LoadValueOptions,
+) -> Result<RedisValue> {
+    let mut meta_conn = conn.clone();
+    let (meta, value) = future::join(
+        load_key_meta(&mut meta_conn, key),
+        load_typed_value(conn, server_id, key, key_type, options),
+    )
+    .await;
+    let meta = meta?;
+    // the key does not exist
+    if meta.ttl == -2 {
+        return Ok(RedisValue {
+            expire_at: Some(-2),
+            ..Default::default()
+        });
+    }
+    match value {
+        Ok(mut value) if meta.key_type == key_type => {
+            meta.apply(&mut value);
+            Ok(value)
+        }
+        Ok(_) => load_redis_value(conn, server_id, key, options).await,
+        Err(e) if is_wrong_type(&e) => load_redis_value(conn, server_id, key, options).await,
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether a command failed because the key holds a value of another type
+fn is_wrong_type(err: &Error) -> bool {
+    matches!(err, Error::Redis { source } if source.code() == Some("WRONGTYPE"))
+}
+
+/// Loads the first page of a key's value assuming it has the given type.
+async fn load_typed_value(
+    conn: &mut RedisAsyncConn,
+    server_id: &str,
+    key: &str,
+    key_type: KeyType,
+    options: &LoadValueOptions,
+) -> Result<RedisValue> {
+    match key_type {
+        KeyType::String => {
+            let mut data = get_redis_bytes_value(conn, key, options.max_load_bytes).await?;
+            data.detect_and_update(server_id, key, options.max_truncate_length, options.proto_raw_json);
+            let truncated = data.bytes.len() < data.full_len;
+            Ok(RedisValue {
+                key_type: KeyType::String,
+                data: Some(RedisValueData::Bytes(Arc::new(data))),
+                truncated,
+                ..Default::default()
+            })
+        }
+        KeyType::List => first_load_list_value(conn, key).await,
+        KeyType::Set => first_load_set_value(conn, key).await,
+        KeyType::Zset => first_load_zset_value(conn, key, SortOrder::Asc).await,
+        KeyType::Hash => first_load_hash_value(conn, key, options.with_field_ttl).await,
+        KeyType::Stream => first_load_stream_value(conn, key).await,
+        _ => Err(Error::Invalid {
+            message: "unsupported key type".to_string(),
+        }),
+    }
 }
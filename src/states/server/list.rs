@@ -19,11 +19,13 @@ use super::{
 use crate::{
     connection::{RedisAsyncConn, get_connection_manager},
     error::Error,
-    states::ServerEvent,
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore},
 };
 use gpui::{SharedString, prelude::*};
 use redis::{cmd, pipe};
+use rust_i18n::t;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use uuid::Uuid;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
@@ -273,6 +275,11 @@ impl ZedisServerState {
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        // A canceled load stays stopped until the filter changes
+        if value.is_load_canceled() {
+            return;
+        }
+        let cancel = value.load_cancel.clone();
         value.status = RedisValueStatus::Loading;
         cx.notify();
 
@@ -298,6 +305,10 @@ impl ZedisServerState {
                 Ok(new_values)
             },
             move |this, result, cx| {
+                // Drop the batch of a canceled load, the rows loaded before stay
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
                 if let Ok(new_values) = result
                     && !new_values.is_empty()
                 {
@@ -317,4 +328,51 @@ impl ZedisServerState {
             cx,
         );
     }
+    /// Trim the current List to the inclusive range `[start, stop]` (LTRIM).
+    ///
+    /// Elements outside the range are removed on the server. Afterwards the
+    /// length and the first page are reloaded, since every index may have shifted.
+    pub fn trim_list(&mut self, start: i64, stop: i64, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::TrimList,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let _: () = cmd("LTRIM")
+                    .arg(key.as_str())
+                    .arg(start)
+                    .arg(stop)
+                    .query_async(&mut conn)
+                    .await?;
+                let size: usize = cmd("LLEN").arg(key.as_str()).query_async(&mut conn).await?;
+                let values = get_redis_list_value(&mut conn, &key, 0, 99).await?;
+                Ok((size, values))
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((size, values)) = result {
+                        value.data = Some(RedisValueData::List(Arc::new(RedisListValue {
+                            size,
+                            values: values.into_iter().map(|v| v.into()).collect(),
+                            ..Default::default()
+                        })));
+                        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                        let msg = t!("list_editor.trim_success", len = size, locale = locale).to_string();
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_success(msg.into())));
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
 }
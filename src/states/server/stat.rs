@@ -13,19 +13,38 @@
 // limitations under the License.
 
 use crate::connection::get_connection_manager;
-use crate::states::{ServerEvent, ServerTask, ZedisServerState};
+use crate::error::Error;
+use crate::states::{NotificationAction, ServerEvent, ServerTask, ZedisGlobalStore, ZedisServerState};
 use gpui::prelude::*;
 use redis::cmd;
+use rust_i18n::t;
 use std::collections::HashMap;
 use std::time::Duration;
 use std::time::Instant;
-use tracing::error;
+use tracing::{error, warn};
 
 #[derive(Debug, Default, Clone)]
 pub struct RedisKeySpaceStats {
     pub keys: u64,
     pub expires: u64,
     pub avg_ttl: u64,
+    /// Hits of the db, only some servers expose them in the keyspace section
+    pub hits: Option<u64>,
+    /// Misses of the db, only some servers expose them in the keyspace section
+    pub misses: Option<u64>,
+}
+
+impl RedisKeySpaceStats {
+    /// Calculate the hit rate of the db, `None` when the server doesn't expose per-db stats
+    pub fn hit_rate(&self) -> Option<f64> {
+        let hits = self.hits?;
+        let total = hits + self.misses?;
+        if total == 0 {
+            Some(0.0)
+        } else {
+            Some((hits as f64 / total as f64) * 100.0)
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -64,6 +83,12 @@ pub struct RedisInfo {
 
     // --- Keyspace (db0, db1...) ---
     pub keyspace: HashMap<String, RedisKeySpaceStats>,
+
+    // --- Refresh health ---
+    /// Number of nodes that didn't respond in the last refresh (their previous values are used)
+    pub unreachable_nodes: usize,
+    /// Number of nodes queried in the last refresh
+    pub total_nodes: usize,
 }
 
 /// Aggregates metrics from multiple Redis Cluster nodes into a single global view.
@@ -125,6 +150,13 @@ pub fn aggregate_redis_info(infos: Vec<RedisInfo>) -> RedisInfo {
             // Sum keys and expires
             entry.keys += stats.keys;
             entry.expires += stats.expires;
+            // Per-db hits only add up when the node exposes them
+            if let Some(hits) = stats.hits {
+                entry.hits = Some(entry.hits.unwrap_or_default() + hits);
+            }
+            if let Some(misses) = stats.misses {
+                entry.misses = Some(entry.misses.unwrap_or_default() + misses);
+            }
 
             // Prepare data for weighted average calculation of avg_ttl
             if stats.expires > 0 {
@@ -215,6 +247,11 @@ impl RedisInfo {
         }
     }
 
+    /// Whether the server exposes hits and misses of each db, not only the global ones
+    pub fn has_db_hit_stats(&self) -> bool {
+        self.keyspace.values().any(|stats| stats.hit_rate().is_some())
+    }
+
     /// Get the total number of keys
     pub fn total_keys(&self) -> u64 {
         self.keyspace.values().map(|k| k.keys).sum()
@@ -232,6 +269,8 @@ fn parse_f64(v: &str) -> f64 {
 }
 
 /// Parse the keyspace value: keys=10,expires=0,avg_ttl=0
+///
+/// Extended fields `hits`/`misses` (or `keyspace_hits`/`keyspace_misses`) are kept when present.
 fn parse_keyspace_value(v: &str) -> Result<RedisKeySpaceStats, ()> {
     let mut stats = RedisKeySpaceStats::default();
     for part in v.split(',') {
@@ -240,6 +279,8 @@ fn parse_keyspace_value(v: &str) -> Result<RedisKeySpaceStats, ()> {
                 "keys" => stats.keys = parse_u64(val),
                 "expires" => stats.expires = parse_u64(val),
                 "avg_ttl" => stats.avg_ttl = parse_u64(val),
+                "hits" | "keyspace_hits" => stats.hits = Some(parse_u64(val)),
+                "misses" | "keyspace_misses" => stats.misses = Some(parse_u64(val)),
                 _ => {}
             }
         }
@@ -256,33 +297,100 @@ impl ZedisServerState {
         let server_id = self.server_id.clone();
         let db = self.db;
         let server_id_clone = server_id.clone();
+        let previous_node_infos = self.node_redis_infos.clone();
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let info_concurrency = store.info_concurrency();
+        let info_node_timeout = store.info_node_timeout();
 
         self.spawn(
             ServerTask::RefreshRedisInfo,
             move || async move {
-                let client = get_connection_manager().get_client(&server_id, db).await?;
-                let start = Instant::now();
-                client.ping().await?;
-                let latency = start.elapsed();
-
-                let list: Vec<String> = client.query_async_masters(vec![cmd("INFO").arg("ALL").clone()]).await?;
-                let infos: Vec<RedisInfo> = list.iter().map(|info| RedisInfo::parse(info)).collect();
-                let mut info = aggregate_redis_info(infos);
+                // INFO runs on the retried client too, so it doesn't reuse the dropped connections
+                let (client, latency, results) = get_connection_manager()
+                    .with_client_retry(&server_id, db, |client, _| async move {
+                        let start = Instant::now();
+                        client.ping().await?;
+                        let latency = start.elapsed();
+                        let results: Vec<(String, Result<String, Error>)> = client
+                            .query_async_masters_settled(
+                                vec![cmd("INFO").arg("ALL").clone()],
+                                info_concurrency,
+                                info_node_timeout,
+                            )
+                            .await;
+                        Ok((client, latency, results))
+                    })
+                    .await?;
+                let total_nodes = results.len();
+                let mut unreachable_nodes = 0;
+                // Nodes that didn't respond keep their previous values
+                let node_infos: Vec<(String, Option<RedisInfo>)> = results
+                    .into_iter()
+                    .map(|(node, result)| {
+                        let info = match result {
+                            Ok(info) => Some(RedisInfo::parse(&info)),
+                            Err(e) => {
+                                unreachable_nodes += 1;
+                                warn!(error = %e, node = %node, "Refresh node info failed");
+                                previous_node_infos
+                                    .iter()
+                                    .find(|(previous, _)| previous == &node)
+                                    .and_then(|(_, info)| info.clone())
+                            }
+                        };
+                        (node, info)
+                    })
+                    .collect();
+                if unreachable_nodes == total_nodes {
+                    return Err(Error::Invalid {
+                        message: "no node responded to INFO".to_string(),
+                    });
+                }
+                let mut info = aggregate_redis_info(node_infos.iter().filter_map(|(_, info)| info.clone()).collect());
                 info.latency = latency;
-                Ok(info)
+                info.unreachable_nodes = unreachable_nodes;
+                info.total_nodes = total_nodes;
+                Ok((info, node_infos))
             },
             move |this, result, cx| match result {
-                Ok(info) => {
+                Ok((info, node_infos)) => {
+                    this.node_redis_infos = node_infos;
                     this.redis_info = Some(info);
                     cx.emit(ServerEvent::ServerRedisInfoUpdated(server_id_clone.clone()));
                 }
                 Err(e) => {
                     // Connection is invalid, remove cached client
-                    get_connection_manager().remove_client(&server_id_clone);
+                    get_connection_manager().remove_client(&server_id_clone, db);
                     error!(error = %e, "Ping failed, client connection removed");
                 }
             },
             cx,
         );
     }
+    /// Kill the clients listed by `RedisClient::idle_client_ids`, grouped by master
+    pub fn kill_idle_clients(&mut self, ids: Vec<Vec<u64>>, cx: &mut Context<Self>) {
+        if self.server_id.is_empty() || self.readonly() {
+            return;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.spawn(
+            ServerTask::KillIdleClients,
+            move || async move {
+                let client = get_connection_manager().get_client(&server_id, db).await?;
+                client.kill_clients(ids).await
+            },
+            move |this, result, cx| {
+                if let Ok(killed) = result {
+                    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                    let message = t!("status_bar.idle_clients_killed", count = killed, locale = locale).to_string();
+                    cx.emit(ServerEvent::Notification(NotificationAction::new_success(
+                        message.into(),
+                    )));
+                    this.refresh_redis_info(cx);
+                }
+            },
+            cx,
+        );
+    }
 }
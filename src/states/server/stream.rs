@@ -0,0 +1,320 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    KeyType, RedisValueData, ServerTask, ZedisServerState,
+    value::{RedisStreamValue, RedisValue, RedisValueStatus},
+};
+use crate::{
+    connection::{RedisAsyncConn, get_connection_manager},
+    error::Error,
+    states::{NotificationAction, ServerEvent, ZedisGlobalStore},
+};
+use gpui::{SharedString, prelude::*};
+use redis::cmd;
+use rust_i18n::t;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Entries loaded per page
+const STREAM_PAGE_SIZE: usize = 100;
+
+type StreamEntry = (SharedString, Vec<(SharedString, SharedString)>);
+
+/// The smallest id after `id`, used as the inclusive start of the next page.
+///
+/// `XRANGE` only supports exclusive ranges (`(id`) since Redis 6.2.
+fn next_stream_id(id: &str) -> String {
+    let Some((ms, seq)) = id.split_once('-') else {
+        return id.to_string();
+    };
+    match (ms.parse::<u64>(), seq.parse::<u64>()) {
+        (Ok(ms), Ok(u64::MAX)) => format!("{}-0", ms.saturating_add(1)),
+        (Ok(ms), Ok(seq)) => format!("{ms}-{}", seq + 1),
+        _ => id.to_string(),
+    }
+}
+
+/// Fetch up to `count` entries of a Redis Stream from `start` (inclusive).
+///
+/// Field names and values are lossily converted to UTF-8.
+async fn get_redis_stream_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    start: &str,
+    count: usize,
+) -> Result<Vec<StreamEntry>> {
+    let entries: Vec<(String, Vec<Vec<u8>>)> = cmd("XRANGE")
+        .arg(key)
+        .arg(start)
+        .arg("+")
+        .arg("COUNT")
+        .arg(count)
+        .query_async(conn)
+        .await?;
+    Ok(entries
+        .into_iter()
+        .map(|(id, fields)| {
+            let fields = fields
+                .chunks(2)
+                .map(|pair| {
+                    let field = String::from_utf8_lossy(&pair[0]).to_string().into();
+                    let value = pair
+                        .get(1)
+                        .map(|value| String::from_utf8_lossy(value).to_string())
+                        .unwrap_or_default()
+                        .into();
+                    (field, value)
+                })
+                .collect();
+            (id.into(), fields)
+        })
+        .collect())
+}
+
+/// Initial load for a Stream key.
+/// Fetches the length (XLEN) and the first page of entries.
+pub(crate) async fn first_load_stream_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisValue> {
+    let size: usize = cmd("XLEN").arg(key).query_async(conn).await?;
+    let values = get_redis_stream_value(conn, key, "-", STREAM_PAGE_SIZE).await?;
+    Ok(RedisValue {
+        key_type: KeyType::Stream,
+        data: Some(RedisValueData::Stream(Arc::new(RedisStreamValue {
+            size,
+            values,
+            ..Default::default()
+        }))),
+        ..Default::default()
+    })
+}
+
+impl ZedisServerState {
+    pub fn filter_stream_value(&mut self, keyword: SharedString, cx: &mut Context<Self>) {
+        let Some((_, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(stream_value) = value.stream_value() else {
+            return;
+        };
+        let new_stream_value = RedisStreamValue {
+            keyword: Some(keyword.clone()),
+            size: stream_value.size,
+            values: stream_value.values.clone(),
+        };
+        value.data = Some(RedisValueData::Stream(Arc::new(new_stream_value)));
+        cx.emit(ServerEvent::ValueUpdated(self.key.clone().unwrap_or_default()));
+    }
+    /// Load the page of entries following the last loaded one.
+    pub fn load_more_stream_value(&mut self, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        // A canceled load stays stopped until the filter changes
+        if value.is_load_canceled() {
+            return;
+        }
+        let Some(start) = value
+            .stream_value()
+            .and_then(|stream| stream.values.last())
+            .map(|(id, _)| next_stream_id(id))
+        else {
+            return;
+        };
+        let cancel = value.load_cancel.clone();
+        value.status = RedisValueStatus::Loading;
+        cx.notify();
+
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        cx.emit(ServerEvent::ValuePaginationStarted(key.clone()));
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::LoadMoreValue,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                get_redis_stream_value(&mut conn, &key, &start, STREAM_PAGE_SIZE).await
+            },
+            move |this, result, cx| {
+                // Drop the batch of a canceled load, the entries loaded before stay
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok(new_values) = result
+                        && let Some(RedisValueData::Stream(stream_data)) = value.data.as_mut()
+                    {
+                        let stream = Arc::make_mut(stream_data);
+                        // Entries deleted meanwhile would otherwise keep the stream "not done"
+                        if new_values.is_empty() {
+                            stream.size = stream.values.len();
+                        }
+                        stream.values.extend(new_values);
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.emit(ServerEvent::ValuePaginationFinished(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Append an entry with XADD, `id` is `*` (or empty) to let Redis generate it.
+    pub fn add_stream_entry(
+        &mut self,
+        id: SharedString,
+        fields: Vec<(SharedString, SharedString)>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        let new_fields = fields.clone();
+        self.spawn(
+            ServerTask::AddStreamEntry,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let id = if id.trim().is_empty() { "*" } else { id.trim() };
+                let mut command = cmd("XADD");
+                command.arg(key.as_str()).arg(id);
+                for (field, value) in fields.iter() {
+                    command.arg(field.as_str()).arg(value.as_str());
+                }
+                let id: String = command.query_async(&mut conn).await?;
+                Ok(id)
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok(id) = result
+                        && let Some(RedisValueData::Stream(stream_data)) = value.data.as_mut()
+                    {
+                        let stream = Arc::make_mut(stream_data);
+                        // New entries go last, shown right away once every entry is loaded
+                        if stream.values.len() >= stream.size {
+                            stream.values.push((id.into(), new_fields));
+                        }
+                        stream.size += 1;
+                    }
+                }
+                cx.emit(ServerEvent::ValueAdded(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Delete the entry at `index` of the loaded entries with XDEL.
+    pub fn remove_stream_entry(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        let Some(id) = value
+            .stream_value()
+            .and_then(|stream| stream.values.get(index))
+            .map(|(id, _)| id.clone())
+        else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::RemoveStreamEntry,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let count: usize = cmd("XDEL")
+                    .arg(key.as_str())
+                    .arg(id.as_str())
+                    .query_async(&mut conn)
+                    .await?;
+                Ok((id, count))
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    if let Ok((id, count)) = result
+                        && let Some(RedisValueData::Stream(stream_data)) = value.data.as_mut()
+                    {
+                        let stream = Arc::make_mut(stream_data);
+                        stream.values.retain(|(entry_id, _)| entry_id != &id);
+                        stream.size = stream.size.saturating_sub(count);
+                        cx.emit(ServerEvent::ValueUpdated(key_clone));
+                    }
+                    value.status = RedisValueStatus::Idle;
+                }
+                cx.notify();
+            },
+            cx,
+        );
+    }
+    /// Trim the current Stream to its `max_len` newest entries (XTRIM MAXLEN).
+    ///
+    /// The length and the first page are reloaded afterwards, the oldest entries are gone.
+    pub fn trim_stream(&mut self, max_len: usize, cx: &mut Context<Self>) {
+        let Some((key, value)) = self.try_get_mut_key_value() else {
+            return;
+        };
+        value.status = RedisValueStatus::Updating;
+        cx.notify();
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let key_clone = key.clone();
+        self.spawn(
+            ServerTask::TrimStream,
+            move || async move {
+                let mut conn = get_connection_manager().get_connection(&server_id, db).await?;
+                let removed: usize = cmd("XTRIM")
+                    .arg(key.as_str())
+                    .arg("MAXLEN")
+                    .arg(max_len)
+                    .query_async(&mut conn)
+                    .await?;
+                let size: usize = cmd("XLEN").arg(key.as_str()).query_async(&mut conn).await?;
+                let values = get_redis_stream_value(&mut conn, &key, "-", STREAM_PAGE_SIZE).await?;
+                Ok((removed, size, values))
+            },
+            move |this, result, cx| {
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((removed, size, values)) = result {
+                        value.data = Some(RedisValueData::Stream(Arc::new(RedisStreamValue {
+                            size,
+                            values,
+                            ..Default::default()
+                        })));
+                        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                        let msg = t!(
+                            "stream_editor.trim_success",
+                            removed = removed,
+                            len = size,
+                            locale = locale
+                        )
+                        .to_string();
+                        cx.emit(ServerEvent::Notification(NotificationAction::new_success(msg.into())));
+                    }
+                }
+                cx.emit(ServerEvent::ValueUpdated(key_clone));
+                cx.notify();
+            },
+            cx,
+        );
+    }
+}
@@ -20,13 +20,36 @@ use bytes::Bytes;
 use flate2::read::GzDecoder;
 use gpui::SharedString;
 use lz4_flex::block::decompress_size_prepended;
-use redis::cmd;
+use redis::{cmd, pipe};
 use serde_json::Value;
 use snap::read::FrameDecoder;
 use std::io::Read;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Number of leading chars sampled when sniffing text vs binary
+const TEXT_SNIFF_CHARS: usize = 4096;
+/// Max share of control chars (besides whitespace) for valid UTF-8 to still count as text
+const MAX_CONTROL_CHAR_RATIO: f64 = 0.1;
+/// Bytes of a string value fetched when a key is selected, the rest is loaded on demand
+pub(crate) const MAX_STRING_LOAD_BYTES: usize = 10 * 1024 * 1024;
+
+/// Whether valid UTF-8 data reads as text rather than binary that happens to decode.
+///
+/// Binary payloads (counters, packed structs...) are often valid UTF-8 but full of
+/// NUL and other control chars, which would render as mojibake in the text editor.
+fn is_probably_text(s: &str) -> bool {
+    let mut total = 0;
+    let mut control = 0;
+    for c in s.chars().take(TEXT_SNIFF_CHARS) {
+        total += 1;
+        if c.is_control() && !matches!(c, '\n' | '\r' | '\t') {
+            control += 1;
+        }
+    }
+    total == 0 || (control as f64) / (total as f64) <= MAX_CONTROL_CHAR_RATIO
+}
+
 fn truncate_long_strings(max_truncate_length: usize, v: &mut Value, truncated: &mut bool) {
     match v {
         Value::String(s) => {
@@ -71,7 +94,7 @@ fn pretty_json(value: &str, max_truncate_length: usize) -> Option<(SharedString,
 
 fn format_text(data: &[u8], max_truncate_length: usize) -> Option<(DataFormat, SharedString)> {
     match std::str::from_utf8(data) {
-        Ok(s) => {
+        Ok(s) if is_probably_text(s) => {
             if let Some((pretty, truncated)) = pretty_json(s, max_truncate_length) {
                 let format = if truncated {
                     DataFormat::Preview
@@ -83,12 +106,12 @@ fn format_text(data: &[u8], max_truncate_length: usize) -> Option<(DataFormat, S
                 Some((DataFormat::Text, s.to_string().into()))
             }
         }
-        Err(_) => None,
+        _ => None,
     }
 }
 
 impl RedisBytesValue {
-    pub fn detect_and_update(&mut self, server_id: &str, key: &str, max_truncate_length: usize) {
+    pub fn detect_and_update(&mut self, server_id: &str, key: &str, max_truncate_length: usize, proto_raw_json: bool) {
         let data = self.bytes.as_ref();
         if data.is_empty() {
             return;
@@ -126,8 +149,9 @@ impl RedisBytesValue {
 
             _ => {
                 if let Some(id) = ProtoManager::match_key_to_name(server_id, key)
-                    && let Ok(data) = ProtoManager::decode_data(&id, data)
+                    && let Ok(data) = ProtoManager::decode_data(&id, data, proto_raw_json)
                 {
+                    self.proto_id = Some(id.into());
                     Some((DataFormat::Protobuf, SharedString::from(data)))
                 } else if let Ok(decompressed) = decompress_size_prepended(data) {
                     process_decompressed(Some(decompressed))
@@ -142,14 +166,44 @@ impl RedisBytesValue {
             self.text = Some(text);
         } else {
             self.format = initial_format;
+            self.binary_utf8 =
+                initial_format == DataFormat::Bytes && std::str::from_utf8(data).is_ok_and(|s| !is_probably_text(s));
         }
     }
 }
 
-pub(crate) async fn get_redis_bytes_value(conn: &mut RedisAsyncConn, key: &str) -> Result<RedisBytesValue> {
-    let value_bytes: Vec<u8> = cmd("GET").arg(key).query_async(conn).await?;
+/// Fetches a string value, only its first `max_bytes` when given.
+///
+/// `full_len` of the result tells the length in Redis, it is larger than the
+/// fetched bytes when the value was capped.
+pub(crate) async fn get_redis_bytes_value(
+    conn: &mut RedisAsyncConn,
+    key: &str,
+    max_bytes: Option<usize>,
+) -> Result<RedisBytesValue> {
+    let value_bytes: Vec<u8> = match max_bytes {
+        Some(max_bytes) if max_bytes > 0 => {
+            let (full_len, value_bytes): (usize, Vec<u8>) = pipe()
+                .cmd("STRLEN")
+                .arg(key)
+                .cmd("GETRANGE")
+                .arg(key)
+                .arg(0)
+                .arg(max_bytes - 1)
+                .query_async(conn)
+                .await?;
+            return Ok(RedisBytesValue {
+                format: DataFormat::Text,
+                full_len: full_len.max(value_bytes.len()),
+                bytes: Bytes::from(value_bytes),
+                ..Default::default()
+            });
+        }
+        _ => cmd("GET").arg(key).query_async(conn).await?,
+    };
     Ok(RedisBytesValue {
         format: DataFormat::Text,
+        full_len: value_bytes.len(),
         bytes: Bytes::from(value_bytes),
         ..Default::default()
     })
@@ -167,3 +221,32 @@ pub(crate) async fn get_redis_bytes_value(conn: &mut RedisAsyncConn, key: &str)
 //         ..Default::default()
 //     })
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_probably_text() {
+        assert!(is_probably_text(""));
+        assert!(is_probably_text("hello\r\n\tworld"));
+        assert!(is_probably_text("你好, Zedis 🚀"));
+        // A few control chars in a long text are tolerated
+        assert!(is_probably_text(&format!("{}\x1b[0m", "a".repeat(100))));
+
+        assert!(!is_probably_text("\0\0\0\x01"));
+        assert!(!is_probably_text("ab\x00\x02\x7f"));
+    }
+
+    #[test]
+    fn test_format_text() {
+        let (format, text) = format_text("café".as_bytes(), 100).expect("utf-8 text");
+        assert_eq!(DataFormat::Text, format);
+        assert_eq!("café", text.to_string());
+
+        // Latin-1 isn't valid UTF-8
+        assert!(format_text(b"caf\xe9", 100).is_none());
+        // Valid UTF-8 made of control bytes, e.g. a packed integer
+        assert!(format_text(&[0, 0, 0, 42], 100).is_none());
+    }
+}
@@ -14,14 +14,24 @@
 
 use super::{ServerEvent, ServerTask, ZedisServerState};
 use crate::connection::get_connection_manager;
+use crate::db::ProtoManager;
+use crate::error::Error;
+use ahash::AHashMap;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use bytes::Bytes;
 use chrono::Local;
 use gpui::{Action, Hsla, SharedString, prelude::*};
 use redis::cmd;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde_json::json;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::io::Cursor;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// Notification category for user feedback
 #[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema, Default)]
@@ -224,6 +234,7 @@ pub enum RedisValueData {
     Set(Arc<RedisSetValue>),
     Zset(Arc<RedisZsetValue>),
     Hash(Arc<RedisHashValue>),
+    Stream(Arc<RedisStreamValue>),
 }
 
 /// Redis Set value structure with pagination support
@@ -263,6 +274,8 @@ pub struct RedisHashValue {
     pub size: usize,
     pub done: bool,
     pub values: Vec<(SharedString, SharedString)>,
+    /// Absolute expiration timestamp of fields with a TTL (Redis 7.4+ HTTL)
+    pub field_expire_at: AHashMap<SharedString, i64>,
 }
 
 /// Redis List value structure
@@ -272,12 +285,55 @@ pub struct RedisListValue {
     pub size: usize,
     pub values: Vec<SharedString>,
 }
+/// Redis Stream value structure, entries are loaded in id order
+#[derive(Debug, Clone, Default)]
+pub struct RedisStreamValue {
+    pub keyword: Option<SharedString>,
+    /// Number of entries (XLEN)
+    pub size: usize,
+    /// Entry ids with their field/value pairs
+    pub values: Vec<(SharedString, Vec<(SharedString, SharedString)>)>,
+}
+/// Command used to delete keys
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DeleteCommand {
+    /// Frees the value in the background, available since Redis 4.0
+    #[default]
+    Unlink,
+    /// Frees the value before replying, blocking the server for big values
+    Del,
+}
+
+impl DeleteCommand {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeleteCommand::Unlink => "UNLINK",
+            DeleteCommand::Del => "DEL",
+        }
+    }
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "DEL" => DeleteCommand::Del,
+            _ => DeleteCommand::Unlink,
+        }
+    }
+    /// Key of the label describing the command in the `common` locale section
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            DeleteCommand::Unlink => "delete_command_unlink",
+            DeleteCommand::Del => "delete_command_del",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ViewMode {
     #[default]
     Auto,
     Plain,
     Hex,
+    /// Pretty printed JSON, a JSON string holding JSON is decoded once more
+    Json,
 }
 
 impl ViewMode {
@@ -286,15 +342,26 @@ impl ViewMode {
             ViewMode::Auto => "Auto",
             ViewMode::Plain => "Plain",
             ViewMode::Hex => "Hex",
+            ViewMode::Json => "Json",
         }
     }
     pub fn from_str(s: &str) -> Self {
         match s {
             "Plain" => ViewMode::Plain,
             "Hex" => ViewMode::Hex,
+            "Json" => ViewMode::Json,
             _ => ViewMode::Auto,
         }
     }
+    /// Position of the mode in the viewer select
+    pub fn index(&self) -> usize {
+        match self {
+            ViewMode::Auto => 0,
+            ViewMode::Plain => 1,
+            ViewMode::Hex => 2,
+            ViewMode::Json => 3,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -304,6 +371,12 @@ pub struct RedisBytesValue {
     pub mime: Option<SharedString>,
     pub text: Option<SharedString>,
     pub view_mode: ViewMode,
+    /// Valid UTF-8 that `is_probably_text` judged binary, so it isn't shown as text
+    pub binary_utf8: bool,
+    /// Length of the value in Redis, more than `bytes` when the load was capped
+    pub full_len: usize,
+    /// Id of the proto config the value was decoded with
+    pub proto_id: Option<SharedString>,
 }
 
 impl RedisBytesValue {
@@ -350,10 +423,18 @@ impl RedisValue {
         }
         None
     }
+
+    /// Returns the stream value if the data is a Stream type
+    pub fn stream_value(&self) -> Option<&Arc<RedisStreamValue>> {
+        if let Some(RedisValueData::Stream(data)) = self.data.as_ref() {
+            return Some(data);
+        }
+        None
+    }
 }
 
 /// Redis key types: string, list, set, zset, hash, stream, and vectorset
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum KeyType {
     #[default]
     Unknown,
@@ -380,6 +461,20 @@ impl KeyType {
         }
     }
 
+    /// Returns the name `TYPE` reports for this key type, `none` for unknown
+    pub fn redis_name(&self) -> &'static str {
+        match self {
+            KeyType::String => "string",
+            KeyType::List => "list",
+            KeyType::Hash => "hash",
+            KeyType::Set => "set",
+            KeyType::Zset => "zset",
+            KeyType::Stream => "stream",
+            KeyType::Vectorset => "vectorset",
+            KeyType::Unknown => "none",
+        }
+    }
+
     /// Returns the color associated with this key type for UI display
     pub fn color(&self) -> Hsla {
         match self {
@@ -411,10 +506,47 @@ pub struct RedisValue {
     pub(crate) key_type: KeyType,
     pub(crate) data: Option<RedisValueData>,
     pub(crate) expire_at: Option<i64>,
-    pub(crate) size: u64,
+    /// Bytes reported by `MEMORY USAGE`, None when the server doesn't support it
+    pub(crate) memory_usage: Option<u64>,
+    /// Reported by `OBJECT ENCODING`, e.g. listpack or hashtable
+    pub(crate) encoding: Option<SharedString>,
+    /// Seconds since the key was last accessed, reported by `OBJECT IDLETIME`
+    pub(crate) idle_time: Option<i64>,
+    /// Only the first bytes of the value were fetched
+    pub(crate) truncated: bool,
+    /// Set when the user stops the pagination, the rows loaded so far are kept
+    pub(crate) load_cancel: Arc<AtomicBool>,
+    /// Viewer mode of the cells of a collection, a string keeps its own in `RedisBytesValue`
+    pub(crate) view_mode: ViewMode,
 }
 
 impl RedisValue {
+    /// Returns the viewer mode of the value, the one of its bytes for a string
+    pub fn view_mode(&self) -> ViewMode {
+        match &self.data {
+            Some(RedisValueData::Bytes(bytes_value)) => bytes_value.view_mode.clone(),
+            _ => self.view_mode.clone(),
+        }
+    }
+
+    /// Sets the viewer mode of the value, see `view_mode`
+    pub fn set_view_mode(&mut self, view_mode: ViewMode) {
+        match &mut self.data {
+            Some(RedisValueData::Bytes(bytes_value)) => Arc::make_mut(bytes_value).view_mode = view_mode,
+            _ => self.view_mode = view_mode,
+        }
+    }
+
+    /// Checks if only a part of the value was loaded, such a value must not be saved back
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Checks if the pagination was stopped before every row was loaded
+    pub fn is_load_canceled(&self) -> bool {
+        self.load_cancel.load(Ordering::Relaxed)
+    }
+
     /// Checks if the value is currently being loaded or updated
     pub fn is_busy(&self) -> bool {
         !matches!(self.status, RedisValueStatus::Idle)
@@ -443,9 +575,19 @@ impl RedisValue {
         None
     }
 
-    /// Returns the size of the value in bytes
-    pub fn size(&self) -> u64 {
-        self.size
+    /// Returns the memory used by the key in bytes, if the server reported it
+    pub fn memory_usage(&self) -> Option<u64> {
+        self.memory_usage
+    }
+
+    /// Returns the internal encoding of the key, if the server reported it
+    pub fn encoding(&self) -> Option<&SharedString> {
+        self.encoding.as_ref()
+    }
+
+    /// Returns the seconds since the key was last accessed, if the server reported it
+    pub fn idle_time(&self) -> Option<i64> {
+        self.idle_time
     }
 
     /// Returns the time-to-live duration for this key
@@ -482,6 +624,95 @@ impl RedisValue {
     pub fn is_expired(&self) -> bool {
         self.expire_at.is_some_and(|expire_at| expire_at == -2)
     }
+
+    /// Hash of the loaded content, two loads of an unchanged key give the same fingerprint
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.key_type.as_str().hash(&mut hasher);
+        match &self.data {
+            Some(RedisValueData::Bytes(value)) => value.bytes.hash(&mut hasher),
+            Some(RedisValueData::List(value)) => (value.size, &value.values).hash(&mut hasher),
+            Some(RedisValueData::Set(value)) => (value.size, &value.values).hash(&mut hasher),
+            Some(RedisValueData::Zset(value)) => {
+                value.size.hash(&mut hasher);
+                for (member, score) in value.values.iter() {
+                    (member, score.to_bits()).hash(&mut hasher);
+                }
+            }
+            Some(RedisValueData::Hash(value)) => (value.size, &value.values).hash(&mut hasher),
+            Some(RedisValueData::Stream(value)) => (value.size, &value.values).hash(&mut hasher),
+            None => {}
+        }
+        hasher.finish()
+    }
+
+    /// Checks if every member of a collection has been loaded
+    pub fn is_fully_loaded(&self) -> bool {
+        match self.data.as_ref() {
+            Some(RedisValueData::List(value)) => value.values.len() >= value.size,
+            Some(RedisValueData::Stream(value)) => value.values.len() >= value.size,
+            Some(RedisValueData::Set(value)) => value.done,
+            Some(RedisValueData::Zset(value)) => value.done,
+            Some(RedisValueData::Hash(value)) => value.done,
+            _ => true,
+        }
+    }
+
+    /// Builds a JSON bundle with the key's metadata and its loaded value
+    ///
+    /// Strings are exported as their displayed text, or base64 when there is none.
+    pub fn to_export_json(&self, key: &str) -> serde_json::Value {
+        let mut bundle = json!({
+            "key": key,
+            "type": self.key_type.as_str(),
+            "ttl": self.ttl().map(|ttl| ttl.num_seconds()),
+            "size": self.memory_usage,
+            "complete": self.is_fully_loaded(),
+        });
+        let (format, value) = match self.data.as_ref() {
+            Some(RedisValueData::Bytes(value)) => match value.text.as_ref() {
+                Some(text) => (value.format.as_str(), json!(text.as_str())),
+                None => ("base64", json!(BASE64.encode(&value.bytes))),
+            },
+            Some(RedisValueData::List(value)) => ("list", json!(value.values)),
+            Some(RedisValueData::Set(value)) => ("set", json!(value.values)),
+            Some(RedisValueData::Zset(value)) => (
+                "zset",
+                value
+                    .values
+                    .iter()
+                    .map(|(member, score)| json!({ "member": member, "score": score }))
+                    .collect(),
+            ),
+            Some(RedisValueData::Hash(value)) => (
+                "hash",
+                value
+                    .values
+                    .iter()
+                    .map(|(field, value)| (field.to_string(), json!(value)))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into(),
+            ),
+            Some(RedisValueData::Stream(value)) => (
+                "stream",
+                value
+                    .values
+                    .iter()
+                    .map(|(id, fields)| {
+                        let fields = fields
+                            .iter()
+                            .map(|(field, value)| (field.to_string(), json!(value)))
+                            .collect::<serde_json::Map<_, _>>();
+                        json!({ "id": id, "fields": fields })
+                    })
+                    .collect(),
+            ),
+            None => ("none", serde_json::Value::Null),
+        };
+        bundle["format"] = json!(format);
+        bundle["value"] = value;
+        bundle
+    }
 }
 
 /// Converts a string representation to a KeyType
@@ -500,30 +731,82 @@ impl From<&str> for KeyType {
     }
 }
 
+/// Sets a string value while preserving the key's TTL.
+///
+/// Uses `KEEPTTL` on Redis 6.0+, otherwise re-applies the remaining TTL (in ms).
+/// Returns the new memory usage of the key if available.
+async fn set_value_keep_ttl(server_id: &str, db: usize, key: &str, value: &[u8], ttl: i64) -> Result<Option<u64>> {
+    let client = get_connection_manager().get_client(server_id, db).await?;
+    let mut conn = client.connection();
+    let mut binding = cmd("SET");
+    let mut new_cmd = binding.arg(key).arg(value);
+    // keep ttl if the version is at least 6.0.0
+    new_cmd = if client.is_at_least_version("6.0.0") {
+        new_cmd.arg("KEEPTTL")
+    } else if ttl > 0 {
+        new_cmd.arg("PX").arg(ttl)
+    } else {
+        new_cmd
+    };
+    let _: () = new_cmd.query_async(&mut conn).await?;
+
+    let memory_usage = cmd("MEMORY")
+        .arg("USAGE")
+        .arg(key)
+        .query_async::<u64>(&mut conn)
+        .await
+        .ok();
+
+    Ok(memory_usage)
+}
+
 impl ZedisServerState {
+    /// Stops the pagination of the current value.
+    ///
+    /// The batch in flight is dropped when it arrives, the rows loaded so far stay
+    /// visible and no more are fetched until the filter changes or the key is reloaded.
+    pub fn cancel_value_load(&mut self, cx: &mut Context<Self>) {
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        let Some(value) = self.value.as_mut().filter(|value| value.is_loading()) else {
+            return;
+        };
+        value.load_cancel.store(true, Ordering::Relaxed);
+        value.status = RedisValueStatus::Idle;
+        cx.emit(ServerEvent::ValuePaginationFinished(key));
+        cx.notify();
+    }
     /// Saves a new value for a Redis string key
     ///
     /// This method updates the UI immediately with the new value and then
     /// asynchronously persists it to Redis. If the save fails, the original
     /// value is restored.
-    pub fn save_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) {
+    ///
+    /// Returns whether the save started, `ValueSaveFinished` is emitted once it is done.
+    pub fn save_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) -> bool {
         let server_id = self.server_id.clone();
         let db = self.db;
         let Some(value) = self.value.as_mut() else {
-            return;
+            return false;
         };
+        // Writing back the loaded part would drop the rest of the value
+        if value.truncated {
+            return false;
+        }
 
         let Some(original_bytes_value) = value.bytes_value() else {
-            return;
+            return false;
         };
         let format = original_bytes_value.format;
-        let original_size = value.size;
+        let original_memory_usage = value.memory_usage;
 
         value.status = RedisValueStatus::Updating;
         value.data = Some(RedisValueData::Bytes(Arc::new(RedisBytesValue {
             bytes: Bytes::from(new_value.clone().to_string().into_bytes()),
             text: Some(new_value.clone()),
             format,
+            full_len: new_value.len(),
             ..Default::default()
         })));
         let current_key = key.clone();
@@ -532,65 +815,95 @@ impl ZedisServerState {
         cx.notify();
         self.spawn(
             ServerTask::SaveValue,
-            move || async move {
-                let client = get_connection_manager().get_client(&server_id, db).await?;
-                let mut conn = client.connection();
-                let mut binding = cmd("SET");
-                let mut new_cmd = binding.arg(key.as_str()).arg(new_value.as_str());
-                // keep ttl if the version is at least 6.0.0
-                new_cmd = if client.is_at_least_version("6.0.0") {
-                    new_cmd.arg("KEEPTTL")
-                } else if ttl > 0 {
-                    new_cmd.arg("PX").arg(ttl)
-                } else {
-                    new_cmd
-                };
-                let _: () = new_cmd.query_async(&mut conn).await?;
-
-                let mut size = None;
-                if let Ok(memory_usage) = cmd("MEMORY")
-                    .arg("USAGE")
-                    .arg(key.as_str())
-                    .query_async::<u64>(&mut conn)
-                    .await
-                {
-                    size = Some(memory_usage);
-                }
-
-                Ok(size)
-            },
+            move || async move { set_value_keep_ttl(&server_id, db, &key, new_value.as_bytes(), ttl).await },
             move |this, result, cx| {
+                let saved = result.is_ok();
                 if let Some(value) = this.value.as_mut() {
                     value.status = RedisValueStatus::Idle;
-                    if let Ok(result_size) = result {
-                        if let Some(size) = result_size {
-                            value.size = size;
-                        }
+                    if let Ok(memory_usage) = result {
+                        value.memory_usage = memory_usage;
                     } else {
                         // Recover original value if save failed
-                        value.size = original_size;
+                        value.memory_usage = original_memory_usage;
                         value.data = Some(RedisValueData::Bytes(original_bytes_value.clone()));
                     }
-                    cx.emit(ServerEvent::ValueUpdated(current_key));
+                    cx.emit(ServerEvent::ValueUpdated(current_key.clone()));
+                }
+                cx.emit(ServerEvent::ValueSaveFinished(current_key, saved));
+                cx.notify();
+            },
+            cx,
+        );
+        true
+    }
+
+    /// Encodes edited JSON back into protobuf and saves it to a Redis string key
+    ///
+    /// The JSON is validated against the message of the matched proto config
+    /// before writing, so invalid fields never reach Redis.
+    ///
+    /// Returns whether the save started, `ValueSaveFinished` is emitted once it is done.
+    pub fn save_proto_value(&mut self, key: SharedString, new_value: SharedString, cx: &mut Context<Self>) -> bool {
+        if self.readonly() {
+            return false;
+        }
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let Some(value) = self.value.as_mut() else {
+            return false;
+        };
+        if value.bytes_value().is_none() || value.truncated {
+            return false;
+        }
+        value.status = RedisValueStatus::Updating;
+        let current_key = key.clone();
+        let text = new_value.clone();
+        let ttl = value.ttl().map(|ttl| ttl.num_milliseconds()).unwrap_or_default();
+
+        cx.notify();
+        self.spawn(
+            ServerTask::SaveValue,
+            move || async move {
+                let id = ProtoManager::match_key_to_name(&server_id, &key).ok_or_else(|| Error::Invalid {
+                    message: format!("no proto config matches key: {key}"),
+                })?;
+                let bytes = ProtoManager::encode_data(&id, &new_value)?;
+                let memory_usage = set_value_keep_ttl(&server_id, db, &key, &bytes, ttl).await?;
+                Ok((bytes, memory_usage))
+            },
+            move |this, result, cx| {
+                let saved = result.is_ok();
+                if let Some(value) = this.value.as_mut() {
+                    value.status = RedisValueStatus::Idle;
+                    if let Ok((bytes, memory_usage)) = result {
+                        value.memory_usage = memory_usage;
+                        value.data = Some(RedisValueData::Bytes(Arc::new(RedisBytesValue {
+                            full_len: bytes.len(),
+                            bytes: Bytes::from(bytes),
+                            text: Some(text),
+                            format: DataFormat::Protobuf,
+                            ..Default::default()
+                        })));
+                    }
+                    cx.emit(ServerEvent::ValueUpdated(current_key.clone()));
                 }
+                cx.emit(ServerEvent::ValueSaveFinished(current_key, saved));
                 cx.notify();
             },
             cx,
         );
+        true
     }
 
-    pub fn update_bytes_value_view_mode(&mut self, view_mode: SharedString, cx: &mut Context<Self>) {
+    /// Changes the viewer mode of the current value only, the type default is kept
+    pub fn update_value_view_mode(&mut self, view_mode: SharedString, cx: &mut Context<Self>) {
         let Some(value) = self.value.as_mut() else {
             return;
         };
-        let view_mode = ViewMode::from_str(view_mode.as_str());
         let key = self.key.clone().unwrap_or_default();
         // Directly modify the data in place
-        if let Some(RedisValueData::Bytes(bytes_value)) = &mut value.data {
-            let bytes_value = Arc::make_mut(bytes_value);
-            bytes_value.view_mode = view_mode;
-            cx.emit(ServerEvent::ValueModeViewUpdated(key));
-            cx.notify();
-        }
+        value.set_view_mode(ViewMode::from_str(view_mode.as_str()));
+        cx.emit(ServerEvent::ValueModeViewUpdated(key));
+        cx.notify();
     }
 }
@@ -34,6 +34,7 @@ use crate::{
 use gpui::{SharedString, prelude::*};
 use redis::cmd;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -330,6 +331,9 @@ impl ZedisServerState {
             ..Default::default()
         };
         value.data = Some(RedisValueData::Zset(Arc::new(new_zset)));
+        // A new filter starts a new load, the batches of the previous one are dropped
+        value.load_cancel.store(true, Ordering::Relaxed);
+        value.load_cancel = Arc::default();
 
         // Trigger load with the new filter
         self.load_more_zset_value(cx);
@@ -349,6 +353,11 @@ impl ZedisServerState {
         let Some((key, value)) = self.try_get_mut_key_value() else {
             return;
         };
+        // A canceled load stays stopped until the filter changes
+        if value.is_load_canceled() {
+            return;
+        }
+        let cancel = value.load_cancel.clone();
 
         // Update UI to show loading state
         value.status = RedisValueStatus::Loading;
@@ -393,6 +402,10 @@ impl ZedisServerState {
             },
             // UI callback: merge results and handle auto-loading for filters
             move |this, result, cx| {
+                // Drop the batch of a canceled load, the rows loaded before stay
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
                 let mut should_load_more = false;
 
                 if let Ok((new_cursor, new_values)) = result
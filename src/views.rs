@@ -14,34 +14,48 @@
 
 mod about;
 mod bytes_editor;
+mod cluster_topology;
+mod command_log;
 mod content;
 mod editor;
 mod hash_editor;
+mod json_tree;
 mod key_tree;
 mod kv_table;
 mod list_editor;
+mod node_info;
 mod proto_editor;
+mod pubsub;
 mod servers;
 mod set_editor;
 mod setting_editor;
 mod sidebar;
+mod slowlog;
 mod status_bar;
+mod stream_editor;
 mod title_bar;
 mod zset_editor;
 
 pub use about::open_about_window;
 pub use bytes_editor::ZedisBytesEditor;
+pub use cluster_topology::open_cluster_topology_dialog;
+pub use command_log::open_command_log_dialog;
 pub use content::ZedisContent;
 pub use editor::ZedisEditor;
 pub use hash_editor::ZedisHashEditor;
+pub use json_tree::ZedisJsonTree;
 pub use key_tree::ZedisKeyTree;
 pub use kv_table::{KvTableColumn, KvTableColumnType, ZedisKvTable};
 pub use list_editor::ZedisListEditor;
+pub use node_info::open_node_info_dialog;
 pub use proto_editor::ZedisProtoEditor;
+pub use pubsub::open_pubsub_dialog;
 pub use servers::ZedisServers;
 pub use set_editor::ZedisSetEditor;
 pub use setting_editor::ZedisSettingEditor;
 pub use sidebar::ZedisSidebar;
+pub use slowlog::open_slowlog_dialog;
 pub use status_bar::ZedisStatusBar;
+pub use stream_editor::ZedisStreamEditor;
 pub use title_bar::ZedisTitleBar;
 pub use zset_editor::ZedisZsetEditor;
@@ -13,16 +13,21 @@
 // limitations under the License.
 
 use crate::helpers::get_font_family;
-use crate::states::{DataFormat, RedisBytesValue, ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState};
+use crate::states::{
+    DataFormat, RedisBytesValue, ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState, i18n_editor,
+};
 use gpui::{App, Entity, Image, ObjectFit, SharedString, Subscription, Window, img, px};
 use gpui::{div, hsla, prelude::*};
+use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::highlighter::Language;
 use gpui_component::input::{Input, InputEvent, InputState, TabSize};
 use gpui_component::label::Label;
 use gpui_component::list::{List, ListDelegate, ListItem, ListState};
-use gpui_component::{ActiveTheme, IndexPath, h_flex};
+use gpui_component::{ActiveTheme, Disableable, IconName, IndexPath, Sizable, h_flex, v_flex};
 use pretty_hex::HexConfig;
 use pretty_hex::config_hex;
+use regex::{NoExpand, Regex, RegexBuilder};
+use rust_i18n::t;
 use std::sync::Arc;
 use tracing::info;
 
@@ -72,6 +77,15 @@ pub struct ZedisBytesEditor {
     /// The data to display in the editor
     data: ByteEditorData,
 
+    /// Whether the find and replace bar is visible
+    show_replace: bool,
+    /// Text to find
+    find_state: Entity<InputState>,
+    /// Replacement text
+    replace_state: Entity<InputState>,
+    /// Whether find matches case
+    case_sensitive: bool,
+
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
 }
@@ -90,6 +104,17 @@ impl ByteEditorData {
         }
     }
 }
+/// Pretty prints the bytes as JSON, a double encoded value (a JSON string of JSON) is decoded once more
+fn decode_json_text(bytes: &[u8]) -> Option<SharedString> {
+    let mut value = serde_json::from_slice::<serde_json::Value>(bytes).ok()?;
+    if let serde_json::Value::String(text) = &value
+        && let Ok(inner) = serde_json::from_str::<serde_json::Value>(text)
+    {
+        value = inner;
+    }
+    serde_json::to_string_pretty(&value).ok().map(Into::into)
+}
+
 /// Extract string value from Redis value, with hex fallback for binary data
 ///
 /// If the value is a string, returns Text(SharedString).
@@ -139,6 +164,10 @@ fn format_byte_editor_data(value: &Arc<RedisBytesValue>, cx: &App) -> ByteEditor
             ByteEditorData::Text(text)
         }
 
+        ViewMode::Json => ByteEditorData::Text(
+            decode_json_text(&value.bytes).unwrap_or_else(|| String::from_utf8_lossy(&value.bytes).to_string().into()),
+        ),
+
         _ => {
             if value.is_image() {
                 let format = match value.format {
@@ -254,7 +283,7 @@ impl ZedisBytesEditor {
             }),
         );
 
-        let soft_wrap = server_state.read(cx).soft_wrap();
+        let soft_wrap = server_state.read(cx).editor_soft_wrap();
 
         // Configure code editor with JSON syntax highlighting
         let default_language = Language::from_str(DEFAULT_LANGUAGE);
@@ -279,7 +308,24 @@ impl ZedisBytesEditor {
                 // Compare with original value to determine if modified
                 let original = this.data.to_string().unwrap_or_default();
 
-                this.value_modified = original != value.as_str();
+                this.set_value_modified(original != value.as_str(), cx);
+                cx.notify();
+            }
+        }));
+
+        let find_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_editor(cx, "find_placeholder"))
+                .clean_on_escape()
+        });
+        let replace_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_editor(cx, "replace_placeholder"))
+                .clean_on_escape()
+        });
+        // Refresh the match count as the find text changes
+        subscriptions.push(cx.subscribe(&find_state, |_this, _, event, cx| {
+            if let InputEvent::Change = &event {
                 cx.notify();
             }
         }));
@@ -297,6 +343,10 @@ impl ZedisBytesEditor {
             should_update_editor: true,
             server_state,
             readonly,
+            show_replace: false,
+            find_state,
+            replace_state,
+            case_sensitive: false,
             _subscriptions: subscriptions,
         };
         this.update_editor_data(cx);
@@ -315,13 +365,21 @@ impl ZedisBytesEditor {
             return;
         }
 
-        // Reset modification flag since we're loading a new value
-        self.value_modified = false;
         let readonly = server_state.readonly();
 
         let redis_bytes_value = value.and_then(|v| v.bytes_value());
         if let Some(redis_bytes_value) = &redis_bytes_value {
-            self.readonly = readonly || !redis_bytes_value.is_utf8_text();
+            // Protobuf values are edited as JSON and encoded back on save,
+            // the raw structural view can't be parsed back so it stays read-only
+            let proto_raw_json = cx.global::<ZedisGlobalStore>().read(cx).proto_raw_json();
+            // A partially loaded value can't be saved back without losing the rest
+            let truncated = value.is_some_and(|value| value.is_truncated());
+            // The decoded JSON view may not match the stored bytes, so it can't be saved back
+            let editable = !truncated
+                && redis_bytes_value.view_mode != ViewMode::Json
+                && (redis_bytes_value.is_utf8_text()
+                    || (redis_bytes_value.format == DataFormat::Protobuf && !proto_raw_json));
+            self.readonly = readonly || !editable;
             self.data = format_byte_editor_data(redis_bytes_value, cx);
         } else {
             self.data = ByteEditorData::Text(SharedString::default());
@@ -330,6 +388,9 @@ impl ZedisBytesEditor {
         if !matches!(self.data, ByteEditorData::Hex(_)) {
             self.hex_viewer_state = None;
         }
+
+        // Reset modification flag since we're loading a new value
+        self.set_value_modified(false, cx);
     }
 
     /// Check if the current editor value differs from the original Redis value
@@ -337,6 +398,15 @@ impl ZedisBytesEditor {
         self.value_modified
     }
 
+    /// Track the modification state, mirrored to the server state so that
+    /// navigating away can ask before dropping the edits
+    fn set_value_modified(&mut self, value_modified: bool, cx: &mut Context<Self>) {
+        self.value_modified = value_modified;
+        self.server_state.update(cx, |state, _cx| {
+            state.set_unsaved_changes(value_modified);
+        });
+    }
+
     /// Check if the editor is readonly
     pub fn is_readonly(&self) -> bool {
         self.readonly
@@ -346,6 +416,115 @@ impl ZedisBytesEditor {
     pub fn value(&self, cx: &mut Context<Self>) -> SharedString {
         self.editor.read(cx).value()
     }
+
+    /// Whether find and replace can be used on the current value
+    pub fn can_replace(&self) -> bool {
+        !self.readonly && matches!(self.data, ByteEditorData::Text(_))
+    }
+
+    /// Show or hide the find and replace bar
+    pub fn toggle_replace(&mut self, cx: &mut Context<Self>) {
+        self.show_replace = !self.show_replace;
+        cx.notify();
+    }
+
+    /// Build the matcher for the find text, `None` if there is nothing to find
+    fn find_regex(&self, cx: &App) -> Option<Regex> {
+        let find = self.find_state.read(cx).value();
+        if find.is_empty() {
+            return None;
+        }
+        RegexBuilder::new(&regex::escape(&find))
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .ok()
+    }
+
+    /// Replace the first match (or all matches) in the editor buffer.
+    ///
+    /// Only the in-memory text changes, the value is written to Redis on save.
+    fn replace(&mut self, all: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(re) = self.find_regex(cx) else {
+            return;
+        };
+        let replacement = self.replace_state.read(cx).value();
+        let text = self.editor.read(cx).value();
+        let limit = if all { 0 } else { 1 };
+        let new_text = re.replacen(&text, limit, NoExpand(&replacement));
+        if new_text == text.as_str() {
+            return;
+        }
+        let new_text = new_text.to_string();
+        let original = self.data.to_string().unwrap_or_default();
+        self.set_value_modified(original != new_text.as_str(), cx);
+        self.editor.update(cx, |state, cx| {
+            state.set_value(new_text, window, cx);
+        });
+        cx.notify();
+    }
+
+    fn render_replace_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let match_count = self
+            .find_regex(cx)
+            .map(|re| re.find_iter(&self.editor.read(cx).value()).count())
+            .unwrap_or_default();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let match_label = t!("editor.replace_match_count", count = match_count, locale = locale).to_string();
+        h_flex()
+            .w_full()
+            .gap_2()
+            .p_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(Input::new(&self.find_state).small().flex_1())
+            .child(
+                Button::new("zedis-bytes-editor-case-sensitive")
+                    .small()
+                    .label("Aa")
+                    .tooltip(i18n_editor(cx, "case_sensitive_tooltip"))
+                    .when(self.case_sensitive, |this| this.primary())
+                    .when(!self.case_sensitive, |this| this.ghost())
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.case_sensitive = !this.case_sensitive;
+                        cx.notify();
+                    })),
+            )
+            .child(Input::new(&self.replace_state).small().flex_1())
+            .child(
+                Label::new(match_label)
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .child(
+                Button::new("zedis-bytes-editor-replace")
+                    .small()
+                    .outline()
+                    .disabled(match_count == 0)
+                    .label(i18n_editor(cx, "replace"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.replace(false, window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-bytes-editor-replace-all")
+                    .small()
+                    .outline()
+                    .disabled(match_count == 0)
+                    .label(i18n_editor(cx, "replace_all"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.replace(true, window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-bytes-editor-replace-close")
+                    .small()
+                    .ghost()
+                    .icon(IconName::Close)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.toggle_replace(cx);
+                    })),
+            )
+    }
 }
 
 impl Render for ZedisBytesEditor {
@@ -386,7 +565,7 @@ impl Render for ZedisBytesEditor {
                         this.set_value(value, window, cx);
                     });
                 }
-                Input::new(&self.editor)
+                let editor = Input::new(&self.editor)
                     .flex_1()
                     .bordered(false)
                     .disabled(self.readonly)
@@ -395,8 +574,16 @@ impl Render for ZedisBytesEditor {
                     .w_full()
                     .h_full()
                     .font_family(get_font_family())
-                    .focus_bordered(false)
-                    .into_any_element()
+                    .focus_bordered(false);
+                if self.show_replace && self.can_replace() {
+                    v_flex()
+                        .size_full()
+                        .child(self.render_replace_bar(cx))
+                        .child(editor)
+                        .into_any_element()
+                } else {
+                    editor.into_any_element()
+                }
             }
         }
     }
@@ -0,0 +1,309 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::assets::CustomIconName;
+use crate::connection::{ClusterNodeInfo, NodeRole, get_connection_manager};
+use crate::helpers::get_font_family;
+use crate::states::{ZedisGlobalStore, ZedisServerState, i18n_status_bar};
+use gpui::{App, Entity, SharedString, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    notification::Notification,
+    table::{Column, Table, TableDelegate, TableState},
+    v_flex,
+};
+use rust_i18n::t;
+use std::sync::Arc;
+
+/// Total hash slots of a cluster
+const CLUSTER_SLOTS: usize = 16_384;
+/// Characters of the node ids shown, like git short hashes
+const SHORT_NODE_ID_LEN: usize = 8;
+
+fn short_node_id(id: &str) -> &str {
+    id.get(..SHORT_NODE_ID_LEN).unwrap_or(id)
+}
+
+/// Formats slot ranges like `0-5460, 10923`
+fn format_slots(slots: &[(u16, u16)]) -> String {
+    slots
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn slot_count(slots: &[(u16, u16)]) -> usize {
+    slots
+        .iter()
+        .map(|(start, end)| (*end as usize).saturating_sub(*start as usize) + 1)
+        .sum()
+}
+
+/// Masters ordered by their first slot, each followed by its replicas
+fn sort_topology(nodes: Vec<ClusterNodeInfo>) -> Vec<ClusterNodeInfo> {
+    let (mut masters, others): (Vec<_>, Vec<_>) = nodes.into_iter().partition(|node| node.role == NodeRole::Master);
+    masters.sort_by_key(|node| node.slots.first().map(|(start, _)| *start).unwrap_or(u16::MAX));
+    let mut sorted = Vec::with_capacity(masters.len() + others.len());
+    let mut others: Vec<Option<ClusterNodeInfo>> = others.into_iter().map(Some).collect();
+    for master in masters {
+        let id = master.id.clone();
+        sorted.push(master);
+        for node in others.iter_mut() {
+            if node
+                .as_ref()
+                .is_some_and(|node| node.master_id.as_deref() == Some(id.as_str()))
+            {
+                sorted.extend(node.take());
+            }
+        }
+    }
+    // Replicas of unknown masters and failed nodes go last
+    sorted.extend(others.into_iter().flatten());
+    sorted
+}
+
+struct ClusterTopologyTableDelegate {
+    nodes: Arc<Vec<ClusterNodeInfo>>,
+    columns: Vec<Column>,
+    master_label: SharedString,
+    replica_label: SharedString,
+    fail_label: SharedString,
+}
+
+impl ClusterTopologyTableDelegate {
+    fn role_label(&self, node: &ClusterNodeInfo) -> String {
+        match node.role {
+            NodeRole::Master => self.master_label.to_string(),
+            NodeRole::Slave => match &node.master_id {
+                Some(master_id) => format!("{} ({})", self.replica_label, short_node_id(master_id)),
+                None => self.replica_label.to_string(),
+            },
+            NodeRole::Fail => self.fail_label.to_string(),
+            NodeRole::Unknown => "-".to_string(),
+        }
+    }
+}
+
+/// Migrations of a node like `5461 -> 9f8e7d6c, 5462 <- 1a2b3c4d`
+fn format_migrations(node: &ClusterNodeInfo) -> String {
+    node.migrating
+        .iter()
+        .map(|(slot, id)| format!("{slot} -> {}", short_node_id(id)))
+        .chain(
+            node.importing
+                .iter()
+                .map(|(slot, id)| format!("{slot} <- {}", short_node_id(id))),
+        )
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl TableDelegate for ClusterTopologyTableDelegate {
+    fn columns_count(&self, _: &App) -> usize {
+        self.columns.len()
+    }
+
+    fn rows_count(&self, _: &App) -> usize {
+        self.nodes.len()
+    }
+
+    fn column(&self, index: usize, _: &App) -> &Column {
+        &self.columns[index]
+    }
+
+    fn render_td(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        let text = if let Some(node) = self.nodes.get(row_ix) {
+            match col_ix {
+                0 => format!("{}:{}", node.ip, node.port),
+                1 => short_node_id(&node.id).to_string(),
+                2 => self.role_label(node),
+                3 => format_slots(&node.slots),
+                4 if node.role == NodeRole::Master => slot_count(&node.slots).to_string(),
+                5 => format_migrations(node),
+                _ => String::new(),
+            }
+        } else {
+            String::new()
+        };
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .when(col_ix == 1 || col_ix == 3 || col_ix == 5, |this| {
+                this.font_family(get_font_family())
+            })
+            .child(Label::new(text))
+    }
+}
+
+/// Lists the nodes of a cluster with their role, slot ranges and slot migrations.
+pub struct ZedisClusterTopology {
+    server_id: String,
+    db: usize,
+    table_state: Entity<TableState<ClusterTopologyTableDelegate>>,
+    /// Slots served by a master, less than `CLUSTER_SLOTS` when some aren't covered
+    covered_slots: usize,
+    masters: usize,
+    loading: bool,
+}
+
+impl ZedisClusterTopology {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let state = server_state.read(cx);
+        let server_id = state.server_id().to_string();
+        let db = state.db();
+        let columns = vec![
+            Column::new("node", i18n_status_bar(cx, "cluster_topology_node")).width(px(160.)),
+            Column::new("id", i18n_status_bar(cx, "cluster_topology_id")).width(px(90.)),
+            Column::new("role", i18n_status_bar(cx, "cluster_topology_role")).width(px(150.)),
+            Column::new("slots", i18n_status_bar(cx, "cluster_topology_slots")).width(px(220.)),
+            Column::new("count", i18n_status_bar(cx, "cluster_topology_slot_count")).width(px(80.)),
+            Column::new("migrations", i18n_status_bar(cx, "cluster_topology_migrations")).width(px(220.)),
+        ];
+        let delegate = ClusterTopologyTableDelegate {
+            nodes: Arc::new(vec![]),
+            columns,
+            master_label: i18n_status_bar(cx, "cluster_topology_master"),
+            replica_label: i18n_status_bar(cx, "cluster_topology_replica"),
+            fail_label: i18n_status_bar(cx, "cluster_topology_fail"),
+        };
+        let table_state = cx.new(|cx| TableState::new(delegate, window, cx));
+        let mut this = Self {
+            server_id,
+            db,
+            table_state,
+            covered_slots: 0,
+            masters: 0,
+            loading: false,
+        };
+        this.load(window, cx);
+        this
+    }
+    /// Fetch `CLUSTER NODES` again
+    fn load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.loading = true;
+        cx.notify();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    let client = get_connection_manager().get_client(&server_id, db).await?;
+                    client.cluster_nodes().await
+                })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| {
+                this.loading = false;
+                match result {
+                    Ok(nodes) => {
+                        let masters: Vec<&ClusterNodeInfo> =
+                            nodes.iter().filter(|node| node.role == NodeRole::Master).collect();
+                        this.masters = masters.len();
+                        this.covered_slots = masters.iter().map(|node| slot_count(&node.slots)).sum();
+                        let nodes = sort_topology(nodes);
+                        this.table_state.update(cx, |state, cx| {
+                            state.delegate_mut().nodes = Arc::new(nodes);
+                            cx.notify();
+                        });
+                    }
+                    Err(e) => {
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for ZedisClusterTopology {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let summary: SharedString = t!(
+            "status_bar.cluster_topology_summary",
+            masters = self.masters,
+            covered = self.covered_slots,
+            total = CLUSTER_SLOTS,
+            locale = locale
+        )
+        .into();
+        let summary_color = if self.covered_slots < CLUSTER_SLOTS && !self.loading {
+            cx.theme().yellow
+        } else {
+            cx.theme().muted_foreground
+        };
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(Label::new(summary).text_sm().text_color(summary_color)),
+                    )
+                    .child(
+                        Button::new("zedis-cluster-topology-reload")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::RotateCw)
+                            .tooltip(i18n_status_bar(cx, "cluster_topology_reload"))
+                            .loading(self.loading)
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.load(window, cx);
+                            })),
+                    ),
+            )
+            .child(
+                div().w_full().h(px(480.0)).child(
+                    Table::new(&self.table_state)
+                        .stripe(true)
+                        .bordered(true)
+                        .scrollbar_visible(true, true),
+                ),
+            )
+    }
+}
+
+/// Opens a dialog showing the slot ownership of each node of a cluster.
+pub fn open_cluster_topology_dialog(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut App) {
+    let view = cx.new(|cx| ZedisClusterTopology::new(server_state, window, cx));
+    window.open_dialog(cx, move |dialog, _, cx| {
+        dialog
+            .title(i18n_status_bar(cx, "cluster_topology_title"))
+            .width(px(1000.0))
+            .overlay_closable(true)
+            .child(view.clone())
+    });
+}
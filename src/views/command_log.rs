@@ -0,0 +1,154 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::assets::CustomIconName;
+use crate::connection::{CommandLog, clear_command_logs, command_logs};
+use crate::helpers::get_font_family;
+use crate::states::{ZedisGlobalStore, i18n_settings};
+use gpui::{App, ClipboardItem, Entity, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, IconName, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    notification::Notification,
+    v_flex,
+};
+use rust_i18n::t;
+
+/// Renders a log entry as one line: time, node, latency, command and the error if any
+fn format_command_log(log: &CommandLog) -> String {
+    let mut line = format!(
+        "{} {} {:.1}ms {}",
+        log.time.format("%H:%M:%S%.3f"),
+        log.node,
+        log.latency.as_secs_f64() * 1000.0,
+        log.command
+    );
+    if let Some(error) = &log.error {
+        line.push_str(&format!(" -> ERR {error}"));
+    }
+    line
+}
+
+/// Shows the commands recorded by the connection layer while command logging is on.
+pub struct ZedisCommandLog {
+    content_state: Entity<InputState>,
+    count: usize,
+}
+
+impl ZedisCommandLog {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let content_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .searchable(true)
+                .soft_wrap(false)
+        });
+        let mut this = Self {
+            content_state,
+            count: 0,
+        };
+        this.reload(window, cx);
+        this
+    }
+    /// Read the recorded commands again, newest at the bottom
+    fn reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let logs = command_logs();
+        self.count = logs.len();
+        let content = logs.iter().map(format_command_log).collect::<Vec<_>>().join("\n");
+        self.content_state.update(cx, |state, cx| {
+            state.set_value(content, window, cx);
+        });
+        cx.notify();
+    }
+}
+
+impl Render for ZedisCommandLog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        div().flex_1().child(
+                            Label::new(
+                                t!("settings.command_log_count", count = self.count, locale = locale).to_string(),
+                            )
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground),
+                        ),
+                    )
+                    .child(
+                        Button::new("zedis-command-log-reload")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::RotateCw)
+                            .tooltip(i18n_settings(cx, "command_log_reload"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.reload(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("zedis-command-log-clear")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::Eraser)
+                            .tooltip(i18n_settings(cx, "command_log_clear"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                clear_command_logs();
+                                this.reload(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("zedis-command-log-copy")
+                            .outline()
+                            .small()
+                            .icon(IconName::Copy)
+                            .tooltip(i18n_settings(cx, "command_log_copy"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                let content = this.content_state.read(cx).value();
+                                cx.write_to_clipboard(ClipboardItem::new_string(content.to_string()));
+                                window
+                                    .push_notification(Notification::info(i18n_settings(cx, "command_log_copied")), cx);
+                            })),
+                    ),
+            )
+            .child(
+                div().w_full().h(px(480.0)).child(
+                    Input::new(&self.content_state)
+                        .h_full()
+                        .font_family(get_font_family())
+                        .text_xs(),
+                ),
+            )
+    }
+}
+
+/// Opens a dialog listing the commands logged so far.
+pub fn open_command_log_dialog(window: &mut Window, cx: &mut App) {
+    let view = cx.new(|cx| ZedisCommandLog::new(window, cx));
+    window.open_dialog(cx, move |dialog, _, cx| {
+        dialog
+            .title(i18n_settings(cx, "command_log_title"))
+            .width(px(900.0))
+            .overlay_closable(true)
+            .child(view.clone())
+    });
+}
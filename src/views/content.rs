@@ -13,24 +13,29 @@
 // limitations under the License.
 
 use crate::{
-    components::SkeletonLoading,
+    components::{SkeletonLoading, platform_button_order},
     connection::{get_command_description, get_connection_manager, list_commands},
     error::Error,
     helpers::{
         EditorAction, get_font_family, get_key_tree_widths, redis_value_to_string, starts_with_ignore_ascii_case,
     },
-    states::{Route, ServerEvent, ZedisGlobalStore, ZedisServerState, save_app_state},
+    states::{
+        HostKeyPrompt, Route, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_servers,
+        save_app_state,
+    },
     views::{ZedisEditor, ZedisKeyTree, ZedisProtoEditor, ZedisServers, ZedisSettingEditor, ZedisStatusBar},
 };
 use gpui::{Entity, FocusHandle, Pixels, ScrollHandle, SharedString, Subscription, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme,
+    ActiveTheme, WindowExt,
+    button::{Button, ButtonVariants},
     input::{Input, InputEvent, InputState},
     label::Label,
     resizable::{ResizableState, h_resizable, resizable_panel},
     v_flex,
 };
 use redis::cmd;
+use rust_i18n::t;
 use tracing::{debug, error, info};
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -142,10 +147,22 @@ impl ZedisContent {
                 ServerEvent::ServerSelected(_, _) => {
                     this.reset_cmd_state(cx);
                 }
+                ServerEvent::ConnectionProgress(_) => {
+                    cx.notify();
+                }
                 _ => {}
             }),
         );
 
+        // Ask whether to trust an SSH server missing from known_hosts, the dialog needs the window
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |view, _state, event, window, cx| {
+                if let ServerEvent::HostKeyPrompted(prompt) = event {
+                    view.prompt_host_key(prompt.clone(), window, cx);
+                }
+            }),
+        );
+
         // Restore persisted key tree width from global state
         let global_store = cx.global::<ZedisGlobalStore>().read(cx);
         let key_tree_width = global_store.key_tree_width();
@@ -218,6 +235,47 @@ impl ZedisContent {
             _subscriptions: subscriptions,
         }
     }
+    /// Show the fingerprint of an unknown SSH host and let the user trust it
+    fn prompt_host_key(&mut self, prompt: HostKeyPrompt, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!(
+                "servers.host_key_prompt",
+                host = prompt.host,
+                port = prompt.port,
+                fingerprint = prompt.fingerprint,
+                locale = locale
+            )
+            .to_string();
+            let server_state = server_state.clone();
+            let prompt = prompt.clone();
+
+            dialog
+                .title(i18n_servers(cx, "host_key_title"))
+                .child(message)
+                .footer(move |_, _, _, cx| {
+                    let cancel_label = i18n_common(cx, "cancel");
+                    let trust_label = i18n_servers(cx, "host_key_trust");
+
+                    platform_button_order(vec![
+                        Button::new("cancel").label(cancel_label).on_click(|_, window, cx| {
+                            window.close_dialog(cx);
+                        }),
+                        Button::new("trust").primary().label(trust_label).on_click({
+                            let server_state = server_state.clone();
+                            let prompt = prompt.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                server_state.update(cx, |state, cx| {
+                                    state.trust_host_key(prompt.clone(), cx);
+                                });
+                            }
+                        }),
+                    ])
+                })
+        });
+    }
     fn reset_cmd_state(&mut self, _cx: &mut Context<Self>) {
         self.cmd_outputs.clear();
         self.cmd_outputs.extend(
@@ -382,13 +440,27 @@ impl ZedisContent {
     ///
     /// Displayed when the application is busy (e.g., connecting to Redis server,
     /// loading keys). Provides visual feedback that something is happening.
-    fn render_loading(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_loading(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Long connects (SSH, sentinel, cluster) show the step they are at so they don't look hung
+        let step = self
+            .server_state
+            .read(cx)
+            .connection_step()
+            .map(|step| i18n_common(cx, &format!("connection_step_{}", step.as_str())));
         v_flex()
             .w_full()
             .h_full()
             .items_center()
             .justify_center()
             .child(div().w(px(LOADING_SKELETON_WIDTH)).child(SkeletonLoading::new()))
+            .when_some(step, |this, step| {
+                this.child(
+                    Label::new(step)
+                        .mt_4()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
     }
     /// Render the main editor interface with resizable panels
     ///
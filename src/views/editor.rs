@@ -14,14 +14,27 @@
 
 use crate::{
     assets::CustomIconName,
-    helpers::{EditorAction, format_duration, humanize_keystroke, validate_ttl},
-    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_editor},
-    views::{ZedisBytesEditor, ZedisHashEditor, ZedisListEditor, ZedisSetEditor, ZedisZsetEditor},
+    components::{
+        FormDialog, FormField, delete_command_field, export_to_file, open_add_form_dialog, platform_button_order,
+        sanitize_file_name, selected_delete_command,
+    },
+    connection::AccessMode,
+    db::ProtoManager,
+    helpers::{EditorAction, format_duration, format_timestamp, humanize_keystroke, parse_duration, validate_ttl},
+    states::{
+        DataFormat, DeleteCommand, KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_editor,
+    },
+    views::{
+        ZedisBytesEditor, ZedisHashEditor, ZedisJsonTree, ZedisListEditor, ZedisSetEditor, ZedisStreamEditor,
+        ZedisZsetEditor,
+    },
 };
-use gpui::{ClipboardItem, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use gpui::{App, ClipboardItem, Entity, SharedString, Subscription, Task, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, WindowExt,
-    button::Button,
+    ActiveTheme, Disableable, Icon, IconName, Selectable, Sizable, WindowExt,
+    alert::Alert,
+    button::{Button, ButtonVariants},
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
@@ -31,12 +44,15 @@ use gpui_component::{
 };
 use humansize::{DECIMAL, format_size};
 use rust_i18n::t;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
 // Constants
 const RECENTLY_SELECTED_THRESHOLD_MS: u64 = 300;
 const TTL_INPUT_MAX_WIDTH: f32 = 100.0;
+const TTL_ABSOLUTE_WIDTH: f32 = 180.0;
+const VALUE_CHANGED_HIGHLIGHT_MS: u64 = 3000;
 
 /// Main editor component for displaying and editing Redis key values
 /// Supports different key types (String, List, etc.) with type-specific editors
@@ -50,6 +66,12 @@ pub struct ZedisEditor {
     set_editor: Option<Entity<ZedisSetEditor>>,
     zset_editor: Option<Entity<ZedisZsetEditor>>,
     hash_editor: Option<Entity<ZedisHashEditor>>,
+    stream_editor: Option<Entity<ZedisStreamEditor>>,
+
+    /// Show string values that are JSON as a collapsible tree
+    json_tree_enabled: bool,
+    /// The tree of the value, None when it isn't valid JSON
+    json_tree: Option<Entity<ZedisJsonTree>>,
 
     /// TTL editing state
     should_enter_ttl_edit_mode: Option<bool>,
@@ -59,7 +81,18 @@ pub struct ZedisEditor {
     /// Track when a key was selected to handle loading states smoothly
     selected_key_at: Option<Instant>,
 
+    /// Whether write operations are disabled, and why
     readonly: bool,
+    access_mode: AccessMode,
+
+    /// Key whose pending save continues the held back navigation once it finishes
+    resume_after_save: Option<SharedString>,
+
+    /// Fingerprint of the last loaded value, to notice it changed on the server when reloaded
+    value_fingerprint: Option<(SharedString, u64)>,
+    /// Whether the reloaded value differs from the one displayed before
+    value_changed: bool,
+    value_changed_task: Option<Task<()>>,
 
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
@@ -76,6 +109,25 @@ fn format_ttl_string(ttl: &str) -> String {
     trimmed.to_string()
 }
 
+/// The new name of a key from a form, warns and returns `None` when it's empty or unchanged
+fn validate_new_key_name(
+    key: &str,
+    value: Option<&SharedString>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<SharedString> {
+    let new_key = value.cloned().unwrap_or_default();
+    let warning = if new_key.trim().is_empty() {
+        "new_key_name_required"
+    } else if new_key == key {
+        "new_key_name_unchanged"
+    } else {
+        return Some(new_key);
+    };
+    window.push_notification(Notification::warning(i18n_editor(cx, warning)), cx);
+    None
+}
+
 impl ZedisEditor {
     /// Create a new editor instance with event subscriptions
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
@@ -100,8 +152,56 @@ impl ZedisEditor {
                 ServerEvent::KeySelected(_) => {
                     this.selected_key_at = Some(Instant::now());
                 }
+                ServerEvent::ValueLoaded(key) => {
+                    let state = server_state.read(cx);
+                    let fingerprint = state.value().map(|value| value.fingerprint());
+                    let changed = match (&this.value_fingerprint, fingerprint) {
+                        (Some((previous_key, previous)), Some(current)) => previous_key == key && *previous != current,
+                        _ => false,
+                    };
+                    let dirty = state.has_unsaved_changes();
+                    let text = state
+                        .value()
+                        .and_then(|value| value.bytes_value())
+                        .and_then(|value| value.text.clone());
+                    if this.json_tree_enabled {
+                        this.update_json_tree(text.as_deref().unwrap_or_default(), cx);
+                    }
+                    this.value_fingerprint = fingerprint.map(|fingerprint| (key.clone(), fingerprint));
+                    if changed && !dirty {
+                        this.highlight_value_changed(cx);
+                    } else if this.value_changed {
+                        this.value_changed = false;
+                        this.value_changed_task = None;
+                        cx.notify();
+                    }
+                }
+                ServerEvent::ValueAdded(_) => {
+                    // Our own change, the next load must not be reported as an external one
+                    this.value_fingerprint = None;
+                }
+                ServerEvent::ValueUpdated(_) => {
+                    this.value_fingerprint = None;
+                }
+                ServerEvent::ValueSaveFinished(key, saved) => {
+                    // Only the save confirmed in the dialog continues, a failed one stays on the edited value
+                    if this.resume_after_save.as_ref() != Some(key) {
+                        return;
+                    }
+                    this.resume_after_save = None;
+                    server_state.update(cx, |state, cx| {
+                        if *saved {
+                            state.resume_navigation(cx);
+                        } else {
+                            state.cancel_navigation();
+                        }
+                    });
+                }
                 ServerEvent::ServerInfoUpdated(_) => {
-                    this.readonly = server_state.read(cx).readonly();
+                    let state = server_state.read(cx);
+                    this.readonly = state.readonly();
+                    this.access_mode = state.access_mode();
+                    cx.notify();
                 }
                 ServerEvent::EditionActionTriggered(action) => match action {
                     EditorAction::UpdateTtl => {
@@ -117,6 +217,15 @@ impl ZedisEditor {
             }),
         );
 
+        // Ask before dropping unsaved value edits, the dialog needs the window
+        subscriptions.push(
+            cx.subscribe_in(&server_state, window, |view, _state, event, window, cx| {
+                if let ServerEvent::UnsavedChangesPrompted(_) = event {
+                    view.prompt_unsaved_changes(window, cx);
+                }
+            }),
+        );
+
         // Subscribe to TTL input events for Enter key and blur
         subscriptions.push(cx.subscribe_in(
             &ttl_input_state,
@@ -134,6 +243,7 @@ impl ZedisEditor {
         ));
 
         let readonly = server_state.read(cx).readonly();
+        let access_mode = server_state.read(cx).access_mode();
         info!("Creating new editor view");
 
         Self {
@@ -143,10 +253,18 @@ impl ZedisEditor {
             set_editor: None,
             zset_editor: None,
             hash_editor: None,
+            stream_editor: None,
+            json_tree_enabled: false,
+            json_tree: None,
             readonly,
+            access_mode,
             ttl_edit_mode: false,
             ttl_input_state,
             should_enter_ttl_edit_mode: None,
+            resume_after_save: None,
+            value_fingerprint: None,
+            value_changed: false,
+            value_changed_task: None,
             _subscriptions: subscriptions,
             selected_key_at: None,
         }
@@ -159,18 +277,71 @@ impl ZedisEditor {
             .map(|t| t.elapsed() < Duration::from_millis(RECENTLY_SELECTED_THRESHOLD_MS))
             .unwrap_or(false)
     }
-    /// Handle TTL update when user submits new value
-    fn handle_update_ttl(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+    /// Show the editor content as a JSON tree, or back as text
+    fn toggle_json_tree(&mut self, cx: &mut Context<Self>) {
+        self.json_tree_enabled = !self.json_tree_enabled;
+        if !self.json_tree_enabled {
+            self.json_tree = None;
+        } else if let Some(bytes_editor) = self.bytes_editor.clone() {
+            // The edited content, not the loaded value
+            let text = bytes_editor.update(cx, |state, cx| state.value(cx));
+            self.update_json_tree(&text, cx);
+        }
+        cx.notify();
+    }
+    /// Rebuild the JSON tree from `text`, dropping it when `text` isn't valid JSON
+    fn update_json_tree(&mut self, text: &str, cx: &mut Context<Self>) {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+            self.json_tree = None;
+            cx.notify();
+            return;
+        };
+        if let Some(json_tree) = &self.json_tree {
+            json_tree.update(cx, |state, cx| {
+                state.set_value(&value, cx);
+            });
+        } else {
+            self.json_tree = Some(cx.new(|cx| ZedisJsonTree::new(&value, cx)));
+        }
+        cx.notify();
+    }
+    /// Briefly highlight the editor because the reloaded value was changed on the server
+    fn highlight_value_changed(&mut self, cx: &mut Context<Self>) {
+        self.value_changed = true;
+        cx.notify();
+        self.value_changed_task = Some(cx.spawn(async move |handle, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(VALUE_CHANGED_HIGHLIGHT_MS))
+                .await;
+            let _ = handle.update(cx, |this, cx| {
+                this.value_changed = false;
+                cx.notify();
+            });
+        }));
+    }
+    /// Handle TTL update when user submits new value, an empty value removes the TTL
+    fn handle_update_ttl(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let key = self.server_state.clone().read(cx).key().unwrap_or_default();
         if key.is_empty() {
             return;
         }
 
+        let ttl = self.ttl_input_state.read(cx).value();
+        let seconds = if ttl.trim().is_empty() {
+            None
+        } else {
+            match parse_duration(&format_ttl_string(&ttl)) {
+                Ok(ttl) => Some(ttl.as_secs() as i64),
+                Err(e) => {
+                    window.push_notification(Notification::error(e.to_string()), cx);
+                    return;
+                }
+            }
+        };
         self.ttl_edit_mode = false;
-        let ttl = format_ttl_string(&self.ttl_input_state.read(cx).value());
 
         self.server_state.update(cx, move |state, cx| {
-            state.update_key_ttl(key, ttl.into(), cx);
+            state.set_ttl(key, seconds, cx);
         });
         cx.notify();
     }
@@ -182,24 +353,96 @@ impl ZedisEditor {
         };
 
         let server_state = self.server_state.clone();
-        window.open_dialog(cx, move |dialog, _, cx| {
-            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
-            let message = t!("editor.delete_key_prompt", key = key, locale = locale).to_string();
-            let server_state = server_state.clone();
+        let commands = server_state.read(cx).delete_commands(cx);
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!("editor.delete_key_prompt", key = key, locale = locale).to_string();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, _window: &mut Window, cx: &mut App| {
             let key = key.clone();
-
-            dialog
-                .confirm()
-                .child(v_flex().w_full().max_h(px(200.0)).overflow_y_scrollbar().child(message))
-                .on_ok(move |_, window, cx| {
-                    let key = key.clone();
-                    server_state.update(cx, move |state, cx| {
-                        state.delete_select_key(key, cx);
-                    });
-                    window.close_dialog(cx);
-                    true
-                })
+            let command = selected_delete_command(&commands, values.first());
+            server_state.update(cx, move |state, cx| {
+                state.delete_select_key(key, command, cx);
+            });
+            true
         });
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_editor(cx, "delete_key_title"),
+                fields: vec![delete_command_field(message.into(), &commands, cx)],
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+    /// Opens a dialog asking for the new name of the key, then renames it with RENAME
+    fn rename_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(key) = self.server_state.read(cx).key() else {
+            return;
+        };
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(new_key) = validate_new_key_name(&key, values.first(), window, cx) else {
+                return false;
+            };
+            let key = key.clone();
+            server_state.update(cx, move |state, cx| {
+                state.rename_key(key, new_key, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_editor(cx, "new_key_name"))
+                .with_placeholder(self.server_state.read(cx).key().unwrap_or_default())
+                .with_focus(),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_editor(cx, "rename_key_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+    /// Opens a dialog asking for the name of the copy, then duplicates the key with COPY
+    fn copy_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(key) = self.server_state.read(cx).key() else {
+            return;
+        };
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(new_key) = validate_new_key_name(&key, values.first(), window, cx) else {
+                return false;
+            };
+            // values[1] is the index of the replace choice, "1" overwrites an existing key
+            let replace = values.get(1).is_some_and(|value| value == "1");
+            let key = key.clone();
+            server_state.update(cx, move |state, cx| {
+                state.copy_key(key, new_key, replace, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_editor(cx, "new_key_name"))
+                .with_placeholder(self.server_state.read(cx).key().unwrap_or_default())
+                .with_focus(),
+            FormField::new(i18n_editor(cx, "copy_key_existing")).with_options(vec![
+                i18n_editor(cx, "copy_key_keep_existing"),
+                i18n_editor(cx, "copy_key_replace_existing"),
+            ]),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_editor(cx, "copy_key_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
     }
     fn reload(&mut self, cx: &mut Context<Self>) {
         let Some(key) = self.server_state.read(cx).key() else {
@@ -209,24 +452,223 @@ impl ZedisEditor {
             state.select_key(key, cx);
         });
     }
-    fn save(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+    /// Save the edited value, returns false if there was nothing to save
+    fn save(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> bool {
+        self.start_save(cx).is_some()
+    }
+    /// Starts saving the edited value, returns the saved key when the save started
+    fn start_save(&mut self, cx: &mut Context<Self>) -> Option<SharedString> {
+        if self.readonly {
+            return None;
+        }
         let server_state = self.server_state.read(cx);
         let is_busy = server_state.value().map(|v| v.is_busy()).unwrap_or(false);
         if is_busy {
+            return None;
+        }
+        let key = server_state.key()?;
+        let is_proto = server_state
+            .value()
+            .and_then(|value| value.bytes_value())
+            .is_some_and(|value| value.format == DataFormat::Protobuf);
+        let editor = self.bytes_editor.clone()?;
+        let saved_key = key.clone();
+        let started = editor.update(cx, move |state, cx| {
+            let value = state.value(cx);
+            self.server_state.update(cx, move |state, cx| {
+                if is_proto {
+                    state.save_proto_value(key, value, cx)
+                } else {
+                    state.save_value(key, value, cx)
+                }
+            })
+        });
+        started.then_some(saved_key)
+    }
+    /// Ask whether to save or discard the value edits before leaving the key.
+    ///
+    /// Cancel keeps the edits and drops the held back navigation.
+    fn prompt_unsaved_changes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let key = self.server_state.read(cx).key().unwrap_or_default();
+        let view = cx.entity();
+        let server_state = self.server_state.clone();
+        // Quitting may come from a hidden window (macOS keeps it around)
+        window.activate_window();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!("editor.unsaved_changes_prompt", key = key, locale = locale).to_string();
+            let view = view.clone();
+            let server_state = server_state.clone();
+
+            dialog
+                .title(i18n_editor(cx, "unsaved_changes_title"))
+                .child(v_flex().w_full().max_h(px(200.0)).overflow_y_scrollbar().child(message))
+                .footer(move |_, _, _, cx| {
+                    let cancel_label = i18n_common(cx, "cancel");
+                    let discard_label = i18n_editor(cx, "discard");
+                    let save_label = i18n_common(cx, "save");
+
+                    platform_button_order(vec![
+                        // Cancel button - keeps the edits and stays on the key
+                        Button::new("cancel").label(cancel_label).on_click({
+                            let server_state = server_state.clone();
+                            move |_, window, cx| {
+                                server_state.update(cx, |state, _cx| {
+                                    state.cancel_navigation();
+                                });
+                                window.close_dialog(cx);
+                            }
+                        }),
+                        // Discard button - drops the edits and continues
+                        Button::new("discard").label(discard_label).on_click({
+                            let server_state = server_state.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                server_state.update(cx, |state, cx| {
+                                    state.resume_navigation(cx);
+                                });
+                            }
+                        }),
+                        // Save button - continues once the value is saved
+                        Button::new("save").primary().label(save_label).on_click({
+                            let view = view.clone();
+                            let server_state = server_state.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                let saving = view.update(cx, |this, cx| {
+                                    this.resume_after_save = this.start_save(cx);
+                                    this.resume_after_save.is_some()
+                                });
+                                if !saving {
+                                    server_state.update(cx, |state, _cx| {
+                                        state.cancel_navigation();
+                                    });
+                                }
+                            }
+                        }),
+                    ])
+                })
+        });
+    }
+    /// Warn instead of copying or exporting a truncated value, which would only get its loaded bytes
+    fn warn_truncated_value(&self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let truncated = self
+            .server_state
+            .read(cx)
+            .value()
+            .is_some_and(|value| value.is_truncated());
+        if truncated {
+            window.push_notification(Notification::warning(i18n_editor(cx, "value_truncated_action")), cx);
+        }
+        truncated
+    }
+    /// Export the decoded protobuf value as a JSON file named after the key
+    fn export_proto_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.warn_truncated_value(window, cx) {
             return;
         }
+        let server_state = self.server_state.read(cx);
         let Some(key) = server_state.key() else {
             return;
         };
-        let Some(editor) = self.bytes_editor.as_ref() else {
+        let Some(text) = server_state
+            .value()
+            .and_then(|value| value.bytes_value())
+            .filter(|value| value.format == DataFormat::Protobuf)
+            .and_then(|value| value.text.clone())
+        else {
             return;
         };
-        editor.clone().update(cx, move |state, cx| {
-            let value = state.value(cx);
-            self.server_state.update(cx, move |state, cx| {
-                state.save_value(key, value, cx);
-            });
-        });
+        let file_name = format!("{}.json", sanitize_file_name(&key));
+        export_to_file(file_name, text.as_bytes().to_vec(), window, cx);
+    }
+    /// Copy the value as stored, binary values are copied as base64
+    fn copy_value(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.warn_truncated_value(window, cx) {
+            return;
+        }
+        let Some(value) = self.server_state.read(cx).value().and_then(|value| value.bytes_value()) else {
+            return;
+        };
+        let (content, message) = match std::str::from_utf8(&value.bytes) {
+            Ok(text) => (text.to_string(), i18n_editor(cx, "copied_value")),
+            Err(_) => (BASE64.encode(&value.bytes), i18n_editor(cx, "copied_value_base64")),
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(content));
+        window.push_notification(Notification::info(message), cx);
+    }
+    /// Copy the value pretty-printed as JSON
+    ///
+    /// The stored bytes are parsed first, so strings shortened for display are copied in full,
+    /// then the displayed text, e.g. decoded MessagePack or decompressed data.
+    fn copy_value_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.warn_truncated_value(window, cx) {
+            return;
+        }
+        let Some(value) = self.server_state.read(cx).value().and_then(|value| value.bytes_value()) else {
+            return;
+        };
+        let json = serde_json::from_slice::<serde_json::Value>(&value.bytes)
+            .ok()
+            .or_else(|| {
+                value
+                    .text
+                    .as_ref()
+                    .and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok())
+            })
+            .and_then(|json| serde_json::to_string_pretty(&json).ok());
+        let Some(json) = json else {
+            window.push_notification(Notification::warning(i18n_editor(cx, "copy_json_invalid")), cx);
+            return;
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(json));
+        window.push_notification(Notification::info(i18n_editor(cx, "copied_json")), cx);
+    }
+    /// Copy the decoded protobuf JSON to the clipboard, or the raw bytes as hex when they don't decode
+    fn copy_proto_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.warn_truncated_value(window, cx) {
+            return;
+        }
+        let Some(value) = self.server_state.read(cx).value().and_then(|value| value.bytes_value()) else {
+            return;
+        };
+        let (content, message) = match value.text.clone().filter(|_| value.format == DataFormat::Protobuf) {
+            Some(text) => {
+                let pretty = serde_json::from_str::<serde_json::Value>(&text)
+                    .ok()
+                    .and_then(|json| serde_json::to_string_pretty(&json).ok())
+                    .unwrap_or_else(|| text.to_string());
+                (pretty, i18n_editor(cx, "copied_proto_json"))
+            }
+            None => {
+                let hex: String = value.bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+                (hex, i18n_editor(cx, "copied_proto_hex"))
+            }
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(content));
+        window.push_notification(Notification::info(message), cx);
+    }
+    /// Export the key's metadata and loaded value as a JSON bundle
+    fn export_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.warn_truncated_value(window, cx) {
+            return;
+        }
+        let server_state = self.server_state.read(cx);
+        let Some(key) = server_state.key() else {
+            return;
+        };
+        let Some(value) = server_state.value() else {
+            return;
+        };
+        let fully_loaded = value.is_fully_loaded();
+        let Ok(content) = serde_json::to_vec_pretty(&value.to_export_json(&key)) else {
+            return;
+        };
+        let file_name = format!("{}-export.json", sanitize_file_name(&key));
+        if !fully_loaded {
+            window.push_notification(Notification::warning(i18n_editor(cx, "export_key_partial")), cx);
+        }
+        export_to_file(file_name, content, window, cx);
     }
     fn enter_ttl_edit_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let server_state = self.server_state.read(cx);
@@ -261,13 +703,19 @@ impl ZedisEditor {
         let mut is_busy = false;
         let mut btns = vec![];
         let mut ttl = SharedString::default();
+        // The other TTL representation, shown in the tooltip
+        let mut ttl_detail: Option<SharedString> = None;
+        let ttl_absolute = cx.global::<ZedisGlobalStore>().read(cx).ttl_absolute();
         let mut size = SharedString::default();
+        let mut memory_usage = SharedString::default();
+        let mut encoding = None;
+        let mut idle_time = None;
 
         // Extract value information if available
         if let Some(value) = server_state.value() {
             is_busy = value.is_busy();
 
-            // Format TTL display
+            // Format TTL display, -2 and -1 never have an expiry time
             ttl = if let Some(ttl) = value.ttl() {
                 let seconds = ttl.num_seconds();
                 if seconds == -2 {
@@ -275,17 +723,37 @@ impl ZedisEditor {
                 } else if seconds < 0 {
                     i18n_common(cx, "permanent")
                 } else {
-                    format_duration(Duration::from_secs(seconds as u64)).into()
+                    let relative: SharedString = format_duration(Duration::from_secs(seconds as u64)).into();
+                    match value.expire_at.and_then(format_timestamp) {
+                        Some(absolute) if ttl_absolute => {
+                            ttl_detail = Some(relative);
+                            absolute.into()
+                        }
+                        Some(absolute) => {
+                            ttl_detail = Some(absolute.into());
+                            relative
+                        }
+                        None => relative,
+                    }
                 }
             } else {
                 "--".into()
             };
 
-            size = format_size(value.size(), DECIMAL).into();
+            if let Some(bytes_value) = value.bytes_value() {
+                size = format_size(bytes_value.full_len, DECIMAL).into();
+            }
+            if let Some(bytes) = value.memory_usage() {
+                memory_usage = format_size(bytes, DECIMAL).into();
+            }
+            encoding = value.encoding().cloned();
+            idle_time = value.idle_time();
         }
 
         // Show loading only if busy and not recently selected (avoid flashing)
         let should_show_loading = is_busy && !self.is_selected_key_recently();
+        // Copy and export would only get the loaded bytes of a truncated value
+        let value_truncated = server_state.value().is_some_and(|value| value.is_truncated());
         // Add size label if available
         if !size.is_empty() {
             let size_label = i18n_common(cx, "size");
@@ -296,20 +764,93 @@ impl ZedisEditor {
                     .into_any_element(),
             );
         }
+        // Memory is only shown when the server supports MEMORY USAGE
+        if !memory_usage.is_empty() {
+            let memory_label = i18n_common(cx, "memory_usage");
+            btns.push(
+                Label::new(format!("{memory_label} : {memory_usage}"))
+                    .ml_2()
+                    .text_sm()
+                    .into_any_element(),
+            );
+        }
+        // OBJECT ENCODING and IDLETIME may be disabled on managed Redis
+        if let Some(encoding) = encoding {
+            let encoding_label = i18n_editor(cx, "encoding");
+            btns.push(
+                Label::new(format!("{encoding_label} : {encoding}"))
+                    .ml_2()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .into_any_element(),
+            );
+        }
+        if let Some(idle_time) = idle_time {
+            let idle_time_label = i18n_editor(cx, "idle_time");
+            btns.push(
+                Label::new(format!(
+                    "{idle_time_label} : {}",
+                    format_duration(Duration::from_secs(idle_time.max(0) as u64))
+                ))
+                .ml_2()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .into_any_element(),
+            );
+        }
+
+        // Protobuf values are saved by encoding the edited JSON
+        let is_proto = server_state
+            .value()
+            .and_then(|value| value.bytes_value())
+            .is_some_and(|value| value.format == DataFormat::Protobuf);
+
+        // Show which proto config decoded the value, clicking it opens the config for editing
+        if let Some(proto_id) = server_state
+            .value()
+            .and_then(|value| value.bytes_value())
+            .filter(|value| value.format == DataFormat::Protobuf)
+            .and_then(|value| value.proto_id.clone())
+        {
+            let name = ProtoManager::proto_name(&proto_id).unwrap_or_else(|| proto_id.to_string());
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            btns.push(
+                Button::new("zedis-editor-proto-source")
+                    .ml_2()
+                    .ghost()
+                    .small()
+                    .label(t!("editor.decoded_via", name = name, locale = locale).to_string())
+                    .tooltip(i18n_editor(cx, "edit_proto_tooltip"))
+                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                        let proto_id = proto_id.clone();
+                        this.server_state.update(cx, |state, cx| {
+                            state.edit_proto(proto_id, cx);
+                        });
+                    }))
+                    .into_any_element(),
+            );
+        }
 
         // Add save button for string editor if value is modified
         if let Some(bytes_editor) = &self.bytes_editor {
             let state = bytes_editor.read(cx);
             let value_modified = state.is_value_modified();
             let readonly = state.is_readonly();
+            let save_denied = !self.server_state.read(cx).can_run("SET");
             let tooltip = if self.readonly {
-                i18n_common(cx, "disable_in_readonly")
+                self.server_state.read(cx).readonly_reason(cx)
+            } else if save_denied {
+                self.server_state.read(cx).acl_denied_reason("SET", cx)
             } else if readonly {
                 i18n_editor(cx, "can_not_edit_value")
             } else {
                 format!(
                     "{} ({})",
-                    i18n_editor(cx, "save_data_tooltip"),
+                    if is_proto {
+                        i18n_editor(cx, "save_proto_tooltip")
+                    } else {
+                        i18n_editor(cx, "save_data_tooltip")
+                    },
                     humanize_keystroke("cmd-s")
                 )
                 .into()
@@ -318,7 +859,7 @@ impl ZedisEditor {
             btns.push(
                 Button::new("zedis-editor-save-key")
                     .ml_2()
-                    .disabled(self.readonly || !value_modified || should_show_loading)
+                    .disabled(self.readonly || save_denied || !value_modified || should_show_loading)
                     .outline()
                     .label(i18n_common(cx, "save"))
                     .tooltip(tooltip)
@@ -330,6 +871,185 @@ impl ZedisEditor {
             );
         }
 
+        // Add find and replace toggle for editable text values
+        if let Some(bytes_editor) = &self.bytes_editor
+            && bytes_editor.read(cx).can_replace()
+        {
+            let bytes_editor = bytes_editor.clone();
+            btns.push(
+                Button::new("zedis-editor-find-replace")
+                    .ml_2()
+                    .outline()
+                    .disabled(should_show_loading)
+                    .tooltip(i18n_editor(cx, "find_replace_tooltip"))
+                    .icon(CustomIconName::Replace)
+                    .on_click(move |_event, _window, cx| {
+                        bytes_editor.update(cx, |state, cx| {
+                            state.toggle_replace(cx);
+                        });
+                    })
+                    .into_any_element(),
+            );
+        }
+
+        // Add a soft wrap toggle that only applies to the current key
+        if self.bytes_editor.is_some() {
+            let soft_wrap = self.server_state.read(cx).editor_soft_wrap();
+            btns.push(
+                Button::new("zedis-editor-soft-wrap")
+                    .ml_2()
+                    .outline()
+                    .selected(soft_wrap)
+                    .disabled(should_show_loading)
+                    .tooltip(i18n_editor(cx, "soft_wrap_tooltip"))
+                    .icon(CustomIconName::WrapText)
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.toggle_editor_soft_wrap(cx);
+                        });
+                        cx.notify();
+                    }))
+                    .into_any_element(),
+            );
+            btns.push(
+                Button::new("zedis-editor-json-tree")
+                    .ml_2()
+                    .outline()
+                    .selected(self.json_tree_enabled)
+                    .disabled(should_show_loading)
+                    .tooltip(i18n_editor(cx, "json_tree_tooltip"))
+                    .icon(CustomIconName::ListTree)
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.toggle_json_tree(cx);
+                    }))
+                    .into_any_element(),
+            );
+        }
+
+        // Add copy button for keys matched by a proto config, decoded or not
+        let is_proto_key = is_proto
+            || (server_state
+                .value()
+                .is_some_and(|value| value.key_type() == KeyType::String)
+                && ProtoManager::match_key_to_name(server_state.server_id(), &key).is_some());
+        if is_proto_key {
+            btns.push(
+                Button::new("zedis-editor-copy-proto-json")
+                    .ml_2()
+                    .outline()
+                    .disabled(should_show_loading || value_truncated)
+                    .tooltip(i18n_editor(cx, "copy_proto_json_tooltip"))
+                    .icon(IconName::Copy)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.copy_proto_json(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+        }
+
+        // Copy the value, and as JSON when it is JSON or was decoded to it (proto keys have their own button)
+        if let Some(format) = server_state
+            .value()
+            .and_then(|value| value.bytes_value())
+            .map(|value| value.format)
+        {
+            btns.push(
+                Button::new("zedis-editor-copy-value")
+                    .ml_2()
+                    .outline()
+                    .disabled(should_show_loading || value_truncated)
+                    .tooltip(i18n_editor(cx, "copy_value_tooltip"))
+                    .icon(IconName::Copy)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.copy_value(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+            if !is_proto_key && matches!(format, DataFormat::Json | DataFormat::Preview) {
+                btns.push(
+                    Button::new("zedis-editor-copy-json")
+                        .ml_2()
+                        .outline()
+                        .disabled(should_show_loading || value_truncated)
+                        .tooltip(i18n_editor(cx, "copy_json_tooltip"))
+                        .icon(IconName::Copy)
+                        .label("JSON")
+                        .on_click(cx.listener(move |this, _event, window, cx| {
+                            this.copy_value_json(window, cx);
+                        }))
+                        .into_any_element(),
+                );
+            }
+        }
+
+        // Add export button for protobuf values decoded to JSON
+        if is_proto {
+            btns.push(
+                Button::new("zedis-editor-export-proto-json")
+                    .ml_2()
+                    .outline()
+                    .disabled(should_show_loading || value_truncated)
+                    .tooltip(i18n_editor(cx, "export_proto_json_tooltip"))
+                    .icon(CustomIconName::Download)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.export_proto_json(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+        }
+
+        // Add export button for the key's metadata and loaded value
+        if server_state
+            .value()
+            .is_some_and(|value| value.key_type() != KeyType::Unknown)
+        {
+            btns.push(
+                Button::new("zedis-editor-export-key")
+                    .ml_2()
+                    .outline()
+                    .disabled(should_show_loading || value_truncated)
+                    .tooltip(i18n_editor(cx, "export_key_tooltip"))
+                    .icon(CustomIconName::FileCheckCorner)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.export_key(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+        }
+
+        // Rename and copy the key, COPY needs Redis 6.2
+        let readonly_reason = self.readonly.then(|| server_state.readonly_reason(cx));
+        btns.push(
+            Button::new("zedis-editor-rename-key")
+                .ml_2()
+                .outline()
+                .disabled(self.readonly || should_show_loading)
+                .tooltip(
+                    readonly_reason
+                        .clone()
+                        .unwrap_or_else(|| i18n_editor(cx, "rename_key_tooltip")),
+                )
+                .icon(CustomIconName::FilePenLine)
+                .on_click(cx.listener(move |this, _event, window, cx| {
+                    this.rename_key(window, cx);
+                }))
+                .into_any_element(),
+        );
+        if server_state.supports_copy() {
+            btns.push(
+                Button::new("zedis-editor-copy-key")
+                    .ml_2()
+                    .outline()
+                    .disabled(self.readonly || should_show_loading)
+                    .tooltip(readonly_reason.unwrap_or_else(|| i18n_editor(cx, "copy_key_tooltip")))
+                    .icon(CustomIconName::FilePlusCorner)
+                    .on_click(cx.listener(move |this, _event, window, cx| {
+                        this.copy_key(window, cx);
+                    }))
+                    .into_any_element(),
+            );
+        }
+
         // Add TTL button (or input field when in edit mode)
         if !ttl.is_empty() {
             let ttl_btn = if self.ttl_edit_mode {
@@ -348,7 +1068,7 @@ impl ZedisEditor {
             } else {
                 // Show TTL button that switches to edit mode on click
                 let ttl_tooltip: SharedString = if self.readonly {
-                    i18n_common(cx, "disable_in_readonly")
+                    self.server_state.read(cx).readonly_reason(cx)
                 } else {
                     format!(
                         "{} ({})",
@@ -357,10 +1077,19 @@ impl ZedisEditor {
                     )
                     .into()
                 };
+                let ttl_tooltip: SharedString = match ttl_detail {
+                    Some(detail) => format!("{detail}\n{ttl_tooltip}").into(),
+                    None => ttl_tooltip,
+                };
+                let ttl_width = if ttl_absolute {
+                    TTL_ABSOLUTE_WIDTH
+                } else {
+                    TTL_INPUT_MAX_WIDTH
+                };
                 Button::new("zedis-editor-ttl-btn")
                     .ml_2()
                     .outline()
-                    .w(px(TTL_INPUT_MAX_WIDTH))
+                    .w(px(ttl_width))
                     .disabled(self.readonly || should_show_loading)
                     .tooltip(ttl_tooltip)
                     .label(ttl.clone())
@@ -394,13 +1123,18 @@ impl ZedisEditor {
         );
 
         // Add delete button
+        let delete_denied = !self.server_state.read(cx).can_delete();
         btns.push(
             Button::new("zedis-editor-delete-key")
                 .ml_2()
                 .outline()
-                .disabled(self.readonly || should_show_loading)
+                .disabled(self.readonly || delete_denied || should_show_loading)
                 .tooltip(if self.readonly {
-                    i18n_common(cx, "disable_in_readonly")
+                    self.server_state.read(cx).readonly_reason(cx)
+                } else if delete_denied {
+                    self.server_state
+                        .read(cx)
+                        .acl_denied_reason(DeleteCommand::Del.as_str(), cx)
                 } else {
                     i18n_editor(cx, "delete_key_tooltip")
                 })
@@ -444,10 +1178,57 @@ impl ZedisEditor {
             )
             .children(btns)
     }
+    /// Render a banner explaining why write operations are disabled
+    fn render_readonly_banner(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let message = match self.access_mode {
+            AccessMode::StrictReadOnly => i18n_editor(cx, "readonly_acl_banner"),
+            _ => i18n_editor(cx, "readonly_config_banner"),
+        };
+        div()
+            .p_2()
+            .child(Alert::warning("zedis-editor-readonly-banner", message))
+    }
+    /// Render a banner when only the first bytes of the value were loaded, with an action to load all of it
+    fn render_truncated_banner(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let state = self.server_state.read(cx);
+        let value = state.value().filter(|value| value.is_truncated())?;
+        let bytes_value = value.bytes_value()?;
+        let is_busy = value.is_busy();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = t!(
+            "editor.value_truncated_banner",
+            loaded = format_size(bytes_value.bytes.len(), DECIMAL),
+            total = format_size(bytes_value.full_len, DECIMAL),
+            locale = locale
+        )
+        .to_string();
+        Some(
+            h_flex()
+                .p_2()
+                .gap_2()
+                .child(
+                    div()
+                        .flex_1()
+                        .child(Alert::warning("zedis-editor-truncated-banner", message)),
+                )
+                .child(
+                    Button::new("zedis-editor-load-full-value")
+                        .outline()
+                        .label(i18n_editor(cx, "load_full_value"))
+                        .disabled(is_busy)
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.server_state.update(cx, |state, cx| {
+                                state.load_full_value(cx);
+                            });
+                        })),
+                ),
+        )
+    }
     /// Clean up unused editors when switching between key types
     fn reset_editors(&mut self, key_type: KeyType) {
         if key_type != KeyType::String {
             let _ = self.bytes_editor.take();
+            let _ = self.json_tree.take();
         }
         if key_type != KeyType::List {
             let _ = self.list_editor.take();
@@ -461,6 +1242,9 @@ impl ZedisEditor {
         if key_type != KeyType::Hash {
             let _ = self.hash_editor.take();
         }
+        if key_type != KeyType::Stream {
+            let _ = self.stream_editor.take();
+        }
     }
 
     /// Render the appropriate editor based on the key type
@@ -508,6 +1292,14 @@ impl ZedisEditor {
                 });
                 editor.clone().into_any_element()
             }
+            KeyType::Stream => {
+                self.reset_editors(KeyType::Stream);
+                let editor = self.stream_editor.get_or_insert_with(|| {
+                    debug!("Creating new stream editor");
+                    cx.new(|cx| ZedisStreamEditor::new(self.server_state.clone(), window, cx))
+                });
+                editor.clone().into_any_element()
+            }
             _ => {
                 // Default to bytes editor for String type and other types
                 self.reset_editors(KeyType::String);
@@ -516,7 +1308,24 @@ impl ZedisEditor {
                     debug!("Creating new bytes editor");
                     cx.new(|cx| ZedisBytesEditor::new(self.server_state.clone(), window, cx))
                 });
-                editor.clone().into_any_element()
+                if !self.json_tree_enabled {
+                    return editor.clone().into_any_element();
+                }
+                if let Some(json_tree) = &self.json_tree {
+                    return json_tree.clone().into_any_element();
+                }
+                // Not JSON, fall back to the text with a hint
+                v_flex()
+                    .size_full()
+                    .child(
+                        Label::new(i18n_editor(cx, "json_tree_invalid"))
+                            .text_xs()
+                            .px_2()
+                            .py_1()
+                            .text_color(cx.theme().muted_foreground),
+                    )
+                    .child(div().flex_1().child(editor.clone()))
+                    .into_any_element()
             }
         }
     }
@@ -539,6 +1348,16 @@ impl Render for ZedisEditor {
             .w_full()
             .h_full()
             .child(self.render_select_key(cx))
+            .when(self.readonly, |this| this.child(self.render_readonly_banner(cx)))
+            .children(self.render_truncated_banner(cx))
+            .when(self.value_changed, |this| {
+                this.border_1()
+                    .border_color(cx.theme().yellow)
+                    .child(div().p_2().child(Alert::info(
+                        "zedis-editor-value-changed",
+                        i18n_editor(cx, "value_changed_on_server"),
+                    )))
+            })
             .child(self.render_editor(window, cx))
             .on_action(cx.listener(move |this, event: &EditorAction, window, cx| match event {
                 EditorAction::Save => {
@@ -18,19 +18,83 @@
 //! It supports operations like:
 //! - Viewing HASH field-value pairs in a two-column table
 //! - Adding new fields with values via a dialog form
+//! - Importing many fields at once with a single HSET
 //! - Updating values of existing fields (inline editing)
 //! - Removing field-value pairs
 //! - Filtering fields with pattern matching
 //! - Incremental loading of large HASHes with pagination
+//! - Viewing and editing per-field TTL on Redis 7.4+
 
 use crate::{
     components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
-    states::{RedisValue, ZedisServerState, i18n_common, i18n_hash_editor},
+    helpers::{format_duration, unix_ts},
+    states::{RedisValue, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_hash_editor},
     views::{KvTableColumn, ZedisKvTable},
 };
+use ahash::AHashMap;
 use gpui::{App, Entity, SharedString, Window, div, prelude::*};
-use gpui_component::WindowExt;
+use gpui_component::{WindowExt, notification::Notification};
+use parking_lot::Mutex;
+use rust_i18n::t;
 use std::rc::Rc;
+use std::time::Duration;
+
+/// Placeholder shown in the TTL column for fields without expiration
+const NO_TTL: &str = "--";
+/// Width of the TTL column in pixels
+const TTL_COLUMN_WIDTH: f32 = 100.0;
+/// Index of the "trim whitespace" option of the import value mode
+const IMPORT_TRIM_VALUES: usize = 1;
+/// Duplicate fields listed in the import error, the rest are counted
+const IMPORT_DUPLICATE_SAMPLE: usize = 5;
+
+/// Why the import text couldn't be turned into field-value pairs.
+enum HashImportError {
+    /// The line (1-based) has no `=` or tab between field and value
+    MissingSeparator(usize),
+    /// The line (1-based) has an empty field name
+    EmptyField(usize),
+    /// Fields given more than once, in order of first appearance
+    DuplicateFields(Vec<String>),
+}
+
+/// Parses one `field=value` or `field<Tab>value` pair per line.
+///
+/// A tab takes precedence, so values may contain `=`. Fields and values are kept verbatim
+/// unless `trim` is set, numeric-looking values stay the strings they were written as.
+/// Blank lines are skipped and repeated fields are rejected rather than letting HSET
+/// keep the last one.
+fn parse_hash_import(text: &str, trim: bool) -> Result<Vec<(SharedString, SharedString)>, HashImportError> {
+    let mut pairs = Vec::new();
+    let mut seen: AHashMap<&str, usize> = AHashMap::new();
+    let mut duplicates = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((field, value)) = line.split_once('\t').or_else(|| line.split_once('=')) else {
+            return Err(HashImportError::MissingSeparator(index + 1));
+        };
+        let (field, value) = if trim {
+            (field.trim(), value.trim())
+        } else {
+            (field, value)
+        };
+        if field.is_empty() {
+            return Err(HashImportError::EmptyField(index + 1));
+        }
+        let count = seen.entry(field).or_default();
+        *count += 1;
+        if *count == 2 {
+            duplicates.push(field.to_string());
+        }
+        pairs.push((field.to_string().into(), value.to_string().into()));
+    }
+    if !duplicates.is_empty() {
+        return Err(HashImportError::DuplicateFields(duplicates));
+    }
+    Ok(pairs)
+}
 
 /// Data adapter for Redis HASH values to work with the KV table component.
 ///
@@ -41,12 +105,18 @@ struct ZedisHashValues {
     value: RedisValue,
     /// Reference to server state for executing Redis operations
     server_state: Entity<ZedisServerState>,
+    /// TTL text each edited row was filled with, to tell whether the user changed it
+    edit_ttls: Mutex<AHashMap<usize, SharedString>>,
 }
 
 impl ZedisKvFetcher for ZedisHashValues {
     /// Creates a new data adapter instance.
     fn new(server_state: Entity<ZedisServerState>, value: RedisValue) -> Self {
-        Self { server_state, value }
+        Self {
+            server_state,
+            value,
+            edit_ttls: Mutex::new(AHashMap::new()),
+        }
     }
 
     fn is_form_editor(&self) -> bool {
@@ -58,18 +128,43 @@ impl ZedisKvFetcher for ZedisHashValues {
     /// Column layout:
     /// - Column 1: Field name
     /// - Column 2: Field value
+    /// - Column 3: Field TTL (only on Redis 7.4+)
     fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
         let hash = self.value.hash_value()?;
         let (field, value) = hash.values.get(row_ix)?;
 
-        // Column 2 is the value, others show the field name
-        if col_ix == 2 {
-            Some(value.clone())
-        } else {
-            Some(field.clone())
+        match col_ix {
+            2 => Some(value.clone()),
+            3 => {
+                let ttl = hash
+                    .field_expire_at
+                    .get(field)
+                    .map(|expire_at| format_duration(Duration::from_secs((expire_at - unix_ts()).max(0) as u64)))
+                    .unwrap_or_else(|| NO_TTL.to_string());
+                Some(ttl.into())
+            }
+            // Other columns show the field name
+            _ => Some(field.clone()),
         }
     }
 
+    /// The TTL column is edited as whole seconds, empty meaning no TTL.
+    fn get_edit(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
+        if col_ix != 3 {
+            return self.get(row_ix, col_ix);
+        }
+        let hash = self.value.hash_value()?;
+        let (field, _) = hash.values.get(row_ix)?;
+        let ttl: SharedString = hash
+            .field_expire_at
+            .get(field)
+            .map(|expire_at| (expire_at - unix_ts()).max(1).to_string())
+            .unwrap_or_default()
+            .into();
+        self.edit_ttls.lock().insert(row_ix, ttl.clone());
+        Some(ttl)
+    }
+
     /// Returns the total number of fields in the HASH (from Redis HLEN).
     fn count(&self) -> usize {
         self.value.hash_value().map_or(0, |v| v.size)
@@ -101,6 +196,11 @@ impl ZedisKvFetcher for ZedisHashValues {
         self.value.hash_value().is_some_and(|v| v.done)
     }
 
+    /// Checks if the user stopped loading more fields.
+    fn is_canceled(&self) -> bool {
+        self.value.is_load_canceled()
+    }
+
     /// Triggers loading of the next batch of HASH field-value pairs.
     ///
     /// Uses cursor-based pagination via HSCAN to load more values.
@@ -140,8 +240,9 @@ impl ZedisKvFetcher for ZedisHashValues {
     /// Handles inline editing of a HASH field's value.
     ///
     /// Called when the user edits the value column directly in the table.
-    /// Updates the value for the existing field using Redis HSET.
-    fn handle_update_value(&self, _row_ix: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
+    /// Updates the value for the existing field using Redis HSET, and the
+    /// field TTL via HEXPIRE/HPERSIST when the TTL column was changed.
+    fn handle_update_value(&self, row_ix: usize, values: Vec<SharedString>, _window: &mut Window, cx: &mut App) {
         // Extract field name and new value from values
         let Some(field) = values.first() else {
             return;
@@ -150,9 +251,18 @@ impl ZedisKvFetcher for ZedisHashValues {
             return;
         };
 
+        // TTL column is only present on servers supporting field expiration,
+        // and it's only sent when the user changed the text it was filled with
+        let original_ttl = self.edit_ttls.lock().remove(&row_ix).unwrap_or_default();
+        let ttl = values
+            .get(2)
+            .map(|ttl| ttl.trim())
+            .filter(|ttl| *ttl != original_ttl.as_str())
+            .map(|ttl| SharedString::from(ttl.to_string()));
+
         // Execute update operation
         self.server_state.update(cx, |this, cx| {
-            this.update_hash_value(field.clone(), value.clone(), cx);
+            this.update_hash_value(field.clone(), value.clone(), ttl, cx);
         });
     }
 
@@ -201,6 +311,78 @@ impl ZedisKvFetcher for ZedisHashValues {
             cx,
         );
     }
+
+    fn can_import(&self) -> bool {
+        true
+    }
+
+    /// Opens a dialog to paste many field-value pairs, written with a single HSET.
+    fn handle_import(&self, window: &mut Window, cx: &mut App) {
+        let server_state = self.server_state.clone();
+
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(text) = values.first() else {
+                return false;
+            };
+            let trim = values
+                .get(1)
+                .and_then(|mode| mode.parse::<usize>().ok())
+                .is_some_and(|mode| mode == IMPORT_TRIM_VALUES);
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = match parse_hash_import(text, trim) {
+                Ok(pairs) if pairs.is_empty() => t!("hash_editor.import_empty", locale = locale).to_string(),
+                Ok(pairs) => {
+                    server_state.update(cx, |this, cx| {
+                        this.import_hash_values(pairs, cx);
+                    });
+                    window.close_dialog(cx);
+                    return true;
+                }
+                Err(HashImportError::MissingSeparator(line)) => {
+                    t!("hash_editor.import_missing_separator", line = line, locale = locale).to_string()
+                }
+                Err(HashImportError::EmptyField(line)) => {
+                    t!("hash_editor.import_empty_field", line = line, locale = locale).to_string()
+                }
+                Err(HashImportError::DuplicateFields(fields)) => {
+                    let mut sample = fields.iter().take(IMPORT_DUPLICATE_SAMPLE).cloned().collect::<Vec<_>>();
+                    if fields.len() > IMPORT_DUPLICATE_SAMPLE {
+                        sample.push("…".to_string());
+                    }
+                    t!(
+                        "hash_editor.import_duplicate_fields",
+                        count = fields.len(),
+                        fields = sample.join(", "),
+                        locale = locale
+                    )
+                    .to_string()
+                }
+            };
+            window.push_notification(Notification::error(message), cx);
+            false
+        });
+
+        let fields = vec![
+            FormField::new(i18n_hash_editor(cx, "import_fields"))
+                .with_placeholder(i18n_hash_editor(cx, "import_fields_placeholder"))
+                .with_multi_line()
+                .with_focus(),
+            FormField::new(i18n_hash_editor(cx, "import_value_mode")).with_options(vec![
+                i18n_hash_editor(cx, "import_value_verbatim"),
+                i18n_hash_editor(cx, "import_value_trim"),
+            ]),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_hash_editor(cx, "import_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
 }
 /// Main HASH editor view component.
 ///
@@ -221,7 +403,7 @@ impl ZedisHashEditor {
     /// * `cx` - GPUI context for component initialization
     ///
     /// # Returns
-    /// A new `ZedisHashEditor` instance with a two-column table (Field and Value)
+    /// A new `ZedisHashEditor` instance with a Field and Value table, plus a TTL column on Redis 7.4+
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let window_width = window.viewport_size().width.to_f64();
         let field_width = if window_width > 1800. {
@@ -232,18 +414,17 @@ impl ZedisHashEditor {
             0.4
         };
 
-        // Initialize the KV table with two columns: field and value
-        let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisHashValues>::new(
-                vec![
-                    KvTableColumn::new("Field", Some(field_width)).with_readonly(true), // Field name column (flexible width)
-                    KvTableColumn::new("Value", None), // Field value column (flexible width)
-                ],
-                server_state,
-                window,
-                cx,
-            )
-        });
+        let mut columns = vec![
+            KvTableColumn::new("Field", Some(field_width)).with_readonly(true), // Field name column (flexible width)
+            KvTableColumn::new("Value", None),                                  // Field value column (flexible width)
+        ];
+        // Per-field TTL is only available since Redis 7.4
+        if server_state.read(cx).features().hash_field_ttl {
+            columns.push(KvTableColumn::new("TTL", Some(TTL_COLUMN_WIDTH)).with_single_line(true));
+        }
+
+        // Initialize the KV table with field, value and optional TTL columns
+        let table_state = cx.new(|cx| ZedisKvTable::<ZedisHashValues>::new(columns, server_state, window, cx));
 
         Self { table_state }
     }
@@ -255,3 +436,61 @@ impl Render for ZedisHashEditor {
         div().size_full().child(self.table_state.clone()).into_any_element()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(SharedString, SharedString)> {
+        items
+            .iter()
+            .map(|(field, value)| (field.to_string().into(), value.to_string().into()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_hash_import() {
+        let parsed = parse_hash_import("name=zedis\n\nurl\thttps://a.io/?a=1\ncount=007\n", false);
+        assert_eq!(
+            Some(pairs(&[
+                ("name", "zedis"),
+                ("url", "https://a.io/?a=1"),
+                ("count", "007")
+            ])),
+            parsed.ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_hash_import_trim() {
+        let text = " name = zedis \nkey\t value ";
+        assert_eq!(
+            Some(pairs(&[(" name ", " zedis "), ("key", " value ")])),
+            parse_hash_import(text, false).ok()
+        );
+        assert_eq!(
+            Some(pairs(&[("name", "zedis"), ("key", "value")])),
+            parse_hash_import(text, true).ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_hash_import_errors() {
+        assert!(matches!(
+            parse_hash_import("a=1\nb", false),
+            Err(HashImportError::MissingSeparator(2))
+        ));
+        assert!(matches!(
+            parse_hash_import("a=1\n=2", false),
+            Err(HashImportError::EmptyField(2))
+        ));
+        assert!(matches!(
+            parse_hash_import("a=1\n  =2", true),
+            Err(HashImportError::EmptyField(2))
+        ));
+        let Err(HashImportError::DuplicateFields(fields)) = parse_hash_import("a=1\nb=2\na=3\nb=4\na=5", false) else {
+            panic!("duplicate fields accepted");
+        };
+        assert_eq!(vec!["a".to_string(), "b".to_string()], fields);
+    }
+}
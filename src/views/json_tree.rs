@@ -0,0 +1,134 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::get_font_family;
+use crate::states::i18n_editor;
+use gpui::{ClipboardItem, Entity, SharedString, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, WindowExt, h_flex,
+    label::Label,
+    list::ListItem,
+    notification::Notification,
+    tree::{TreeItem, TreeState, tree},
+};
+use serde_json::Value;
+
+const TREE_INDENT_BASE: f32 = 16.0; // Base indentation per level in pixels
+const TREE_INDENT_OFFSET: f32 = 8.0; // Additional offset for all items
+const LEAF_VALUE_MAX_CHARS: usize = 200; // Leaf values are cut beyond it, the full value stays in the editor
+
+/// Appends an object key to a JSON path, bracketed when it isn't a plain identifier
+fn object_path(parent: &str, key: &str) -> String {
+    let plain = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{parent}.{key}")
+    } else {
+        format!("{parent}[{}]", Value::String(key.to_string()))
+    }
+}
+
+/// The label of an entry, `name {n}`/`name [n]` for containers and `name: value` for leaves
+fn entry_label(name: &str, value: &Value) -> String {
+    match value {
+        Value::Object(map) => format!("{name} {{{}}}", map.len()),
+        Value::Array(items) => format!("{name} [{}]", items.len()),
+        _ => {
+            let value = value.to_string();
+            if value.chars().count() > LEAF_VALUE_MAX_CHARS {
+                let value: String = value.chars().take(LEAF_VALUE_MAX_CHARS).collect();
+                format!("{name}: {value}…")
+            } else {
+                format!("{name}: {value}")
+            }
+        }
+    }
+}
+
+/// Maps a JSON value to tree items, the id of each item is its JSON path
+fn new_tree_item(name: &str, path: String, value: &Value, expanded: bool) -> TreeItem {
+    let item = TreeItem::new(path.clone(), entry_label(name, value));
+    let children: Vec<TreeItem> = match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| new_tree_item(key, object_path(&path, key), value, false))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, value)| new_tree_item(&index.to_string(), format!("{path}[{index}]"), value, false))
+            .collect(),
+        _ => return item,
+    };
+    item.expanded(expanded).children(children)
+}
+
+/// Collapsible tree of a JSON value, clicking a leaf copies its JSON path.
+pub struct ZedisJsonTree {
+    tree_state: Entity<TreeState>,
+}
+
+impl ZedisJsonTree {
+    pub fn new(value: &Value, cx: &mut Context<Self>) -> Self {
+        let items = vec![new_tree_item("$", "$".to_string(), value, true)];
+        let tree_state = cx.new(|cx| TreeState::new(cx).items(items));
+        Self { tree_state }
+    }
+    /// Replace the displayed value, e.g. after the key was reloaded
+    pub fn set_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        let items = vec![new_tree_item("$", "$".to_string(), value, true)];
+        self.tree_state.update(cx, |state, cx| {
+            state.set_items(items, cx);
+        });
+        cx.notify();
+    }
+}
+
+impl Render for ZedisJsonTree {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().size_full().p_2().font_family(get_font_family()).child(tree(
+            &self.tree_state,
+            |ix, entry, _selected, _window, cx| {
+                let yellow = cx.theme().colors.yellow;
+                let item = entry.item();
+                let icon = if !entry.is_folder() {
+                    Icon::new(IconName::Dash).text_color(cx.theme().muted_foreground)
+                } else if entry.is_expanded() {
+                    Icon::new(IconName::FolderOpen).text_color(yellow)
+                } else {
+                    Icon::new(IconName::Folder).text_color(yellow)
+                };
+                let list_item = ListItem::new(ix)
+                    .w_full()
+                    .py_1()
+                    .px_2()
+                    .pl(px(TREE_INDENT_BASE) * entry.depth() + px(TREE_INDENT_OFFSET))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(icon)
+                            .child(Label::new(item.label.clone()).text_ellipsis()),
+                    );
+                if entry.is_folder() {
+                    return list_item;
+                }
+                let path: SharedString = item.id.clone();
+                list_item.on_click(move |_, window, cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(path.to_string()));
+                    window.push_notification(Notification::info(i18n_editor(cx, "json_path_copied")), cx);
+                })
+            },
+        ))
+    }
+}
@@ -14,32 +14,41 @@
 
 use crate::{
     assets::CustomIconName,
-    components::{FormDialog, FormField, SkeletonLoading, open_add_form_dialog},
-    connection::QueryMode,
+    components::{
+        FormDialog, FormField, SkeletonLoading, delete_command_field, export_to_file, open_add_form_dialog,
+        open_typed_confirm_dialog, selected_delete_command,
+    },
+    connection::{AccessMode, QueryMode, get_connection_manager},
     db::HistoryManager,
-    helpers::{EditorAction, validate_long_string, validate_ttl},
-    states::{KeyType, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_key_tree},
+    error::Error,
+    helpers::{EditorAction, format_duration, parse_restore_commands, unix_ts, validate_long_string, validate_ttl},
+    states::{
+        KeyType, PendingNavigation, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_key_tree,
+        update_app_state_and_save,
+    },
 };
 use ahash::{AHashMap, AHashSet};
 use gpui::{
-    Action, App, AppContext, Corner, Entity, Hsla, ScrollStrategy, SharedString, Subscription, Window, div, prelude::*,
-    px,
+    Action, App, AppContext, ClipboardItem, Corner, Entity, Hsla, PathPromptOptions, ScrollStrategy, SharedString,
+    Subscription, Window, div, prelude::*, px,
 };
 use gpui_component::IndexPath;
 use gpui_component::list::{List, ListDelegate, ListEvent, ListItem, ListState};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, StyledExt, WindowExt,
+    ActiveTheme, Disableable, Icon, IconName, Selectable, Sizable, StyledExt, WindowExt,
     button::{Button, ButtonVariants, DropdownButton},
     h_flex,
     input::{Input, InputEvent, InputState},
     label::Label,
     menu::ContextMenuExt,
+    notification::Notification,
     v_flex,
 };
+use redis::cmd;
 use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::rc::Rc;
+use std::{rc::Rc, sync::Arc, time::Duration};
 use tracing::info;
 
 // Constants for tree layout and behavior
@@ -51,6 +60,21 @@ const KEY_TYPE_FADE_ALPHA: f32 = 0.8; // Background transparency for key type ba
 const KEY_TYPE_BORDER_FADE_ALPHA: f32 = 0.5; // Border transparency for key type badges
 const STRIPE_BACKGROUND_ALPHA_DARK: f32 = 0.1; // Odd row background alpha for dark theme
 const STRIPE_BACKGROUND_ALPHA_LIGHT: f32 = 0.03; // Odd row background alpha for light theme
+const DELETE_PREVIEW_SAMPLE: usize = 20; // Keys listed in the folder delete preview
+const DELETE_SCAN_COUNT: u64 = 10_000; // SCAN count used to list the keys of a folder to delete
+const DELETE_SCAN_ROUNDS: usize = 20; // SCAN rounds at most, so a huge folder doesn't block for too long
+const DELETE_CONFIRM_KEYWORD: &str = "DELETE"; // Word the user types to confirm a folder delete
+const IMPORT_BATCH_SIZE: usize = 500; // Keys restored between two progress updates
+const IMPORT_SKIPPED_SAMPLE: usize = 10; // Skipped keys listed in the import result
+const FILTER_KEY_TYPES: [KeyType; 7] = [
+    KeyType::String,
+    KeyType::List,
+    KeyType::Set,
+    KeyType::Zset,
+    KeyType::Hash,
+    KeyType::Stream,
+    KeyType::Vectorset,
+]; // Types the scan can be restricted to
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Action)]
 enum KeyTreeAction {
@@ -59,6 +83,19 @@ enum KeyTreeAction {
     DeleteMultipleKeys,
     DeleteKey(SharedString),
     DeleteFolder(SharedString),
+    /// Count the keys on the server under a folder
+    CountFolderKeys(SharedString),
+    DeleteMatchingKeys,
+    /// Export the listed keys as RESTORE commands
+    ExportKeys,
+    /// Restore the keys of an exported file
+    ImportKeys,
+    /// Remove every key of the db, or of all dbs when set
+    Flush(bool),
+    CopyFolderPath(SharedString),
+    ScanFolder(SharedString),
+    /// Restrict the scan to the type `TYPE` reports, None scans every type
+    FilterKeyType(Option<SharedString>),
 }
 
 #[derive(Default)]
@@ -78,6 +115,8 @@ struct KeyTreeState {
     expanded_items: AHashSet<SharedString>,
     /// Index path to scroll to when the tree is updated
     scroll_to_index: Option<IndexPath>,
+    /// Key types shown in the tree, empty shows every type (persisted per server)
+    visible_key_types: AHashSet<KeyType>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -96,6 +135,7 @@ fn new_key_tree_items(
     keyword: SharedString,
     expand_all: bool,
     expanded_items: AHashSet<SharedString>,
+    visible_key_types: AHashSet<KeyType>,
     separator: &str,
     max_key_tree_depth: usize,
 ) -> Vec<KeyTreeItem> {
@@ -107,6 +147,11 @@ fn new_key_tree_items(
         if !keyword.is_empty() && !key.contains(keyword.as_str()) {
             continue;
         }
+        // Keys of an unknown type stay hidden until their type is fetched,
+        // folders are built from the remaining keys so the empty ones disappear
+        if !visible_key_types.is_empty() && !visible_key_types.contains(&key_type) {
+            continue;
+        }
         // no colon in the key, it's a simple key
         if !key.contains(separator) {
             items.insert(
@@ -190,6 +235,10 @@ struct KeyTreeDelegate {
     selected_index: Option<IndexPath>,
     enabled_multiple_selection: bool,
     selected_items: AHashSet<SharedString>,
+    /// Hides the delete entries of the context menu
+    readonly: bool,
+    /// Prefetched expiration timestamps, shown next to the keys
+    key_expire_at: Arc<AHashMap<SharedString, i64>>,
 }
 
 impl KeyTreeDelegate {
@@ -259,11 +308,16 @@ impl ListDelegate for KeyTreeDelegate {
             Hsla::black().alpha(STRIPE_BACKGROUND_ALPHA_LIGHT)
         };
 
-        // Show child count for folders
+        // Show child count for folders and the remaining TTL for keys
         let count_label = if entry.is_folder {
             Label::new(entry.children_count.to_string())
                 .text_sm()
                 .text_color(cx.theme().muted_foreground)
+        } else if let Some(expire_at) = self.key_expire_at.get(&entry.id) {
+            let ttl = Duration::from_secs((expire_at - unix_ts()).max(0) as u64);
+            Label::new(format_duration(ttl))
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
         } else {
             Label::new("")
         };
@@ -279,6 +333,7 @@ impl ListDelegate for KeyTreeDelegate {
         let selected_items_count = self.selected_items.len();
         let id = entry.id.clone();
         let is_folder = entry.is_folder;
+        let readonly = self.readonly;
         Some(
             ListItem::new(ix)
                 .w_full()
@@ -299,17 +354,39 @@ impl ListDelegate for KeyTreeDelegate {
                                 );
                             } else {
                                 menu = if is_folder {
-                                    menu.menu_element_with_icon(
-                                        CustomIconName::X,
-                                        Box::new(KeyTreeAction::DeleteFolder(id)),
-                                        move |_, cx| Label::new(i18n_key_tree(cx, "delete_folder_tooltip")),
-                                    )
-                                } else {
+                                    menu = menu
+                                        .menu_element_with_icon(
+                                            IconName::Copy,
+                                            Box::new(KeyTreeAction::CopyFolderPath(id.clone())),
+                                            move |_, cx| Label::new(i18n_key_tree(cx, "copy_folder_path_tooltip")),
+                                        )
+                                        .menu_element_with_icon(
+                                            IconName::Search,
+                                            Box::new(KeyTreeAction::ScanFolder(id.clone())),
+                                            move |_, cx| Label::new(i18n_key_tree(cx, "scan_folder_tooltip")),
+                                        )
+                                        .menu_element_with_icon(
+                                            IconName::Info,
+                                            Box::new(KeyTreeAction::CountFolderKeys(id.clone())),
+                                            move |_, cx| Label::new(i18n_key_tree(cx, "count_folder_keys_tooltip")),
+                                        );
+                                    if readonly {
+                                        menu
+                                    } else {
+                                        menu.menu_element_with_icon(
+                                            CustomIconName::X,
+                                            Box::new(KeyTreeAction::DeleteFolder(id)),
+                                            move |_, cx| Label::new(i18n_key_tree(cx, "delete_folder_tooltip")),
+                                        )
+                                    }
+                                } else if !readonly {
                                     menu.menu_element_with_icon(
                                         CustomIconName::X,
                                         Box::new(KeyTreeAction::DeleteKey(id)),
                                         move |_, cx| Label::new(i18n_key_tree(cx, "delete_key_tooltip")),
                                     )
+                                } else {
+                                    menu
                                 };
                             }
                             menu
@@ -374,6 +451,9 @@ pub struct ZedisKeyTree {
     /// Whether to enter add key mode
     should_enter_add_key_mode: Option<bool>,
 
+    /// Keys restored and total of the running import
+    import_progress: Option<(usize, usize)>,
+
     /// Event subscriptions for reactive updates
     _subscriptions: Vec<Subscription>,
 }
@@ -394,11 +474,24 @@ impl ZedisKeyTree {
             cx.subscribe(&server_state, |this, _server_state, event, cx| match event {
                 ServerEvent::KeyCollapseAll => {
                     this.state.expanded_items.clear();
+                    this.save_expanded_items(cx);
                     this.update_key_tree(true, cx);
                 }
                 ServerEvent::ServerSelected(_, _) => {
                     this.reset(cx);
                 }
+                // The type filter needs the types of the newly loaded keys
+                ServerEvent::KeyScanPaged(_) | ServerEvent::KeyScanFinished(_) => {
+                    if !this.state.visible_key_types.is_empty() {
+                        this.server_state.update(cx, |state, cx| {
+                            state.fill_all_key_types(cx);
+                        });
+                    }
+                }
+                // Keys that are gone may still be listed, scan the keyword again
+                ServerEvent::MatchingKeysDeleted(_) => {
+                    this.handle_filter(cx);
+                }
                 ServerEvent::EditionActionTriggered(action) => {
                     if action == &EditorAction::Create {
                         this.should_enter_add_key_mode = Some(true);
@@ -426,8 +519,11 @@ impl ZedisKeyTree {
 
         // Subscribe to search input events (Enter key triggers filter)
         subscriptions.push(cx.subscribe_in(&keyword_state, window, |view, _, event, _, cx| {
-            if let InputEvent::PressEnter { .. } = &event {
-                view.handle_filter(cx);
+            match &event {
+                InputEvent::PressEnter { .. } => view.handle_filter(cx),
+                // Keep the search pattern tooltip in sync with the keyword
+                InputEvent::Change => cx.notify(),
+                _ => {}
             }
         }));
 
@@ -438,6 +534,8 @@ impl ZedisKeyTree {
             enabled_multiple_selection: false,
             selected_index: None,
             selected_items: AHashSet::with_capacity(5),
+            readonly: false,
+            key_expire_at: Arc::default(),
         };
         let key_tree_list_state = cx.new(|cx| ListState::new(delegate, window, cx));
         subscriptions.push(cx.subscribe(&key_tree_list_state, |view, _, event, cx| match event {
@@ -461,8 +559,11 @@ impl ZedisKeyTree {
             keyword_state,
             server_state,
             should_enter_add_key_mode: None,
+            import_progress: None,
             _subscriptions: subscriptions,
         };
+        this.restore_expanded_items(cx);
+        this.restore_visible_key_types(cx);
 
         // Initial tree build
         this.update_key_tree(true, cx);
@@ -470,8 +571,70 @@ impl ZedisKeyTree {
         this
     }
 
-    fn reset(&mut self, _cx: &mut Context<Self>) {
+    fn reset(&mut self, cx: &mut Context<Self>) {
         self.state = KeyTreeState::default();
+        self.restore_expanded_items(cx);
+        self.restore_visible_key_types(cx);
+    }
+    /// Restore the key types shown for the current server
+    fn restore_visible_key_types(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        if server_id.is_empty() {
+            return;
+        }
+        let key_types = cx.global::<ZedisGlobalStore>().read(cx).visible_key_types(&server_id);
+        self.state.visible_key_types = key_types
+            .iter()
+            .map(|name| KeyType::from(name.as_str()))
+            .filter(|key_type| *key_type != KeyType::Unknown)
+            .collect();
+    }
+    /// Show or hide the keys of a type, hiding every other type when it's the first one picked
+    fn toggle_visible_key_type(&mut self, key_type: KeyType, cx: &mut Context<Self>) {
+        if !self.state.visible_key_types.remove(&key_type) {
+            self.state.visible_key_types.insert(key_type);
+        }
+        if !self.state.visible_key_types.is_empty() {
+            self.server_state.update(cx, |state, cx| {
+                state.fill_all_key_types(cx);
+            });
+        }
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        if !server_id.is_empty() {
+            let mut key_types: Vec<String> = self
+                .state
+                .visible_key_types
+                .iter()
+                .map(|key_type| key_type.redis_name().to_string())
+                .collect();
+            key_types.sort_unstable();
+            update_app_state_and_save(cx, "save_visible_key_types", move |state, _cx| {
+                state.set_visible_key_types(&server_id, key_types.clone());
+            });
+        }
+        self.update_key_tree(true, cx);
+        cx.notify();
+    }
+    /// Expand the folders saved for the current server
+    fn restore_expanded_items(&mut self, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        if server_id.is_empty() {
+            return;
+        }
+        let folders = cx.global::<ZedisGlobalStore>().read(cx).expanded_folders(&server_id);
+        self.state.expanded_items = folders.into_iter().map(SharedString::from).collect();
+    }
+    /// Persist the expanded folders of the current server
+    fn save_expanded_items(&self, cx: &mut Context<Self>) {
+        let server_id = self.server_state.read(cx).server_id().to_string();
+        if server_id.is_empty() {
+            return;
+        }
+        let mut folders: Vec<String> = self.state.expanded_items.iter().map(|item| item.to_string()).collect();
+        folders.sort_unstable();
+        update_app_state_and_save(cx, "save_expanded_folders", move |state, _cx| {
+            state.set_expanded_folders(&server_id, folders.clone());
+        });
     }
     fn reset_expand(&mut self, _cx: &mut Context<Self>) {
         self.state.expanded_items.clear();
@@ -505,7 +668,13 @@ impl ZedisKeyTree {
         let expand_all = server_state.scan_count() < AUTO_EXPAND_THRESHOLD;
         let keys_snapshot: Vec<(SharedString, KeyType)> =
             server_state.keys().iter().map(|(k, v)| (k.clone(), *v)).collect();
+        let has_keys = !keys_snapshot.is_empty();
+        // Only a finished unfiltered scan tells that a saved folder no longer exists
+        let should_prune = has_keys && server_state.scan_completed() && self.state.keyword.is_empty();
         let expanded_items = self.state.expanded_items.clone();
+        let visible_key_types = self.state.visible_key_types.clone();
+        let readonly = server_state.readonly();
+        let key_expire_at = server_state.key_expire_at();
 
         let view_handle = cx.entity().downgrade();
         let keyword = self.state.keyword.clone();
@@ -517,27 +686,48 @@ impl ZedisKeyTree {
             cx.spawn(async move |handle, cx| {
                 let task = cx.background_spawn(async move {
                     let start = std::time::Instant::now();
+                    let pruned_items = should_prune
+                        .then(|| {
+                            expanded_items
+                                .iter()
+                                .filter(|dir| {
+                                    let prefix = format!("{dir}{separator}");
+                                    keys_snapshot.iter().any(|(key, _)| key.starts_with(&prefix))
+                                })
+                                .cloned()
+                                .collect::<AHashSet<SharedString>>()
+                        })
+                        .filter(|pruned| pruned.len() != expanded_items.len());
                     let items = new_key_tree_items(
                         keys_snapshot,
                         keyword,
                         expand_all,
                         expanded_items,
+                        visible_key_types,
                         &separator,
                         max_key_tree_depth,
                     );
                     tracing::debug!("Key tree build time: {:?}", start.elapsed());
-                    items
+                    (items, pruned_items)
                 });
 
-                let result = task.await;
-                if result.is_empty() {
+                let (result, pruned_items) = task.await;
+                // Nothing loaded yet (e.g. reconnecting), keep the expanded folders for the coming keys
+                if result.is_empty() && has_keys {
                     let _ = view_handle.update(cx, |view: &mut ZedisKeyTree, cx| {
                         view.reset_expand(cx);
                     });
+                } else if let Some(pruned_items) = pruned_items {
+                    let _ = view_handle.update(cx, |view: &mut ZedisKeyTree, cx| {
+                        view.state.expanded_items = pruned_items;
+                        view.save_expanded_items(cx);
+                    });
                 }
                 handle.update(cx, |this, cx| {
                     this.delegate_mut().selected_items.clear();
                     this.delegate_mut().items = result;
+                    this.delegate_mut().readonly = readonly;
+                    this.delegate_mut().key_expire_at = key_expire_at;
                     cx.notify();
                 })
             })
@@ -573,6 +763,475 @@ impl ZedisKeyTree {
             handle.handle_filter(keyword, cx);
         });
     }
+    /// Full namespace prefix of a folder, e.g. `user:session:` for the `user:session` folder
+    fn folder_prefix(&self, folder: &str, cx: &App) -> String {
+        let separator = cx.global::<ZedisGlobalStore>().read(cx).key_separator();
+        format!("{folder}{separator}")
+    }
+    /// Scan the keys under a folder and list them, the folder is only deleted after typing the keyword
+    fn handle_delete_folder(&mut self, folder: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let prefix = self.folder_prefix(folder, cx);
+        let pattern = QueryMode::Prefix.scan_pattern(&prefix, true);
+        let delete_pattern: SharedString = pattern.clone().into();
+        let state = self.server_state.read(cx);
+        if state.readonly() {
+            return;
+        }
+        let server_id = state.server_id().to_string();
+        let db = state.db();
+        let commands = state.delete_commands(cx);
+        let server_state = self.server_state.clone();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    let client = get_connection_manager().get_client(&server_id, db).await?;
+                    client
+                        .scan_rounds(&pattern, DELETE_SCAN_COUNT, DELETE_SCAN_ROUNDS)
+                        .await
+                })
+                .await;
+            let _ = handle.update_in(cx, |_this, window, cx| {
+                let (keys, complete) = match result {
+                    Ok(value) => value,
+                    Err(e) => {
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                        return;
+                    }
+                };
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                if keys.is_empty() {
+                    let message = t!("key_tree.delete_folder_no_keys", prefix = prefix, locale = locale).to_string();
+                    window.push_notification(Notification::info(message), cx);
+                    return;
+                }
+                let mut lines = vec![
+                    t!(
+                        "key_tree.delete_folder_preview",
+                        count = keys.len(),
+                        prefix = prefix,
+                        keyword = DELETE_CONFIRM_KEYWORD,
+                        locale = locale
+                    )
+                    .to_string(),
+                ];
+                lines.extend(keys.iter().take(DELETE_PREVIEW_SAMPLE).map(|key| format!("  {key}")));
+                if keys.len() > DELETE_PREVIEW_SAMPLE {
+                    lines.push(
+                        t!(
+                            "key_tree.delete_folder_preview_more",
+                            count = keys.len() - DELETE_PREVIEW_SAMPLE,
+                            locale = locale
+                        )
+                        .to_string(),
+                    );
+                }
+                // The scan stopped early, keys it didn't reach are left alone
+                if !complete {
+                    lines.push(t!("key_tree.delete_folder_preview_partial", locale = locale).to_string());
+                }
+                open_typed_confirm_dialog(
+                    i18n_key_tree(cx, "delete_folder_title"),
+                    lines.join("\n").into(),
+                    DELETE_CONFIRM_KEYWORD.into(),
+                    vec![delete_command_field(i18n_common(cx, "delete_command"), &commands, cx)],
+                    move |values, _window, cx| {
+                        let command = selected_delete_command(&commands, values.first());
+                        // Keys the scan didn't reach are removed by a full SCAN on the server
+                        if !complete {
+                            let pattern = delete_pattern.clone();
+                            server_state.update(cx, |state, cx| {
+                                state.delete_matching_keys(pattern, command, cx);
+                            });
+                            return;
+                        }
+                        let keys = keys.clone();
+                        server_state.update(cx, |state, cx| {
+                            state.delete_folder(keys, command, cx);
+                        });
+                    },
+                    window,
+                    cx,
+                );
+            });
+        })
+        .detach();
+    }
+    /// Asks for confirmation and the delete command before deleting keys of the tree
+    fn handle_delete_keys(&mut self, keys: Vec<SharedString>, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.server_state.read(cx);
+        if state.readonly() || keys.is_empty() {
+            return;
+        }
+        let commands = state.delete_commands(cx);
+        let command = commands.first().copied().unwrap_or_default().as_str();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let message = if let [key] = keys.as_slice() {
+            t!(
+                "key_tree.delete_key_prompt",
+                key = key,
+                command = command,
+                locale = locale
+            )
+        } else {
+            t!(
+                "key_tree.delete_keys_prompt",
+                count = keys.len(),
+                command = command,
+                locale = locale
+            )
+        };
+        let server_state = self.server_state.clone();
+        let fields = vec![delete_command_field(message.to_string().into(), &commands, cx)];
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, _window: &mut Window, cx: &mut App| {
+            let command = selected_delete_command(&commands, values.first());
+            let keys = keys.clone();
+            server_state.update(cx, move |state, cx| {
+                if let [key] = keys.as_slice() {
+                    state.delete_key(key.clone(), command, cx);
+                } else {
+                    state.delete_keys(keys, command, cx);
+                }
+            });
+            true
+        });
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_key_tree(cx, "delete_keys_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+    /// Delete every key matching the searched pattern on the server, after typing the keyword
+    fn handle_delete_matching_keys(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let keyword = self.keyword_state.read(cx).value();
+        let query_mode = self.state.query_mode;
+        if query_mode == QueryMode::Pattern && keyword.trim().is_empty() {
+            window.push_notification(Notification::warning(i18n_key_tree(cx, "pattern_empty")), cx);
+            return;
+        }
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let pattern: SharedString = query_mode.scan_pattern(&keyword, store.escape_glob_keyword()).into();
+        let locale = store.locale();
+        let message = t!(
+            "key_tree.delete_matching_keys_confirm",
+            pattern = pattern,
+            db = self.server_state.read(cx).db(),
+            keyword = DELETE_CONFIRM_KEYWORD,
+            locale = locale
+        )
+        .to_string();
+        let server_state = self.server_state.clone();
+        let commands = server_state.read(cx).delete_commands(cx);
+        open_typed_confirm_dialog(
+            i18n_key_tree(cx, "delete_matching_keys"),
+            message.into(),
+            DELETE_CONFIRM_KEYWORD.into(),
+            vec![delete_command_field(i18n_common(cx, "delete_command"), &commands, cx)],
+            move |values, _window, cx| {
+                let pattern = pattern.clone();
+                let command = selected_delete_command(&commands, values.first());
+                server_state.update(cx, |state, cx| {
+                    state.delete_matching_keys(pattern, command, cx);
+                });
+            },
+            window,
+            cx,
+        );
+    }
+    /// Export the listed keys (keyword and type filter applied) as a file of `RESTORE` commands
+    ///
+    /// The file is in the RESP format `redis-cli --pipe` reads, so it is binary safe.
+    fn handle_export_keys(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.read(cx);
+        let server_id = server_state.server_id().to_string();
+        let db = server_state.db();
+        let keyword = self.state.keyword.clone();
+        let visible_key_types = &self.state.visible_key_types;
+        let mut keys: Vec<String> = server_state
+            .keys()
+            .iter()
+            .filter(|(key, key_type)| {
+                (keyword.is_empty() || key.contains(keyword.as_str()))
+                    && (visible_key_types.is_empty() || visible_key_types.contains(key_type))
+            })
+            .map(|(key, _)| key.to_string())
+            .collect();
+        if keys.is_empty() {
+            window.push_notification(Notification::warning(i18n_key_tree(cx, "export_keys_empty")), cx);
+            return;
+        }
+        keys.sort_unstable();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    let client = get_connection_manager().get_client(&server_id, db).await?;
+                    let dumps = client.dump_keys(keys).await?;
+                    let mut content = Vec::new();
+                    for (key, payload, ttl) in dumps.iter() {
+                        // RESTORE takes 0 for keys without expiry
+                        content.extend(
+                            cmd("RESTORE")
+                                .arg(key)
+                                .arg((*ttl).max(0))
+                                .arg(payload.as_slice())
+                                .arg("REPLACE")
+                                .get_packed_command(),
+                        );
+                    }
+                    Ok::<_, Error>((content, dumps.len()))
+                })
+                .await;
+            let _ = handle.update_in(cx, |_this, window, cx| match result {
+                Ok((_, 0)) => {
+                    window.push_notification(Notification::warning(i18n_key_tree(cx, "export_keys_empty")), cx);
+                }
+                Ok((content, count)) => {
+                    export_to_file(format!("zedis-db{db}-{count}-keys.resp"), content, window, cx);
+                }
+                Err(e) => {
+                    window.push_notification(Notification::error(e.to_string()), cx);
+                }
+            });
+        })
+        .detach();
+    }
+    /// Pick a file written by the export, then ask whether existing keys are replaced before restoring
+    fn handle_import_keys(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.import_progress.is_some() {
+            window.push_notification(Notification::warning(i18n_key_tree(cx, "import_keys_running")), cx);
+            return;
+        }
+        let path_rx = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+        cx.spawn_in(window, async move |handle, cx| {
+            let Ok(Ok(Some(paths))) = path_rx.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let result = cx
+                .background_spawn(async move {
+                    let content = smol::fs::read(&path).await?;
+                    parse_restore_commands(&content)
+                })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| match result {
+                Ok(entries) if entries.is_empty() => {
+                    window.push_notification(Notification::warning(i18n_key_tree(cx, "import_keys_empty")), cx);
+                }
+                Ok(entries) => this.open_import_dialog(entries, window, cx),
+                Err(e) => {
+                    window.push_notification(Notification::error(e.to_string()), cx);
+                }
+            });
+        })
+        .detach();
+    }
+    fn open_import_dialog(
+        &mut self,
+        entries: Vec<(String, Vec<u8>, i64)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let title = t!("key_tree.import_keys_title", count = entries.len(), locale = locale).to_string();
+        let fields = vec![
+            FormField::new(i18n_key_tree(cx, "import_existing_keys")).with_options(vec![
+                i18n_key_tree(cx, "import_existing_keys_skip"),
+                i18n_key_tree(cx, "import_existing_keys_replace"),
+            ]),
+        ];
+        let view = cx.entity().downgrade();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let replace = values.first().map(|value| value.as_ref()) == Some("1");
+            let entries = entries.clone();
+            let _ = view.update(cx, |this, cx| {
+                this.import_keys(entries, replace, window, cx);
+            });
+            window.close_dialog(cx);
+            true
+        });
+        open_add_form_dialog(
+            FormDialog {
+                title: title.into(),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+    /// Restore the entries in batches, keys that already exist are reported unless replaced
+    fn import_keys(
+        &mut self,
+        entries: Vec<(String, Vec<u8>, i64)>,
+        replace: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let server_state = self.server_state.read(cx);
+        let server_id = server_state.server_id().to_string();
+        let db = server_state.db();
+        let total = entries.len();
+        self.import_progress = Some((0, total));
+        cx.notify();
+        cx.spawn_in(window, async move |handle, cx| {
+            let mut restored = 0;
+            let mut skipped = Vec::new();
+            let mut error = None;
+            for batch in entries.chunks(IMPORT_BATCH_SIZE) {
+                let batch = batch.to_vec();
+                let server_id = server_id.clone();
+                let result = cx
+                    .background_spawn(async move {
+                        let client = get_connection_manager().get_client(&server_id, db).await?;
+                        client.restore_keys(batch, replace).await
+                    })
+                    .await;
+                match result {
+                    Ok((count, busy_keys)) => {
+                        restored += count;
+                        skipped.extend(busy_keys);
+                    }
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+                let done = restored + skipped.len();
+                let _ = handle.update(cx, |this, cx| {
+                    this.import_progress = Some((done, total));
+                    cx.notify();
+                });
+            }
+            let _ = handle.update_in(cx, |this, window, cx| {
+                this.import_progress = None;
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                if let Some(e) = error {
+                    window.push_notification(Notification::error(e.to_string()), cx);
+                } else if skipped.is_empty() {
+                    let message = t!("key_tree.import_keys_success", count = restored, locale = locale).to_string();
+                    window.push_notification(Notification::success(message), cx);
+                } else {
+                    let mut keys = skipped
+                        .iter()
+                        .take(IMPORT_SKIPPED_SAMPLE)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if skipped.len() > IMPORT_SKIPPED_SAMPLE {
+                        keys.push_str(", …");
+                    }
+                    let message = t!(
+                        "key_tree.import_keys_skipped",
+                        count = restored,
+                        skipped = skipped.len(),
+                        keys = keys,
+                        locale = locale
+                    )
+                    .to_string();
+                    window.push_notification(Notification::warning(message), cx);
+                }
+                this.handle_filter(cx);
+            });
+        })
+        .detach();
+    }
+    /// Count the keys under a folder on the server, only their names are scanned
+    fn handle_count_folder_keys(&mut self, folder: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let prefix = self.folder_prefix(folder, cx);
+        let pattern = QueryMode::Prefix.scan_pattern(&prefix, true);
+        let state = self.server_state.read(cx);
+        let server_id = state.server_id().to_string();
+        let db = state.db();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    let client = get_connection_manager().get_client(&server_id, db).await?;
+                    client.count_matching(&pattern, DELETE_SCAN_COUNT).await
+                })
+                .await;
+            let _ = handle.update_in(cx, |_this, window, cx| {
+                let notification = match result {
+                    Ok(count) => {
+                        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                        let message = t!(
+                            "key_tree.folder_keys_count",
+                            count = count,
+                            prefix = prefix,
+                            locale = locale
+                        );
+                        Notification::info(message.to_string())
+                    }
+                    Err(e) => Notification::error(e.to_string()),
+                };
+                window.push_notification(notification, cx);
+            });
+        })
+        .detach();
+    }
+    /// Flush the db, or all dbs, after typing the name of the server like deleting a repository
+    fn handle_flush(&mut self, everything: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.server_state.read(cx);
+        if state.access_mode() != AccessMode::ReadWrite {
+            window.push_notification(Notification::warning(state.readonly_reason(cx)), cx);
+            return;
+        }
+        let server_id = state.server_id();
+        let name: SharedString = state
+            .server(server_id)
+            .map(|server| server.name.clone())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| server_id.to_string())
+            .into();
+        let db = state.db();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let (title, message) = if everything {
+            (
+                i18n_key_tree(cx, "flush_all_title"),
+                t!("key_tree.flush_all_confirm", name = name, locale = locale),
+            )
+        } else {
+            (
+                i18n_key_tree(cx, "flush_db_title"),
+                t!("key_tree.flush_db_confirm", db = db, name = name, locale = locale),
+            )
+        };
+        let server_state = self.server_state.clone();
+        open_typed_confirm_dialog(
+            title,
+            message.to_string().into(),
+            name,
+            vec![],
+            move |_values, _window, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.flush_db(everything, cx);
+                });
+            },
+            window,
+            cx,
+        );
+    }
+    /// Re-scan the keys under a folder by using its namespace as a prefix keyword
+    fn handle_scan_folder(&mut self, folder: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let prefix = self.folder_prefix(folder, cx);
+        self.server_state.update(cx, |state, cx| {
+            state.set_query_mode(QueryMode::Prefix, cx);
+        });
+        self.state.query_mode = QueryMode::Prefix;
+        self.keyword_state.update(cx, |state, cx| {
+            state.set_value(prefix, window, cx);
+        });
+        self.handle_filter(cx);
+    }
     fn handle_clear_history(&mut self, cx: &mut Context<Self>) {
         let server_state = self.server_state.read(cx);
         let server_id = server_state.server_id().to_string();
@@ -590,7 +1249,7 @@ impl ZedisKeyTree {
     }
 
     fn handle_add_key(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let category_list = ["String", "List", "Set", "Zset", "Hash"];
+        let category_list = ["String", "List", "Set", "Zset", "Hash", "Stream"];
         let fields = vec![
             FormField::new(i18n_key_tree(cx, "category"))
                 .with_options(category_list.iter().map(|s| s.to_string().into()).collect()),
@@ -703,9 +1362,11 @@ impl ZedisKeyTree {
                 }
                 // User clicked an expanded folder -> collapse it
                 self.state.expanded_items.remove(&item_id);
+                self.save_expanded_items(cx);
             } else {
                 // User clicked a collapsed folder -> expand it and load data
                 self.state.expanded_items.insert(item_id.clone());
+                self.save_expanded_items(cx);
                 self.server_state.update(cx, |state, cx| {
                     state.scan_prefix(format!("{}:", item_id.as_str()).into(), cx);
                 });
@@ -716,7 +1377,7 @@ impl ZedisKeyTree {
             // Select Key
             if !is_selected {
                 self.server_state.update(cx, |state, cx| {
-                    state.select_key(item_id.clone(), cx);
+                    state.request_navigation(PendingNavigation::SelectKey(item_id.clone()), cx);
                 });
             }
         }
@@ -732,15 +1393,157 @@ impl ZedisKeyTree {
         if let Some(status_view) = self.get_tree_status_view(cx) {
             return status_view.into_any_element();
         }
+        let server_state = self.server_state.read(cx);
+        let loaded_keys_limit_reached = !server_state.scanning() && server_state.loaded_keys_limit_reached();
+        let loaded_keys_count = server_state.scan_count();
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
 
-        div()
+        v_flex()
             .p_1()
             .bg(cx.theme().sidebar)
             .text_color(cx.theme().sidebar_foreground)
             .h_full()
-            .child(List::new(&self.key_tree_list_state))
+            .child(div().flex_1().min_h_0().child(List::new(&self.key_tree_list_state)))
+            .when(loaded_keys_limit_reached, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .p_2()
+                        .items_center()
+                        .border_t_1()
+                        .border_color(cx.theme().border)
+                        .child(Icon::new(IconName::Info).text_sm())
+                        .child(
+                            Label::new(
+                                t!(
+                                    "key_tree.loaded_keys_limit_reached",
+                                    count = loaded_keys_count,
+                                    locale = locale
+                                )
+                                .to_string(),
+                            )
+                            .flex_1()
+                            .text_sm()
+                            .whitespace_normal(),
+                        )
+                        .child(
+                            Button::new("zedis-key-tree-load-more")
+                                .small()
+                                .outline()
+                                .label(i18n_key_tree(cx, "load_more"))
+                                .on_click(cx.listener(|this, _, _window, cx| {
+                                    this.server_state.update(cx, |state, cx| {
+                                        state.scan_next(cx);
+                                    });
+                                })),
+                        ),
+                )
+            })
             .into_any_element()
     }
+    /// Renders the server type of the connection with the current db (standalone)
+    /// or the number of masters (cluster), empty until the connection is made.
+    /// While a cluster scan is unfinished, the masters done with it are shown too.
+    fn render_server_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let server_state = self.server_state.read(cx);
+        let description = server_state.nodes_description();
+        if description.server_type.is_empty() {
+            return h_flex();
+        }
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let tag_color = server_state
+            .server(server_state.server_id())
+            .and_then(|server| server.tag_color());
+        // Shards still scanning tell why keys keep trickling in, only meaningful with several masters
+        let scan_progress = server_state.scan_masters_progress().filter(|(_, total)| *total > 1);
+        let (icon, color, detail) = if server_state.supports_db_selection() {
+            let color = if description.server_type == "Sentinel" {
+                cx.theme().info
+            } else {
+                cx.theme().success
+            };
+            (
+                Icon::new(CustomIconName::DatabaseZap),
+                color,
+                t!("key_tree.header_db", db = server_state.db(), locale = locale),
+            )
+        } else {
+            (
+                Icon::new(CustomIconName::Network),
+                cx.theme().warning,
+                t!(
+                    "key_tree.header_masters",
+                    count = server_state.nodes().0,
+                    locale = locale
+                ),
+            )
+        };
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .items_center()
+            .text_xs()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .when_some(tag_color, |this, tag_color| {
+                this.child(div().size_2().rounded_full().bg(tag_color))
+            })
+            .child(icon.text_color(color))
+            .child(Label::new(description.server_type.clone()).text_color(color))
+            .child(Label::new("·").text_color(cx.theme().muted_foreground))
+            .child(Label::new(detail.to_string()).text_color(cx.theme().muted_foreground))
+            .when_some(self.import_progress, |this, (done, total)| {
+                this.child(Label::new("·").text_color(cx.theme().muted_foreground))
+                    .child(
+                        Label::new(
+                            t!("key_tree.header_importing", done = done, total = total, locale = locale).to_string(),
+                        )
+                        .text_color(cx.theme().info),
+                    )
+            })
+            .when_some(scan_progress, |this, (done, total)| {
+                this.child(Label::new("·").text_color(cx.theme().muted_foreground))
+                    .child(
+                        Label::new(
+                            t!(
+                                "key_tree.header_masters_scanned",
+                                done = done,
+                                total = total,
+                                locale = locale
+                            )
+                            .to_string(),
+                        )
+                        .text_color(cx.theme().muted_foreground),
+                    )
+            })
+    }
+
+    /// Toggle chips hiding the keys of the other types, none selected shows every type
+    fn render_key_type_chips(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let visible_key_types = &self.state.visible_key_types;
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .flex_wrap()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .children(FILTER_KEY_TYPES.into_iter().enumerate().map(|(index, key_type)| {
+                let selected = visible_key_types.contains(&key_type);
+                Button::new(("key-tree-key-type-chip", index))
+                    .xsmall()
+                    .outline()
+                    .selected(selected)
+                    .when(selected, |this| this.text_color(key_type.color()))
+                    .tooltip(i18n_key_tree(cx, "key_type_chip_tooltip"))
+                    .label(key_type.as_str())
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.toggle_visible_key_type(key_type, cx);
+                    }))
+            }))
+    }
+
     /// Render the search/filter input bar with query mode selector
     ///
     /// Features:
@@ -752,7 +1555,11 @@ impl ZedisKeyTree {
         let server_state_clone = self.server_state.clone();
         let server_state = self.server_state.read(cx);
         let readonly = server_state.readonly();
+        let readonly_reason = server_state.readonly_reason(cx);
+        let can_flush_db = server_state.can_run("FLUSHDB");
+        let can_flush_all = server_state.can_run("FLUSHALL");
         let scanning = server_state.scanning();
+        let db = server_state.db();
         let server_id = server_state.server_id();
         if server_id != self.state.server_id.as_str() {
             self.state.server_id = server_id.to_string().into();
@@ -761,15 +1568,23 @@ impl ZedisKeyTree {
             });
         }
         let query_mode = self.state.query_mode;
+        let key_type_filter = server_state.key_type_filter();
 
         // Select icon based on query mode
         let icon = match query_mode {
             QueryMode::All => Icon::new(IconName::Asterisk), // * for all keys
             QueryMode::Prefix => Icon::new(CustomIconName::ChevronUp), // ~ for prefix
             QueryMode::Exact => Icon::new(CustomIconName::Equal), // = for exact match
+            QueryMode::Pattern => Icon::new(CustomIconName::ChevronsLeftRightEllipsis), // raw glob pattern
         };
         let query_mode_dropdown = DropdownButton::new("dropdown")
-            .button(Button::new("key-tree-query-mode-btn").ghost().px_2().icon(icon))
+            .button(
+                Button::new("key-tree-query-mode-btn")
+                    .ghost()
+                    .px_2()
+                    .icon(icon)
+                    .when_some(key_type_filter, |this, key_type| this.label(key_type.as_str())),
+            )
             .dropdown_menu_with_anchor(Corner::TopLeft, move |menu, window, cx| {
                 let mut menu = menu.label(i18n_key_tree(cx, "search_history"));
                 let keywords = server_state_clone.read(cx).search_history();
@@ -786,6 +1601,43 @@ impl ZedisKeyTree {
                         move |_, cx| Label::new(i18n_key_tree(cx, "clear_history")),
                     );
                 }
+                // Exact keys are deleted from the editor, the other modes scan a pattern
+                if !readonly && query_mode != QueryMode::Exact {
+                    menu = menu.separator().menu_element_with_icon(
+                        CustomIconName::FileXCorner,
+                        Box::new(KeyTreeAction::DeleteMatchingKeys),
+                        move |_, cx| Label::new(i18n_key_tree(cx, "delete_matching_keys")),
+                    );
+                }
+                menu = menu.menu_element_with_icon(
+                    CustomIconName::Download,
+                    Box::new(KeyTreeAction::ExportKeys),
+                    move |_, cx| Label::new(i18n_key_tree(cx, "export_keys")),
+                );
+                if !readonly {
+                    menu = menu.menu_element_with_icon(
+                        CustomIconName::Import,
+                        Box::new(KeyTreeAction::ImportKeys),
+                        move |_, cx| Label::new(i18n_key_tree(cx, "import_keys")),
+                    );
+                }
+                if !readonly && (can_flush_db || can_flush_all) {
+                    menu = menu.separator();
+                    if can_flush_db {
+                        menu = menu.menu_element_with_icon(
+                            CustomIconName::Eraser,
+                            Box::new(KeyTreeAction::Flush(false)),
+                            move |_, cx| Label::new(i18n_key_tree(cx, "flush_db")),
+                        );
+                    }
+                    if can_flush_all {
+                        menu = menu.menu_element_with_icon(
+                            CustomIconName::Eraser,
+                            Box::new(KeyTreeAction::Flush(true)),
+                            move |_, cx| Label::new(i18n_key_tree(cx, "flush_all")),
+                        );
+                    }
+                }
                 menu.separator()
                     .submenu(i18n_key_tree(cx, "query_mode"), window, cx, move |submenu, _, _| {
                         // Build menu with checkmarks for current mode
@@ -803,11 +1655,53 @@ impl ZedisKeyTree {
                                 Box::new(QueryMode::Exact),
                                 |_, cx| Label::new(i18n_key_tree(cx, "query_mode_exact")).ml_2().text_xs(),
                             )
+                            .menu_element_with_check(
+                                query_mode == QueryMode::Pattern,
+                                Box::new(QueryMode::Pattern),
+                                |_, cx| Label::new(i18n_key_tree(cx, "query_mode_pattern")).ml_2().text_xs(),
+                            )
                     })
+                    .submenu(
+                        i18n_key_tree(cx, "key_type_filter"),
+                        window,
+                        cx,
+                        move |submenu, _, _| {
+                            let mut submenu = submenu.menu_element_with_check(
+                                key_type_filter.is_none(),
+                                Box::new(KeyTreeAction::FilterKeyType(None)),
+                                |_, cx| Label::new(i18n_key_tree(cx, "key_type_filter_any")).ml_2().text_xs(),
+                            );
+                            for key_type in FILTER_KEY_TYPES {
+                                submenu = submenu.menu_element_with_check(
+                                    key_type_filter == Some(key_type),
+                                    Box::new(KeyTreeAction::FilterKeyType(Some(key_type.redis_name().into()))),
+                                    move |_, _| Label::new(key_type.redis_name()).ml_2().text_xs(),
+                                );
+                            }
+                            submenu
+                        },
+                    )
             });
+        // Show the effective pattern so users can tell why a key is (not) listed
+        let keyword = self.keyword_state.read(cx).value();
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let pattern = query_mode.scan_pattern(&keyword, store.escape_glob_keyword());
+        let locale = store.locale();
+        let search_tooltip = if query_mode == QueryMode::Exact {
+            t!("key_tree.exact_key_tooltip", key = pattern, db = db, locale = locale)
+        } else {
+            t!(
+                "key_tree.scan_pattern_tooltip",
+                pattern = pattern,
+                db = db,
+                locale = locale
+            )
+        }
+        .to_string();
         // Search button (shows loading spinner during scan)
         let search_btn = Button::new("key-tree-search-btn")
             .ghost()
+            .tooltip(search_tooltip)
             .loading(scanning)
             .disabled(scanning)
             .icon(IconName::Search)
@@ -834,7 +1728,7 @@ impl ZedisKeyTree {
                     .disabled(readonly)
                     .outline()
                     .icon(CustomIconName::ListCheck)
-                    .when(readonly, |this| this.tooltip(i18n_common(cx, "disable_in_readonly")))
+                    .when(readonly, |this| this.tooltip(readonly_reason.clone()))
                     .when(!readonly, |this| {
                         this.tooltip(i18n_key_tree(cx, "toggle_multi_select_mode_tooltip"))
                     })
@@ -847,7 +1741,7 @@ impl ZedisKeyTree {
             .child(
                 Button::new("key-tree-add-btn")
                     .disabled(readonly)
-                    .when(readonly, |this| this.tooltip(i18n_common(cx, "disable_in_readonly")))
+                    .when(readonly, |this| this.tooltip(readonly_reason))
                     .when(!readonly, |this| this.tooltip(i18n_key_tree(cx, "add_key_tooltip")))
                     .outline()
                     .icon(CustomIconName::FilePlusCorner)
@@ -872,7 +1766,9 @@ impl Render for ZedisKeyTree {
         v_flex()
             .h_full()
             .w_full()
+            .child(self.render_server_header(cx))
             .child(self.render_keyword_input(window, cx))
+            .child(self.render_key_type_chips(cx))
             .child(self.render_tree(cx))
             .on_action(cx.listener(|this, e: &QueryMode, _window, cx| {
                 let new_mode = *e;
@@ -896,22 +1792,51 @@ impl Render for ZedisKeyTree {
                     this.handle_clear_history(cx);
                 }
                 KeyTreeAction::DeleteMultipleKeys => {
-                    this.key_tree_list_state.update(cx, |state, cx| {
-                        let keys = state.delegate().selected_items.iter().cloned().collect();
-                        this.server_state.update(cx, |state, cx| {
-                            state.unlink_key(keys, cx);
-                        });
-                    });
+                    let keys = this
+                        .key_tree_list_state
+                        .read(cx)
+                        .delegate()
+                        .selected_items
+                        .iter()
+                        .cloned()
+                        .collect();
+                    this.handle_delete_keys(keys, window, cx);
                 }
                 KeyTreeAction::DeleteKey(id) => {
-                    this.server_state.update(cx, |state, cx| {
-                        state.delete_key(id.clone(), cx);
-                    });
+                    this.handle_delete_keys(vec![id.clone()], window, cx);
                 }
                 KeyTreeAction::DeleteFolder(id) => {
-                    this.server_state.update(cx, |state, cx| {
-                        state.delete_folder(id.clone(), cx);
+                    this.handle_delete_folder(id, window, cx);
+                }
+                KeyTreeAction::CountFolderKeys(id) => {
+                    this.handle_count_folder_keys(id, window, cx);
+                }
+                KeyTreeAction::DeleteMatchingKeys => {
+                    this.handle_delete_matching_keys(window, cx);
+                }
+                KeyTreeAction::ExportKeys => {
+                    this.handle_export_keys(window, cx);
+                }
+                KeyTreeAction::ImportKeys => {
+                    this.handle_import_keys(window, cx);
+                }
+                KeyTreeAction::Flush(everything) => {
+                    this.handle_flush(*everything, window, cx);
+                }
+                KeyTreeAction::CopyFolderPath(id) => {
+                    let prefix = this.folder_prefix(id, cx);
+                    cx.write_to_clipboard(ClipboardItem::new_string(prefix));
+                    window.push_notification(Notification::info(i18n_key_tree(cx, "copied_folder_path")), cx);
+                }
+                KeyTreeAction::ScanFolder(id) => {
+                    this.handle_scan_folder(id, window, cx);
+                }
+                KeyTreeAction::FilterKeyType(name) => {
+                    let key_type = name.as_deref().map(KeyType::from);
+                    this.server_state.update(cx, |state, _cx| {
+                        state.set_key_type_filter(key_type);
                     });
+                    this.handle_filter(cx);
                 }
             }))
     }
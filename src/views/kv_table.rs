@@ -16,12 +16,12 @@ use crate::helpers::get_font_family;
 use crate::{
     assets::CustomIconName,
     components::{INDEX_COLUMN_NAME, ZedisKvDelegate, ZedisKvFetcher},
-    states::{ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_kv_table},
+    states::{ServerEvent, ViewMode, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_kv_table},
 };
-use gpui::{Entity, SharedString, Subscription, TextAlign, Window, div, prelude::*, px};
+use gpui::{App, Entity, Focusable, KeyDownEvent, SharedString, Subscription, TextAlign, Window, div, prelude::*, px};
 use gpui_component::highlighter::Language;
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, PixelsExt,
+    ActiveTheme, Disableable, Icon, IconName, PixelsExt, Sizable,
     button::{Button, ButtonVariants},
     form::field,
     h_flex,
@@ -53,6 +53,8 @@ pub enum KvTableColumnType {
 pub struct KvTableColumn {
     /// Whether the column is readonly
     pub readonly: bool,
+    /// Whether the column is edited with a single line input instead of a code editor
+    pub single_line: bool,
     /// Type of the column
     pub column_type: KvTableColumnType,
     /// Display name of the column
@@ -76,6 +78,14 @@ impl KvTableColumn {
         self.readonly = readonly;
         self
     }
+    pub fn with_single_line(mut self, single_line: bool) -> Self {
+        self.single_line = single_line;
+        self
+    }
+    /// Whether the column is edited with the multi-line code editor
+    pub(crate) fn is_code_editor(&self) -> bool {
+        !self.readonly && !self.single_line
+    }
 }
 
 /// A generic table view for displaying Redis key-value data.
@@ -88,6 +98,8 @@ impl KvTableColumn {
 pub struct ZedisKvTable<T: ZedisKvFetcher> {
     /// Table state managing the delegate and data
     table_state: Entity<TableState<ZedisKvDelegate<T>>>,
+    /// Server state owning the value, used to stop its pagination
+    server_state: Entity<ZedisServerState>,
     /// Input field state for keyword search/filter
     keyword_state: Entity<InputState>,
     /// Number of currently loaded items
@@ -96,14 +108,22 @@ pub struct ZedisKvTable<T: ZedisKvFetcher> {
     total_count: usize,
     /// Whether all data has been loaded
     done: bool,
+    /// Whether the user stopped loading before all data was loaded
+    canceled: bool,
+    /// Whether a page of data is being loaded
+    paginating: bool,
     /// Whether a filter operation is in progress
     loading: bool,
     /// Flag indicating the selected key has changed (triggers input reset)
     key_changed: bool,
     /// Whether the table is readonly
     readonly: bool,
+    /// Why the table is readonly, shown on disabled actions
+    readonly_reason: SharedString,
     /// The row index that is being edited
     edit_row: Option<usize>,
+    /// Focus the first editable field on the next render
+    should_focus_edit_form: bool,
     /// Columns configuration
     edit_columns: Vec<KvTableColumn>,
     /// Input states for editable cells, keyed by column index.
@@ -120,6 +140,15 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         T::new(server_state, value)
     }
 
+    /// Viewer mode of the current value, applied to the value cells.
+    fn view_mode(server_state: &Entity<ZedisServerState>, cx: &App) -> ViewMode {
+        server_state
+            .read(cx)
+            .value()
+            .map(|value| value.view_mode())
+            .unwrap_or_default()
+    }
+
     /// Prepares table columns by adding index and action columns, then calculating widths.
     ///
     /// # Logic:
@@ -211,14 +240,29 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                 | ServerEvent::ValueAdded(_)
                 | ServerEvent::ValueUpdated(_) => {
                     let fetcher = Self::new_values(server_state.clone(), cx);
+                    let view_mode = Self::view_mode(&server_state, cx);
                     this.loading = false;
+                    this.paginating = false;
                     this.done = fetcher.is_done();
+                    this.canceled = fetcher.is_canceled();
                     this.items_count = fetcher.rows_count();
                     this.total_count = fetcher.count();
                     this.table_state.update(cx, |state, _| {
                         state.delegate_mut().set_fetcher(fetcher);
+                        state.delegate_mut().set_view_mode(view_mode);
                     });
                 }
+                ServerEvent::ValueModeViewUpdated(_) => {
+                    let view_mode = Self::view_mode(&server_state, cx);
+                    this.table_state.update(cx, |state, cx| {
+                        state.delegate_mut().set_view_mode(view_mode);
+                        cx.notify();
+                    });
+                }
+                ServerEvent::ValuePaginationStarted(_) => {
+                    this.paginating = true;
+                    cx.notify();
+                }
                 // Clear search when key selection changes
                 ServerEvent::KeySelected(_) => {
                     this.key_changed = true;
@@ -242,9 +286,11 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         }));
 
         let readonly = server_state.read(cx).readonly();
+        let readonly_reason = server_state.read(cx).readonly_reason(cx);
         // Initialize table data and state
-        let fetcher = Self::new_values(server_state, cx);
+        let fetcher = Self::new_values(server_state.clone(), cx);
         let done = fetcher.is_done();
+        let canceled = fetcher.is_canceled();
         let items_count = fetcher.rows_count();
         let total_count = fetcher.count();
         let edit_columns = columns
@@ -260,8 +306,9 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             cx,
         );
         if readonly {
-            delegate.enable_readonly();
+            delegate.enable_readonly(readonly_reason.clone());
         }
+        delegate.set_view_mode(Self::view_mode(&server_state, cx));
 
         if fetcher.is_form_editor() {
             let view = cx.entity();
@@ -277,32 +324,50 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             .iter()
             .map(|column| {
                 cx.new(|cx| {
-                    if column.readonly {
-                        InputState::new(window, cx)
-                    } else {
+                    if column.is_code_editor() {
                         InputState::new(window, cx)
                             .code_editor(Language::from_str("json").name())
                             .line_number(true)
                             .indent_guides(true)
                             .searchable(true)
                             .soft_wrap(true)
+                    } else {
+                        InputState::new(window, cx)
                     }
                 })
             })
             .collect::<Vec<_>>();
+
+        // Enter saves the row, code editors keep Enter for new lines and save on cmd/ctrl-enter
+        for (column, state) in edit_columns.iter().zip(value_states.iter()) {
+            let code_editor = column.is_code_editor();
+            subscriptions.push(cx.subscribe_in(state, window, move |this, _state, event, window, cx| {
+                if let InputEvent::PressEnter { secondary } = event
+                    && (*secondary || !code_editor)
+                {
+                    this.handle_update_row(window, cx);
+                    cx.notify();
+                }
+            }));
+        }
         info!("Creating new key value table view");
 
         Self {
             table_state,
+            server_state,
             keyword_state,
             items_count,
             total_count,
             done,
+            canceled,
+            paginating: false,
             loading: false,
             key_changed: false,
             edit_row: None,
+            should_focus_edit_form: false,
             value_states,
             readonly,
+            readonly_reason,
             fetcher,
             edit_columns,
             _subscriptions: subscriptions,
@@ -321,6 +386,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             return;
         }
         self.edit_row = Some(row_ix);
+        self.should_focus_edit_form = true;
         self.value_states.iter().enumerate().for_each(|(index, state)| {
             state.update(cx, |input, cx| {
                 let Some(value) = values.get(index) else {
@@ -351,8 +417,77 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
         self.fetcher.handle_update_value(row_ix, values, window, cx);
         self.edit_row = None;
     }
+    /// Index of the edit field that currently has focus.
+    fn focused_field(&self, window: &Window, cx: &App) -> Option<usize> {
+        self.value_states
+            .iter()
+            .position(|state| state.focus_handle(cx).is_focused(window))
+    }
+    /// Indexes of the edit fields that accept input.
+    fn editable_fields(&self) -> Vec<usize> {
+        self.edit_columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.readonly)
+            .map(|(index, _)| index)
+            .collect()
+    }
+    fn focus_field(&self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(state) = self.value_states.get(index) {
+            state.update(cx, |input, cx| input.focus(window, cx));
+        }
+    }
+    /// Moves focus to the next (or previous) editable field, wrapping around.
+    fn focus_sibling_field(&mut self, backward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let editable = self.editable_fields();
+        if editable.is_empty() {
+            return;
+        }
+        let current = self
+            .focused_field(window, cx)
+            .and_then(|index| editable.iter().position(|&i| i == index));
+        let next = match (current, backward) {
+            (Some(pos), false) => (pos + 1) % editable.len(),
+            (Some(pos), true) => (pos + editable.len() - 1) % editable.len(),
+            (None, false) => 0,
+            (None, true) => editable.len() - 1,
+        };
+        self.focus_field(editable[next], window, cx);
+    }
+    /// Handles Escape and Tab inside the edit overlay.
+    ///
+    /// Tab is left to code editors for indentation, so it only moves focus
+    /// from single line fields.
+    fn handle_edit_form_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        match keystroke.key.as_str() {
+            "escape" => {
+                self.edit_row = None;
+                cx.notify();
+                cx.stop_propagation();
+            }
+            "tab" => {
+                let in_code_editor = self
+                    .focused_field(window, cx)
+                    .and_then(|index| self.edit_columns.get(index))
+                    .is_some_and(|column| column.is_code_editor());
+                if in_code_editor {
+                    return;
+                }
+                self.focus_sibling_field(keystroke.modifiers.shift, window, cx);
+                cx.stop_propagation();
+            }
+            _ => {}
+        }
+    }
     /// Renders the edit form for the current row.
-    fn render_edit_form(&mut self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_edit_form(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.should_focus_edit_form {
+            self.should_focus_edit_form = false;
+            if let Some(&index) = self.editable_fields().first() {
+                self.focus_field(index, window, cx);
+            }
+        }
         let mut form = v_flex().size_full().gap_3();
         for (index, column) in self.edit_columns.iter().enumerate() {
             let Some(value_state) = self.value_states.get(index) else {
@@ -365,7 +500,8 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                 .font_family(get_font_family())
                 .focus_bordered(false);
 
-            let inner_content = if index != 0 {
+            let compact = index == 0 || column.single_line;
+            let inner_content = if !compact {
                 v_flex()
                     .size_full()
                     .gap_1()
@@ -379,7 +515,7 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
             let wrapped_field = v_flex()
                 .w_full()
                 .child(inner_content)
-                .when(index != 0, |this| this.flex_1().h_full());
+                .when(!compact, |this| this.flex_1().h_full());
 
             form = form.child(wrapped_field);
         }
@@ -398,8 +534,9 @@ impl<T: ZedisKvFetcher> ZedisKvTable<T> {
                             Button::new("cancel-edit-btn")
                                 .icon(IconName::CircleX)
                                 .label(cancel_label)
-                                .on_click(cx.listener(|this, _, _, _cx| {
+                                .on_click(cx.listener(|this, _, _, cx| {
                                     this.edit_row = None;
+                                    cx.notify();
                                 })),
                         )
                         .child(
@@ -436,6 +573,14 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
             });
         });
 
+        // Handler for importing many values at once
+        let handle_import = cx.listener(|this, _, window, cx| {
+            this.table_state.update(cx, |state, cx| {
+                state.delegate().fetcher().handle_import(window, cx);
+            });
+        });
+        let can_import = self.table_state.read(cx).delegate().fetcher().can_import();
+
         // Search button with loading state
         let search_btn = Button::new("kv-table-search-btn")
             .ghost()
@@ -449,9 +594,21 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
 
         // Completion indicator icon
         let status_icon = if self.done {
-            Icon::new(CustomIconName::CircleCheckBig) // All data loaded
+            Icon::new(CustomIconName::CircleCheckBig).text_color(text_color) // All data loaded
+        } else if self.canceled {
+            Icon::new(CustomIconName::CircleDotDashed).text_color(cx.theme().warning) // Load stopped by the user
         } else {
-            Icon::new(CustomIconName::CircleDotDashed) // More data available
+            Icon::new(CustomIconName::CircleDotDashed).text_color(text_color) // More data available
+        };
+        let count_label = if self.canceled && !self.done {
+            format!(
+                "{} / {} ({})",
+                self.items_count,
+                self.total_count,
+                i18n_kv_table(cx, "load_canceled")
+            )
+        } else {
+            format!("{} / {}", self.items_count, self.total_count)
         };
 
         v_flex()
@@ -481,12 +638,25 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
                                     .icon(CustomIconName::FilePlusCorner)
                                     .disabled(self.readonly)
                                     .tooltip(if self.readonly {
-                                        i18n_common(cx, "disable_in_readonly")
+                                        self.readonly_reason.clone()
                                     } else {
                                         i18n_kv_table(cx, "add_value_tooltip")
                                     })
                                     .on_click(handle_add_value),
                             )
+                            .when(can_import, |this| {
+                                this.child(
+                                    Button::new("import-values-btn")
+                                        .icon(CustomIconName::Import)
+                                        .disabled(self.readonly)
+                                        .tooltip(if self.readonly {
+                                            self.readonly_reason.clone()
+                                        } else {
+                                            i18n_kv_table(cx, "import_tooltip")
+                                        })
+                                        .on_click(handle_import),
+                                )
+                            })
                             .child(
                                 Input::new(&self.keyword_state)
                                     .w(px(KEYWORD_INPUT_WIDTH))
@@ -495,13 +665,24 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
                             )
                             .flex_1(),
                     )
-                    // Right side: Status icon and count
-                    .child(status_icon.text_color(text_color).mr_2())
-                    .child(
-                        Label::new(format!("{} / {}", self.items_count, self.total_count))
-                            .text_sm()
-                            .text_color(text_color),
-                    ),
+                    // Right side: Cancel button while paginating, status icon and count
+                    .when(self.paginating && !self.done, |this| {
+                        this.child(
+                            Button::new("cancel-load-btn")
+                                .mr_2()
+                                .small()
+                                .ghost()
+                                .icon(CustomIconName::X)
+                                .tooltip(i18n_kv_table(cx, "cancel_load_tooltip"))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.server_state.update(cx, |state, cx| {
+                                        state.cancel_value_load(cx);
+                                    });
+                                })),
+                        )
+                    })
+                    .child(status_icon.mr_2())
+                    .child(Label::new(count_label).text_sm().text_color(text_color)),
             )
             .when(self.edit_row.is_some(), |this| {
                 this.child(
@@ -518,7 +699,10 @@ impl<T: ZedisKvFetcher> Render for ZedisKvTable<T> {
                         .p_2()
                         .flex()
                         .flex_col()
-                        .child(self.render_edit_form(cx))
+                        .child(self.render_edit_form(window, cx))
+                        .capture_key_down(cx.listener(|this, event: &KeyDownEvent, window, cx| {
+                            this.handle_edit_form_key_down(event, window, cx);
+                        }))
                         .on_click(cx.listener(|_this, _, _, cx| {
                             cx.stop_propagation();
                         })),
@@ -13,13 +13,21 @@
 // limitations under the License.
 
 use crate::{
-    components::{FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog},
+    assets::CustomIconName,
+    components::{ConfirmDialog, FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog, open_confirm_dialog},
     helpers::fast_contains_ignore_case,
-    states::{RedisValue, ZedisServerState, i18n_common, i18n_list_editor},
+    states::{RedisValue, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_list_editor},
     views::{KvTableColumn, ZedisKvTable},
 };
-use gpui::{App, Entity, SharedString, Window, div, prelude::*};
-use gpui_component::WindowExt;
+use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    v_flex,
+};
+use rust_i18n::t;
 use std::rc::Rc;
 use tracing::info;
 
@@ -120,6 +128,11 @@ impl ZedisKvFetcher for ZedisListValues {
         self.value.list_value().is_some_and(|v| v.values.len() == v.size)
     }
 
+    /// Checks if the user stopped loading more items.
+    fn is_canceled(&self) -> bool {
+        self.value.is_load_canceled()
+    }
+
     /// Triggers loading more list items from Redis (pagination).
     fn load_more(&self, _window: &mut Window, cx: &mut App) {
         self.server_state.update(cx, |state, cx| {
@@ -175,7 +188,10 @@ impl ZedisKvFetcher for ZedisListValues {
 
         let fields = vec![
             // Position choice: RPUSH (right/end) or LPUSH (left/start)
-            FormField::new(i18n_list_editor(cx, "position")).with_options(vec!["RPUSH".into(), "LPUSH".into()]),
+            FormField::new(i18n_list_editor(cx, "position")).with_options(vec![
+                i18n_list_editor(cx, "position_tail"),
+                i18n_list_editor(cx, "position_head"),
+            ]),
             // Value input field
             FormField::new(i18n_common(cx, "value"))
                 .with_placeholder(i18n_common(cx, "value_placeholder"))
@@ -239,7 +255,7 @@ impl ZedisKvFetcher for ZedisListValues {
 /// Editor view for Redis List data type.
 ///
 /// Provides a table-based interface for viewing and manipulating Redis lists,
-/// supporting operations like LRANGE, LSET, LREM, LPUSH, and RPUSH.
+/// supporting operations like LRANGE, LSET, LREM, LPUSH, RPUSH and LTRIM.
 ///
 /// Features:
 /// - Paginated loading of large lists
@@ -247,9 +263,14 @@ impl ZedisKvFetcher for ZedisListValues {
 /// - In-place value editing
 /// - Add values to either end of the list
 /// - Delete individual items
+/// - Trim the list to an index range
 pub struct ZedisListEditor {
+    /// Reference to server state for list length and trim operations
+    server_state: Entity<ZedisServerState>,
     /// Table component managing the list data display and interactions
     table_state: Entity<ZedisKvTable<ZedisListValues>>,
+    /// Event subscriptions for server state changes
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ZedisListEditor {
@@ -257,18 +278,137 @@ impl ZedisListEditor {
     ///
     /// Initializes a single-column table to display list values.
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
+        // Re-render the length label whenever the list changes
+        subscriptions.push(cx.subscribe(&server_state, |_this, _, event, cx| {
+            if matches!(
+                event,
+                ServerEvent::ValueLoaded(_) | ServerEvent::ValueUpdated(_) | ServerEvent::ValueAdded(_)
+            ) {
+                cx.notify();
+            }
+        }));
         let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisListValues>::new(vec![KvTableColumn::new("Value", None)], server_state, window, cx)
+            ZedisKvTable::<ZedisListValues>::new(
+                vec![KvTableColumn::new("Value", None)],
+                server_state.clone(),
+                window,
+                cx,
+            )
         });
 
         info!("Creating new list editor view");
 
-        Self { table_state }
+        Self {
+            server_state,
+            table_state,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Opens a dialog asking for the LTRIM range, then confirms before trimming.
+    fn handle_trim(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let (Some(start), Some(stop)) = (
+                values.first().and_then(|v| v.trim().parse::<i64>().ok()),
+                values.get(1).and_then(|v| v.trim().parse::<i64>().ok()),
+            ) else {
+                return false;
+            };
+            let Some(key) = server_state.read(cx).key() else {
+                return false;
+            };
+            window.close_dialog(cx);
+
+            // LTRIM is destructive, ask again before sending it
+            let server_state = server_state.clone();
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!(
+                "list_editor.trim_prompt",
+                key = key,
+                start = start,
+                stop = stop,
+                locale = locale
+            )
+            .to_string();
+            let dialog = ConfirmDialog::new(message, move |_window, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.trim_list(start, stop, cx);
+                });
+            });
+            open_confirm_dialog(dialog, window, cx);
+            true
+        });
+
+        let validate = |s: &str| s.is_empty() || s.trim().parse::<i64>().is_ok();
+        let fields = vec![
+            FormField::new(i18n_list_editor(cx, "trim_start"))
+                .with_placeholder(i18n_list_editor(cx, "trim_placeholder"))
+                .with_validate(validate)
+                .with_focus(),
+            FormField::new(i18n_list_editor(cx, "trim_stop"))
+                .with_placeholder(i18n_list_editor(cx, "trim_placeholder"))
+                .with_validate(validate),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_list_editor(cx, "trim_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
     }
 }
 
 impl Render for ZedisListEditor {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        div().size_full().child(self.table_state.clone()).into_any_element()
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let server_state = self.server_state.read(cx);
+        let readonly = server_state.readonly();
+        let readonly_reason = server_state.readonly_reason(cx);
+        let (size, busy) = server_state
+            .value()
+            .map(|value| (value.list_value().map_or(0, |v| v.size), value.is_busy()))
+            .unwrap_or_default();
+        let text_color = cx.theme().muted_foreground;
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .w_full()
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Label::new(format!("{} (LLEN): {size}", i18n_list_editor(cx, "length")))
+                            .text_sm()
+                            .text_color(text_color)
+                            .flex_1(),
+                    )
+                    .child(
+                        Button::new("list-editor-trim-btn")
+                            .small()
+                            .ghost()
+                            .icon(CustomIconName::Scissors)
+                            .label("LTRIM")
+                            .disabled(readonly || busy)
+                            .tooltip(if readonly {
+                                readonly_reason
+                            } else {
+                                i18n_list_editor(cx, "trim_tooltip")
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_trim(window, cx);
+                            })),
+                    ),
+            )
+            .child(div().flex_1().w_full().child(self.table_state.clone()))
+            .into_any_element()
     }
 }
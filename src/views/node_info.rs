@@ -0,0 +1,208 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::assets::CustomIconName;
+use crate::connection::get_connection_manager;
+use crate::error::Error;
+use crate::helpers::get_font_family;
+use crate::states::{ZedisServerState, i18n_status_bar};
+use gpui::{App, ClipboardItem, Entity, SharedString, Subscription, Window, div, prelude::*, px};
+use gpui_component::{
+    IconName, IndexPath, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    notification::Notification,
+    select::{SearchableVec, Select, SelectEvent, SelectState},
+    v_flex,
+};
+
+/// Shows the unparsed `INFO ALL` output of a single node.
+///
+/// Complements the parsed `RedisInfo` for fields it doesn't know about.
+pub struct ZedisNodeInfo {
+    server_id: String,
+    db: usize,
+    node_state: Entity<SelectState<SearchableVec<SharedString>>>,
+    content_state: Entity<InputState>,
+    node: Option<SharedString>,
+    loading: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisNodeInfo {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let state = server_state.read(cx);
+        let server_id = state.server_id().to_string();
+        let db = state.db();
+        let node_state =
+            cx.new(|cx| SelectState::new(SearchableVec::new(Vec::<SharedString>::new()), None, window, cx));
+        let content_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .searchable(true)
+                .soft_wrap(false)
+        });
+        let subscriptions = vec![cx.subscribe_in(
+            &node_state,
+            window,
+            |this, _state, event: &SelectEvent<SearchableVec<SharedString>>, window, cx| {
+                if let SelectEvent::Confirm(Some(node)) = event {
+                    this.load_info(node.clone(), window, cx);
+                }
+            },
+        )];
+        let mut this = Self {
+            server_id,
+            db,
+            node_state,
+            content_state,
+            node: None,
+            loading: false,
+            _subscriptions: subscriptions,
+        };
+        this.load_nodes(window, cx);
+        this
+    }
+    /// List the nodes of the server and show the info of the first one
+    fn load_nodes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.loading = true;
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    let client = get_connection_manager().get_client(&server_id, db).await?;
+                    Ok::<_, Error>(client.node_addrs())
+                })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| {
+                this.loading = false;
+                match result {
+                    Ok(addrs) => {
+                        let nodes: Vec<SharedString> = addrs.into_iter().map(SharedString::from).collect();
+                        let first = nodes.first().cloned();
+                        this.node_state.update(cx, |state, cx| {
+                            state.set_items(SearchableVec::new(nodes), window, cx);
+                            state.set_selected_index(first.as_ref().map(|_| IndexPath::new(0)), window, cx);
+                        });
+                        if let Some(node) = first {
+                            this.load_info(node, window, cx);
+                        }
+                    }
+                    Err(e) => {
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    /// Fetch `INFO ALL` from the node and show it as is
+    fn load_info(&mut self, node: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        let addr = node.to_string();
+        self.node = Some(node);
+        self.loading = true;
+        cx.notify();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    let client = get_connection_manager().get_client(&server_id, db).await?;
+                    client.node_raw_info(&addr).await
+                })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| {
+                this.loading = false;
+                match result {
+                    Ok(info) => {
+                        let info = info.replace("\r\n", "\n");
+                        this.content_state.update(cx, |state, cx| {
+                            state.set_value(info, window, cx);
+                        });
+                    }
+                    Err(e) => {
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for ZedisNodeInfo {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let node = self.node.clone();
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(div().flex_1().child(Select::new(&self.node_state).small()))
+                    .child(
+                        Button::new("zedis-node-info-reload")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::RotateCw)
+                            .tooltip(i18n_status_bar(cx, "node_info_reload"))
+                            .loading(self.loading)
+                            .disabled(self.loading || node.is_none())
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                if let Some(node) = node.clone() {
+                                    this.load_info(node, window, cx);
+                                }
+                            })),
+                    )
+                    .child(
+                        Button::new("zedis-node-info-copy")
+                            .outline()
+                            .small()
+                            .icon(IconName::Copy)
+                            .tooltip(i18n_status_bar(cx, "node_info_copy"))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                let info = this.content_state.read(cx).value();
+                                cx.write_to_clipboard(ClipboardItem::new_string(info.to_string()));
+                                window
+                                    .push_notification(Notification::info(i18n_status_bar(cx, "node_info_copied")), cx);
+                            })),
+                    ),
+            )
+            .child(
+                div().w_full().h(px(480.0)).child(
+                    Input::new(&self.content_state)
+                        .h_full()
+                        .font_family(get_font_family())
+                        .text_xs(),
+                ),
+            )
+    }
+}
+
+/// Opens a dialog showing the raw `INFO ALL` of a node picked from the server's topology.
+pub fn open_node_info_dialog(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut App) {
+    let view = cx.new(|cx| ZedisNodeInfo::new(server_state, window, cx));
+    window.open_dialog(cx, move |dialog, _, cx| {
+        dialog
+            .title(i18n_status_bar(cx, "node_info_title"))
+            .width(px(760.0))
+            .overlay_closable(true)
+            .child(view.clone())
+    });
+}
@@ -13,25 +13,29 @@
 // limitations under the License.
 
 use crate::assets::CustomIconName;
-use crate::db::{ProtoConfig, ProtoManager};
+use crate::components::{ConfirmDialog, open_confirm_dialog};
+use crate::db::{ALL_SERVERS_ID, MatchMode, ProtoConfig, ProtoManager};
 use crate::error::Error;
 use crate::helpers::get_font_family;
-use crate::states::ZedisServerState;
 use crate::states::i18n_proto_editor;
+use crate::states::{ZedisGlobalStore, ZedisServerState};
 use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
 use gpui_component::button::{Button, ButtonVariants};
 use gpui_component::highlighter::Language;
 use gpui_component::label::Label;
 use gpui_component::radio::RadioGroup;
 use gpui_component::table::{Column, Table, TableDelegate, TableState};
-use gpui_component::{IconName, h_flex};
+use gpui_component::{ActiveTheme, IconName, h_flex};
 use gpui_component::{
-    IndexPath, WindowExt,
+    IndexPath,
     alert::Alert,
+    checkbox::Checkbox,
     form::{field, v_form},
     input::{Input, InputEvent, InputState},
-    select::{Select, SelectEvent, SelectItem, SelectState},
+    select::{SearchableVec, Select, SelectEvent, SelectItem, SelectState},
+    tag::Tag,
     text::TextView,
+    tooltip::Tooltip,
     v_flex,
 };
 use rust_i18n::t;
@@ -67,27 +71,34 @@ struct ProtoTableDelegate {
     data: Arc<Vec<(String, ProtoConfig)>>,
     columns: Vec<Column>,
     servers: Vec<KeyValueOption>,
+    current_server_id: String,
     on_edit: OnProtoAction,
+    on_duplicate: OnProtoAction,
     on_delete: OnProtoAction,
 }
 
 impl ProtoTableDelegate {
-    fn new<F1, F2>(
+    fn new<F1, F2, F3>(
         data: Arc<Vec<(String, ProtoConfig)>>,
         servers: Vec<KeyValueOption>,
+        current_server_id: String,
         columns: Vec<Column>,
         on_edit: F1,
-        on_delete: F2,
+        on_duplicate: F2,
+        on_delete: F3,
     ) -> Self
     where
         F1: Fn(usize, &mut Window, &mut Context<TableState<ProtoTableDelegate>>) + Send + Sync + 'static,
         F2: Fn(usize, &mut Window, &mut Context<TableState<ProtoTableDelegate>>) + Send + Sync + 'static,
+        F3: Fn(usize, &mut Window, &mut Context<TableState<ProtoTableDelegate>>) + Send + Sync + 'static,
     {
         Self {
             data,
             columns,
             servers,
+            current_server_id,
             on_edit: Arc::new(on_edit),
+            on_duplicate: Arc::new(on_duplicate),
             on_delete: Arc::new(on_delete),
         }
     }
@@ -116,6 +127,7 @@ impl TableDelegate for ProtoTableDelegate {
         let proto = self.data.get(row_ix);
         if col_ix == self.columns_count(cx) - 1 {
             let on_edit = self.on_edit.clone();
+            let on_duplicate = self.on_duplicate.clone();
             let on_delete = self.on_delete.clone();
             return div().size_full().flex().items_center().child(
                 h_flex()
@@ -128,6 +140,15 @@ impl TableDelegate for ProtoTableDelegate {
                                 (on_edit)(row_ix, window, cx);
                             })),
                     )
+                    .child(
+                        Button::new("duplicate-proto-btn")
+                            .icon(IconName::Copy)
+                            .ghost()
+                            .tooltip(i18n_proto_editor(cx, "duplicate_tooltip"))
+                            .on_click(cx.listener(move |_this, _, window, cx| {
+                                (on_duplicate)(row_ix, window, cx);
+                            })),
+                    )
                     .child(
                         Button::new("delete-proto-btn")
                             .icon(CustomIconName::X)
@@ -139,18 +160,44 @@ impl TableDelegate for ProtoTableDelegate {
             );
         }
 
-        let text = if let Some((_, proto)) = proto {
+        let text = if let Some((id, proto)) = proto {
             match col_ix {
                 0 => {
                     // Convert server_id to server_name
-                    self.servers
+                    let server_name = self
+                        .servers
                         .iter()
                         .find(|s| s.value.as_ref() == proto.server_id)
                         .map(|s| s.key.to_string())
-                        .unwrap_or_else(|| proto.server_id.clone())
+                        .unwrap_or_else(|| proto.server_id.clone());
+                    let applies_here = proto.applies_to(&self.current_server_id);
+                    return h_flex()
+                        .size_full()
+                        .items_center()
+                        .gap_2()
+                        .child(Label::new(server_name))
+                        .when(applies_here, |this| {
+                            this.child(Tag::success().child(i18n_proto_editor(cx, "applies_here")))
+                        });
                 }
                 1 => proto.name.clone(),
-                2 => proto.match_pattern.clone(),
+                2 => {
+                    // Flag regex patterns that don't compile, they never match a key
+                    let pattern_error = ProtoManager::pattern_error(id);
+                    return h_flex()
+                        .size_full()
+                        .items_center()
+                        .gap_2()
+                        .child(Label::new(proto.match_pattern.clone()))
+                        .when_some(pattern_error, |this, pattern_error| {
+                            this.child(
+                                div()
+                                    .id(("invalid-pattern", row_ix))
+                                    .child(Tag::danger().child(i18n_proto_editor(cx, "invalid_pattern")))
+                                    .tooltip(move |window, cx| Tooltip::new(pattern_error.clone()).build(window, cx)),
+                            )
+                        });
+                }
                 3 => format!("{:?}", proto.mode),
                 4 => proto.target_message.clone().unwrap_or_default(),
                 _ => String::new(),
@@ -174,12 +221,17 @@ pub struct ZedisProtoEditor {
     match_pattern_state: Entity<InputState>,
     match_mode_select_state: Entity<usize>,
     content_state: Entity<InputState>,
-    target_message_state: Entity<InputState>,
+    target_message_select_state: Entity<SelectState<SearchableVec<SharedString>>>,
+    test_key_state: Entity<InputState>,
     field_errors: Entity<HashMap<String, SharedString>>,
 
     protos: Arc<Vec<(String, ProtoConfig)>>,
     servers: Vec<KeyValueOption>,
     server_id: SharedString,
+    current_server_id: SharedString,
+    only_current_server: bool,
+    table_protos: Arc<Vec<(String, ProtoConfig)>>,
+    target_message: SharedString,
     edit_proto_id: Option<String>,
     view_mode: ViewMode,
     table_state: Entity<TableState<ProtoTableDelegate>>,
@@ -191,10 +243,12 @@ impl ZedisProtoEditor {
     fn create_table_state(
         protos: Arc<Vec<(String, ProtoConfig)>>,
         servers: Vec<KeyValueOption>,
+        current_server_id: String,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Entity<TableState<ProtoTableDelegate>> {
         let view_update_entity = cx.entity();
+        let view_duplicate_entity = cx.entity();
         let view_delete_entity = cx.entity();
 
         let on_edit = move |row_ix: usize, window: &mut Window, cx: &mut Context<TableState<ProtoTableDelegate>>| {
@@ -203,6 +257,13 @@ impl ZedisProtoEditor {
             });
         };
 
+        let on_duplicate =
+            move |row_ix: usize, window: &mut Window, cx: &mut Context<TableState<ProtoTableDelegate>>| {
+                view_duplicate_entity.update(cx, |this, cx| {
+                    this.handle_duplicate(row_ix, window, cx);
+                });
+            };
+
         let on_delete = move |row_ix: usize, window: &mut Window, cx: &mut Context<TableState<ProtoTableDelegate>>| {
             view_delete_entity.update(cx, |this, cx| {
                 this.handle_delete(row_ix, window, cx);
@@ -217,7 +278,15 @@ impl ZedisProtoEditor {
             Column::new("actions", i18n_proto_editor(cx, "actions")).width(px(150.)),
         ];
 
-        let delegate = ProtoTableDelegate::new(protos, servers, columns, on_edit, on_delete);
+        let delegate = ProtoTableDelegate::new(
+            protos,
+            servers,
+            current_server_id,
+            columns,
+            on_edit,
+            on_duplicate,
+            on_delete,
+        );
         cx.new(|cx| TableState::new(delegate, window, cx))
     }
 
@@ -225,13 +294,20 @@ impl ZedisProtoEditor {
         let server_id = server_state.read(cx).server_id().to_string();
         let protos = ProtoManager::list_protos_with_id();
         let mut subscriptions = Vec::new();
-        let servers = server_state
-            .read(cx)
-            .servers()
-            .unwrap_or_default()
-            .iter()
-            .map(|server| KeyValueOption::new(server.name.clone().into(), server.id.clone().into()))
-            .collect::<Vec<_>>();
+        // The wildcard option lets one proto config apply to every server
+        let servers = std::iter::once(KeyValueOption::new(
+            i18n_proto_editor(cx, "all_servers"),
+            ALL_SERVERS_ID.into(),
+        ))
+        .chain(
+            server_state
+                .read(cx)
+                .servers()
+                .unwrap_or_default()
+                .iter()
+                .map(|server| KeyValueOption::new(server.name.clone().into(), server.id.clone().into())),
+        )
+        .collect::<Vec<_>>();
         let name_state = cx.new(|cx| InputState::new(window, cx).clean_on_escape());
         let match_pattern_state = cx.new(|cx| InputState::new(window, cx).clean_on_escape());
         let content_state = cx.new(|cx| {
@@ -242,7 +318,13 @@ impl ZedisProtoEditor {
                 .searchable(true)
                 .soft_wrap(true)
         });
-        let target_message_state = cx.new(|cx| InputState::new(window, cx));
+        let target_message_select_state =
+            cx.new(|cx| SelectState::new(SearchableVec::new(Vec::<SharedString>::new()), None, window, cx));
+        let test_key_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_proto_editor(cx, "test_key_placeholder"))
+                .clean_on_escape()
+        });
         let match_mode_select_state = cx.new(|_cx| 0_usize);
         let found = servers
             .iter()
@@ -264,6 +346,30 @@ impl ZedisProtoEditor {
                 }
             }
         }));
+        subscriptions.push(cx.subscribe(
+            &target_message_select_state,
+            |this, _state, event: &SelectEvent<SearchableVec<SharedString>>, _cx| {
+                if let SelectEvent::Confirm(value) = event {
+                    this.target_message = value.clone().unwrap_or_default();
+                }
+            },
+        ));
+        // List the messages of the proto content once the user leaves the editor
+        subscriptions.push(
+            cx.subscribe_in(&content_state, window, |view, _state, event, window, cx| {
+                if let InputEvent::Blur = event {
+                    view.refresh_message_names(window, cx);
+                }
+            }),
+        );
+        // Re-render the match test result as the sample key or pattern changes
+        for item in [test_key_state.clone(), match_pattern_state.clone()] {
+            subscriptions.push(cx.subscribe(&item, |_view, _state, event, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }));
+        }
         for item in [name_state.clone(), match_pattern_state.clone()] {
             subscriptions.push(
                 cx.subscribe_in(&item.clone(), window, move |view, _state, event, _window, cx| {
@@ -280,25 +386,45 @@ impl ZedisProtoEditor {
         }
 
         let protos = Arc::new(protos);
-        let table_state = Self::create_table_state(protos.clone(), servers_for_delegate.clone(), window, cx);
-
-        Self {
+        let table_state = Self::create_table_state(
+            protos.clone(),
+            servers_for_delegate.clone(),
+            server_id.clone(),
+            window,
+            cx,
+        );
+
+        let proto_to_edit = server_state.update(cx, |state, _cx| state.take_proto_to_edit());
+        let mut this = Self {
             server_select_state,
             name_state,
             match_pattern_state,
             match_mode_select_state,
             content_state,
-            target_message_state,
+            target_message_select_state,
+            test_key_state,
             view_mode: ViewMode::Table,
             table_state,
             protos,
             servers: servers_for_delegate,
+            current_server_id: server_id.clone().into(),
             server_id: server_id.into(),
+            only_current_server: false,
+            table_protos: protos.clone(),
+            target_message: SharedString::default(),
             needs_table_recreate: None,
             edit_proto_id: None,
             field_errors,
             _subscriptions: subscriptions,
+        };
+        // Opened from the value editor to edit the config a value was decoded with
+        if let Some(id) = proto_to_edit
+            && let Ok(proto) = ProtoManager::get_proto(&id)
+        {
+            this.edit_proto_id = Some(id.to_string());
+            this.fill_form(proto, window, cx);
         }
+        this
     }
 
     fn handle_save(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
@@ -307,7 +433,7 @@ impl ZedisProtoEditor {
         let match_pattern = self.match_pattern_state.read(cx).value();
         let match_mode = self.match_mode_select_state.read(cx).to_owned();
         let content = self.content_state.read(cx).value();
-        let target_message = self.target_message_state.read(cx).value();
+        let target_message = self.target_message.clone();
         let field_errors = self.field_errors.clone();
         field_errors.update(cx, |state, _cx| {
             state.clear();
@@ -350,6 +476,13 @@ impl ZedisProtoEditor {
         cx.spawn(async move |handle, cx| {
             let result: Result<(String, ProtoConfig), Error> = cx
                 .background_spawn(async move {
+                    if !target_message.is_empty() {
+                        ProtoManager::validate_target_message(
+                            &config.name,
+                            config.content.as_deref().unwrap_or_default(),
+                            &target_message,
+                        )?;
+                    }
                     ProtoManager::upsert_proto(&id, config.clone())?;
                     Ok((id.to_string(), config))
                 })
@@ -376,6 +509,12 @@ impl ZedisProtoEditor {
                 }
                 Err(e) => {
                     error!(error = %e, "add proto fail",);
+                    let _ = handle.update(cx, |this, cx| {
+                        this.field_errors.update(cx, |state, _cx| {
+                            state.insert(this.content_state.entity_id().to_string(), e.to_string().into());
+                        });
+                        cx.notify();
+                    });
                 }
             }
         })
@@ -392,21 +531,41 @@ impl ZedisProtoEditor {
         self.match_mode_select_state.update(cx, |state, _cx| {
             *state = 0;
         });
-        self.target_message_state.update(cx, |state, cx| {
-            state.set_value(String::new(), window, cx);
+        self.target_message = SharedString::default();
+        self.target_message_select_state.update(cx, |state, cx| {
+            state.set_items(SearchableVec::new(Vec::<SharedString>::new()), window, cx);
         });
         self.content_state.update(cx, |state, cx| {
             state.set_value(String::new(), window, cx);
         });
+        self.test_key_state.update(cx, |state, cx| {
+            state.set_value(String::new(), window, cx);
+        });
     }
     fn handle_update(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
-        let Some((id, _)) = self.protos.get(row_ix) else {
+        let Some((id, _)) = self.table_protos.get(row_ix) else {
             return;
         };
         let Ok(proto) = ProtoManager::get_proto(id) else {
             return;
         };
         self.edit_proto_id = Some(id.clone());
+        self.fill_form(proto, window, cx);
+    }
+    /// Opens the edit form pre-filled with a copy of the proto config,
+    /// saving it creates a new entry with a fresh id.
+    fn handle_duplicate(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((id, _)) = self.table_protos.get(row_ix) else {
+            return;
+        };
+        let Ok(mut proto) = ProtoManager::get_proto(id) else {
+            return;
+        };
+        proto.name = format!("{} copy", proto.name);
+        self.edit_proto_id = None;
+        self.fill_form(proto, window, cx);
+    }
+    fn fill_form(&mut self, proto: ProtoConfig, window: &mut Window, cx: &mut Context<Self>) {
         let selected_index = self
             .servers
             .iter()
@@ -426,66 +585,133 @@ impl ZedisProtoEditor {
             *state = proto.mode.clone().into();
         });
 
-        self.target_message_state.update(cx, |state, cx| {
-            state.set_value(proto.target_message.clone().unwrap_or_default(), window, cx);
-        });
+        self.target_message = proto.target_message.clone().unwrap_or_default().into();
 
         self.content_state.update(cx, |state, cx| {
             state.set_value(proto.content.clone().unwrap_or_default(), window, cx);
         });
+        self.test_key_state.update(cx, |state, cx| {
+            state.set_value(String::new(), window, cx);
+        });
         self.view_mode = ViewMode::Edit;
+        self.refresh_message_names(window, cx);
+    }
+    /// Compiles the proto content in the background and offers its messages
+    /// as target message options, compile errors are shown as field errors.
+    fn refresh_message_names(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_state.read(cx).value().to_string();
+        let content = self.content_state.read(cx).value().to_string();
+        let content_id = self.content_state.entity_id().to_string();
+        if content.trim().is_empty() {
+            return;
+        }
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move { ProtoManager::list_message_names(&name, &content) })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok(names) => {
+                        this.field_errors.update(cx, |state, _cx| {
+                            state.remove(&content_id);
+                        });
+                        let names: Vec<SharedString> = names.into_iter().map(SharedString::from).collect();
+                        // Keep the current choice if it still exists, otherwise pick the first message
+                        if !names.contains(&this.target_message) {
+                            this.target_message = names.first().cloned().unwrap_or_default();
+                        }
+                        let target_message = this.target_message.clone();
+                        this.target_message_select_state.update(cx, |state, cx| {
+                            state.set_items(SearchableVec::new(names), window, cx);
+                            state.set_selected_value(&target_message, window, cx);
+                        });
+                    }
+                    Err(e) => {
+                        this.field_errors.update(cx, |state, _cx| {
+                            state.insert(content_id, e.to_string().into());
+                        });
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
     }
     fn handle_delete(&mut self, row_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
-        let Some((id, proto)) = self.protos.get(row_ix) else {
+        let Some((id, proto)) = self.table_protos.get(row_ix) else {
             return;
         };
         let name = proto.name.clone();
 
         let id = id.to_string();
         let view_handle = cx.entity();
-        window.open_dialog(cx, move |dialog, _, _cx| {
+        let text = t!("remove_proto_prompt", name = name).to_string();
+        let dialog = ConfirmDialog::new(text, move |_window, cx| {
             let id = id.clone();
             let view_handle = view_handle.clone();
-            let text = t!("remove_proto_prompt", name = name).to_string();
-            dialog.confirm().child(text).on_ok(move |_, _window, cx| {
-                let id = id.clone();
-                let view_handle = view_handle.clone();
-                cx.spawn(async move |cx| {
-                    let result: Result<String, Error> = cx
-                        .background_spawn({
-                            let id = id.clone();
-                            async move {
-                                ProtoManager::delete_proto(&id)?;
-                                Ok(id)
-                            }
-                        })
-                        .await;
-                    match result {
-                        Ok(deleted_id) => {
-                            let _ = view_handle.update(cx, |this, cx| {
-                                // Remove deleted proto from the list
-                                let new_protos: Vec<_> = this
-                                    .protos
-                                    .iter()
-                                    .filter(|(id, _)| id != &deleted_id)
-                                    .cloned()
-                                    .collect();
-                                this.protos = Arc::new(new_protos);
-
-                                // Mark for recreation of table on next render
-                                this.needs_table_recreate = Some(true);
-                                cx.notify();
-                            });
-                        }
-                        Err(e) => {
-                            error!(error = %e, "delete proto fail",);
+            cx.spawn(async move |cx| {
+                let result: Result<String, Error> = cx
+                    .background_spawn({
+                        let id = id.clone();
+                        async move {
+                            ProtoManager::delete_proto(&id)?;
+                            Ok(id)
                         }
+                    })
+                    .await;
+                match result {
+                    Ok(deleted_id) => {
+                        let _ = view_handle.update(cx, |this, cx| {
+                            // Remove deleted proto from the list
+                            let new_protos: Vec<_> = this
+                                .protos
+                                .iter()
+                                .filter(|(id, _)| id != &deleted_id)
+                                .cloned()
+                                .collect();
+                            this.protos = Arc::new(new_protos);
+
+                            // Mark for recreation of table on next render
+                            this.needs_table_recreate = Some(true);
+                            cx.notify();
+                        });
                     }
-                })
-                .detach();
-                true
+                    Err(e) => {
+                        error!(error = %e, "delete proto fail",);
+                    }
+                }
             })
+            .detach();
         });
+        open_confirm_dialog(dialog, window, cx);
+    }
+    /// Renders whether the sample key matches the pattern under the selected mode.
+    fn render_test_result(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let test_key = self.test_key_state.read(cx).value();
+        let match_pattern = self.match_pattern_state.read(cx).value();
+        let mode = MatchMode::from(*self.match_mode_select_state.read(cx));
+        let (text, color) = if test_key.is_empty() {
+            (SharedString::default(), cx.theme().muted_foreground)
+        } else {
+            match mode.is_match(&match_pattern, &test_key) {
+                Ok(true) => (i18n_proto_editor(cx, "test_key_matched"), cx.theme().green),
+                Ok(false) => (
+                    i18n_proto_editor(cx, "test_key_not_matched"),
+                    cx.theme().muted_foreground,
+                ),
+                Err(e) => (
+                    t!(
+                        "proto_editor.test_key_invalid_regex",
+                        error = e.to_string(),
+                        locale = cx.global::<ZedisGlobalStore>().read(cx).locale()
+                    )
+                    .to_string()
+                    .into(),
+                    cx.theme().red,
+                ),
+            }
+        };
+        Label::new(text).text_sm().text_color(color)
     }
     fn render_edit_form(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let match_mode_select_state_clone = self.match_mode_select_state.clone();
@@ -530,10 +756,19 @@ impl ZedisProtoEditor {
                         ),
                     )
                     .child(
-                        field()
-                            .label(i18n_proto_editor(cx, "target_message"))
-                            .child(Input::new(&self.target_message_state))
-                            .col_span(2),
+                        field().label(i18n_proto_editor(cx, "target_message")).child(
+                            Select::new(&self.target_message_select_state)
+                                .placeholder(i18n_proto_editor(cx, "target_message_placeholder")),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_proto_editor(cx, "test_key")).child(
+                            v_flex()
+                                .w_full()
+                                .gap_1()
+                                .child(Input::new(&self.test_key_state))
+                                .child(self.render_test_result(cx)),
+                        ),
                     ),
             )
             .child(
@@ -596,9 +831,29 @@ impl ZedisProtoEditor {
                     ),
             )
     }
+    /// Protos shown in the table, optionally limited to the ones applying to the current server.
+    fn visible_protos(&self) -> Arc<Vec<(String, ProtoConfig)>> {
+        if !self.only_current_server {
+            return self.protos.clone();
+        }
+        Arc::new(
+            self.protos
+                .iter()
+                .filter(|(_, proto)| proto.applies_to(&self.current_server_id))
+                .cloned()
+                .collect(),
+        )
+    }
     fn render_table_view(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         if let Some(true) = self.needs_table_recreate.take() {
-            self.table_state = Self::create_table_state(self.protos.clone(), self.servers.clone(), window, cx);
+            self.table_protos = self.visible_protos();
+            self.table_state = Self::create_table_state(
+                self.table_protos.clone(),
+                self.servers.clone(),
+                self.current_server_id.to_string(),
+                window,
+                cx,
+            );
         }
         v_flex()
             .size_full()
@@ -608,7 +863,17 @@ impl ZedisProtoEditor {
                 h_flex()
                     .w_full()
                     .justify_between()
-                    .child(Label::new(i18n_proto_editor(cx, "title")).text_xl()),
+                    .child(Label::new(i18n_proto_editor(cx, "title")).text_xl())
+                    .child(
+                        Checkbox::new("proto-editor-only-current-server")
+                            .label(i18n_proto_editor(cx, "only_current_server"))
+                            .checked(self.only_current_server)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.only_current_server = *checked;
+                                this.needs_table_recreate = Some(true);
+                                cx.notify();
+                            })),
+                    ),
             )
             .child(
                 div().flex_1().w_full().child(
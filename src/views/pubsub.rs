@@ -0,0 +1,378 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::assets::CustomIconName;
+use crate::connection::get_connection_manager;
+use crate::error::Error;
+use crate::helpers::get_font_family;
+use crate::states::{ZedisGlobalStore, ZedisServerState, i18n_status_bar};
+use chrono::Local;
+use futures::StreamExt;
+use gpui::{App, Entity, SharedString, Subscription, Task, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    label::Label,
+    notification::Notification,
+    table::{Column, Table, TableDelegate, TableState},
+    v_flex,
+};
+use rust_i18n::t;
+use std::collections::VecDeque;
+
+/// Messages kept in the list, the oldest are dropped beyond it
+const MAX_PUBSUB_MESSAGES: usize = 1_000;
+
+struct PubSubMessage {
+    time: String,
+    channel: String,
+    /// The pattern that matched, only set for PSUBSCRIBE
+    pattern: Option<String>,
+    payload: String,
+}
+
+/// Updates sent from the subscription task to the view
+enum PubSubUpdate {
+    Subscribed { node: String, cluster: bool },
+    Message(PubSubMessage),
+    Failed(String),
+}
+
+struct PubSubTableDelegate {
+    /// Newest first
+    messages: VecDeque<PubSubMessage>,
+    columns: Vec<Column>,
+}
+
+impl TableDelegate for PubSubTableDelegate {
+    fn columns_count(&self, _: &App) -> usize {
+        self.columns.len()
+    }
+
+    fn rows_count(&self, _: &App) -> usize {
+        self.messages.len()
+    }
+
+    fn column(&self, index: usize, _: &App) -> &Column {
+        &self.columns[index]
+    }
+
+    fn render_td(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        let text = if let Some(message) = self.messages.get(row_ix) {
+            match col_ix {
+                0 => message.time.clone(),
+                1 => message.channel.clone(),
+                2 => message.pattern.clone().unwrap_or_default(),
+                3 => message.payload.clone(),
+                _ => String::new(),
+            }
+        } else {
+            String::new()
+        };
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .when(col_ix == 3, |this| this.font_family(get_font_family()))
+            .child(Label::new(text))
+    }
+}
+
+/// Subscribes to channels or patterns on a dedicated connection and lists the messages live.
+pub struct ZedisPubSub {
+    server_id: String,
+    channels_state: Entity<InputState>,
+    table_state: Entity<TableState<PubSubTableDelegate>>,
+    /// PSUBSCRIBE the input instead of SUBSCRIBE
+    pattern: bool,
+    /// `host:port` of the node subscribed on, set once subscribed
+    node: Option<String>,
+    cluster: bool,
+    /// Pub/sub connections aren't tunneled, subscribing is disabled over SSH
+    ssh_tunnel: bool,
+    /// Messages received since subscribing, including the dropped ones
+    received: usize,
+    /// The connection and the forwarding of its messages, dropping them unsubscribes
+    subscription: Option<(Task<()>, Task<()>)>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisPubSub {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let state = server_state.read(cx);
+        let server_id = state.server_id().to_string();
+        let ssh_tunnel = state.server(&server_id).is_some_and(|server| server.is_ssh_tunnel());
+        let channels_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_status_bar(cx, "pubsub_channels_placeholder"))
+        });
+        let subscriptions = vec![
+            cx.subscribe_in(&channels_state, window, |view, _state, event, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    view.subscribe(window, cx);
+                }
+            }),
+        ];
+        let columns = vec![
+            Column::new("time", i18n_status_bar(cx, "pubsub_time")).width(px(110.)),
+            Column::new("channel", i18n_status_bar(cx, "pubsub_channel")).width(px(180.)),
+            Column::new("pattern", i18n_status_bar(cx, "pubsub_pattern")).width(px(120.)),
+            Column::new("payload", i18n_status_bar(cx, "pubsub_payload")).width(px(480.)),
+        ];
+        let delegate = PubSubTableDelegate {
+            messages: VecDeque::new(),
+            columns,
+        };
+        let table_state = cx.new(|cx| TableState::new(delegate, window, cx));
+        Self {
+            server_id,
+            channels_state,
+            table_state,
+            pattern: false,
+            node: None,
+            cluster: false,
+            ssh_tunnel,
+            received: 0,
+            subscription: None,
+            _subscriptions: subscriptions,
+        }
+    }
+    /// SUBSCRIBE (or PSUBSCRIBE) the whitespace separated channels of the input
+    fn subscribe(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.ssh_tunnel {
+            window.push_notification(Notification::warning(i18n_status_bar(cx, "pubsub_ssh_unavailable")), cx);
+            return;
+        }
+        let channels: Vec<String> = self
+            .channels_state
+            .read(cx)
+            .value()
+            .split_whitespace()
+            .map(|channel| channel.to_string())
+            .collect();
+        if channels.is_empty() {
+            window.push_notification(
+                Notification::warning(i18n_status_bar(cx, "pubsub_channels_required")),
+                cx,
+            );
+            return;
+        }
+        self.unsubscribe(cx);
+        let server_id = self.server_id.clone();
+        let pattern = self.pattern;
+        let (tx, rx) = smol::channel::unbounded::<PubSubUpdate>();
+        let connection_task = cx.background_spawn(async move {
+            let result = async {
+                let mut connection = get_connection_manager().get_pubsub_connection(&server_id).await?;
+                if pattern {
+                    connection.pubsub.psubscribe(&channels).await?;
+                } else {
+                    connection.pubsub.subscribe(&channels).await?;
+                }
+                let _ = tx
+                    .send(PubSubUpdate::Subscribed {
+                        node: connection.node,
+                        cluster: connection.cluster,
+                    })
+                    .await;
+                let mut messages = connection.pubsub.into_on_message();
+                while let Some(msg) = messages.next().await {
+                    let message = PubSubMessage {
+                        time: Local::now().format("%H:%M:%S%.3f").to_string(),
+                        channel: msg.get_channel_name().to_string(),
+                        pattern: msg.get_pattern::<Option<String>>().ok().flatten(),
+                        payload: String::from_utf8_lossy(msg.get_payload_bytes()).to_string(),
+                    };
+                    // The view is gone
+                    if tx.send(PubSubUpdate::Message(message)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok::<(), Error>(())
+            }
+            .await;
+            if let Err(e) = result {
+                let _ = tx.send(PubSubUpdate::Failed(e.to_string())).await;
+            }
+        });
+        let forward_task = cx.spawn_in(window, async move |handle, cx| {
+            while let Ok(update) = rx.recv().await {
+                // Apply the messages that piled up together, one render per batch
+                let mut updates = vec![update];
+                while let Ok(update) = rx.try_recv() {
+                    updates.push(update);
+                }
+                let result = handle.update_in(cx, |this, window, cx| {
+                    for update in updates {
+                        this.apply_update(update, window, cx);
+                    }
+                    cx.notify();
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        });
+        self.subscription = Some((connection_task, forward_task));
+        cx.notify();
+    }
+    fn apply_update(&mut self, update: PubSubUpdate, window: &mut Window, cx: &mut Context<Self>) {
+        match update {
+            PubSubUpdate::Subscribed { node, cluster } => {
+                self.node = Some(node);
+                self.cluster = cluster;
+            }
+            PubSubUpdate::Message(message) => {
+                self.received += 1;
+                self.table_state.update(cx, |state, cx| {
+                    let messages = &mut state.delegate_mut().messages;
+                    messages.push_front(message);
+                    messages.truncate(MAX_PUBSUB_MESSAGES);
+                    cx.notify();
+                });
+            }
+            PubSubUpdate::Failed(message) => {
+                self.subscription = None;
+                self.node = None;
+                window.push_notification(Notification::error(message), cx);
+            }
+        }
+    }
+    /// Drop the pub/sub connection, which ends every subscription of it
+    fn unsubscribe(&mut self, cx: &mut Context<Self>) {
+        self.subscription = None;
+        self.node = None;
+        self.cluster = false;
+        cx.notify();
+    }
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.received = 0;
+        self.table_state.update(cx, |state, cx| {
+            state.delegate_mut().messages.clear();
+            cx.notify();
+        });
+        cx.notify();
+    }
+}
+
+impl Render for ZedisPubSub {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let subscribed = self.subscription.is_some();
+        let status: SharedString = match &self.node {
+            Some(node) => t!(
+                "status_bar.pubsub_status",
+                node = node,
+                received = self.received,
+                max = MAX_PUBSUB_MESSAGES,
+                locale = locale
+            )
+            .into(),
+            None if subscribed => i18n_status_bar(cx, "pubsub_connecting"),
+            None => i18n_status_bar(cx, "pubsub_idle"),
+        };
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(div().flex_1().child(Input::new(&self.channels_state).small()))
+                    .child(
+                        Checkbox::new("zedis-pubsub-pattern")
+                            .label(i18n_status_bar(cx, "pubsub_pattern_check_label"))
+                            .checked(self.pattern)
+                            .disabled(subscribed)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.pattern = *checked;
+                                cx.notify();
+                            })),
+                    )
+                    .child(if subscribed {
+                        Button::new("zedis-pubsub-unsubscribe")
+                            .outline()
+                            .small()
+                            .label(i18n_status_bar(cx, "pubsub_unsubscribe"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.unsubscribe(cx);
+                            }))
+                    } else {
+                        Button::new("zedis-pubsub-subscribe")
+                            .primary()
+                            .small()
+                            .label(i18n_status_bar(cx, "pubsub_subscribe"))
+                            .disabled(self.ssh_tunnel)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.subscribe(window, cx);
+                            }))
+                    })
+                    .child(
+                        Button::new("zedis-pubsub-clear")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::Eraser)
+                            .tooltip(i18n_status_bar(cx, "pubsub_clear"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.clear(cx);
+                            })),
+                    ),
+            )
+            .child(Label::new(status).text_sm().text_color(cx.theme().muted_foreground))
+            .when(self.ssh_tunnel, |this| {
+                this.child(
+                    Label::new(i18n_status_bar(cx, "pubsub_ssh_unavailable"))
+                        .text_sm()
+                        .text_color(cx.theme().warning),
+                )
+            })
+            .when(self.cluster, |this| {
+                this.child(
+                    Label::new(i18n_status_bar(cx, "pubsub_cluster_note"))
+                        .text_sm()
+                        .text_color(cx.theme().warning),
+                )
+            })
+            .child(
+                div().w_full().h(px(480.0)).child(
+                    Table::new(&self.table_state)
+                        .stripe(true)
+                        .bordered(true)
+                        .scrollbar_visible(true, true),
+                ),
+            )
+    }
+}
+
+/// Opens a dialog subscribing to channels of the server, closing it unsubscribes.
+pub fn open_pubsub_dialog(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut App) {
+    let view = cx.new(|cx| ZedisPubSub::new(server_state, window, cx));
+    window.open_dialog(cx, move |dialog, _, cx| {
+        dialog
+            .title(i18n_status_bar(cx, "pubsub_title"))
+            .width(px(1000.0))
+            .overlay_closable(true)
+            .child(view.clone())
+    });
+}
@@ -13,35 +13,42 @@
 // limitations under the License.
 
 use crate::assets::CustomIconName;
-use crate::components::Card;
-use crate::connection::RedisServer;
-use crate::helpers::{is_windows, validate_common_string, validate_host, validate_long_string};
-use crate::states::{Route, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_servers};
+use crate::components::{Card, ConfirmDialog, open_confirm_dialog, platform_button_order};
+use crate::connection::{RedisServer, SERVER_COLORS, server_color};
+use crate::helpers::{format_timestamp, validate_common_string, validate_host, validate_long_string};
+use crate::states::{Route, ServerGridLayout, ZedisGlobalStore, ZedisServerState, i18n_common, i18n_servers};
 use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*, px};
 use gpui_component::{
-    ActiveTheme, Colorize, Icon, IconName, WindowExt,
+    ActiveTheme, Colorize, Icon, IconName, Sizable, WindowExt,
     alert::Alert,
     button::{Button, ButtonVariants},
     checkbox::Checkbox,
     form::{field, v_form},
+    h_flex,
     input::{Input, InputEvent, InputState, NumberInput, NumberInputEvent, StepAction},
     label::Label,
+    notification::Notification,
     radio::RadioGroup,
     scroll::ScrollableElement,
     tab::{Tab, TabBar},
     text::TextView,
+    tooltip::Tooltip,
+    v_flex,
 };
+use percent_encoding::percent_decode_str;
 use rust_i18n::t;
 use std::collections::HashMap;
 use std::{cell::Cell, rc::Rc};
 use substring::Substring;
 use tracing::info;
 use url::Url;
+use uuid::Uuid;
 
 // Constants for UI layout
 const DEFAULT_REDIS_PORT: u16 = 6379;
 const VIEWPORT_BREAKPOINT_SMALL: f32 = 800.0; // Single column
 const VIEWPORT_BREAKPOINT_MEDIUM: f32 = 1200.0; // Two columns
+const SSH_TAB_INDEX: usize = 2;
 const UPDATED_AT_SUBSTRING_LENGTH: usize = 10; // Length of date string to display
 const THEME_LIGHTEN_AMOUNT_DARK: f32 = 1.0;
 const THEME_DARKEN_AMOUNT_LIGHT: f32 = 0.02;
@@ -53,6 +60,8 @@ struct RedisUrl {
     username: String,
     password: Option<String>,
     tls: bool,
+    /// Db given as the path, e.g. `redis://host:6379/2`
+    db: Option<usize>,
 }
 
 fn parse_url(host: SharedString) -> RedisUrl {
@@ -67,9 +76,12 @@ fn parse_url(host: SharedString) -> RedisUrl {
         RedisUrl {
             host: host.to_string(),
             port,
-            username: u.username().to_string(),
-            password: u.password().map(|p| p.to_string()),
+            username: percent_decode_str(u.username()).decode_utf8_lossy().to_string(),
+            password: u
+                .password()
+                .map(|p| percent_decode_str(p).decode_utf8_lossy().to_string()),
             tls: u.scheme() == "rediss",
+            db: u.path().trim_start_matches('/').parse().ok(),
         }
     } else {
         RedisUrl {
@@ -79,6 +91,61 @@ fn parse_url(host: SharedString) -> RedisUrl {
     }
 }
 
+/// Builds a server that isn't saved from a `redis://` or `rediss://` URL.
+fn transient_server_from_url(url: &str) -> Option<RedisServer> {
+    let url = url.trim();
+    if !url.starts_with("redis://") && !url.starts_with("rediss://") {
+        return None;
+    }
+    let info = parse_url(url.to_string().into());
+    if info.host.is_empty() || info.host == url {
+        return None;
+    }
+    let port = info.port.unwrap_or(DEFAULT_REDIS_PORT);
+    Some(RedisServer {
+        id: Uuid::now_v7().to_string(),
+        name: format!("{}:{port}", info.host),
+        host: info.host,
+        port,
+        username: Some(info.username).filter(|username| !username.is_empty()),
+        password: info.password.filter(|password| !password.is_empty()),
+        tls: Some(info.tls),
+        default_db: info.db.filter(|db| *db > 0),
+        ..Default::default()
+    })
+}
+
+/// Swatches of `SERVER_COLORS`, the first one leaves the server without a color.
+fn render_color_palette(state: &Entity<usize>, cx: &App) -> impl IntoElement {
+    let selected = *state.read(cx);
+    let swatches = std::iter::once(None)
+        .chain(SERVER_COLORS.iter().map(|(_, hue)| Some(server_color(*hue))))
+        .enumerate()
+        .map(|(index, color)| {
+            let state = state.clone();
+            div()
+                .id(("redis-server-color", index))
+                .size_5()
+                .rounded_full()
+                .cursor_pointer()
+                .border_2()
+                .border_color(if selected == index {
+                    cx.theme().foreground
+                } else {
+                    cx.theme().border
+                })
+                .when_some(color, |this, color| this.bg(color))
+                .on_click(move |_, _, cx| {
+                    state.update(cx, |state, cx| {
+                        *state = index;
+                        cx.notify();
+                    });
+                    cx.stop_propagation();
+                })
+        });
+    h_flex().gap_2().children(swatches)
+}
+
 /// Server management view component
 ///
 /// Displays a grid of server cards with:
@@ -96,19 +163,29 @@ pub struct ZedisServers {
     name_state: Entity<InputState>,
     host_state: Entity<InputState>,
     port_state: Entity<InputState>,
+    socket_path_state: Entity<InputState>,
     username_state: Entity<InputState>,
     password_state: Entity<InputState>,
     server_type_state: Entity<usize>,
+    /// 0 for no color, otherwise the index in `SERVER_COLORS` plus one
+    server_color_state: Entity<usize>,
     client_cert_state: Entity<InputState>,
     client_key_state: Entity<InputState>,
     root_cert_state: Entity<InputState>,
     master_name_state: Entity<InputState>,
+    default_db_state: Entity<InputState>,
+    /// Milliseconds overriding the global timeouts, empty keeps them
+    connect_timeout_state: Entity<InputState>,
+    response_timeout_state: Entity<InputState>,
     ssh_addr_state: Entity<InputState>,
     ssh_username_state: Entity<InputState>,
     ssh_password_state: Entity<InputState>,
     ssh_key_state: Entity<InputState>,
+    ssh_key_passphrase_state: Entity<InputState>,
     description_state: Entity<InputState>,
     field_errors: Entity<HashMap<String, SharedString>>,
+    /// Redis URL connected to without saving a server
+    quick_connect_state: Entity<InputState>,
 
     /// Flag indicating if we're adding a new server (vs editing existing)
     server_id: String,
@@ -116,7 +193,15 @@ pub struct ZedisServers {
     server_enable_tls: Rc<Cell<bool>>,
     server_insecure_tls: Rc<Cell<bool>>,
     server_ssh_tunnel: Rc<Cell<bool>>,
+    /// Authenticate the tunnel with the SSH agent, key and password become optional
+    server_ssh_agent: Rc<Cell<bool>>,
+    /// Ask before trusting SSH hosts missing from known_hosts
+    server_ssh_strict_host_key: Rc<Cell<bool>>,
+    /// Connect over a Unix domain socket instead of host and port
+    server_unix_socket: Rc<Cell<bool>>,
     server_readonly: Rc<Cell<bool>>,
+    /// Read from cluster replicas, writes still go to the masters
+    server_prefer_replica: Rc<Cell<bool>>,
 
     _subscriptions: Vec<Subscription>,
 }
@@ -138,6 +223,11 @@ impl ZedisServers {
                 .validate(|s, _cx| validate_host(s))
         });
         let port_state = cx.new(|cx| InputState::new(window, cx).placeholder(i18n_common(cx, "port_placeholder")));
+        let socket_path_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "socket_path_placeholder"))
+                .validate(|s, _cx| validate_long_string(s))
+        });
 
         let username_state = cx.new(|cx| {
             InputState::new(window, cx)
@@ -188,8 +278,15 @@ impl ZedisServers {
                 .auto_grow(cert_min_rows, cert_max_rows)
                 .placeholder(i18n_servers(cx, "ssh_key_placeholder"))
         });
+        let ssh_key_passphrase_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "ssh_key_passphrase_placeholder"))
+                .validate(|s, _cx| validate_common_string(s))
+                .masked(true)
+        });
         let description_state = cx.new(|cx| {
             InputState::new(window, cx)
+                .auto_grow(cert_min_rows, cert_max_rows)
                 .placeholder(i18n_common(cx, "description_placeholder"))
                 .validate(|s, _cx| validate_long_string(s))
         });
@@ -198,11 +295,33 @@ impl ZedisServers {
                 .placeholder(i18n_servers(cx, "master_name_placeholder"))
                 .validate(|s, _cx| validate_common_string(s))
         });
+        let default_db_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "default_db_placeholder"))
+                .validate(|s, _cx| s.chars().all(|c| c.is_ascii_digit()))
+        });
+        let connect_timeout_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "timeout_placeholder"))
+                .validate(|s, _cx| s.chars().all(|c| c.is_ascii_digit()))
+        });
+        let response_timeout_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_servers(cx, "timeout_placeholder"))
+                .validate(|s, _cx| s.chars().all(|c| c.is_ascii_digit()))
+        });
         let server_type_state = cx.new(|_cx| 0_usize);
+        let server_color_state = cx.new(|_cx| 0_usize);
+        let quick_connect_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .clean_on_escape()
+                .placeholder(i18n_servers(cx, "quick_connect_placeholder"))
+        });
 
         let port_state_clone = port_state.clone();
         let username_state_clone = username_state.clone();
         let password_state_clone = password_state.clone();
+        let default_db_state_clone = default_db_state.clone();
         let mut subscriptions = vec![];
         subscriptions.push(
             cx.subscribe_in(&host_state, window, move |view, state, event, window, cx| {
@@ -229,10 +348,22 @@ impl ZedisServers {
                                 state.set_value(password, window, cx);
                             });
                         }
+                        if let Some(db) = info.db {
+                            default_db_state_clone.update(cx, |state, cx| {
+                                state.set_value(db.to_string(), window, cx);
+                            });
+                        }
                     }
                 }
             }),
         );
+        subscriptions.push(
+            cx.subscribe_in(&quick_connect_state, window, |view, _state, event, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    view.handle_quick_connect(window, cx);
+                }
+            }),
+        );
         subscriptions.push(cx.subscribe_in(&port_state, window, |_view, state, event, window, cx| {
             let NumberInputEvent::Step(action) = event;
 
@@ -254,7 +385,13 @@ impl ZedisServers {
         let field_errors = cx.new(|_cx| HashMap::new());
 
         // add validation error subscription
-        for item in [name_state.clone(), host_state.clone()] {
+        for item in [
+            name_state.clone(),
+            host_state.clone(),
+            socket_path_state.clone(),
+            ssh_addr_state.clone(),
+            ssh_key_state.clone(),
+        ] {
             subscriptions.push(
                 cx.subscribe_in(&item.clone(), window, move |view, _state, event, _window, cx| {
                     if let InputEvent::Blur = event {
@@ -276,27 +413,54 @@ impl ZedisServers {
             name_state,
             host_state,
             port_state,
+            socket_path_state,
             username_state,
             password_state,
             server_type_state,
+            server_color_state,
             client_cert_state,
             client_key_state,
             root_cert_state,
             master_name_state,
+            default_db_state,
+            connect_timeout_state,
+            response_timeout_state,
             ssh_addr_state,
             ssh_username_state,
             ssh_password_state,
             ssh_key_state,
+            ssh_key_passphrase_state,
             description_state,
             field_errors,
+            quick_connect_state,
             server_id: String::new(),
             server_enable_tls: Rc::new(Cell::new(false)),
             server_insecure_tls: Rc::new(Cell::new(false)),
             server_ssh_tunnel: Rc::new(Cell::new(false)),
+            server_ssh_agent: Rc::new(Cell::new(false)),
+            server_ssh_strict_host_key: Rc::new(Cell::new(false)),
+            server_unix_socket: Rc::new(Cell::new(false)),
             server_readonly: Rc::new(Cell::new(false)),
+            server_prefer_replica: Rc::new(Cell::new(false)),
             _subscriptions: subscriptions,
         }
     }
+    /// Connect to the URL of the quick connect input without saving a server
+    fn handle_quick_connect(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let url = self.quick_connect_state.read(cx).value();
+        let Some(server) = transient_server_from_url(&url) else {
+            window.push_notification(Notification::error(i18n_servers(cx, "quick_connect_invalid_url")), cx);
+            return;
+        };
+        self.server_state.update(cx, |state, cx| {
+            state.quick_connect(server, cx);
+        });
+        cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
+            store.update(cx, |state, cx| {
+                state.go_to(Route::Editor, cx);
+            });
+        });
+    }
     /// Fill input fields with server data for editing
     ///
     fn fill_inputs(&mut self, window: &mut Window, cx: &mut Context<Self>, server: &RedisServer) {
@@ -321,6 +485,9 @@ impl ZedisServers {
         self.port_state.update(cx, |state, cx| {
             state.set_value(port, window, cx);
         });
+        self.socket_path_state.update(cx, |state, cx| {
+            state.set_value(server.socket_path.clone().unwrap_or_default(), window, cx);
+        });
 
         self.password_state.update(cx, |state, cx| {
             state.set_value(server.password.clone().unwrap_or_default(), window, cx);
@@ -328,6 +495,18 @@ impl ZedisServers {
         self.master_name_state.update(cx, |state, cx| {
             state.set_value(server.master_name.clone().unwrap_or_default(), window, cx);
         });
+        self.default_db_state.update(cx, |state, cx| {
+            let db = server.default_db.map(|db| db.to_string()).unwrap_or_default();
+            state.set_value(db, window, cx);
+        });
+        self.connect_timeout_state.update(cx, |state, cx| {
+            let timeout = server.connect_timeout_ms.map(|ms| ms.to_string()).unwrap_or_default();
+            state.set_value(timeout, window, cx);
+        });
+        self.response_timeout_state.update(cx, |state, cx| {
+            let timeout = server.response_timeout_ms.map(|ms| ms.to_string()).unwrap_or_default();
+            state.set_value(timeout, window, cx);
+        });
         self.description_state.update(cx, |state, cx| {
             state.set_value(server.description.clone().unwrap_or_default(), window, cx);
         });
@@ -352,13 +531,28 @@ impl ZedisServers {
         self.ssh_key_state.update(cx, |state, cx| {
             state.set_value(server.ssh_key.clone().unwrap_or_default(), window, cx);
         });
+        self.ssh_key_passphrase_state.update(cx, |state, cx| {
+            state.set_value(server.ssh_key_passphrase.clone().unwrap_or_default(), window, cx);
+        });
         self.server_enable_tls.set(server.tls.unwrap_or(false));
         self.server_insecure_tls.set(server.insecure.unwrap_or(false));
         self.server_ssh_tunnel.set(server.ssh_tunnel.unwrap_or(false));
+        self.server_ssh_agent.set(server.ssh_agent.unwrap_or(false));
+        self.server_ssh_strict_host_key
+            .set(server.ssh_strict_host_key.unwrap_or(false));
+        self.server_unix_socket.set(server.unix_socket().is_some());
         self.server_readonly.set(server.readonly.unwrap_or(false));
+        self.server_prefer_replica.set(server.prefer_replica());
         self.server_type_state.update(cx, |state, _cx| {
             *state = server.server_type.unwrap_or(0);
         });
+        self.server_color_state.update(cx, |state, _cx| {
+            *state = server
+                .color
+                .as_deref()
+                .and_then(|color| SERVER_COLORS.iter().position(|(name, _)| *name == color))
+                .map_or(0, |index| index + 1);
+        });
     }
 
     /// Show confirmation dialog and remove server from configuration
@@ -375,23 +569,14 @@ impl ZedisServers {
         // let server = server.to_string();
         let locale = cx.global::<ZedisGlobalStore>().read(cx).locale().to_string();
 
-        window.open_dialog(cx, move |dialog, _, cx| {
-            let message = t!("servers.remove_prompt", server = server, locale = locale).to_string();
-            let server_state = server_state.clone();
-            let server_id = server_id.clone();
-
-            dialog
-                .confirm()
-                .title(i18n_servers(cx, "remove_server_title"))
-                .child(message)
-                .on_ok(move |_, window, cx| {
-                    server_state.update(cx, |state, cx| {
-                        state.remove_server(&server_id, cx);
-                    });
-                    window.close_dialog(cx);
-                    true
-                })
-        });
+        let message = t!("servers.remove_prompt", server = server, locale = locale).to_string();
+        let dialog = ConfirmDialog::new(message, move |_window, cx| {
+            server_state.update(cx, |state, cx| {
+                state.remove_server(&server_id, cx);
+            });
+        })
+        .with_title(i18n_servers(cx, "remove_server_title"));
+        open_confirm_dialog(dialog, window, cx);
     }
     /// Open dialog to add new server or update existing server
     ///
@@ -402,9 +587,13 @@ impl ZedisServers {
         let name_state = self.name_state.clone();
         let host_state = self.host_state.clone();
         let port_state = self.port_state.clone();
+        let socket_path_state = self.socket_path_state.clone();
         let username_state = self.username_state.clone();
         let password_state = self.password_state.clone();
         let master_name_state = self.master_name_state.clone();
+        let default_db_state = self.default_db_state.clone();
+        let connect_timeout_state = self.connect_timeout_state.clone();
+        let response_timeout_state = self.response_timeout_state.clone();
         let description_state = self.description_state.clone();
         let client_cert_state = self.client_cert_state.clone();
         let client_key_state = self.client_key_state.clone();
@@ -415,18 +604,27 @@ impl ZedisServers {
         let ssh_username_state = self.ssh_username_state.clone();
         let ssh_password_state = self.ssh_password_state.clone();
         let ssh_key_state = self.ssh_key_state.clone();
+        let ssh_key_passphrase_state = self.ssh_key_passphrase_state.clone();
         // Create shared state for TLS checkbox
         let server_enable_tls = self.server_enable_tls.clone();
         let server_insecure_tls = self.server_insecure_tls.clone();
         let server_ssh_tunnel = self.server_ssh_tunnel.clone();
+        let server_ssh_agent = self.server_ssh_agent.clone();
+        let server_ssh_strict_host_key = self.server_ssh_strict_host_key.clone();
+        let server_unix_socket = self.server_unix_socket.clone();
         let server_state_clone = server_state.clone();
         let server_type_state = self.server_type_state.clone();
+        let server_color_state = self.server_color_state.clone();
         let name_state_clone = name_state.clone();
         let host_state_clone = host_state.clone();
         let port_state_clone = port_state.clone();
+        let socket_path_state_clone = socket_path_state.clone();
         let username_state_clone = username_state.clone();
         let password_state_clone = password_state.clone();
         let master_name_state_clone = master_name_state.clone();
+        let default_db_state_clone = default_db_state.clone();
+        let connect_timeout_state_clone = connect_timeout_state.clone();
+        let response_timeout_state_clone = response_timeout_state.clone();
         let description_state_clone = description_state.clone();
         let client_cert_state_clone = client_cert_state.clone();
         let client_key_state_clone = client_key_state.clone();
@@ -435,15 +633,24 @@ impl ZedisServers {
         let ssh_username_state_clone = ssh_username_state.clone();
         let ssh_password_state_clone = ssh_password_state.clone();
         let ssh_key_state_clone = ssh_key_state.clone();
+        let ssh_key_passphrase_state_clone = ssh_key_passphrase_state.clone();
         let server_id_clone = server_id.clone();
         let server_enable_tls_for_submit = self.server_enable_tls.clone();
         let server_insecure_tls_for_submit = self.server_insecure_tls.clone();
         let server_ssh_tunnel_for_submit = server_ssh_tunnel.clone();
+        let server_ssh_agent_for_submit = server_ssh_agent.clone();
+        let server_ssh_strict_host_key_for_submit = server_ssh_strict_host_key.clone();
+        let server_unix_socket_for_submit = server_unix_socket.clone();
         let server_readonly = self.server_readonly.clone();
         let server_readonly_for_submit = server_readonly.clone();
+        let server_prefer_replica = self.server_prefer_replica.clone();
+        let server_prefer_replica_for_submit = server_prefer_replica.clone();
         let server_type_state_clone = server_type_state.clone();
+        let server_color_state_clone = server_color_state.clone();
         let field_errors = self.field_errors.clone();
         let field_errors_clone = field_errors.clone();
+        let tab_selected_index = cx.new(|_cx| 0_usize);
+        let tab_selected_index_for_submit = tab_selected_index.clone();
         let handle_submit = Rc::new(move |window: &mut Window, cx: &mut App| {
             field_errors.update(cx, |state, _cx| {
                 state.clear();
@@ -461,12 +668,47 @@ impl ZedisServers {
                     state.insert(id, "name is required".into());
                 });
             }
-            if host.is_empty() {
+            // A socket path replaces host and port
+            let unix_socket = server_unix_socket_for_submit.get();
+            let socket_path = socket_path_state_clone.read(cx).value();
+            if unix_socket {
+                if socket_path.is_empty() {
+                    let id = socket_path_state_clone.entity_id().to_string();
+                    field_errors.update(cx, |state, _cx| {
+                        state.insert(id, "socket path is required".into());
+                    });
+                }
+            } else if host.is_empty() {
                 let id = host_state_clone.entity_id().to_string();
                 field_errors.update(cx, |state, _cx| {
                     state.insert(id, "host is required".into());
                 });
             }
+            // The tunnel is only usable with an address and a way to authenticate, a socket ignores it
+            if server_ssh_tunnel_for_submit.get() && !unix_socket {
+                let general_errors = !field_errors.read(cx).is_empty();
+                if ssh_addr_state_clone.read(cx).value().is_empty() {
+                    let id = ssh_addr_state_clone.entity_id().to_string();
+                    field_errors.update(cx, |state, _cx| {
+                        state.insert(id, "ssh address is required".into());
+                    });
+                }
+                if !server_ssh_agent_for_submit.get()
+                    && ssh_key_state_clone.read(cx).value().is_empty()
+                    && ssh_password_state_clone.read(cx).value().is_empty()
+                {
+                    let id = ssh_key_state_clone.entity_id().to_string();
+                    field_errors.update(cx, |state, _cx| {
+                        state.insert(id, "ssh key or password is required".into());
+                    });
+                }
+                if !general_errors && !field_errors.read(cx).is_empty() {
+                    tab_selected_index_for_submit.update(cx, |state, cx| {
+                        *state = SSH_TAB_INDEX;
+                        cx.notify();
+                    });
+                }
+            }
             if !field_errors.read(cx).is_empty() {
                 return false;
             }
@@ -520,10 +762,32 @@ impl ZedisServers {
             } else {
                 Some(master_name_val)
             };
+            // Empty or 0 keeps the config free of the field
+            let default_db = default_db_state_clone
+                .read(cx)
+                .value()
+                .parse::<usize>()
+                .ok()
+                .filter(|db| *db > 0);
+            // Empty or 0 falls back to the global timeouts
+            let connect_timeout_ms = connect_timeout_state_clone
+                .read(cx)
+                .value()
+                .parse::<u64>()
+                .ok()
+                .filter(|ms| *ms > 0);
+            let response_timeout_ms = response_timeout_state_clone
+                .read(cx)
+                .value()
+                .parse::<u64>()
+                .ok()
+                .filter(|ms| *ms > 0);
             let desc_val = description_state_clone.read(cx).value();
             let description = if desc_val.is_empty() { None } else { Some(desc_val) };
 
             let ssh_tunnel = server_ssh_tunnel_for_submit.get();
+            let ssh_agent = server_ssh_agent_for_submit.get().then_some(true);
+            let ssh_strict_host_key = server_ssh_strict_host_key_for_submit.get().then_some(true);
             let ssh_addr_val = ssh_addr_state_clone.read(cx).value();
             let ssh_addr = if ssh_addr_val.is_empty() {
                 None
@@ -548,52 +812,95 @@ impl ZedisServers {
             } else {
                 Some(ssh_key_val)
             };
+            let ssh_key_passphrase_val = ssh_key_passphrase_state_clone.read(cx).value();
+            let ssh_key_passphrase = if ssh_key_passphrase_val.is_empty() {
+                None
+            } else {
+                Some(ssh_key_passphrase_val)
+            };
 
             let readonly = if server_readonly_for_submit.get() {
                 Some(true)
             } else {
                 None
             };
+            let prefer_replica = server_prefer_replica_for_submit.get().then_some(true);
             let server_type = *server_type_state.read(cx);
             let server_type = if server_type > 0 { Some(server_type) } else { None };
+            let color = server_color_state
+                .read(cx)
+                .checked_sub(1)
+                .and_then(|index| SERVER_COLORS.get(index))
+                .map(|(name, _)| name.to_string());
 
+            let state = server_state_clone.read(cx);
+            let current_server = state.server(server_id_clone.as_str()).cloned().unwrap_or_default();
+            let server = RedisServer {
+                id: server_id_clone.clone(),
+                name: name.to_string(),
+                host: host.to_string(),
+                port,
+                socket_path: unix_socket.then(|| socket_path.to_string()),
+                username: username.map(|u| u.to_string()),
+                password: password.map(|p| p.to_string()),
+                server_type,
+                master_name: master_name.map(|m| m.to_string()),
+                default_db,
+                connect_timeout_ms,
+                response_timeout_ms,
+                color,
+                description: description.map(|d| d.to_string()),
+                tls: if enable_tls { Some(enable_tls) } else { None },
+                insecure: insecure_tls,
+                client_cert: client_cert.map(|c| c.to_string()),
+                client_key: client_key.map(|k| k.to_string()),
+                root_cert: root_cert.map(|r| r.to_string()),
+                ssh_tunnel: if ssh_tunnel { Some(ssh_tunnel) } else { None },
+                ssh_addr: ssh_addr.map(|a| a.to_string()),
+                ssh_username: ssh_username.map(|u| u.to_string()),
+                ssh_password: ssh_password.map(|p| p.to_string()),
+                ssh_key: ssh_key.map(|k| k.to_string()),
+                ssh_key_passphrase: ssh_key_passphrase.map(|p| p.to_string()),
+                ssh_agent,
+                ssh_strict_host_key,
+                readonly,
+                prefer_replica,
+                ..current_server
+            };
+            // Re-adding a server under another name would list the same connection twice
+            if let Some(existing) = state.find_duplicate_server(&server) {
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                let message = t!(
+                    "servers.duplicate_server_prompt",
+                    server = existing.name,
+                    locale = locale
+                )
+                .to_string();
+                let existing_id = existing.id.clone();
+                let server_state = server_state_clone.clone();
+                let dialog = ConfirmDialog::new(message, move |window, cx| {
+                    let server = RedisServer {
+                        id: existing_id.clone(),
+                        ..server.clone()
+                    };
+                    server_state.update(cx, |state, cx| {
+                        state.update_or_insrt_server(server, cx);
+                    });
+                    // Closes the prompt, the server form is closed right after it
+                    window.close_dialog(cx);
+                })
+                .with_title(i18n_servers(cx, "duplicate_server_title"));
+                open_confirm_dialog(dialog, window, cx);
+                return false;
+            }
             server_state_clone.update(cx, |state, cx| {
-                let current_server = state.server(server_id_clone.as_str()).cloned().unwrap_or_default();
-
-                state.update_or_insrt_server(
-                    RedisServer {
-                        id: server_id_clone.clone(),
-                        name: name.to_string(),
-                        host: host.to_string(),
-                        port,
-                        username: username.map(|u| u.to_string()),
-                        password: password.map(|p| p.to_string()),
-                        server_type,
-                        master_name: master_name.map(|m| m.to_string()),
-                        description: description.map(|d| d.to_string()),
-                        tls: if enable_tls { Some(enable_tls) } else { None },
-                        insecure: insecure_tls,
-                        client_cert: client_cert.map(|c| c.to_string()),
-                        client_key: client_key.map(|k| k.to_string()),
-                        root_cert: root_cert.map(|r| r.to_string()),
-                        ssh_tunnel: if ssh_tunnel { Some(ssh_tunnel) } else { None },
-                        ssh_addr: ssh_addr.map(|a| a.to_string()),
-                        ssh_username: ssh_username.map(|u| u.to_string()),
-                        ssh_password: ssh_password.map(|p| p.to_string()),
-                        ssh_key: ssh_key.map(|k| k.to_string()),
-                        readonly,
-                        ..current_server
-                    },
-                    cx,
-                );
+                state.update_or_insrt_server(server, cx);
             });
 
             window.close_dialog(cx);
             true
         });
 
-        let tab_selected_index = cx.new(|_cx| 0_usize);
-
         let focus_handle_done = Cell::new(false);
         window.open_dialog(cx, move |dialog, window, cx| {
             // let field_errors_clone = field_errors.clone();
@@ -609,6 +916,9 @@ impl ZedisServers {
             // Prepare field labels
             let name_label = i18n_common(cx, "name");
             let host_label = i18n_common(cx, "host");
+            let unix_socket_label = i18n_servers(cx, "unix_socket");
+            let unix_socket_check_label = i18n_servers(cx, "unix_socket_check_label");
+            let socket_path_label = i18n_servers(cx, "socket_path");
             let port_label = i18n_common(cx, "port");
             let username_label = i18n_common(cx, "username");
             let password_label = i18n_common(cx, "password");
@@ -621,19 +931,31 @@ impl ZedisServers {
             let root_cert_label = i18n_common(cx, "root_cert");
             let description_label = i18n_common(cx, "description");
             let master_name_label = i18n_servers(cx, "master_name");
+            let default_db_label = i18n_servers(cx, "default_db");
+            let connect_timeout_label = i18n_servers(cx, "connect_timeout");
+            let response_timeout_label = i18n_servers(cx, "response_timeout");
             let ssh_addr_label = i18n_servers(cx, "ssh_addr");
             let ssh_username_label = i18n_servers(cx, "ssh_username");
             let ssh_password_label = i18n_servers(cx, "ssh_password");
             let ssh_key_label = i18n_servers(cx, "ssh_key");
+            let ssh_key_passphrase_label = i18n_servers(cx, "ssh_key_passphrase");
             let ssh_tunnel_label = i18n_servers(cx, "ssh_tunnel");
             let ssh_tunnel_check_label = i18n_servers(cx, "ssh_tunnel_check_label");
+            let ssh_agent_label = i18n_servers(cx, "ssh_agent");
+            let ssh_agent_check_label = i18n_servers(cx, "ssh_agent_check_label");
+            let ssh_strict_host_key_label = i18n_servers(cx, "ssh_strict_host_key");
+            let ssh_strict_host_key_check_label = i18n_servers(cx, "ssh_strict_host_key_check_label");
             let readonly_label = i18n_servers(cx, "readonly");
             let readonly_check_label = i18n_servers(cx, "readonly_check_label");
+            let prefer_replica_label = i18n_servers(cx, "prefer_replica");
+            let prefer_replica_check_label = i18n_servers(cx, "prefer_replica_check_label");
+            let prefer_replica_description = i18n_servers(cx, "prefer_replica_description");
             let tab_general_label = i18n_servers(cx, "tab_general");
             let tab_tls_label = i18n_servers(cx, "tab_tls");
             let tab_ssh_label = i18n_servers(cx, "tab_ssh");
             let server_type_label = i18n_servers(cx, "server_type");
             let server_type_list = i18n_servers(cx, "server_type_list");
+            let color_label = i18n_servers(cx, "color");
             let current_tab_index = *tab_selected_index.read(cx);
             dialog
                 .title(title)
@@ -672,7 +994,7 @@ impl ZedisServers {
                             .child(field().label(client_cert_label).child(Input::new(&client_cert_state)))
                             .child(field().label(client_key_label).child(Input::new(&client_key_state)))
                             .child(field().label(root_cert_label).child(Input::new(&root_cert_state))),
-                        2 => form
+                        SSH_TAB_INDEX => form
                             .child(field().label(ssh_tunnel_label).child({
                                 let server_ssh_tunnel = server_ssh_tunnel.clone();
                                 Checkbox::new("redis-server-ssh-tunnel")
@@ -683,14 +1005,44 @@ impl ZedisServers {
                                         cx.stop_propagation();
                                     })
                             }))
-                            .child(field().label(ssh_addr_label).child(Input::new(&ssh_addr_state)))
+                            .child(
+                                field()
+                                    .required(server_ssh_tunnel.get())
+                                    .label(ssh_addr_label)
+                                    .child(Input::new(&ssh_addr_state)),
+                            )
+                            .child(field().label(ssh_agent_label).child({
+                                let server_ssh_agent = server_ssh_agent.clone();
+                                Checkbox::new("redis-server-ssh-agent")
+                                    .label(ssh_agent_check_label)
+                                    .checked(server_ssh_agent.get())
+                                    .on_click(move |checked, _, cx| {
+                                        server_ssh_agent.set(*checked);
+                                        cx.stop_propagation();
+                                    })
+                            }))
+                            .child(field().label(ssh_strict_host_key_label).child({
+                                let server_ssh_strict_host_key = server_ssh_strict_host_key.clone();
+                                Checkbox::new("redis-server-ssh-strict-host-key")
+                                    .label(ssh_strict_host_key_check_label)
+                                    .checked(server_ssh_strict_host_key.get())
+                                    .on_click(move |checked, _, cx| {
+                                        server_ssh_strict_host_key.set(*checked);
+                                        cx.stop_propagation();
+                                    })
+                            }))
                             .child(field().label(ssh_username_label).child(Input::new(&ssh_username_state)))
                             .child(
                                 field()
                                     .label(ssh_password_label)
                                     .child(Input::new(&ssh_password_state).mask_toggle()),
                             )
-                            .child(field().label(ssh_key_label).child(Input::new(&ssh_key_state))),
+                            .child(field().label(ssh_key_label).child(Input::new(&ssh_key_state)))
+                            .child(
+                                field()
+                                    .label(ssh_key_passphrase_label)
+                                    .child(Input::new(&ssh_key_passphrase_state).mask_toggle()),
+                            ),
                         _ => {
                             form.child(
                                 field()
@@ -699,8 +1051,30 @@ impl ZedisServers {
                                     // Name is read-only when editing existing server
                                     .child(Input::new(&name_state)),
                             )
-                            .child(field().required(true).label(host_label).child(Input::new(&host_state)))
-                            .child(field().label(port_label).child(NumberInput::new(&port_state)))
+                            .child(field().label(unix_socket_label).child({
+                                let server_unix_socket = server_unix_socket.clone();
+                                Checkbox::new("redis-server-unix-socket")
+                                    .label(unix_socket_check_label)
+                                    .checked(server_unix_socket.get())
+                                    .on_click(move |checked, window, cx| {
+                                        server_unix_socket.set(*checked);
+                                        // Swap the host and port fields for the socket path
+                                        window.refresh();
+                                        cx.stop_propagation();
+                                    })
+                            }))
+                            .when(server_unix_socket.get(), |form| {
+                                form.child(
+                                    field()
+                                        .required(true)
+                                        .label(socket_path_label)
+                                        .child(Input::new(&socket_path_state)),
+                                )
+                            })
+                            .when(!server_unix_socket.get(), |form| {
+                                form.child(field().required(true).label(host_label).child(Input::new(&host_state)))
+                                    .child(field().label(port_label).child(NumberInput::new(&port_state)))
+                            })
                             .child(field().label(username_label).child(Input::new(&username_state)))
                             .child(
                                 field()
@@ -718,6 +1092,21 @@ impl ZedisServers {
                                         cx.stop_propagation();
                                     })
                             }))
+                            .child(
+                                field()
+                                    .label(prefer_replica_label)
+                                    .description(prefer_replica_description)
+                                    .child({
+                                        let server_prefer_replica = server_prefer_replica.clone();
+                                        Checkbox::new("redis-server-prefer-replica")
+                                            .label(prefer_replica_check_label)
+                                            .checked(server_prefer_replica.get())
+                                            .on_click(move |checked, _, cx| {
+                                                server_prefer_replica.set(*checked);
+                                                cx.stop_propagation();
+                                            })
+                                    }),
+                            )
                             .child(
                                 field().label(server_type_label).child(
                                     RadioGroup::horizontal("horizontal-group")
@@ -736,7 +1125,23 @@ impl ZedisServers {
                                 ),
                             )
                             .child(field().label(master_name_label).child(Input::new(&master_name_state)))
+                            .child(field().label(default_db_label).child(Input::new(&default_db_state)))
+                            .child(
+                                field()
+                                    .label(connect_timeout_label)
+                                    .child(Input::new(&connect_timeout_state)),
+                            )
+                            .child(
+                                field()
+                                    .label(response_timeout_label)
+                                    .child(Input::new(&response_timeout_state)),
+                            )
                             .child(field().label(description_label).child(Input::new(&description_state)))
+                            .child(
+                                field()
+                                    .label(color_label)
+                                    .child(render_color_palette(&server_color_state_clone, cx)),
+                            )
                         }
                     };
 
@@ -789,7 +1194,7 @@ impl ZedisServers {
                         let submit_label = i18n_common(cx, "submit");
                         let cancel_label = i18n_common(cx, "cancel");
 
-                        let mut buttons = vec![
+                        platform_button_order(vec![
                             // Cancel button - closes dialog without saving
                             Button::new("cancel").label(cancel_label).on_click(|_, window, cx| {
                                 window.close_dialog(cx);
@@ -801,12 +1206,7 @@ impl ZedisServers {
                                     handle.clone()(window, cx);
                                 }
                             }),
-                        ];
-
-                        if is_windows() {
-                            buttons.reverse();
-                        }
-                        buttons
+                        ])
                     }
                 })
         });
@@ -820,15 +1220,19 @@ impl Render for ZedisServers {
     /// - < 800px: 1 column
     /// - 800-1200px: 2 columns  
     /// - > 1200px: 3 columns
+    ///
+    /// A fixed layout chosen in the settings overrides the breakpoints.
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let width = window.viewport_size().width;
+        let layout = cx.global::<ZedisGlobalStore>().read(cx).server_grid_layout();
+        let compact = layout == ServerGridLayout::List;
 
         // Responsive grid columns based on viewport width
-        let cols = match width {
+        let cols = layout.columns().unwrap_or(match width {
             width if width < px(VIEWPORT_BREAKPOINT_SMALL) => 1,
             width if width < px(VIEWPORT_BREAKPOINT_MEDIUM) => 2,
             _ => 3,
-        };
+        });
 
         // Card background color (slightly lighter/darker than theme background)
         let bg = if cx.theme().is_dark() {
@@ -840,6 +1244,8 @@ impl Render for ZedisServers {
         let update_tooltip = i18n_servers(cx, "update_tooltip");
         let remove_tooltip = i18n_servers(cx, "remove_tooltip");
 
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let locale = store.locale();
         // Build card for each configured server
         let children: Vec<_> = self
             .server_state
@@ -863,7 +1269,29 @@ impl Render for ZedisServers {
                     String::new()
                 };
 
-                let title = format!("{} ({}:{})", server.name, server.host, server.port);
+                let title = format!("{} ({})", server.name, server.address());
+
+                let show_updated_at = !compact && !updated_at.is_empty();
+
+                // Last failed connect, cleared once the server connects again
+                let last_error = store.server_error(&server.id).map(|error| {
+                    let time = format_timestamp(error.created_at).unwrap_or_default();
+                    let message: SharedString = error.message.clone().into();
+                    let label = t!("servers.last_error", time = time, message = message, locale = locale).to_string();
+                    div()
+                        .id(("servers-card-error", index))
+                        .w_full()
+                        .overflow_hidden()
+                        .tooltip(move |window, cx| Tooltip::new(message.clone()).build(window, cx))
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .items_center()
+                                .text_color(cx.theme().warning)
+                                .child(Icon::new(IconName::TriangleAlert).small())
+                                .child(Label::new(label).text_sm().text_ellipsis().whitespace_nowrap()),
+                        )
+                });
 
                 // Action buttons for each server card
                 let actions = vec![
@@ -894,7 +1322,8 @@ impl Render for ZedisServers {
 
                     // Connect to server
                     this.server_state.update(cx, |state, cx| {
-                        state.select(select_server_id.into(), 0, cx);
+                        let db = state.default_db(&select_server_id, cx);
+                        state.select(select_server_id.into(), db, cx);
                     });
 
                     // Navigate to editor view
@@ -910,44 +1339,73 @@ impl Render for ZedisServers {
                     .icon(Icon::new(CustomIconName::DatabaseZap))
                     .title(title)
                     .bg(bg)
-                    .when(!description.is_empty(), |this| {
+                    .accent(server.tag_color())
+                    .when(!compact && !description.is_empty(), |this| {
                         this.description(description.to_string())
                     })
-                    .when(!updated_at.is_empty(), |this| {
-                        this.footer(
-                            Label::new(updated_at)
-                                .text_sm()
-                                .text_right()
-                                .whitespace_normal()
-                                .text_color(cx.theme().muted_foreground),
-                        )
+                    .when(last_error.is_some() || show_updated_at, |this| {
+                        let footer = v_flex()
+                            .w_full()
+                            .gap_1()
+                            .children(last_error)
+                            .when(show_updated_at, |this| {
+                                this.child(
+                                    Label::new(updated_at)
+                                        .text_sm()
+                                        .text_right()
+                                        .whitespace_normal()
+                                        .text_color(cx.theme().muted_foreground),
+                                )
+                            });
+                        this.footer(footer)
                     })
                     .actions(actions)
                     .on_click(handle_select_server)
             })
             .collect();
 
-        // Render responsive grid with server cards + add new server card
-        div()
-            .grid()
-            .grid_cols(cols)
-            .gap_1()
+        // Quick connect with a URL, the server isn't saved
+        let quick_connect = h_flex()
             .w_full()
-            .children(children)
+            .gap_2()
             .child(
-                // "Add New Server" card at the end
-                Card::new("servers-card-add")
-                    .icon(IconName::Plus)
-                    .title(i18n_servers(cx, "add_server_title"))
-                    .bg(bg)
-                    .description(i18n_servers(cx, "add_server_description"))
-                    .actions(vec![Button::new("add").ghost().icon(CustomIconName::FilePlusCorner)])
-                    .on_click(cx.listener(move |this, _, window, cx| {
-                        // Fill with empty server data for new entry
-                        this.fill_inputs(window, cx, &RedisServer::default());
-                        this.add_or_update_server(window, cx);
-                    })),
+                Input::new(&self.quick_connect_state)
+                    .flex_1()
+                    .prefix(Icon::new(CustomIconName::Zap))
+                    .cleanable(true),
             )
+            .child(
+                Button::new("servers-quick-connect")
+                    .outline()
+                    .label(i18n_servers(cx, "quick_connect"))
+                    .tooltip(i18n_servers(cx, "quick_connect_tooltip"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_quick_connect(window, cx);
+                    })),
+            );
+
+        // Render responsive grid with server cards + add new server card
+        let grid = div().grid().grid_cols(cols).gap_1().w_full().children(children).child(
+            // "Add New Server" card at the end
+            Card::new("servers-card-add")
+                .icon(IconName::Plus)
+                .title(i18n_servers(cx, "add_server_title"))
+                .bg(bg)
+                .when(!compact, |this| {
+                    this.description(i18n_servers(cx, "add_server_description"))
+                })
+                .actions(vec![Button::new("add").ghost().icon(CustomIconName::FilePlusCorner)])
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    // Fill with empty server data for new entry
+                    this.fill_inputs(window, cx, &RedisServer::default());
+                    this.add_or_update_server(window, cx);
+                })),
+        );
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(quick_connect)
+            .child(grid)
             .into_any_element()
     }
 }
@@ -108,6 +108,11 @@ impl ZedisKvFetcher for ZedisSetValues {
         self.value.set_value().is_some_and(|v| v.done)
     }
 
+    /// Checks if the user stopped loading more members.
+    fn is_canceled(&self) -> bool {
+        self.value.is_load_canceled()
+    }
+
     /// Triggers loading of the next batch of SET members.
     ///
     /// Uses cursor-based pagination via SSCAN to load more values.
@@ -165,9 +170,14 @@ impl ZedisSetEditor {
     /// # Returns
     /// A new `ZedisSetEditor` instance with a single-column table
     pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
-        // Initialize the KV table with a single "Value" column
+        // Initialize the KV table with a single "Member" column, members have no value to edit inline
         let table_state = cx.new(|cx| {
-            ZedisKvTable::<ZedisSetValues>::new(vec![KvTableColumn::new("Value", None)], server_state, window, cx)
+            ZedisKvTable::<ZedisSetValues>::new(
+                vec![KvTableColumn::new("Member", None).with_readonly(true)],
+                server_state,
+                window,
+                cx,
+            )
         });
 
         info!("Creating new SET editor view");
@@ -14,23 +14,60 @@
 
 use crate::{
     helpers::{get_or_create_config_dir, parse_duration},
-    states::{ZedisGlobalStore, i18n_settings, update_app_state_and_save},
+    states::{
+        DeleteCommand, ServerGridLayout, ZedisGlobalStore, clamp_scan_limit, i18n_common, i18n_settings,
+        update_app_state_and_save,
+    },
+    views::open_command_log_dialog,
 };
-use gpui::{Entity, Subscription, Window, prelude::*};
+use gpui::{Entity, SharedString, Subscription, Window, prelude::*};
 use gpui_component::{
+    IndexPath, Sizable,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
     form::{field, v_form},
+    h_flex,
     input::{Input, InputEvent, InputState, NumberInput, NumberInputEvent, StepAction},
     label::Label,
+    radio::RadioGroup,
+    select::{Select, SelectEvent, SelectItem, SelectState},
     v_flex,
 };
 
+/// Delete commands in the order of the settings radio group
+const DELETE_COMMANDS: [DeleteCommand; 2] = [DeleteCommand::Unlink, DeleteCommand::Del];
+
+#[derive(Debug, Clone)]
+struct ServerGridLayoutItem {
+    label: SharedString,
+    layout: ServerGridLayout,
+}
+
+impl SelectItem for ServerGridLayoutItem {
+    type Value = ServerGridLayout;
+    fn title(&self) -> SharedString {
+        self.label.clone()
+    }
+    fn value(&self) -> &Self::Value {
+        &self.layout
+    }
+}
+
 pub struct ZedisSettingEditor {
     max_key_tree_depth_state: Entity<InputState>,
     key_separator_state: Entity<InputState>,
     max_truncate_length_state: Entity<InputState>,
+    max_loaded_keys_state: Entity<InputState>,
+    prefetch_batch_size_state: Entity<InputState>,
+    scan_batch_count_state: Entity<InputState>,
+    max_initial_keys_state: Entity<InputState>,
+    server_grid_layout_state: Entity<SelectState<Vec<ServerGridLayoutItem>>>,
     config_dir_state: Entity<InputState>,
     redis_connection_timeout_state: Entity<InputState>,
     redis_response_timeout_state: Entity<InputState>,
+    cache_cleanup_interval_state: Entity<InputState>,
+    info_concurrency_state: Entity<InputState>,
+    info_node_timeout_state: Entity<InputState>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -40,8 +77,16 @@ impl ZedisSettingEditor {
         let max_key_tree_depth = store.max_key_tree_depth();
         let key_separator = store.key_separator().to_string();
         let max_truncate_length = store.max_truncate_length();
+        let max_loaded_keys = store.max_loaded_keys();
+        let prefetch_batch_size = store.prefetch_batch_size();
+        let scan_batch_count = store.scan_batch_count();
+        let max_initial_keys = store.max_initial_keys();
+        let server_grid_layout = store.server_grid_layout();
         let redis_connection_timeout = store.redis_connection_timeout();
         let redis_response_timeout = store.redis_response_timeout();
+        let cache_cleanup_interval = store.cache_cleanup_interval();
+        let info_concurrency = store.info_concurrency();
+        let info_node_timeout = store.info_node_timeout();
         let max_key_tree_depth_state = cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder(i18n_settings(cx, "max_key_tree_depth_placeholder"))
@@ -57,6 +102,26 @@ impl ZedisSettingEditor {
                 .placeholder(i18n_settings(cx, "max_truncate_length_placeholder"))
                 .default_value(max_truncate_length.to_string())
         });
+        let max_loaded_keys_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "max_loaded_keys_placeholder"))
+                .default_value(max_loaded_keys.to_string())
+        });
+        let prefetch_batch_size_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "prefetch_batch_size_placeholder"))
+                .default_value(prefetch_batch_size.to_string())
+        });
+        let scan_batch_count_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "scan_batch_count_placeholder"))
+                .default_value(scan_batch_count.to_string())
+        });
+        let max_initial_keys_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "max_initial_keys_placeholder"))
+                .default_value(max_initial_keys.to_string())
+        });
         let redis_connection_timeout_state = cx.new(|cx| {
             InputState::new(window, cx)
                 .placeholder(i18n_settings(cx, "redis_connection_timeout_placeholder"))
@@ -67,6 +132,21 @@ impl ZedisSettingEditor {
                 .placeholder(i18n_settings(cx, "redis_response_timeout_placeholder"))
                 .default_value(redis_response_timeout)
         });
+        let cache_cleanup_interval_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "cache_cleanup_interval_placeholder"))
+                .default_value(cache_cleanup_interval.as_secs().to_string())
+        });
+        let info_concurrency_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "info_concurrency_placeholder"))
+                .default_value(info_concurrency.to_string())
+        });
+        let info_node_timeout_state = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder(i18n_settings(cx, "info_node_timeout_placeholder"))
+                .default_value(info_node_timeout.as_secs().to_string())
+        });
 
         let config_dir = get_or_create_config_dir().unwrap_or_default();
 
@@ -108,6 +188,42 @@ impl ZedisSettingEditor {
                 }
             },
         ));
+        subscriptions.push(cx.subscribe_in(
+            &cache_cleanup_interval_state,
+            window,
+            |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    let text = state.read(cx).value();
+                    let interval = parse_duration(&text).ok();
+                    update_app_state_and_save(cx, "save_cache_cleanup_interval", move |state, _cx| {
+                        state.set_cache_cleanup_interval(interval);
+                    });
+                }
+            },
+        ));
+        subscriptions.push(
+            cx.subscribe_in(&info_concurrency_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    let Ok(value) = state.read(cx).value().parse::<usize>() else {
+                        return;
+                    };
+                    update_app_state_and_save(cx, "save_info_concurrency", move |state, _cx| {
+                        state.set_info_concurrency(value);
+                    });
+                }
+            }),
+        );
+        subscriptions.push(
+            cx.subscribe_in(&info_node_timeout_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    let text = state.read(cx).value();
+                    let timeout = parse_duration(&text).ok();
+                    update_app_state_and_save(cx, "save_info_node_timeout", move |state, _cx| {
+                        state.set_info_node_timeout(timeout);
+                    });
+                }
+            }),
+        );
         subscriptions.push(
             cx.subscribe_in(&max_key_tree_depth_state, window, |_view, state, event, window, cx| {
                 let NumberInputEvent::Step(action) = event;
@@ -157,6 +273,107 @@ impl ZedisSettingEditor {
                 }
             },
         ));
+        subscriptions.push(
+            cx.subscribe_in(&max_loaded_keys_state, window, |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    let Ok(value) = state.read(cx).value().parse::<usize>() else {
+                        return;
+                    };
+                    if value == 0 {
+                        return;
+                    };
+                    update_app_state_and_save(cx, "save_max_loaded_keys", move |state, _cx| {
+                        state.set_max_loaded_keys(value);
+                    });
+                }
+            }),
+        );
+        subscriptions.push(cx.subscribe_in(
+            &prefetch_batch_size_state,
+            window,
+            |_view, state, event, _window, cx| {
+                if let InputEvent::Blur = &event {
+                    let Ok(value) = state.read(cx).value().parse::<usize>() else {
+                        return;
+                    };
+                    if value == 0 {
+                        return;
+                    };
+                    update_app_state_and_save(cx, "save_prefetch_batch_size", move |state, _cx| {
+                        state.set_prefetch_batch_size(value);
+                    });
+                }
+            },
+        ));
+        subscriptions.push(
+            cx.subscribe_in(&scan_batch_count_state, window, |_view, state, event, window, cx| {
+                if let InputEvent::Blur = &event {
+                    let Ok(value) = state.read(cx).value().parse::<usize>() else {
+                        return;
+                    };
+                    // Show the value actually saved when it was out of bounds
+                    let clamped = clamp_scan_limit(value);
+                    if clamped != value {
+                        state.update(cx, |input, cx| {
+                            input.set_value(clamped.to_string(), window, cx);
+                        });
+                    }
+                    update_app_state_and_save(cx, "save_scan_batch_count", move |state, _cx| {
+                        state.set_scan_batch_count(clamped);
+                    });
+                }
+            }),
+        );
+        subscriptions.push(
+            cx.subscribe_in(&max_initial_keys_state, window, |_view, state, event, window, cx| {
+                if let InputEvent::Blur = &event {
+                    let Ok(value) = state.read(cx).value().parse::<usize>() else {
+                        return;
+                    };
+                    let clamped = clamp_scan_limit(value);
+                    if clamped != value {
+                        state.update(cx, |input, cx| {
+                            input.set_value(clamped.to_string(), window, cx);
+                        });
+                    }
+                    update_app_state_and_save(cx, "save_max_initial_keys", move |state, _cx| {
+                        state.set_max_initial_keys(clamped);
+                    });
+                }
+            }),
+        );
+        let server_grid_layout_items: Vec<ServerGridLayoutItem> = ServerGridLayout::ALL
+            .into_iter()
+            .map(|layout| ServerGridLayoutItem {
+                label: i18n_settings(cx, &format!("server_grid_layout_{}", layout.as_str())),
+                layout,
+            })
+            .collect();
+        let selected_layout = ServerGridLayout::ALL
+            .iter()
+            .position(|layout| *layout == server_grid_layout)
+            .unwrap_or_default();
+        let server_grid_layout_state = cx.new(|cx| {
+            SelectState::new(
+                server_grid_layout_items,
+                Some(IndexPath::new(selected_layout)),
+                window,
+                cx,
+            )
+        });
+        subscriptions.push(cx.subscribe_in(
+            &server_grid_layout_state,
+            window,
+            |_view, _state, event: &SelectEvent<Vec<ServerGridLayoutItem>>, _window, cx| match event {
+                SelectEvent::Confirm(value) => {
+                    if let Some(layout) = *value {
+                        update_app_state_and_save(cx, "save_server_grid_layout", move |state, _cx| {
+                            state.set_server_grid_layout(layout);
+                        });
+                    }
+                }
+            },
+        ));
         let config_dir_state =
             cx.new(|cx| InputState::new(window, cx).default_value(config_dir.to_string_lossy().to_string()));
 
@@ -164,16 +381,33 @@ impl ZedisSettingEditor {
             _subscriptions: subscriptions,
             config_dir_state,
             max_truncate_length_state,
+            max_loaded_keys_state,
+            prefetch_batch_size_state,
+            scan_batch_count_state,
+            max_initial_keys_state,
+            server_grid_layout_state,
             key_separator_state,
             max_key_tree_depth_state,
             redis_response_timeout_state,
             redis_connection_timeout_state,
+            cache_cleanup_interval_state,
+            info_concurrency_state,
+            info_node_timeout_state,
         }
     }
 }
 
 impl Render for ZedisSettingEditor {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let store = cx.global::<ZedisGlobalStore>().read(cx);
+        let proto_raw_json = store.proto_raw_json();
+        let confirm_unsaved_changes = store.confirm_unsaved_changes();
+        let delete_command = store.delete_command();
+        let ttl_absolute = store.ttl_absolute();
+        let prefetch_key_types = store.prefetch_key_types();
+        let escape_glob_keyword = store.escape_glob_keyword();
+        let command_logging = store.command_logging();
+        let pin_last_error = store.pin_last_error();
         v_flex()
             .p_5()
             .child(Label::new(i18n_settings(cx, "title")).text_3xl().mb_2())
@@ -201,11 +435,172 @@ impl Render for ZedisSettingEditor {
                             .label(i18n_settings(cx, "redis_response_timeout"))
                             .child(Input::new(&self.redis_response_timeout_state)),
                     )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "cache_cleanup_interval"))
+                            .child(Input::new(&self.cache_cleanup_interval_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "info_concurrency"))
+                            .child(Input::new(&self.info_concurrency_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "info_node_timeout"))
+                            .child(Input::new(&self.info_node_timeout_state)),
+                    )
                     .child(
                         field()
                             .label(i18n_settings(cx, "max_truncate_length"))
                             .child(Input::new(&self.max_truncate_length_state)),
                     )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "max_loaded_keys"))
+                            .child(Input::new(&self.max_loaded_keys_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "scan_batch_count"))
+                            .child(Input::new(&self.scan_batch_count_state)),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "max_initial_keys"))
+                            .child(Input::new(&self.max_initial_keys_state)),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "prefetch_key_types")).child(
+                            Checkbox::new("settings-prefetch-key-types")
+                                .label(i18n_settings(cx, "prefetch_key_types_check"))
+                                .checked(prefetch_key_types)
+                                .on_click(|checked, _, cx| {
+                                    let checked = *checked;
+                                    update_app_state_and_save(cx, "save_prefetch_key_types", move |state, _cx| {
+                                        state.set_prefetch_key_types(checked);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "escape_glob_keyword")).child(
+                            Checkbox::new("settings-escape-glob-keyword")
+                                .label(i18n_settings(cx, "escape_glob_keyword_check"))
+                                .checked(escape_glob_keyword)
+                                .on_click(|checked, _, cx| {
+                                    let checked = *checked;
+                                    update_app_state_and_save(cx, "save_escape_glob_keyword", move |state, _cx| {
+                                        state.set_escape_glob_keyword(checked);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "prefetch_batch_size"))
+                            .child(Input::new(&self.prefetch_batch_size_state)),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "proto_raw_json")).child(
+                            Checkbox::new("settings-proto-raw-json")
+                                .label(i18n_settings(cx, "proto_raw_json_check"))
+                                .checked(proto_raw_json)
+                                .on_click(|checked, _, cx| {
+                                    let checked = *checked;
+                                    update_app_state_and_save(cx, "save_proto_raw_json", move |state, _cx| {
+                                        state.set_proto_raw_json(checked);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "confirm_unsaved_changes")).child(
+                            Checkbox::new("settings-confirm-unsaved-changes")
+                                .label(i18n_settings(cx, "confirm_unsaved_changes_check"))
+                                .checked(confirm_unsaved_changes)
+                                .on_click(|checked, _, cx| {
+                                    let checked = *checked;
+                                    update_app_state_and_save(cx, "save_confirm_unsaved_changes", move |state, _cx| {
+                                        state.set_confirm_unsaved_changes(checked);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "ttl_absolute")).child(
+                            Checkbox::new("settings-ttl-absolute")
+                                .label(i18n_settings(cx, "ttl_absolute_check"))
+                                .checked(ttl_absolute)
+                                .on_click(|checked, _, cx| {
+                                    let checked = *checked;
+                                    update_app_state_and_save(cx, "save_ttl_absolute", move |state, _cx| {
+                                        state.set_ttl_absolute(checked);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "pin_last_error")).child(
+                            Checkbox::new("settings-pin-last-error")
+                                .label(i18n_settings(cx, "pin_last_error_check"))
+                                .checked(pin_last_error)
+                                .on_click(|checked, _, cx| {
+                                    let checked = *checked;
+                                    update_app_state_and_save(cx, "save_pin_last_error", move |state, _cx| {
+                                        state.set_pin_last_error(checked);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "delete_command")).child(
+                            RadioGroup::horizontal("settings-delete-command")
+                                .children(
+                                    DELETE_COMMANDS
+                                        .iter()
+                                        .map(|command| i18n_common(cx, command.label_key())),
+                                )
+                                .selected_index(DELETE_COMMANDS.iter().position(|command| *command == delete_command))
+                                .on_click(|index, _, cx| {
+                                    let command = DELETE_COMMANDS[*index];
+                                    update_app_state_and_save(cx, "save_delete_command", move |state, _cx| {
+                                        state.set_delete_command(command);
+                                    });
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label(i18n_settings(cx, "command_logging")).child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Checkbox::new("settings-command-logging")
+                                        .label(i18n_settings(cx, "command_logging_check"))
+                                        .checked(command_logging)
+                                        .on_click(|checked, _, cx| {
+                                            let checked = *checked;
+                                            update_app_state_and_save(cx, "save_command_logging", move |state, _cx| {
+                                                state.set_command_logging(checked);
+                                            });
+                                        }),
+                                )
+                                .child(
+                                    Button::new("settings-command-log-view")
+                                        .outline()
+                                        .small()
+                                        .label(i18n_settings(cx, "command_log_view"))
+                                        .on_click(|_, window, cx| {
+                                            open_command_log_dialog(window, cx);
+                                        }),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field()
+                            .label(i18n_settings(cx, "server_grid_layout"))
+                            .child(Select::new(&self.server_grid_layout_state)),
+                    )
                     .child(
                         field()
                             .label(i18n_settings(cx, "config_dir"))
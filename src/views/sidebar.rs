@@ -16,8 +16,8 @@ use crate::{
     assets::CustomIconName,
     helpers::is_development,
     states::{
-        FontSize, FontSizeAction, LocaleAction, Route, ServerEvent, SettingsAction, ThemeAction, ZedisGlobalStore,
-        ZedisServerState, i18n_sidebar,
+        FontSize, FontSizeAction, LocaleAction, PendingNavigation, Route, ServerEvent, SettingsAction, ThemeAction,
+        ZedisGlobalStore, ZedisServerState, i18n_sidebar,
     },
 };
 use gpui::{Context, Corner, Entity, Pixels, SharedString, Subscription, Window, div, prelude::*, px, uniform_list};
@@ -202,15 +202,13 @@ impl ZedisSidebar {
                             let route = if is_home { Route::Home } else { Route::Editor };
 
                             view.update(cx, |this, cx| {
-                                // Update global route
-                                cx.update_global::<ZedisGlobalStore, ()>(|store, cx| {
-                                    store.update(cx, |state, cx| {
-                                        state.go_to(route, cx);
-                                    });
-                                });
-
+                                // Update global route and server, held back while the value has unsaved edits
                                 this.server_state.update(cx, |state, cx| {
-                                    state.select(server_id.clone(), 0, cx);
+                                    let db = state.default_db(server_id.as_str(), cx);
+                                    state.request_navigation(
+                                        PendingNavigation::SelectServer(server_id.clone(), db, Some(route)),
+                                        cx,
+                                    );
                                 });
                             });
                         })
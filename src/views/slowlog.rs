@@ -0,0 +1,250 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::assets::CustomIconName;
+use crate::connection::{SlowLogEntry, get_connection_manager};
+use crate::helpers::{format_timestamp, get_font_family};
+use crate::states::{ZedisGlobalStore, ZedisServerState, i18n_status_bar};
+use gpui::{App, Entity, SharedString, Window, div, prelude::*, px};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    notification::Notification,
+    table::{Column, Table, TableDelegate, TableState},
+    v_flex,
+};
+use rust_i18n::t;
+use std::sync::Arc;
+
+/// Entries fetched from each master
+const SLOWLOG_COUNT: usize = 128;
+
+struct SlowLogTableDelegate {
+    entries: Arc<Vec<SlowLogEntry>>,
+    columns: Vec<Column>,
+}
+
+impl TableDelegate for SlowLogTableDelegate {
+    fn columns_count(&self, _: &App) -> usize {
+        self.columns.len()
+    }
+
+    fn rows_count(&self, _: &App) -> usize {
+        self.entries.len()
+    }
+
+    fn column(&self, index: usize, _: &App) -> &Column {
+        &self.columns[index]
+    }
+
+    fn render_td(
+        &mut self,
+        row_ix: usize,
+        col_ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<TableState<Self>>,
+    ) -> impl IntoElement {
+        let text = if let Some(entry) = self.entries.get(row_ix) {
+            match col_ix {
+                0 => entry.node.clone(),
+                1 => entry.id.to_string(),
+                2 => format_timestamp(entry.timestamp).unwrap_or_default(),
+                3 => format!("{:.2} ms", entry.duration as f64 / 1000.0),
+                4 => entry.args.join(" "),
+                5 => entry.client.clone(),
+                _ => String::new(),
+            }
+        } else {
+            String::new()
+        };
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .when(col_ix == 4, |this| this.font_family(get_font_family()))
+            .child(Label::new(text))
+    }
+}
+
+/// Lists the `SLOWLOG` entries of every master, newest first.
+pub struct ZedisSlowLog {
+    server_id: String,
+    db: usize,
+    readonly: bool,
+    table_state: Entity<TableState<SlowLogTableDelegate>>,
+    count: usize,
+    loading: bool,
+}
+
+impl ZedisSlowLog {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let state = server_state.read(cx);
+        let server_id = state.server_id().to_string();
+        let db = state.db();
+        let readonly = state.readonly();
+        let columns = vec![
+            Column::new("node", i18n_status_bar(cx, "slowlog_node")).width(px(150.)),
+            Column::new("id", i18n_status_bar(cx, "slowlog_id")).width(px(70.)),
+            Column::new("time", i18n_status_bar(cx, "slowlog_time")).width(px(160.)),
+            Column::new("duration", i18n_status_bar(cx, "slowlog_duration")).width(px(100.)),
+            Column::new("command", i18n_status_bar(cx, "slowlog_command")).width(px(320.)),
+            Column::new("client", i18n_status_bar(cx, "slowlog_client")).width(px(150.)),
+        ];
+        let delegate = SlowLogTableDelegate {
+            entries: Arc::new(vec![]),
+            columns,
+        };
+        let table_state = cx.new(|cx| TableState::new(delegate, window, cx));
+        let mut this = Self {
+            server_id,
+            db,
+            readonly,
+            table_state,
+            count: 0,
+            loading: false,
+        };
+        this.load(window, cx);
+        this
+    }
+    /// Fetch the slow log of every master again
+    fn load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.loading = true;
+        cx.notify();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    get_connection_manager()
+                        .with_client_retry(&server_id, db, |client, _| async move {
+                            client.slowlog(SLOWLOG_COUNT).await
+                        })
+                        .await
+                })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| {
+                this.loading = false;
+                match result {
+                    Ok(entries) => {
+                        this.count = entries.len();
+                        this.table_state.update(cx, |state, cx| {
+                            state.delegate_mut().entries = Arc::new(entries);
+                            cx.notify();
+                        });
+                    }
+                    Err(e) => {
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+    /// Run `SLOWLOG RESET` on every master and reload
+    fn reset(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_id = self.server_id.clone();
+        let db = self.db;
+        self.loading = true;
+        cx.notify();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    get_connection_manager()
+                        .with_client_retry(&server_id, db, |client, _| async move { client.slowlog_reset().await })
+                        .await
+                })
+                .await;
+            let _ = handle.update_in(cx, |this, window, cx| {
+                match result {
+                    Ok(()) => {
+                        window.push_notification(Notification::info(i18n_status_bar(cx, "slowlog_reset_done")), cx);
+                        this.load(window, cx);
+                    }
+                    Err(e) => {
+                        this.loading = false;
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+}
+
+impl Render for ZedisSlowLog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+        let count: SharedString = t!("status_bar.slowlog_count", count = self.count, locale = locale).into();
+        v_flex()
+            .w_full()
+            .gap_2()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .child(Label::new(count).text_sm().text_color(cx.theme().muted_foreground)),
+                    )
+                    .child(
+                        Button::new("zedis-slowlog-reload")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::RotateCw)
+                            .tooltip(i18n_status_bar(cx, "slowlog_reload"))
+                            .loading(self.loading)
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.load(window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("zedis-slowlog-reset")
+                            .outline()
+                            .small()
+                            .icon(CustomIconName::Eraser)
+                            .tooltip(i18n_status_bar(cx, "slowlog_reset"))
+                            .disabled(self.loading || self.readonly)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.reset(window, cx);
+                            })),
+                    ),
+            )
+            .child(
+                div().w_full().h(px(480.0)).child(
+                    Table::new(&self.table_state)
+                        .stripe(true)
+                        .bordered(true)
+                        .scrollbar_visible(true, true),
+                ),
+            )
+    }
+}
+
+/// Opens a dialog listing the slow log of the server, aggregated over the masters.
+pub fn open_slowlog_dialog(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut App) {
+    let view = cx.new(|cx| ZedisSlowLog::new(server_state, window, cx));
+    window.open_dialog(cx, move |dialog, _, cx| {
+        dialog
+            .title(i18n_status_bar(cx, "slowlog_title"))
+            .width(px(1000.0))
+            .overlay_closable(true)
+            .child(view.clone())
+    });
+}
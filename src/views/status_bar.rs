@@ -14,24 +14,89 @@
 
 use crate::{
     assets::CustomIconName,
-    connection::RedisClientDescription,
-    helpers::humanize_keystroke,
+    components::{FormDialog, FormField, open_add_form_dialog, open_typed_confirm_dialog},
+    connection::{RedisClientDescription, get_connection_manager},
+    helpers::{format_timestamp, humanize_keystroke},
     states::{
-        ErrorMessage, ServerEvent, ServerTask, ViewMode, ZedisServerState, i18n_common, i18n_sidebar, i18n_status_bar,
+        ErrorMessage, KeyType, KeyTypePrefetchCost, RedisInfo, ServerEvent, ServerTask, ViewMode, ZedisGlobalStore,
+        ZedisServerState, i18n_common, i18n_sidebar, i18n_status_bar, update_app_state_and_save,
     },
+    views::{open_cluster_topology_dialog, open_node_info_dialog, open_pubsub_dialog, open_slowlog_dialog},
+};
+use gpui::{
+    App, ClipboardItem, Entity, Hsla, SharedString, Subscription, Task, TextAlign, Window, div, prelude::*, px,
 };
-use gpui::{Entity, Hsla, SharedString, Subscription, Task, TextAlign, Window, div, prelude::*};
 use gpui_component::select::{SearchableVec, Select, SelectEvent, SelectItem, SelectState};
 use gpui_component::{
-    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable,
+    ActiveTheme, Disableable, Icon, IconName, IndexPath, Sizable, WindowExt,
     button::{Button, ButtonVariants},
     h_flex,
     label::Label,
+    notification::Notification,
     tooltip::Tooltip,
+    v_flex,
 };
-use std::{sync::Arc, time::Duration};
+use rust_i18n::t;
+use std::{collections::BTreeMap, rc::Rc, sync::Arc, time::Duration};
 use tracing::info;
 
+/// Idle seconds used when killing idle clients without entering a threshold
+const DEFAULT_CLIENT_IDLE_SECS: u64 = 300;
+/// Word the user types to confirm killing idle clients
+const KILL_CONFIRM_KEYWORD: &str = "KILL";
+/// Height of the error detail before it scrolls
+const ERROR_DETAIL_MAX_HEIGHT: f32 = 360.0;
+/// Dbs always offered by the db selector, the `databases` default of redis
+const DEFAULT_DB_COUNT: usize = 16;
+
+/// Opens a dialog with the whole error, including its underlying causes.
+fn open_error_detail_dialog(error: ErrorMessage, window: &mut Window, cx: &mut App) {
+    let time = format_timestamp(error.created_at).unwrap_or_default();
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let detail = error.detail.clone();
+        dialog
+            .title(i18n_status_bar(cx, "error_detail_title"))
+            .overlay_closable(true)
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(
+                        Label::new(format!("{} · {time}", error.category))
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground),
+                    )
+                    .child(
+                        div()
+                            .id("zedis-error-detail")
+                            .max_h(px(ERROR_DETAIL_MAX_HEIGHT))
+                            .overflow_y_scroll()
+                            .child(
+                                Label::new(detail.clone())
+                                    .text_sm()
+                                    .text_color(cx.theme().red)
+                                    .whitespace_normal(),
+                            ),
+                    )
+                    .child(
+                        h_flex().justify_end().child(
+                            Button::new("zedis-error-detail-copy")
+                                .outline()
+                                .small()
+                                .icon(IconName::Copy)
+                                .label(i18n_status_bar(cx, "error_detail_copy"))
+                                .on_click(move |_, window, cx| {
+                                    cx.write_to_clipboard(ClipboardItem::new_string(detail.to_string()));
+                                    window.push_notification(
+                                        Notification::info(i18n_status_bar(cx, "error_detail_copied")),
+                                        cx,
+                                    );
+                                }),
+                        ),
+                    ),
+            )
+    });
+}
+
 /// Formats the database size and scan count string "count/total".
 #[inline]
 fn format_size(dbsize: Option<u64>, scan_count: usize) -> SharedString {
@@ -67,6 +132,46 @@ fn format_latency(latency: Option<Duration>, cx: &Context<ZedisStatusBar>) -> (S
     }
 }
 
+/// Arrow comparing a sample with the previous one, empty without a previous sample or a change.
+fn format_trend(current: f64, previous: Option<f64>) -> &'static str {
+    match previous {
+        Some(previous) if current > previous => " ↑",
+        Some(previous) if current < previous => " ↓",
+        _ => "",
+    }
+}
+
+/// Formats the operations per second with the trend since the previous heartbeat.
+fn format_ops_per_sec(ops: u64, previous: Option<u64>) -> SharedString {
+    format!(
+        "{ops} ops/s{}",
+        format_trend(ops as f64, previous.map(|ops| ops as f64))
+    )
+    .into()
+}
+
+/// Formats the keyspace hit rate and picks a color based on it (green > 90%, yellow > 70%, red otherwise).
+fn format_hit_rate(
+    redis_info: &RedisInfo,
+    previous: Option<f64>,
+    cx: &Context<ZedisStatusBar>,
+) -> (SharedString, Hsla) {
+    // No lookups yet, a 0% rate would look alarming
+    if redis_info.keyspace_hits + redis_info.keyspace_misses == 0 {
+        return ("--".into(), cx.theme().primary);
+    }
+    let rate = redis_info.hit_rate();
+    let theme = cx.theme();
+    let color = if rate > 90.0 {
+        theme.green
+    } else if rate > 70.0 {
+        theme.yellow
+    } else {
+        theme.red
+    };
+    (format!("{rate:.1}%{}", format_trend(rate, previous)).into(), color)
+}
+
 /// Formats the node count and version information.
 #[inline]
 fn format_nodes(nodes: (usize, usize), version: &str) -> SharedString {
@@ -78,12 +183,99 @@ fn format_nodes_description(description: Arc<RedisClientDescription>, cx: &Conte
     let t = i18n_sidebar(cx, "server_type");
     let master_nodes = i18n_sidebar(cx, "master_nodes");
     let slave_nodes = i18n_sidebar(cx, "slave_nodes");
-    let mut messages = Vec::with_capacity(3);
+    let mut messages = Vec::with_capacity(4);
     messages.push(format!("{t}: {}", description.server_type.as_str()));
     messages.push(format!("{master_nodes}: {}", description.master_nodes));
     if !description.slave_nodes.is_empty() {
         messages.push(format!("{slave_nodes}: {}", description.slave_nodes));
     }
+    messages.push(i18n_status_bar(cx, "node_info_tooltip").to_string());
+    messages.join("\n").into()
+}
+
+/// Formats the extra commands spent prefetching key types, empty when nothing was prefetched.
+#[inline]
+fn format_prefetch_cost(cost: KeyTypePrefetchCost, cx: &Context<ZedisStatusBar>) -> SharedString {
+    if cost.commands == 0 {
+        return SharedString::default();
+    }
+    t!(
+        "status_bar.key_type_prefetch_cost",
+        commands = cost.commands,
+        round_trips = cost.round_trips,
+        elapsed = format!("{}ms", cost.elapsed.as_millis()),
+        locale = cx.global::<ZedisGlobalStore>().read(cx).locale()
+    )
+    .to_string()
+    .into()
+}
+
+/// Formats the key count of each master, only meaningful when there are multiple masters.
+#[inline]
+fn format_size_distribution(dbsize_per_master: &[(SharedString, u64)], cx: &Context<ZedisStatusBar>) -> SharedString {
+    if dbsize_per_master.len() < 2 {
+        return SharedString::default();
+    }
+    let total: u64 = dbsize_per_master.iter().map(|(_, size)| size).sum();
+    let mut messages = Vec::with_capacity(dbsize_per_master.len() + 1);
+    messages.push(i18n_status_bar(cx, "keys_per_master").to_string());
+    for (addr, size) in dbsize_per_master {
+        let percent = if total == 0 {
+            0.0
+        } else {
+            *size as f64 * 100.0 / total as f64
+        };
+        messages.push(format!("{addr}: {size} ({percent:.1}%)"));
+    }
+    messages.join("\n").into()
+}
+
+/// Formats the keys and hit rate of each db, falling back to the global hit rate when
+/// the server doesn't expose per-db hits.
+#[inline]
+fn format_keyspace(redis_info: &RedisInfo, cx: &Context<ZedisStatusBar>) -> SharedString {
+    let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+    let mut dbs: Vec<_> = redis_info.keyspace.iter().collect();
+    // db2 before db10
+    dbs.sort_by_key(|(db, _)| db.trim_start_matches("db").parse::<usize>().unwrap_or(usize::MAX));
+    let mut messages = Vec::with_capacity(dbs.len() + 3);
+    messages.push(i18n_status_bar(cx, "keyspace_title").to_string());
+    for (db, stats) in dbs {
+        let message = if let Some(rate) = stats.hit_rate() {
+            t!(
+                "status_bar.keyspace_db_hit_rate",
+                db = db,
+                keys = stats.keys,
+                expires = stats.expires,
+                rate = format!("{rate:.1}%"),
+                hits = stats.hits.unwrap_or_default(),
+                misses = stats.misses.unwrap_or_default(),
+                locale = locale
+            )
+        } else {
+            t!(
+                "status_bar.keyspace_db",
+                db = db,
+                keys = stats.keys,
+                expires = stats.expires,
+                locale = locale
+            )
+        };
+        messages.push(message.to_string());
+    }
+    messages.push(
+        t!(
+            "status_bar.keyspace_global_hit_rate",
+            rate = format!("{:.1}%", redis_info.hit_rate()),
+            hits = redis_info.keyspace_hits,
+            misses = redis_info.keyspace_misses,
+            locale = locale
+        )
+        .to_string(),
+    );
+    if !redis_info.has_db_hit_stats() {
+        messages.push(i18n_status_bar(cx, "keyspace_global_only").to_string());
+    }
     messages.join("\n").into()
 }
 
@@ -92,18 +284,30 @@ fn format_nodes_description(description: Arc<RedisClientDescription>, cx: &Conte
 #[derive(Default)]
 struct StatusBarServerState {
     supports_db_selection: bool,
+    is_cluster: bool,
     server_id: SharedString,
     size: SharedString,
+    size_distribution: SharedString,
+    keyspace: SharedString,
+    prefetch_cost: SharedString,
     latency: (SharedString, Hsla),
+    ops_per_sec: SharedString,
+    hit_rate: (SharedString, Hsla),
+    /// Samples of the previous heartbeat, the trend arrows compare against them
+    last_ops_per_sec: Option<u64>,
+    last_hit_rate: Option<f64>,
     used_memory: SharedString,
     clients: SharedString,
     nodes: SharedString,
     scan_finished: bool,
+    loaded_keys_limit_reached: bool,
+    reconnecting: bool,
     soft_wrap: bool,
     nodes_description: SharedString,
+    unreachable_nodes: Option<SharedString>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct DbInfo {
     label: SharedString,
     db: usize,
@@ -119,12 +323,41 @@ impl SelectItem for DbInfo {
     }
 }
 
+/// Build the options of the db selector from the keyspace, empty dbs are only listed
+/// up to `DEFAULT_DB_COUNT` as INFO doesn't report them.
+fn build_db_items(redis_info: Option<&RedisInfo>, current_db: usize) -> Vec<DbInfo> {
+    let mut dbs: BTreeMap<usize, Option<u64>> = (0..DEFAULT_DB_COUNT).map(|db| (db, None)).collect();
+    dbs.entry(current_db).or_default();
+    if let Some(redis_info) = redis_info {
+        for (name, stats) in &redis_info.keyspace {
+            if let Ok(db) = name.trim_start_matches("db").parse::<usize>() {
+                dbs.insert(db, Some(stats.keys));
+            }
+        }
+    }
+    dbs.into_iter()
+        .map(|(db, keys)| {
+            let label = match keys {
+                Some(keys) => format!("DB: {db} ({keys})"),
+                None => format!("DB: {db}"),
+            };
+            DbInfo {
+                label: label.into(),
+                db,
+            }
+        })
+        .collect()
+}
+
 /// Local state for the status bar to cache formatted strings and colors.
 /// This prevents re-calculating strings on every render frame.
 #[derive(Default)]
 struct StatusBarState {
     server_state: StatusBarServerState,
     data_format: Option<SharedString>,
+    view_mode: ViewMode,
+    /// A value is loaded, the viewer mode applies to every key type
+    viewer_enabled: bool,
     error: Option<ErrorMessage>,
 }
 
@@ -133,7 +366,9 @@ pub struct ZedisStatusBar {
 
     viewer_mode_state: Entity<SelectState<SearchableVec<SharedString>>>,
     db_state: Entity<SelectState<Vec<DbInfo>>>,
+    db_items: Vec<DbInfo>,
     should_reset_viewer_mode: bool,
+    should_sync_db: bool,
     server_state: Entity<ZedisServerState>,
     heartbeat_task: Option<Task<()>>,
     readonly: bool,
@@ -147,13 +382,17 @@ impl ZedisStatusBar {
         let mut subscriptions = vec![];
         subscriptions.push(cx.subscribe(&server_state, |this, server_state, event, cx| {
             match event {
-                ServerEvent::ServerSelected(server_id, _) => {
+                ServerEvent::ServerSelected(server_id, db) => {
                     this.reset(server_id.clone());
+                    this.state.server_state.soft_wrap = server_state.read(cx).soft_wrap();
+                    this.db_items = build_db_items(None, *db);
+                    this.should_sync_db = true;
                 }
                 ServerEvent::ServerRedisInfoUpdated(_) => {
                     this.fill_state(server_state, cx);
                 }
                 ServerEvent::ServerInfoUpdated(_) => {
+                    this.state.server_state.reconnecting = false;
                     this.readonly = server_state.read(cx).readonly();
                     server_state.update(cx, |state, cx| {
                         state.refresh_redis_info(cx);
@@ -161,32 +400,51 @@ impl ZedisStatusBar {
                 }
                 ServerEvent::KeyScanStarted(_) => {
                     this.state.server_state.scan_finished = false;
+                    this.state.server_state.prefetch_cost = SharedString::default();
+                    this.state.server_state.loaded_keys_limit_reached = false;
                 }
                 ServerEvent::KeyScanFinished(_) => {
                     let state = server_state.read(cx);
                     this.state.server_state.size = format_size(state.dbsize(), state.scan_count());
                     this.state.server_state.scan_finished = true;
+                    this.state.server_state.loaded_keys_limit_reached = false;
                 }
                 ServerEvent::KeyScanPaged(_) => {
                     let state = server_state.read(cx);
                     this.state.server_state.size = format_size(state.dbsize(), state.scan_count());
+                    this.state.server_state.loaded_keys_limit_reached = state.loaded_keys_limit_reached();
+                }
+                ServerEvent::KeyTypesPrefetched(_) => {
+                    let cost = server_state.read(cx).key_type_prefetch_cost();
+                    this.state.server_state.prefetch_cost = format_prefetch_cost(cost, cx);
                 }
                 ServerEvent::ErrorOccurred(error) => {
                     this.state.error = Some(error.clone());
                 }
+                // The save button of a server connected from a URL goes away once it's saved
+                ServerEvent::ServerListUpdated => {}
                 ServerEvent::TaskStarted(task) => {
-                    // Clear error when a new task starts (except background ping)
-                    if *task != ServerTask::RefreshRedisInfo {
+                    // Clear error when a new task starts (except background ping), unless it's pinned
+                    if *task != ServerTask::RefreshRedisInfo
+                        && !cx.global::<ZedisGlobalStore>().read(cx).pin_last_error()
+                    {
                         this.state.error = None;
                     }
+                    if *task == ServerTask::Reconnect {
+                        this.state.server_state.reconnecting = true;
+                    }
                 }
                 ServerEvent::ValueLoaded(_) => {
                     let state = server_state.read(cx);
                     this.should_reset_viewer_mode = true;
+                    this.state.view_mode = state.value().map(|value| value.view_mode()).unwrap_or_default();
+                    this.state.viewer_enabled = state.value().is_some_and(|value| value.key_type() != KeyType::Unknown);
                     if let Some(value) = state.value().and_then(|item| item.bytes_value()) {
                         let mut format = value.format.as_str().to_string();
                         if let Some(mime) = &value.mime {
                             format = format!("{}({})", format, mime);
+                        } else if value.binary_utf8 {
+                            format = format!("{}({})", format, i18n_status_bar(cx, "binary_utf8"));
                         }
                         this.state.data_format = Some(format.into());
                     } else {
@@ -205,6 +463,7 @@ impl ZedisStatusBar {
                     ViewMode::Auto.as_str().into(),
                     ViewMode::Plain.as_str().into(),
                     ViewMode::Hex.as_str().into(),
+                    ViewMode::Json.as_str().into(),
                 ]),
                 Some(IndexPath::new(0)),
                 window,
@@ -219,30 +478,27 @@ impl ZedisStatusBar {
                 SelectEvent::Confirm(value) => {
                     if let Some(selected_value) = value {
                         view.server_state.update(cx, |state, cx| {
-                            state.update_bytes_value_view_mode(selected_value.clone(), cx);
+                            state.update_value_view_mode(selected_value.clone(), cx);
                         });
                     }
                 }
             },
         ));
 
-        let db_items = (0..16)
-            .map(|db| DbInfo {
-                label: format!("DB: {}", db).into(),
-                db,
-            })
-            .collect::<Vec<_>>();
-        let db_state = cx.new(|cx| SelectState::new(db_items, Some(IndexPath::new(0)), window, cx));
+        let db_items = build_db_items(None, 0);
+        let db_state = cx.new(|cx| SelectState::new(db_items.clone(), Some(IndexPath::new(0)), window, cx));
         subscriptions.push(cx.subscribe_in(
             &db_state,
             window,
             |view, _state, event: &SelectEvent<Vec<DbInfo>>, _window, cx| match event {
                 SelectEvent::Confirm(value) => {
                     if let Some(db) = value {
-                        view.server_state.update(cx, |state, cx| {
-                            let server_id = state.server_id().to_string();
-                            state.select(server_id.into(), *db, cx);
-                        });
+                        let switched = view.server_state.update(cx, |state, cx| state.select_db(*db, cx));
+                        // Held back by unsaved edits, show the current db until it actually switches
+                        if !switched {
+                            view.should_sync_db = true;
+                            cx.notify();
+                        }
                     }
                 }
             },
@@ -253,9 +509,11 @@ impl ZedisStatusBar {
             heartbeat_task: None,
             viewer_mode_state,
             db_state,
+            db_items,
             server_state: server_state.clone(),
             _subscriptions: subscriptions,
             should_reset_viewer_mode: false,
+            should_sync_db: false,
             state: StatusBarState { ..Default::default() },
             readonly,
         };
@@ -272,6 +530,7 @@ impl ZedisStatusBar {
             self.state.server_state.size = SharedString::default();
         }
         self.state.data_format = None;
+        self.state.viewer_enabled = false;
         self.state.error = None;
     }
     fn fill_state(&mut self, server_state: Entity<ZedisServerState>, cx: &Context<Self>) {
@@ -279,6 +538,14 @@ impl ZedisStatusBar {
         let Some(redis_info) = state.redis_info() else {
             return;
         };
+        let last_ops_per_sec = self.state.server_state.last_ops_per_sec;
+        let last_hit_rate = self.state.server_state.last_hit_rate;
+        let has_lookups = redis_info.keyspace_hits + redis_info.keyspace_misses > 0;
+        let db_items = build_db_items(Some(redis_info), state.db());
+        if db_items != self.db_items {
+            self.db_items = db_items;
+            self.should_sync_db = true;
+        }
         let clients = if redis_info.connected_clients == 0 {
             "--".to_string()
         } else {
@@ -291,15 +558,35 @@ impl ZedisStatusBar {
         };
         self.state.server_state = StatusBarServerState {
             supports_db_selection: state.supports_db_selection(),
+            is_cluster: state.is_cluster(),
             server_id: state.server_id().to_string().into(),
             size: format_size(state.dbsize(), state.scan_count()),
+            size_distribution: format_size_distribution(&state.dbsize_per_master(), cx),
+            keyspace: format_keyspace(redis_info, cx),
+            prefetch_cost: format_prefetch_cost(state.key_type_prefetch_cost(), cx),
             latency: format_latency(Some(redis_info.latency), cx),
+            ops_per_sec: format_ops_per_sec(redis_info.instantaneous_ops_per_sec, last_ops_per_sec),
+            hit_rate: format_hit_rate(redis_info, last_hit_rate, cx),
+            last_ops_per_sec: Some(redis_info.instantaneous_ops_per_sec),
+            last_hit_rate: has_lookups.then(|| redis_info.hit_rate()),
             used_memory: used_memory.into(),
             clients: clients.into(),
             nodes: format_nodes(state.nodes(), state.version()),
             scan_finished: state.scan_completed(),
+            loaded_keys_limit_reached: state.loaded_keys_limit_reached(),
+            reconnecting: state.reconnecting(),
             soft_wrap: state.soft_wrap(),
             nodes_description: format_nodes_description(state.nodes_description().clone(), cx),
+            unreachable_nodes: (redis_info.unreachable_nodes > 0).then(|| {
+                t!(
+                    "status_bar.unreachable_nodes_tooltip",
+                    count = redis_info.unreachable_nodes,
+                    total = redis_info.total_nodes,
+                    locale = cx.global::<ZedisGlobalStore>().read(cx).locale()
+                )
+                .to_string()
+                .into()
+            }),
         };
     }
     /// Start the heartbeat task
@@ -318,15 +605,34 @@ impl ZedisStatusBar {
     fn render_server_status(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = &self.state.server_state;
         let is_completed = server_state.scan_finished;
+        let loaded_keys_limit_reached = server_state.loaded_keys_limit_reached;
+        let reconnecting = server_state.reconnecting;
         let nodes_description = server_state.nodes_description.clone();
+        let size_tooltip: SharedString = [
+            &server_state.size_distribution,
+            &server_state.keyspace,
+            &server_state.prefetch_cost,
+        ]
+        .into_iter()
+        .filter(|text| !text.is_empty())
+        .map(|text| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        .into();
         let terminal_tooltip = format!(
             "{} ({})",
             i18n_status_bar(cx, "toggle_terminal_tooltip"),
             humanize_keystroke("cmd-j")
         );
         let readonly_tooltip = i18n_status_bar(cx, "toggle_readonly_tooltip");
+        let state = self.server_state.read(cx);
+        let is_transient_server = state.is_transient_server();
+        let tag_color = state.server(state.server_id()).and_then(|server| server.tag_color());
         h_flex()
             .items_center()
+            .when_some(tag_color, |this, color| {
+                this.child(div().size_2p5().rounded_full().bg(color).mr_2())
+            })
             .child(
                 Button::new("zedis-status-bar-server-terminal")
                     .outline()
@@ -340,6 +646,21 @@ impl ZedisStatusBar {
                     }))
                     .mr_2(),
             )
+            .child(
+                Button::new("zedis-status-bar-server-reconnect")
+                    .outline()
+                    .small()
+                    .tooltip(i18n_status_bar(cx, "reconnect_tooltip"))
+                    .icon(CustomIconName::Network)
+                    .loading(reconnecting)
+                    .disabled(reconnecting)
+                    .on_click(cx.listener(|this, _, _window, cx| {
+                        this.server_state.update(cx, |state, cx| {
+                            state.reconnect(cx);
+                        });
+                    }))
+                    .mr_2(),
+            )
             .when(server_state.supports_db_selection, |this| {
                 this.child(Select::new(&self.db_state).mr_2().mt_1().small())
             })
@@ -357,6 +678,21 @@ impl ZedisStatusBar {
                     }))
                     .mr_2(),
             )
+            .when(is_transient_server, |this| {
+                this.child(
+                    Button::new("zedis-status-bar-save-server")
+                        .outline()
+                        .small()
+                        .tooltip(i18n_status_bar(cx, "save_server_tooltip"))
+                        .icon(CustomIconName::Save)
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.server_state.update(cx, |state, cx| {
+                                state.save_transient_server(cx);
+                            });
+                        }))
+                        .mr_2(),
+                )
+            })
             .child(
                 Button::new("zedis-status-bar-key-collapse")
                     .outline()
@@ -377,6 +713,8 @@ impl ZedisStatusBar {
                     .disabled(is_completed)
                     .tooltip(if is_completed {
                         i18n_status_bar(cx, "scan_completed")
+                    } else if loaded_keys_limit_reached {
+                        i18n_status_bar(cx, "loaded_keys_limit_reached")
                     } else {
                         i18n_status_bar(cx, "scan_more_keys")
                     })
@@ -388,7 +726,14 @@ impl ZedisStatusBar {
                         });
                     })),
             )
-            .child(Label::new(server_state.size.clone()).mr_4())
+            .child(
+                div()
+                    .id("zedis-status-bar-size")
+                    .child(Label::new(server_state.size.clone()).mr_4())
+                    .when(!size_tooltip.is_empty(), |this| {
+                        this.tooltip(move |window, cx| Tooltip::new(size_tooltip.clone()).build(window, cx))
+                    }),
+            )
             .child(
                 div()
                     .child(
@@ -397,21 +742,75 @@ impl ZedisStatusBar {
                             .child(Label::new(server_state.nodes.clone()).mr_4()),
                     )
                     .id("zedis-servers")
-                    .tooltip(move |window, cx| Tooltip::new(nodes_description.clone()).build(window, cx)),
+                    .cursor_pointer()
+                    .tooltip(move |window, cx| Tooltip::new(nodes_description.clone()).build(window, cx))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        open_node_info_dialog(this.server_state.clone(), window, cx);
+                    })),
             )
+            .when(server_state.is_cluster, |this| {
+                this.child(
+                    Button::new("zedis-status-bar-cluster-topology")
+                        .ghost()
+                        .mr_2()
+                        .tooltip(i18n_status_bar(cx, "cluster_topology_tooltip"))
+                        .icon(Icon::new(CustomIconName::ListTree).text_color(cx.theme().primary))
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            open_cluster_topology_dialog(this.server_state.clone(), window, cx);
+                        })),
+                )
+            })
             .child(
                 Button::new("zedis-status-bar-letency")
                     .ghost()
-                    .disabled(true)
-                    .tooltip(i18n_common(cx, "latency"))
+                    .tooltip(i18n_status_bar(cx, "slowlog_tooltip"))
                     .icon(
                         Icon::new(CustomIconName::ChevronsLeftRightEllipsis)
                             .text_color(cx.theme().primary)
                             .mr_1(),
                     )
                     .text_color(server_state.latency.1)
-                    .label(server_state.latency.0.clone()),
+                    .label(server_state.latency.0.clone())
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        open_slowlog_dialog(this.server_state.clone(), window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-status-bar-pubsub")
+                    .ghost()
+                    .tooltip(i18n_status_bar(cx, "pubsub_tooltip"))
+                    .icon(Icon::new(CustomIconName::Activity).text_color(cx.theme().primary))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        open_pubsub_dialog(this.server_state.clone(), window, cx);
+                    })),
+            )
+            .child(
+                Button::new("zedis-status-bar-ops-per-sec")
+                    .ghost()
+                    .disabled(true)
+                    .tooltip(i18n_status_bar(cx, "ops_per_sec_tooltip"))
+                    .icon(Icon::new(CustomIconName::Zap).text_color(cx.theme().primary))
+                    .text_color(cx.theme().primary)
+                    .label(server_state.ops_per_sec.clone()),
+            )
+            .child(
+                Button::new("zedis-status-bar-hit-rate")
+                    .ghost()
+                    .disabled(true)
+                    .tooltip(i18n_status_bar(cx, "hit_rate_tooltip"))
+                    .icon(Icon::new(CustomIconName::CircleCheckBig).text_color(cx.theme().primary))
+                    .text_color(server_state.hit_rate.1)
+                    .label(server_state.hit_rate.0.clone()),
             )
+            .when_some(server_state.unreachable_nodes.clone(), |this, tooltip| {
+                this.child(
+                    div()
+                        .id("zedis-status-bar-unreachable-nodes")
+                        .mr_2()
+                        .child(Icon::new(IconName::TriangleAlert).text_color(cx.theme().yellow).small())
+                        .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx)),
+                )
+            })
             .child(
                 Button::new("zedis-status-bar-used-memory")
                     .ghost()
@@ -424,13 +823,106 @@ impl ZedisStatusBar {
             .child(
                 Button::new("zedis-status-bar-clients")
                     .ghost()
-                    .disabled(true)
+                    .disabled(self.readonly)
                     .text_color(cx.theme().primary)
-                    .tooltip(i18n_common(cx, "clients"))
+                    .tooltip(if self.readonly {
+                        self.server_state.read(cx).readonly_reason(cx)
+                    } else {
+                        i18n_status_bar(cx, "kill_idle_clients_tooltip")
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.handle_kill_idle_clients(window, cx);
+                    }))
                     .icon(Icon::new(CustomIconName::AudioWaveform))
                     .label(server_state.clients.clone()),
             )
     }
+    /// Ask for an idle threshold, then count the clients idle beyond it before confirming the kill
+    fn handle_kill_idle_clients(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let view = cx.entity();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let min_idle = values
+                .first()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(DEFAULT_CLIENT_IDLE_SECS);
+            view.update(cx, |this, cx| {
+                this.count_idle_clients(min_idle, window, cx);
+            });
+            true
+        });
+        let fields = vec![
+            FormField::new(i18n_status_bar(cx, "client_idle_seconds"))
+                .with_placeholder(DEFAULT_CLIENT_IDLE_SECS.to_string().into())
+                .with_validate(|s| s.is_empty() || s.trim().parse::<u64>().is_ok_and(|value| value > 0))
+                .with_focus(),
+        ];
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_status_bar(cx, "kill_idle_clients_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+    /// Count the idle clients of every master and ask the user to type the keyword to kill them
+    fn count_idle_clients(&mut self, min_idle: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.server_state.read(cx);
+        let server_id = state.server_id().to_string();
+        let db = state.db();
+        let server_state = self.server_state.clone();
+        cx.spawn_in(window, async move |handle, cx| {
+            let result = cx
+                .background_spawn(async move {
+                    get_connection_manager()
+                        .with_client_retry(&server_id, db, |client, _| async move {
+                            client.idle_client_ids(min_idle).await
+                        })
+                        .await
+                })
+                .await;
+            let _ = handle.update_in(cx, |_this, window, cx| {
+                let ids = match result {
+                    Ok(ids) => ids,
+                    Err(e) => {
+                        window.push_notification(Notification::error(e.to_string()), cx);
+                        return;
+                    }
+                };
+                let count: usize = ids.iter().map(|ids| ids.len()).sum();
+                let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+                if count == 0 {
+                    let message = t!("status_bar.no_idle_clients", idle = min_idle, locale = locale).to_string();
+                    window.push_notification(Notification::info(message), cx);
+                    return;
+                }
+                let label = t!(
+                    "status_bar.kill_idle_clients_confirm",
+                    count = count,
+                    idle = min_idle,
+                    keyword = KILL_CONFIRM_KEYWORD,
+                    locale = locale
+                )
+                .to_string();
+                open_typed_confirm_dialog(
+                    i18n_status_bar(cx, "kill_idle_clients_title"),
+                    label.into(),
+                    KILL_CONFIRM_KEYWORD.into(),
+                    vec![],
+                    move |_values, _window, cx| {
+                        let ids = ids.clone();
+                        server_state.update(cx, |state, cx| {
+                            state.kill_idle_clients(ids, cx);
+                        });
+                    },
+                    window,
+                    cx,
+                );
+            });
+        })
+        .detach();
+    }
     fn render_editor_settings(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let server_state = &self.state.server_state;
         Button::new("soft-wrap")
@@ -461,28 +953,74 @@ impl ZedisStatusBar {
             .into_any_element()
     }
     fn render_viewer_mode(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        if self.state.data_format.is_none() {
+        if !self.state.viewer_enabled {
             return h_flex();
         };
         let label = i18n_status_bar(cx, "viewer");
         h_flex()
             .child(Label::new(label).mr_1())
             .child(Select::new(&self.viewer_mode_state).appearance(false))
+            .child(
+                Button::new("zedis-status-bar-default-view-mode")
+                    .ghost()
+                    .xsmall()
+                    .tooltip(i18n_status_bar(cx, "default_view_mode_tooltip"))
+                    .icon(CustomIconName::CircleCheckBig)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        // Only an explicit click changes the type default, picking a mode stays per key
+                        let Some(value) = this.server_state.read(cx).value() else {
+                            return;
+                        };
+                        let key_type = value.key_type();
+                        let view_mode = value.view_mode();
+                        update_app_state_and_save(cx, "save_default_view_mode", move |state, _cx| {
+                            state.set_default_view_mode(key_type, view_mode);
+                        });
+                        window
+                            .push_notification(Notification::info(i18n_status_bar(cx, "default_view_mode_saved")), cx);
+                    })),
+            )
     }
-    /// Render the error message
+    /// Render the error message, click it for the detail
     fn render_errors(&self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let Some(data) = &self.state.error else {
             return h_flex().flex_1();
         };
+        let tooltip = i18n_status_bar(cx, "error_detail_tooltip");
         // error message is always on the right
-        h_flex().flex_1().child(
-            Label::new(data.message.clone())
-                .mr_2()
-                .w_full()
-                .text_xs()
-                .text_color(cx.theme().red)
-                .text_align(TextAlign::Right),
-        )
+        h_flex()
+            .flex_1()
+            .child(
+                div()
+                    .id("zedis-status-bar-error")
+                    .flex_1()
+                    .cursor_pointer()
+                    .tooltip(move |window, cx| Tooltip::new(tooltip.clone()).build(window, cx))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        if let Some(error) = this.state.error.clone() {
+                            open_error_detail_dialog(error, window, cx);
+                        }
+                    }))
+                    .child(
+                        Label::new(data.message.clone())
+                            .mr_2()
+                            .w_full()
+                            .text_xs()
+                            .text_color(cx.theme().red)
+                            .text_align(TextAlign::Right),
+                    ),
+            )
+            .child(
+                Button::new("zedis-status-bar-dismiss-error")
+                    .ghost()
+                    .xsmall()
+                    .icon(CustomIconName::X)
+                    .tooltip(i18n_status_bar(cx, "dismiss_error_tooltip"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.state.error = None;
+                        cx.notify();
+                    })),
+            )
     }
 }
 
@@ -494,10 +1032,19 @@ impl Render for ZedisStatusBar {
         }
         if self.should_reset_viewer_mode {
             self.viewer_mode_state.update(cx, |state, cx| {
-                state.set_selected_index(Some(IndexPath::new(0)), window, cx);
+                state.set_selected_index(Some(IndexPath::new(self.state.view_mode.index())), window, cx);
             });
             self.should_reset_viewer_mode = false;
         }
+        if self.should_sync_db {
+            let db = self.server_state.read(cx).db();
+            let db_items = self.db_items.clone();
+            self.db_state.update(cx, |state, cx| {
+                state.set_items(db_items, window, cx);
+                state.set_selected_value(&db, window, cx);
+            });
+            self.should_sync_db = false;
+        }
         h_flex()
             .justify_between()
             .text_sm()
@@ -0,0 +1,373 @@
+// Copyright 2026 Tree xie.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    assets::CustomIconName,
+    components::{ConfirmDialog, FormDialog, FormField, ZedisKvFetcher, open_add_form_dialog, open_confirm_dialog},
+    helpers::fast_contains_ignore_case,
+    states::{RedisValue, ServerEvent, ZedisGlobalStore, ZedisServerState, i18n_stream_editor},
+    views::{KvTableColumn, ZedisKvTable},
+};
+use gpui::{App, Entity, SharedString, Subscription, Window, div, prelude::*};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    label::Label,
+    notification::Notification,
+    v_flex,
+};
+use rust_i18n::t;
+use std::rc::Rc;
+use tracing::info;
+
+const ID_COLUMN_WIDTH: f32 = 0.25; // Share of the table width for the entry id
+
+/// Parses one `field=value` or `field<Tab>value` pair per line, a tab takes precedence.
+///
+/// Returns the 1-based number of the first line without a separator or field name.
+fn parse_stream_fields(text: &str) -> Result<Vec<(SharedString, SharedString)>, usize> {
+    let mut pairs = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((field, value)) = line.split_once('\t').or_else(|| line.split_once('=')) else {
+            return Err(index + 1);
+        };
+        let field = field.trim();
+        if field.is_empty() {
+            return Err(index + 1);
+        }
+        pairs.push((field.to_string().into(), value.to_string().into()));
+    }
+    Ok(pairs)
+}
+
+/// Manages Redis Stream entries and their display state.
+///
+/// Entries are immutable in Redis, so rows can only be added or deleted.
+struct ZedisStreamValues {
+    /// Indexes of the loaded entries matching the keyword, None when not filtered
+    visible_item_indexes: Option<Vec<usize>>,
+    /// The underlying Redis value data
+    value: RedisValue,
+    /// Reference to server state for performing operations
+    server_state: Entity<ZedisServerState>,
+}
+
+impl ZedisStreamValues {
+    /// Keeps the entries whose id, field names or values contain the keyword.
+    fn recalc_visible_items(&mut self) {
+        let Some(value) = self.value.stream_value() else {
+            return;
+        };
+        let keyword = value.keyword.clone().unwrap_or_default().to_lowercase();
+        if keyword.is_empty() {
+            self.visible_item_indexes = None;
+            return;
+        }
+        let indexes = value
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, (id, fields))| {
+                fast_contains_ignore_case(id, &keyword)
+                    || fields.iter().any(|(field, value)| {
+                        fast_contains_ignore_case(field, &keyword) || fast_contains_ignore_case(value, &keyword)
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect();
+        self.visible_item_indexes = Some(indexes);
+    }
+    /// Maps a visible row to the index of the entry in the loaded entries.
+    fn real_index(&self, row_ix: usize) -> Option<usize> {
+        match &self.visible_item_indexes {
+            Some(indexes) => indexes.get(row_ix).copied(),
+            None => Some(row_ix),
+        }
+    }
+}
+
+impl ZedisKvFetcher for ZedisStreamValues {
+    /// The entry id for the first column, the fields as `field=value` text for the second one.
+    fn get(&self, row_ix: usize, col_ix: usize) -> Option<SharedString> {
+        let (id, fields) = self.value.stream_value()?.values.get(self.real_index(row_ix)?)?;
+        if col_ix == 0 {
+            return Some(id.clone());
+        }
+        let text = fields
+            .iter()
+            .map(|(field, value)| format!("{field}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(text.into())
+    }
+
+    /// Renders the fields of an entry as a nested field/value list.
+    fn get_pairs(&self, row_ix: usize, col_ix: usize) -> Option<Vec<(SharedString, SharedString)>> {
+        if col_ix != 1 {
+            return None;
+        }
+        let (_, fields) = self.value.stream_value()?.values.get(self.real_index(row_ix)?)?;
+        Some(fields.clone())
+    }
+
+    fn readonly_columns(&self) -> Vec<usize> {
+        vec![0, 1]
+    }
+
+    /// Returns the number of entries in the stream (from XLEN).
+    fn count(&self) -> usize {
+        self.value.stream_value().map_or(0, |v| v.size)
+    }
+
+    fn rows_count(&self) -> usize {
+        match &self.visible_item_indexes {
+            Some(indexes) => indexes.len(),
+            None => self.value.stream_value().map_or(0, |v| v.values.len()),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.value.stream_value().is_some_and(|v| v.values.len() >= v.size)
+    }
+
+    fn is_canceled(&self) -> bool {
+        self.value.is_load_canceled()
+    }
+
+    fn load_more(&self, _window: &mut Window, cx: &mut App) {
+        self.server_state.update(cx, |state, cx| {
+            state.load_more_stream_value(cx);
+        });
+    }
+
+    /// Deletes the entry of the row with XDEL.
+    fn remove(&self, index: usize, cx: &mut App) {
+        let Some(real_index) = self.real_index(index) else {
+            return;
+        };
+        self.server_state.update(cx, |state, cx| {
+            state.remove_stream_entry(real_index, cx);
+        });
+    }
+
+    fn filter(&self, keyword: SharedString, cx: &mut App) {
+        self.server_state.update(cx, |state, cx| {
+            state.filter_stream_value(keyword, cx);
+        });
+    }
+
+    /// Opens a dialog asking for the entry id and its fields, then appends it with XADD.
+    fn handle_add_value(&self, window: &mut Window, cx: &mut App) {
+        let server_state = self.server_state.clone();
+
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let (Some(id), Some(text)) = (values.first(), values.get(1)) else {
+                return false;
+            };
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = match parse_stream_fields(text) {
+                Ok(fields) if fields.is_empty() => t!("stream_editor.add_entry_empty", locale = locale).to_string(),
+                Ok(fields) => {
+                    server_state.update(cx, |state, cx| {
+                        state.add_stream_entry(id.clone(), fields, cx);
+                    });
+                    window.close_dialog(cx);
+                    return true;
+                }
+                Err(line) => t!(
+                    "stream_editor.add_entry_missing_separator",
+                    line = line,
+                    locale = locale
+                )
+                .to_string(),
+            };
+            window.push_notification(Notification::error(message), cx);
+            false
+        });
+
+        let fields = vec![
+            FormField::new(i18n_stream_editor(cx, "entry_id"))
+                .with_placeholder(i18n_stream_editor(cx, "entry_id_placeholder")),
+            FormField::new(i18n_stream_editor(cx, "entry_fields"))
+                .with_placeholder(i18n_stream_editor(cx, "entry_fields_placeholder"))
+                .with_multi_line()
+                .with_focus(),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "add_entry_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn new(server_state: Entity<ZedisServerState>, value: RedisValue) -> Self {
+        let mut this = Self {
+            server_state,
+            value,
+            visible_item_indexes: None,
+        };
+
+        this.recalc_visible_items();
+        this
+    }
+}
+
+/// Editor view for Redis Stream data type.
+///
+/// Lists the entries with XRANGE page by page, each row shows the entry id
+/// and its fields. Entries are appended with XADD, deleted with XDEL and the
+/// stream is capped with XTRIM MAXLEN.
+pub struct ZedisStreamEditor {
+    /// Reference to server state for stream length and trim operations
+    server_state: Entity<ZedisServerState>,
+    /// Table component managing the entries display and interactions
+    table_state: Entity<ZedisKvTable<ZedisStreamValues>>,
+    /// Event subscriptions for server state changes
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ZedisStreamEditor {
+    pub fn new(server_state: Entity<ZedisServerState>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut subscriptions = Vec::new();
+        // Re-render the length label whenever the stream changes
+        subscriptions.push(cx.subscribe(&server_state, |_this, _, event, cx| {
+            if matches!(
+                event,
+                ServerEvent::ValueLoaded(_) | ServerEvent::ValueUpdated(_) | ServerEvent::ValueAdded(_)
+            ) {
+                cx.notify();
+            }
+        }));
+        let table_state = cx.new(|cx| {
+            ZedisKvTable::<ZedisStreamValues>::new(
+                vec![
+                    KvTableColumn::new("ID", Some(ID_COLUMN_WIDTH)).with_readonly(true),
+                    KvTableColumn::new("Fields", None).with_readonly(true),
+                ],
+                server_state.clone(),
+                window,
+                cx,
+            )
+        });
+
+        info!("Creating new stream editor view");
+
+        Self {
+            server_state,
+            table_state,
+            _subscriptions: subscriptions,
+        }
+    }
+
+    /// Opens a dialog asking for the maximum length, then confirms before trimming.
+    fn handle_trim(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let server_state = self.server_state.clone();
+        let handle_submit = Rc::new(move |values: Vec<SharedString>, window: &mut Window, cx: &mut App| {
+            let Some(max_len) = values.first().and_then(|v| v.trim().parse::<usize>().ok()) else {
+                return false;
+            };
+            let Some(key) = server_state.read(cx).key() else {
+                return false;
+            };
+            window.close_dialog(cx);
+
+            // XTRIM is destructive, ask again before sending it
+            let server_state = server_state.clone();
+            let locale = cx.global::<ZedisGlobalStore>().read(cx).locale();
+            let message = t!("stream_editor.trim_prompt", key = key, len = max_len, locale = locale).to_string();
+            let dialog = ConfirmDialog::new(message, move |_window, cx| {
+                server_state.update(cx, |state, cx| {
+                    state.trim_stream(max_len, cx);
+                });
+            });
+            open_confirm_dialog(dialog, window, cx);
+            true
+        });
+
+        let fields = vec![
+            FormField::new(i18n_stream_editor(cx, "trim_max_len"))
+                .with_placeholder(i18n_stream_editor(cx, "trim_placeholder"))
+                .with_validate(|s| s.trim().parse::<usize>().is_ok())
+                .with_focus(),
+        ];
+
+        open_add_form_dialog(
+            FormDialog {
+                title: i18n_stream_editor(cx, "trim_title"),
+                fields,
+                handle_submit,
+            },
+            window,
+            cx,
+        );
+    }
+}
+
+impl Render for ZedisStreamEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let server_state = self.server_state.read(cx);
+        let readonly = server_state.readonly();
+        let readonly_reason = server_state.readonly_reason(cx);
+        let (size, busy) = server_state
+            .value()
+            .map(|value| (value.stream_value().map_or(0, |v| v.size), value.is_busy()))
+            .unwrap_or_default();
+        let text_color = cx.theme().muted_foreground;
+
+        v_flex()
+            .size_full()
+            .child(
+                h_flex()
+                    .w_full()
+                    .px_2()
+                    .py_1()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().border)
+                    .child(
+                        Label::new(format!("{} (XLEN): {size}", i18n_stream_editor(cx, "length")))
+                            .text_sm()
+                            .text_color(text_color)
+                            .flex_1(),
+                    )
+                    .child(
+                        Button::new("stream-editor-trim-btn")
+                            .small()
+                            .ghost()
+                            .icon(CustomIconName::Scissors)
+                            .label("XTRIM")
+                            .disabled(readonly || busy)
+                            .tooltip(if readonly {
+                                readonly_reason
+                            } else {
+                                i18n_stream_editor(cx, "trim_tooltip")
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.handle_trim(window, cx);
+                            })),
+                    ),
+            )
+            .child(div().flex_1().w_full().child(self.table_state.clone()))
+            .into_any_element()
+    }
+}
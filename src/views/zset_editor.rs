@@ -96,6 +96,11 @@ impl ZedisKvFetcher for ZedisZsetValues {
             .is_some_and(|v| v.values.len() == v.size || v.done)
     }
 
+    /// Checks if the user stopped loading more members.
+    fn is_canceled(&self) -> bool {
+        self.value.is_load_canceled()
+    }
+
     /// Triggers loading of the next batch of ZSET members.
     ///
     /// Uses range-based or scan-based pagination depending on filter state.